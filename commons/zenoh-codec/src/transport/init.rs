@@ -52,6 +52,7 @@ where
             ext_mlink,
             ext_lowlatency,
             ext_compression,
+            ext_compression_algorithm,
             ext_patch,
             ext_region_name,
         } = x;
@@ -67,6 +68,7 @@ where
             + (ext_mlink.is_some() as u8)
             + (ext_lowlatency.is_some() as u8)
             + (ext_compression.is_some() as u8)
+            + (ext_compression_algorithm.is_some() as u8)
             + (*ext_patch != ext::PatchType::NONE) as u8
             + (ext_region_name.is_some() as u8);
 
@@ -129,6 +131,10 @@ where
             n_exts -= 1;
             self.write(&mut *writer, (compression, n_exts != 0))?;
         }
+        if let Some(compression_algorithm) = ext_compression_algorithm.as_ref() {
+            n_exts -= 1;
+            self.write(&mut *writer, (compression_algorithm, n_exts != 0))?;
+        }
         if *ext_patch != ext::PatchType::NONE {
             n_exts -= 1;
             self.write(&mut *writer, (*ext_patch, n_exts != 0))?;
@@ -198,6 +204,7 @@ where
         let mut ext_mlink = None;
         let mut ext_lowlatency = None;
         let mut ext_compression = None;
+        let mut ext_compression_algorithm = None;
         let mut ext_patch = ext::PatchType::NONE;
         let mut ext_northtag = None;
 
@@ -242,6 +249,11 @@ where
                     ext_compression = Some(q);
                     has_ext = ext;
                 }
+                ext::CompressionAlgorithm::ID => {
+                    let (q, ext): (ext::CompressionAlgorithm, bool) = eodec.read(&mut *reader)?;
+                    ext_compression_algorithm = Some(q);
+                    has_ext = ext;
+                }
                 ext::Patch::ID => {
                     let (p, ext): (ext::PatchType, bool) = eodec.read(&mut *reader)?;
                     ext_patch = p;
@@ -272,6 +284,7 @@ where
             ext_mlink,
             ext_lowlatency,
             ext_compression,
+            ext_compression_algorithm,
             ext_patch,
             ext_region_name: ext_northtag,
         })
@@ -301,6 +314,7 @@ where
             ext_mlink,
             ext_lowlatency,
             ext_compression,
+            ext_compression_algorithm,
             ext_patch,
             ext_region_name,
         } = x;
@@ -316,6 +330,7 @@ where
             + (ext_mlink.is_some() as u8)
             + (ext_lowlatency.is_some() as u8)
             + (ext_compression.is_some() as u8)
+            + (ext_compression_algorithm.is_some() as u8)
             + (*ext_patch != ext::PatchType::NONE) as u8
             + (ext_region_name.is_some() as u8);
 
@@ -381,6 +396,10 @@ where
             n_exts -= 1;
             self.write(&mut *writer, (compression, n_exts != 0))?;
         }
+        if let Some(compression_algorithm) = ext_compression_algorithm.as_ref() {
+            n_exts -= 1;
+            self.write(&mut *writer, (compression_algorithm, n_exts != 0))?;
+        }
         if *ext_patch != ext::PatchType::NONE {
             n_exts -= 1;
             self.write(&mut *writer, (*ext_patch, n_exts != 0))?;
@@ -453,6 +472,7 @@ where
         let mut ext_mlink = None;
         let mut ext_lowlatency = None;
         let mut ext_compression = None;
+        let mut ext_compression_algorithm = None;
         let mut ext_patch = ext::PatchType::NONE;
         let mut ext_region_name = None;
 
@@ -497,6 +517,11 @@ where
                     ext_compression = Some(q);
                     has_ext = ext;
                 }
+                ext::CompressionAlgorithm::ID => {
+                    let (q, ext): (ext::CompressionAlgorithm, bool) = eodec.read(&mut *reader)?;
+                    ext_compression_algorithm = Some(q);
+                    has_ext = ext;
+                }
                 ext::Patch::ID => {
                     let (p, ext): (ext::PatchType, bool) = eodec.read(&mut *reader)?;
                     ext_patch = p;
@@ -528,6 +553,7 @@ where
             ext_mlink,
             ext_lowlatency,
             ext_compression,
+            ext_compression_algorithm,
             ext_patch,
             ext_region_name,
         })
@@ -150,6 +150,7 @@ where
             next_sn,
             ext_qos,
             ext_shm,
+            ext_nack,
             ext_patch,
         } = x;
 
@@ -163,6 +164,7 @@ where
         }
         let mut n_exts = (ext_qos.is_some() as u8)
             + (ext_shm.is_some() as u8)
+            + (ext_nack.is_some() as u8)
             + (*ext_patch != ext::PatchType::NONE) as u8;
         if n_exts != 0 {
             header |= flag::Z;
@@ -204,6 +206,10 @@ where
             n_exts -= 1;
             self.write(&mut *writer, (shm, n_exts != 0))?;
         }
+        if let Some(nack) = ext_nack.as_ref() {
+            n_exts -= 1;
+            self.write(&mut *writer, (nack, n_exts != 0))?;
+        }
         if *ext_patch != ext::PatchType::NONE {
             n_exts -= 1;
             self.write(&mut *writer, (*ext_patch, n_exts != 0))?;
@@ -271,6 +277,7 @@ where
         // Extensions
         let mut ext_qos = None;
         let mut ext_shm = None;
+        let mut ext_nack = None;
         let mut ext_patch = ext::PatchType::NONE;
 
         let mut has_ext = imsg::has_flag(self.header, flag::Z);
@@ -288,6 +295,11 @@ where
                     ext_shm = Some(s);
                     has_ext = ext;
                 }
+                ext::Nack::ID => {
+                    let (n, ext): (ext::Nack, bool) = eodec.read(&mut *reader)?;
+                    ext_nack = Some(n);
+                    has_ext = ext;
+                }
                 ext::Patch::ID => {
                     let (p, ext): (ext::PatchType, bool) = eodec.read(&mut *reader)?;
                     ext_patch = p;
@@ -309,6 +321,7 @@ where
             next_sn,
             ext_qos,
             ext_shm,
+            ext_nack,
             ext_patch,
         })
     }
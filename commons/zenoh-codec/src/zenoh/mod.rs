@@ -17,6 +17,8 @@ pub mod put;
 pub mod query;
 pub mod reply;
 
+use alloc::string::String;
+
 use zenoh_buffers::{
     reader::{DidntRead, Reader},
     writer::{DidntWrite, Writer},
@@ -30,11 +32,9 @@ use zenoh_protocol::{
     zenoh::{ext, id, PushBody, RequestBody, ResponseBody},
 };
 
-#[cfg(not(feature = "shared-memory"))]
-use crate::Zenoh080Bounded;
 #[cfg(feature = "shared-memory")]
 use crate::Zenoh080Sliced;
-use crate::{LCodec, RCodec, WCodec, Zenoh080, Zenoh080Header, Zenoh080Length};
+use crate::{LCodec, RCodec, WCodec, Zenoh080, Zenoh080Bounded, Zenoh080Header, Zenoh080Length};
 
 // Push
 impl<W> WCodec<&PushBody, &mut W> for Zenoh080
@@ -427,3 +427,70 @@ where
         Ok((ext::AttachmentType { buffer }, more))
     }
 }
+
+// Extension: TraceContext
+impl<const ID: u8> LCodec<&ext::TraceContextType<{ ID }>> for Zenoh080 {
+    fn w_len(self, x: &ext::TraceContextType<{ ID }>) -> usize {
+        let ext::TraceContextType {
+            trace_id,
+            span_id,
+            flags: _,
+            state,
+        } = x;
+
+        trace_id.len() + span_id.len() + 1 + self.w_len(state)
+    }
+}
+
+impl<W, const ID: u8> WCodec<(&ext::TraceContextType<{ ID }>, bool), &mut W> for Zenoh080
+where
+    W: Writer,
+{
+    type Output = Result<(), DidntWrite>;
+
+    fn write(self, writer: &mut W, x: (&ext::TraceContextType<{ ID }>, bool)) -> Self::Output {
+        let (x, more) = x;
+        let ext::TraceContextType {
+            trace_id,
+            span_id,
+            flags,
+            state,
+        } = x;
+
+        let header: ZExtZBufHeader<{ ID }> = ZExtZBufHeader::new(self.w_len(x));
+        self.write(&mut *writer, (&header, more))?;
+        writer.write_exact(trace_id)?;
+        writer.write_exact(span_id)?;
+        self.write(&mut *writer, *flags)?;
+        Zenoh080Bounded::<u16>::new().write(&mut *writer, state)?;
+        Ok(())
+    }
+}
+
+impl<R, const ID: u8> RCodec<(ext::TraceContextType<{ ID }>, bool), &mut R> for Zenoh080Header
+where
+    R: Reader,
+{
+    type Error = DidntRead;
+
+    fn read(self, reader: &mut R) -> Result<(ext::TraceContextType<{ ID }>, bool), Self::Error> {
+        let (_, more): (ZExtZBufHeader<{ ID }>, bool) = self.read(&mut *reader)?;
+
+        let mut trace_id = [0u8; 16];
+        reader.read_exact(&mut trace_id)?;
+        let mut span_id = [0u8; 8];
+        reader.read_exact(&mut span_id)?;
+        let flags: u8 = self.codec.read(&mut *reader)?;
+        let state: String = Zenoh080Bounded::<u16>::new().read(&mut *reader)?;
+
+        Ok((
+            ext::TraceContextType {
+                trace_id,
+                span_id,
+                flags,
+                state,
+            },
+            more,
+        ))
+    }
+}
@@ -46,6 +46,7 @@ where
             encoding,
             ext_sinfo,
             ext_attachment,
+            ext_trace,
             #[cfg(feature = "shared-memory")]
             ext_shm,
             ext_unknown,
@@ -62,6 +63,7 @@ where
         }
         let mut n_exts = (ext_sinfo.is_some()) as u8
             + (ext_attachment.is_some()) as u8
+            + (ext_trace.is_some()) as u8
             + (ext_unknown.len() as u8);
         #[cfg(feature = "shared-memory")]
         {
@@ -94,6 +96,10 @@ where
             n_exts -= 1;
             self.write(&mut *writer, (att, n_exts != 0))?;
         }
+        if let Some(trace) = ext_trace.as_ref() {
+            n_exts -= 1;
+            self.write(&mut *writer, (trace, n_exts != 0))?;
+        }
         for u in ext_unknown.iter() {
             n_exts -= 1;
             self.write(&mut *writer, (u, n_exts != 0))?;
@@ -167,6 +173,7 @@ where
         #[cfg(feature = "shared-memory")]
         let mut ext_shm: Option<ext::ShmType> = None;
         let mut ext_attachment: Option<ext::AttachmentType> = None;
+        let mut ext_trace: Option<ext::TraceContextType> = None;
         let mut ext_unknown = Vec::new();
 
         let mut has_ext = imsg::has_flag(self.header, flag::Z);
@@ -177,6 +184,7 @@ where
                 ext_sinfo: &mut Option<ext::SourceInfoType>,
                 #[cfg(feature = "shared-memory")] ext_shm: &mut Option<ext::ShmType>,
                 ext_attachment: &mut Option<ext::AttachmentType>,
+                ext_trace: &mut Option<ext::TraceContextType>,
                 ext_unknown: &mut Vec<ZExtUnknown>,
             ) -> Result<bool, DidntRead> {
                 let codec = Zenoh080::new();
@@ -199,6 +207,11 @@ where
                         *ext_attachment = Some(a);
                         ext
                     }
+                    ext::TraceContext::ID => {
+                        let (t, ext): (ext::TraceContextType, bool) = eodec.read(&mut *reader)?;
+                        *ext_trace = Some(t);
+                        ext
+                    }
                     _ => {
                         let (u, ext) = extension::read(reader, "Put", ext)?;
                         ext_unknown.push(u);
@@ -212,6 +225,7 @@ where
                 #[cfg(feature = "shared-memory")]
                 &mut ext_shm,
                 &mut ext_attachment,
+                &mut ext_trace,
                 &mut ext_unknown,
             )?;
         }
@@ -238,6 +252,7 @@ where
             #[cfg(feature = "shared-memory")]
             ext_shm,
             ext_attachment,
+            ext_trace,
             ext_unknown,
             payload,
         })
@@ -206,6 +206,7 @@ impl Default for TransportUnicastConf {
             lowlatency: false,
             qos: QoSUnicastConf::default(),
             compression: CompressionUnicastConf::default(),
+            multilink: MultilinkUnicastConf::default(),
         }
     }
 }
@@ -217,6 +218,7 @@ impl Default for TransportMulticastConf {
             max_sessions: Some(1000),
             qos: QoSMulticastConf::default(),
             compression: CompressionMulticastConf::default(),
+            reliability: ReliabilityMulticastConf::default(),
         }
     }
 }
@@ -234,17 +236,36 @@ impl Default for QoSMulticastConf {
     }
 }
 
-#[allow(clippy::derivable_impls)]
 impl Default for CompressionUnicastConf {
     fn default() -> Self {
-        Self { enabled: false }
+        Self {
+            enabled: false,
+            algorithm: CompressionAlgorithm::default(),
+            level: 3,
+            dictionary: None,
+        }
     }
 }
 
-#[allow(clippy::derivable_impls)]
 impl Default for CompressionMulticastConf {
     fn default() -> Self {
-        Self { enabled: false }
+        Self {
+            enabled: false,
+            algorithm: CompressionAlgorithm::default(),
+            level: 3,
+            dictionary: None,
+        }
+    }
+}
+
+impl Default for ReliabilityMulticastConf {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            retransmission_buffer_size: 256,
+            nack_interval: 20,
+            nack_timeout: 1000,
+        }
     }
 }
 
@@ -32,7 +32,7 @@ use std::convert::TryFrom;
 // This is a false positive from the rust analyser
 use std::{
     any::Any,
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     fmt,
     io::Read,
     net::SocketAddr,
@@ -410,6 +410,36 @@ pub struct StatsFilterConfig {
     pub key: OwnedKeyExpr,
 }
 
+/// Periodic push of the stats to an OpenTelemetry collector, with OTLP over HTTP.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct StatsExportConfig {
+    /// The OTLP/HTTP metrics endpoint of the collector, e.g. `http://localhost:4318/v1/metrics`.
+    /// The metrics are sent in the JSON encoding of OTLP.
+    pub endpoint: String,
+    /// The interval between two exports, in milliseconds.
+    #[serde(default = "StatsExportConfig::default_interval")]
+    pub interval: u64,
+    /// Additional HTTP headers of the export requests, e.g. for authentication.
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// Whether the metrics are labelled with the remote zid and whatami of the transports.
+    #[serde(default = "set_true")]
+    pub per_transport: bool,
+    /// Whether the metrics are labelled with the links of the transports.
+    #[serde(default = "set_false")]
+    pub per_link: bool,
+    /// Whether the metrics per key expression of the `filters` are exported.
+    #[serde(default = "set_true")]
+    pub per_key: bool,
+}
+
+impl StatsExportConfig {
+    fn default_interval() -> u64 {
+        10000
+    }
+}
+
 pub trait ConfigValidator: Send + Sync {
     fn check_config(
         &self,
@@ -689,6 +719,28 @@ validated_struct::validator! {
                     /// You must compile zenoh with "transport_compression" feature to be able to enable compression.
                     /// When enabled is true, batches will be sent compressed. (default `false`).
                     enabled: bool,
+                    /// The algorithm used to compress the batches, falling back to zstd without
+                    /// dictionary, or to LZ4, when the peer does not support it (default `lz4`).
+                    pub algorithm: CompressionAlgorithm,
+                    /// The zstd compression level (default `3`).
+                    level: i32,
+                    /// The file of the pre-trained dictionary used by the `zstd_dictionary`
+                    /// algorithm (default `null`).
+                    dictionary: Option<String>,
+                },
+                /// Scheduling of the messages on the links of a transport having several links
+                /// matching the same reliability and priority range (see `max_links`).
+                pub multilink: #[derive(Default)]
+                MultilinkUnicastConf {
+                    /// The policy used to pick a link among the matching ones (default `first`).
+                    pub scheduling: MultilinkScheduling,
+                    /// Time in milliseconds after which a link having messages queued without
+                    /// transmitting any of them is considered stalled, its traffic being failed over
+                    /// to the other matching links (default `null`, i.e. no failover).
+                    failover_timeout: Option<u64>,
+                    /// The weights of the links in the `round_robin` and `least_queued` policies.
+                    /// The first matching item applies, links matching no item having a weight of 1.
+                    weights: Vec<MultilinkWeightConf>,
                 },
             },
             pub multicast: TransportMulticastConf {
@@ -705,6 +757,25 @@ validated_struct::validator! {
                     /// You must compile zenoh with "transport_compression" feature to be able to enable compression.
                     /// When enabled is true, batches will be sent compressed. (default `false`).
                     enabled: bool,
+                    /// The algorithm used to compress the batches (default `lz4`).
+                    pub algorithm: CompressionAlgorithm,
+                    /// The zstd compression level (default `3`).
+                    level: i32,
+                    /// The file of the pre-trained dictionary used by the `zstd_dictionary`
+                    /// algorithm (default `null`).
+                    dictionary: Option<String>,
+                },
+                pub reliability: ReliabilityMulticastConf {
+                    /// When enabled is true, the lost reliable frames are retransmitted upon the
+                    /// NACKs of the receivers (default `false`).
+                    enabled: bool,
+                    /// The number of sent batches kept for retransmission (default `256`).
+                    retransmission_buffer_size: usize,
+                    /// The interval in milliseconds between two NACKs of a missing frame (default `20`).
+                    nack_interval: u64,
+                    /// The time in milliseconds after which a missing frame is given up on,
+                    /// the following frames being delivered (default `1000`).
+                    nack_timeout: u64,
                 },
             },
             pub link: #[derive(Default)]
@@ -801,6 +872,11 @@ validated_struct::validator! {
                     connect_certificate: Option<String>,
                     verify_name_on_connect: Option<bool>,
                     close_link_on_expiration: Option<bool>,
+                    /// The certificate revocation list file used to verify the certificates of the peers.
+                    crl: Option<String>,
+                    /// The interval in milliseconds to check the certificate files of the listeners
+                    /// for modifications, reloading them for the new connections.
+                    reload_interval_ms: Option<u64>,
                     /// Configure TCP write buffer size
                     pub so_sndbuf: Option<u32>,
                     /// Configure TCP read buffer size
@@ -928,9 +1004,12 @@ validated_struct::validator! {
         /// Configuration of the low-pass filter
         pub low_pass_filter: Vec<LowPassFilterConf>,
 
-        /// Configuration of the stats per keyexpr
+        /// Configuration of the stats
         pub stats: #[derive(Default, PartialEq, Eq)] StatsConfig {
+            /// Configuration of the stats per keyexpr
             filters: Vec<StatsFilterConfig>,
+            /// Periodic export of the stats to an OpenTelemetry collector
+            export: Option<StatsExportConfig>,
         },
 
         /// A list of directories where plugins may be searched for if no `__path__` was specified for them.
@@ -956,6 +1035,45 @@ pub enum QueueAllocMode {
     Lazy,
 }
 
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MultilinkScheduling {
+    /// All the messages are sent on the first matching link.
+    #[default]
+    First,
+    /// Best-effort messages are sent on the matching links in turn, according to their weights.
+    RoundRobin,
+    /// Best-effort messages are sent on the matching link with the fewest queued batches relative
+    /// to its weight.
+    LeastQueued,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompressionAlgorithm {
+    /// LZ4 block compression.
+    #[default]
+    Lz4,
+    /// Zstandard compression at the configured level.
+    Zstd,
+    /// Zstandard compression at the configured level, using a pre-trained dictionary shared by
+    /// the nodes.
+    ZstdDictionary,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct MultilinkWeightConf {
+    /// A list of link protocols, e.g. "tcp", the weight applies to
+    /// The weight applies to all protocols if the parameter is None
+    pub protocols: Option<NEVec<String>>,
+    /// A list of interfaces the weight applies to
+    /// The weight applies to all interfaces if the parameter is None
+    pub interfaces: Option<NEVec<String>>,
+    /// The weight of the matching links
+    pub weight: NonZeroU16,
+}
+
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ShmInitMode {
@@ -131,6 +131,7 @@ pub struct InitSyn {
     pub ext_mlink: Option<ext::MultiLink>,
     pub ext_lowlatency: Option<ext::LowLatency>,
     pub ext_compression: Option<ext::Compression>,
+    pub ext_compression_algorithm: Option<ext::CompressionAlgorithm>,
     pub ext_patch: ext::PatchType,
     pub ext_region_name: Option<ext::RegionName>,
 }
@@ -164,6 +165,9 @@ pub mod ext {
     /// # Compression extension
     /// Used to negotiate the use of compression on the link
     pub type Compression = zextunit!(0x6, false);
+    /// Used to negotiate the compression algorithm on the link, together with the Compression
+    /// extension that is kept for the nodes only supporting LZ4
+    pub type CompressionAlgorithm = zextz64!(0x6, false);
 
     /// # Patch extension
     /// Used to negotiate the patch version of the protocol
@@ -201,6 +205,7 @@ impl InitSyn {
         let ext_mlink = rng.gen_bool(0.5).then_some(ZExtZBuf::rand());
         let ext_lowlatency = rng.gen_bool(0.5).then_some(ZExtUnit::rand());
         let ext_compression = rng.gen_bool(0.5).then_some(ZExtUnit::rand());
+        let ext_compression_algorithm = rng.gen_bool(0.5).then_some(ZExtZ64::rand());
         let ext_patch = ext::PatchType::rand();
         let ext_region_name = rng.gen_bool(0.5).then_some(ZExtZBuf::rand());
 
@@ -218,6 +223,7 @@ impl InitSyn {
             ext_mlink,
             ext_lowlatency,
             ext_compression,
+            ext_compression_algorithm,
             ext_patch,
             ext_region_name,
         }
@@ -250,6 +256,7 @@ pub struct InitAck {
     pub ext_mlink: Option<ext::MultiLink>,
     pub ext_lowlatency: Option<ext::LowLatency>,
     pub ext_compression: Option<ext::Compression>,
+    pub ext_compression_algorithm: Option<ext::CompressionAlgorithm>,
     pub ext_patch: ext::PatchType,
     pub ext_region_name: Option<ext::RegionName>,
 }
@@ -282,6 +289,7 @@ impl InitAck {
         let ext_mlink = rng.gen_bool(0.5).then_some(ZExtZBuf::rand());
         let ext_lowlatency = rng.gen_bool(0.5).then_some(ZExtUnit::rand());
         let ext_compression = rng.gen_bool(0.5).then_some(ZExtUnit::rand());
+        let ext_compression_algorithm = rng.gen_bool(0.5).then_some(ZExtZ64::rand());
         let ext_patch = ext::PatchType::rand();
         let ext_region_name = rng.gen_bool(0.5).then_some(ZExtZBuf::rand());
 
@@ -300,6 +308,7 @@ impl InitAck {
             ext_mlink,
             ext_lowlatency,
             ext_compression,
+            ext_compression_algorithm,
             ext_patch,
             ext_region_name,
         }
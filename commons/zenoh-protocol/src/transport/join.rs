@@ -102,6 +102,7 @@ pub struct Join {
     pub next_sn: PrioritySn,
     pub ext_qos: Option<ext::QoSType>,
     pub ext_shm: Option<ext::Shm>,
+    pub ext_nack: Option<ext::Nack>,
     pub ext_patch: ext::PatchType,
 }
 
@@ -116,7 +117,7 @@ pub mod ext {
     use alloc::boxed::Box;
 
     use super::{Priority, PrioritySn};
-    use crate::{zextz64, zextzbuf};
+    use crate::{zextunit, zextz64, zextzbuf};
 
     /// # QoS extension
     /// Used to announce next sn when QoS is enabled
@@ -127,6 +128,10 @@ pub mod ext {
     /// Used to advertise shared memory capabilities
     pub type Shm = zextzbuf!(0x2, true);
 
+    /// # Nack extension
+    /// Used to advertise that the lost reliable frames are retransmitted upon NACK
+    pub type Nack = zextunit!(0x3, false);
+
     /// # Patch extension
     /// Used to negotiate the patch version of the protocol
     /// if not present (or 0), then protocol as released with 1.0.0
@@ -141,7 +146,7 @@ impl Join {
     pub fn rand() -> Self {
         use rand::Rng;
 
-        use crate::common::ZExtZBuf;
+        use crate::common::{ZExtUnit, ZExtZBuf};
 
         let mut rng = rand::thread_rng();
 
@@ -160,6 +165,7 @@ impl Join {
             .gen_bool(0.5)
             .then_some(Box::new([PrioritySn::rand(); Priority::NUM]));
         let ext_shm = rng.gen_bool(0.5).then_some(ZExtZBuf::rand());
+        let ext_nack = rng.gen_bool(0.5).then_some(ZExtUnit::rand());
         let ext_patch = ext::PatchType::rand();
 
         Self {
@@ -172,6 +178,7 @@ impl Join {
             next_sn,
             ext_qos,
             ext_shm,
+            ext_nack,
             ext_patch,
         }
     }
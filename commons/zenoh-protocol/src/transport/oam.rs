@@ -15,6 +15,14 @@ use crate::common::ZExtBody;
 
 pub type OamId = u16;
 
+pub mod id {
+    use super::OamId;
+
+    /// Used on multicast to request the retransmission of the reliable frames starting from a
+    /// given sequence number
+    pub const OAM_NACK: OamId = 0x0001;
+}
+
 pub mod flag {
     pub const T: u8 = 1 << 5; // 0x20 Transport
                               // pub const X: u8 = 1 << 6; // 0x40 Reserved
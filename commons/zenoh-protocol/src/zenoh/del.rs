@@ -45,6 +45,7 @@ pub struct Del {
     pub timestamp: Option<Timestamp>,
     pub ext_sinfo: Option<ext::SourceInfoType>,
     pub ext_attachment: Option<ext::AttachmentType>,
+    pub ext_trace: Option<ext::TraceContextType>,
     pub ext_unknown: Vec<ZExtUnknown>,
 }
 
@@ -59,6 +60,11 @@ pub mod ext {
     /// # User attachment
     pub type Attachment = zextzbuf!(0x2, false);
     pub type AttachmentType = crate::zenoh::ext::AttachmentType<{ Attachment::ID }>;
+
+    /// # TraceContext extension
+    /// Used to propagate the W3C trace context of the deletion
+    pub type TraceContext = zextzbuf!(0x3, false);
+    pub type TraceContextType = crate::zenoh::ext::TraceContextType<{ TraceContext::ID }>;
 }
 
 impl Del {
@@ -77,10 +83,11 @@ impl Del {
         });
         let ext_sinfo = rng.gen_bool(0.5).then_some(ext::SourceInfoType::rand());
         let ext_attachment = rng.gen_bool(0.5).then_some(ext::AttachmentType::rand());
+        let ext_trace = rng.gen_bool(0.5).then_some(ext::TraceContextType::rand());
         let mut ext_unknown = Vec::new();
         for _ in 0..rng.gen_range(0..4) {
             ext_unknown.push(ZExtUnknown::rand2(
-                iext::mid(ext::Attachment::ID) + 1,
+                iext::mid(ext::TraceContext::ID) + 1,
                 false,
             ));
         }
@@ -89,6 +96,7 @@ impl Del {
             timestamp,
             ext_sinfo,
             ext_attachment,
+            ext_trace,
             ext_unknown,
         }
     }
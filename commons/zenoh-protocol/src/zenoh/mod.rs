@@ -137,6 +137,8 @@ impl From<Err> for ResponseBody {
 }
 
 pub mod ext {
+    use alloc::string::String;
+
     use zenoh_buffers::ZBuf;
 
     use crate::core::{Encoding, EntityGlobalIdProto};
@@ -265,4 +267,51 @@ pub mod ext {
             }
         }
     }
+
+    /// W3C trace context of a message, as defined by <https://www.w3.org/TR/trace-context/>.
+    ///
+    /// ```text
+    ///  7 6 5 4 3 2 1 0
+    /// +-+-+-+-+-+-+-+-+
+    /// ~ trace_id: [u8;16] ~
+    /// +---------------+
+    /// ~ span_id: [u8;8] ~
+    /// +---------------+
+    /// |  trace_flags  |
+    /// +---------------+
+    /// ~ state: <u8;z16> ~  -- The tracestate, possibly empty
+    /// +---------------+
+    /// ```
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct TraceContextType<const ID: u8> {
+        pub trace_id: [u8; 16],
+        pub span_id: [u8; 8],
+        pub flags: u8,
+        pub state: String,
+    }
+
+    impl<const ID: u8> TraceContextType<{ ID }> {
+        #[cfg(feature = "test")]
+        #[doc(hidden)]
+        pub fn rand() -> Self {
+            use rand::{
+                distributions::{Alphanumeric, DistString},
+                Rng,
+            };
+            let mut rng = rand::thread_rng();
+
+            let state = if rng.gen_bool(0.5) {
+                let len = rng.gen_range(1..32);
+                Alphanumeric.sample_string(&mut rng, len)
+            } else {
+                String::new()
+            };
+            Self {
+                trace_id: rng.gen(),
+                span_id: rng.gen(),
+                flags: rng.gen(),
+                state,
+            }
+        }
+    }
 }
@@ -87,6 +87,7 @@ pub struct Query {
     pub ext_sinfo: Option<ext::SourceInfoType>,
     pub ext_body: Option<ext::QueryBodyType>,
     pub ext_attachment: Option<ext::AttachmentType>,
+    pub ext_trace: Option<ext::TraceContextType>,
     pub ext_unknown: Vec<ZExtUnknown>,
 }
 
@@ -107,6 +108,11 @@ pub mod ext {
     /// # User attachment
     pub type Attachment = zextzbuf!(0x5, false);
     pub type AttachmentType = crate::zenoh::ext::AttachmentType<{ Attachment::ID }>;
+
+    /// # TraceContext extension
+    /// Used to propagate the W3C trace context of the query
+    pub type TraceContext = zextzbuf!(0x6, false);
+    pub type TraceContextType = crate::zenoh::ext::TraceContextType<{ TraceContext::ID }>;
 }
 
 impl Query {
@@ -134,10 +140,11 @@ impl Query {
         let ext_sinfo = rng.gen_bool(0.5).then_some(ext::SourceInfoType::rand());
         let ext_body = rng.gen_bool(0.5).then_some(ext::QueryBodyType::rand());
         let ext_attachment = rng.gen_bool(0.5).then_some(ext::AttachmentType::rand());
+        let ext_trace = rng.gen_bool(0.5).then_some(ext::TraceContextType::rand());
         let mut ext_unknown = Vec::new();
         for _ in 0..rng.gen_range(0..4) {
             ext_unknown.push(ZExtUnknown::rand2(
-                iext::mid(ext::Attachment::ID) + 1,
+                iext::mid(ext::TraceContext::ID) + 1,
                 false,
             ));
         }
@@ -148,6 +155,7 @@ impl Query {
             ext_sinfo,
             ext_body,
             ext_attachment,
+            ext_trace,
             ext_unknown,
         }
     }
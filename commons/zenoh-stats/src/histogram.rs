@@ -19,6 +19,12 @@ use crate::{
 pub const PAYLOAD_SIZE_BUCKETS: HistogramBuckets =
     HistogramBuckets(&[0, 1 << 5, 1 << 10, 1 << 15, 1 << 20, 1 << 25, 1 << 30]);
 
+/// Buckets of the latency histograms, in microseconds.
+pub const LATENCY_BUCKETS: HistogramBuckets = HistogramBuckets(&[
+    100, 250, 500, 1_000, 2_500, 5_000, 10_000, 25_000, 50_000, 100_000, 250_000, 500_000,
+    1_000_000, 2_500_000, 5_000_000, 10_000_000,
+]);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct HistogramBuckets(pub &'static [u64]);
 
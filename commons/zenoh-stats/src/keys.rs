@@ -23,6 +23,13 @@ use crate::{family::TransportMetric, histogram::HistogramBuckets, labels::Labels
 #[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
 pub struct StatsKeys(SmallVec<[(u64, usize); 1]>);
 
+impl StatsKeys {
+    /// Returns `true` if no stats key matches.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
 #[derive(Default)]
 pub struct StatsKeyCache {
     keys: UnsafeCell<StatsKeys>,
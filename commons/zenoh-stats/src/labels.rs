@@ -147,6 +147,29 @@ impl EncodeLabelValue for ReasonLabel {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SchedulingLabel {
+    First,
+    RoundRobin,
+    LeastQueued,
+    Failover,
+}
+
+impl SchedulingLabel {
+    pub(crate) const NUM: usize = 4;
+}
+
+impl EncodeLabelValue for SchedulingLabel {
+    fn encode(&self, encoder: &mut LabelValueEncoder) -> fmt::Result {
+        encoder.write_str(match self {
+            Self::First => "first",
+            Self::RoundRobin => "round-robin",
+            Self::LeastQueued => "least-queued",
+            Self::Failover => "failover",
+        })
+    }
+}
+
 pub(crate) const SHM_NUM: usize = 2;
 
 macro_rules! wrap_label {
@@ -216,6 +239,7 @@ impl LinkLabels {
             "quic",
             "unixsock-stream",
             "ws",
+            "wss",
             "serial",
             "unixpipe",
             "vsock",
@@ -261,6 +285,12 @@ pub(crate) struct NetworkMessageLabels {
     pub(crate) protocol: ProtocolLabel,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash, EncodeLabelSet)]
+pub(crate) struct NetworkMessageScheduledLabels {
+    pub(crate) scheduling: SchedulingLabel,
+    pub(crate) protocol: ProtocolLabel,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, EncodeLabelSet)]
 pub(crate) struct NetworkMessagePayloadLabels {
     pub(crate) space: SpaceLabel,
@@ -269,6 +299,13 @@ pub(crate) struct NetworkMessagePayloadLabels {
     pub(crate) shm: bool,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash, EncodeLabelSet)]
+pub(crate) struct LatencyLabels {
+    pub(crate) space: SpaceLabel,
+    pub(crate) priority: PriorityLabel,
+    pub(crate) message: MessageLabel,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, EncodeLabelSet)]
 pub(crate) struct NetworkMessageDroppedPayloadLabels {
     pub(crate) priority: PriorityLabel,
@@ -5,13 +5,16 @@ mod histogram;
 mod keys;
 mod labels;
 mod link;
+mod otlp;
 mod registry;
 mod stats;
 mod transport;
 
 pub use crate::{
     keys::{StatsKeyCache, StatsKeys, StatsKeysTree},
-    labels::{LocalityLabel, MessageLabel, ReasonLabel, ResourceLabel, SpaceLabel},
+    labels::{
+        LocalityLabel, MessageLabel, ReasonLabel, ResourceLabel, SchedulingLabel, SpaceLabel,
+    },
     link::LinkStats,
     registry::StatsRegistry,
     transport::{DropStats, TransportStats},
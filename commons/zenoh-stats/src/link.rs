@@ -8,8 +8,8 @@ use zenoh_protocol::{core::Priority, network::NetworkMessageExt};
 
 use crate::{
     labels::{
-        BytesLabels, LinkLabels, MessageLabel, NetworkMessageLabels, ProtocolLabel, ReasonLabel,
-        TransportMessageLabels,
+        BytesLabels, LinkLabels, MessageLabel, NetworkMessageLabels, NetworkMessageScheduledLabels,
+        ProtocolLabel, ReasonLabel, SchedulingLabel, TransportMessageLabels,
     },
     DropStats, StatsDirection, TransportStats, Tx,
 };
@@ -51,6 +51,7 @@ impl LinkStats {
             bytes,
             transport_message,
             network_message: Default::default(),
+            tx_network_message_scheduled: Default::default(),
             tx_congestion,
         }))
     }
@@ -93,6 +94,26 @@ impl LinkStats {
             .inc();
     }
 
+    pub fn inc_network_message_scheduled(&self, scheduling: SchedulingLabel) {
+        self.0.tx_network_message_scheduled[scheduling as usize]
+            .get_or_init(|| {
+                let labels = NetworkMessageScheduledLabels {
+                    scheduling,
+                    protocol: self.0.protocol.clone(),
+                };
+                self.0
+                    .transport_stats
+                    .registry()
+                    .tx_network_message_scheduled()
+                    .get_or_create_owned(
+                        self.0.transport_stats.transport(),
+                        Some(self.link()),
+                        &labels,
+                    )
+            })
+            .inc();
+    }
+
     pub fn tx_observe_congestion(&self, msg: impl NetworkMessageExt) {
         self.0
             .tx_congestion
@@ -112,6 +133,7 @@ struct LinkStatsInner {
     #[allow(clippy::type_complexity)]
     network_message:
         [[[[OnceLock<Counter>; SHM_NUM]; MessageLabel::NUM]; Priority::NUM]; StatsDirection::NUM],
+    tx_network_message_scheduled: [OnceLock<Counter>; SchedulingLabel::NUM],
     tx_congestion: DropStats,
 }
 
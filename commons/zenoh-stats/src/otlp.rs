@@ -0,0 +1,430 @@
+//! Conversion of the metrics to the [OTLP](https://opentelemetry.io/docs/specs/otlp/) JSON encoding
+//! of an `ExportMetricsServiceRequest`.
+//!
+//! The metrics are converted from their OpenMetrics text encoding: counters become monotonic sums,
+//! gauges, infos and untyped metrics become gauges, and histograms keep their explicit buckets. All
+//! the temporalities are cumulative, starting at the creation of the registry.
+use std::{
+    collections::HashMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde_json::{json, Map, Value};
+
+const AGGREGATION_TEMPORALITY_CUMULATIVE: u8 = 2;
+const INF: &str = "+Inf";
+
+fn unix_nanos(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as u64)
+}
+
+/// Converts the resource attributes from a JSON object, the keys of nested objects being joined
+/// with `.`, e.g. `{"host": {"name": "a"}}` gives the attribute `host.name`.
+pub(crate) fn resource_attributes(attributes: &mut Vec<Value>, prefix: &str, value: &Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                let key = match prefix {
+                    "" => key.clone(),
+                    _ => format!("{prefix}.{key}"),
+                };
+                resource_attributes(attributes, &key, value);
+            }
+        }
+        Value::Null => {}
+        value => {
+            attributes.retain(|attr| attr["key"] != prefix);
+            attributes.push(json!({"key": prefix, "value": any_value(value)}));
+        }
+    }
+}
+
+fn any_value(value: &Value) -> Value {
+    match value {
+        Value::Bool(b) => json!({"boolValue": b}),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => json!({"intValue": i.to_string()}),
+            None => json!({"doubleValue": n.as_f64()}),
+        },
+        Value::String(s) => json!({"stringValue": s}),
+        Value::Array(values) => {
+            json!({"arrayValue": {"values": values.iter().map(any_value).collect::<Vec<_>>()}})
+        }
+        Value::Object(_) | Value::Null => json!({"stringValue": value.to_string()}),
+    }
+}
+
+/// Converts the OpenMetrics text `metrics` to an `ExportMetricsServiceRequest`.
+pub(crate) fn encode(
+    metrics: &str,
+    attributes: Vec<Value>,
+    scope_version: &str,
+    start_time: SystemTime,
+    time: SystemTime,
+) -> Value {
+    let start_time = unix_nanos(start_time).to_string();
+    let time = unix_nanos(time).to_string();
+    let metrics = parse(metrics)
+        .iter()
+        .filter_map(|family| family.to_otlp(&start_time, &time))
+        .collect::<Vec<_>>();
+    json!({
+        "resourceMetrics": [{
+            "resource": {"attributes": attributes},
+            "scopeMetrics": [{
+                "scope": {"name": env!("CARGO_PKG_NAME"), "version": scope_version},
+                "metrics": metrics,
+            }],
+        }],
+    })
+}
+
+type Labels = Vec<(String, String)>;
+
+#[derive(Debug, Default)]
+struct MetricFamily<'a> {
+    name: &'a str,
+    kind: &'a str,
+    help: &'a str,
+    unit: &'a str,
+    samples: Vec<MetricSample<'a>>,
+}
+
+#[derive(Debug)]
+struct MetricSample<'a> {
+    suffix: &'a str,
+    labels: Labels,
+    value: &'a str,
+}
+
+fn parse(metrics: &str) -> Vec<MetricFamily<'_>> {
+    let mut families: Vec<MetricFamily> = Vec::new();
+    for line in metrics.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        if let Some(descriptor) = line.strip_prefix("# ") {
+            let mut tokens = descriptor.splitn(3, ' ');
+            let (Some(kind), Some(name)) = (tokens.next(), tokens.next()) else {
+                continue;
+            };
+            let text = tokens.next().unwrap_or_default();
+            let family = match families.last_mut() {
+                Some(family) if family.name == name => family,
+                _ => {
+                    families.push(MetricFamily {
+                        name,
+                        kind: "unknown",
+                        ..Default::default()
+                    });
+                    families.last_mut().unwrap()
+                }
+            };
+            match kind {
+                "TYPE" => family.kind = text,
+                "HELP" => family.help = text,
+                "UNIT" => family.unit = text,
+                _ => {}
+            }
+        } else if !line.starts_with('#') {
+            let Some((name, labels, value)) = parse_sample(line) else {
+                continue;
+            };
+            let suffix = match families.last() {
+                Some(family) if name.starts_with(family.name) => &name[family.name.len()..],
+                _ => {
+                    families.push(MetricFamily {
+                        name,
+                        kind: "unknown",
+                        ..Default::default()
+                    });
+                    ""
+                }
+            };
+            families.last_mut().unwrap().samples.push(MetricSample {
+                suffix,
+                labels,
+                value,
+            });
+        }
+    }
+    families
+}
+
+/// Parses a sample line `name{label="value",...} value [timestamp]`.
+fn parse_sample(line: &str) -> Option<(&str, Labels, &str)> {
+    let end = line.find(['{', ' '])?;
+    let name = &line[..end];
+    let mut labels = Labels::new();
+    let mut rest = &line[end..];
+    if let Some(mut chars) = rest.strip_prefix('{') {
+        loop {
+            chars = chars.trim_start_matches(',');
+            if let Some(r) = chars.strip_prefix('}') {
+                rest = r;
+                break;
+            }
+            let (key, r) = chars.split_once("=\"")?;
+            let mut value = String::new();
+            let mut escaped = false;
+            let mut end = None;
+            for (i, c) in r.char_indices() {
+                match (escaped, c) {
+                    (false, '\\') => escaped = true,
+                    (false, '"') => {
+                        end = Some(i);
+                        break;
+                    }
+                    (true, 'n') => {
+                        value.push('\n');
+                        escaped = false;
+                    }
+                    (_, c) => {
+                        value.push(c);
+                        escaped = false;
+                    }
+                }
+            }
+            let end = end?;
+            labels.push((key.to_string(), value));
+            chars = &r[end + 1..];
+        }
+    }
+    let value = rest.split_whitespace().next()?;
+    Some((name, labels, value))
+}
+
+fn attributes(labels: &[(String, String)]) -> Vec<Value> {
+    labels
+        .iter()
+        .map(|(key, value)| json!({"key": key, "value": {"stringValue": value}}))
+        .collect()
+}
+
+fn number_value(value: &str) -> Option<(&'static str, Value)> {
+    if let Ok(i) = value.parse::<i64>() {
+        return Some(("asInt", Value::String(i.to_string())));
+    }
+    let f = value.parse::<f64>().ok().filter(|f| f.is_finite())?;
+    Some(("asDouble", json!(f)))
+}
+
+impl MetricFamily<'_> {
+    fn to_otlp(&self, start_time: &str, time: &str) -> Option<Value> {
+        let mut metric = Map::new();
+        metric.insert("name".into(), self.name.into());
+        metric.insert("description".into(), self.help.into());
+        metric.insert("unit".into(), unit(self.unit).into());
+        let data = match self.kind {
+            "counter" => json!({
+                "dataPoints": self.number_data_points(&["_total", ""], start_time, time),
+                "aggregationTemporality": AGGREGATION_TEMPORALITY_CUMULATIVE,
+                "isMonotonic": true,
+            }),
+            "histogram" => json!({
+                "dataPoints": self.histogram_data_points(start_time, time),
+                "aggregationTemporality": AGGREGATION_TEMPORALITY_CUMULATIVE,
+            }),
+            _ => json!({"dataPoints": self.number_data_points(&["", "_info"], start_time, time)}),
+        };
+        if !data["dataPoints"]
+            .as_array()
+            .is_some_and(|points| !points.is_empty())
+        {
+            return None;
+        }
+        let kind = match self.kind {
+            "counter" => "sum",
+            "histogram" => "histogram",
+            _ => "gauge",
+        };
+        metric.insert(kind.into(), data);
+        Some(Value::Object(metric))
+    }
+
+    fn number_data_points(&self, suffixes: &[&str], start_time: &str, time: &str) -> Vec<Value> {
+        self.samples
+            .iter()
+            .filter(|sample| suffixes.contains(&sample.suffix))
+            .filter_map(|sample| {
+                let (key, value) = number_value(sample.value)?;
+                let mut point = json!({
+                    "attributes": attributes(&sample.labels),
+                    "startTimeUnixNano": start_time,
+                    "timeUnixNano": time,
+                });
+                point[key] = value;
+                Some(point)
+            })
+            .collect()
+    }
+
+    fn histogram_data_points(&self, start_time: &str, time: &str) -> Vec<Value> {
+        #[derive(Default)]
+        struct Series {
+            bounds: Vec<f64>,
+            cumulative_counts: Vec<u64>,
+            count: u64,
+            sum: f64,
+        }
+        let mut index = HashMap::new();
+        let mut series: Vec<(Labels, Series)> = Vec::new();
+        for sample in &self.samples {
+            let mut labels = sample.labels.clone();
+            let le = labels
+                .iter()
+                .position(|(key, _)| key == "le")
+                .map(|i| labels.remove(i).1);
+            let i = *index.entry(labels.clone()).or_insert_with(|| {
+                series.push((labels, Series::default()));
+                series.len() - 1
+            });
+            let s = &mut series[i].1;
+            match (sample.suffix, le) {
+                ("_bucket", Some(le)) => {
+                    let Ok(count) = sample.value.parse::<f64>() else {
+                        continue;
+                    };
+                    if le != INF {
+                        let Ok(bound) = le.parse::<f64>() else {
+                            continue;
+                        };
+                        s.bounds.push(bound);
+                    }
+                    s.cumulative_counts.push(count as u64);
+                }
+                ("_count", None) => s.count = sample.value.parse::<f64>().unwrap_or(0.0) as u64,
+                ("_sum", None) => s.sum = sample.value.parse().unwrap_or(0.0),
+                _ => {}
+            }
+        }
+        series
+            .into_iter()
+            .map(|(labels, s)| {
+                let mut previous = 0;
+                let mut bucket_counts = s
+                    .cumulative_counts
+                    .iter()
+                    .map(|c| {
+                        let count = c.saturating_sub(previous);
+                        previous = *c;
+                        count.to_string()
+                    })
+                    .collect::<Vec<_>>();
+                // the buckets are one more than the bounds, the last one being unbounded
+                bucket_counts.resize(s.bounds.len() + 1, "0".into());
+                json!({
+                    "attributes": attributes(&labels),
+                    "startTimeUnixNano": start_time,
+                    "timeUnixNano": time,
+                    "count": s.count.to_string(),
+                    "sum": s.sum,
+                    "bucketCounts": bucket_counts,
+                    "explicitBounds": s.bounds,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Converts an OpenMetrics unit to a UCUM unit, as recommended by OpenTelemetry.
+fn unit(unit: &str) -> &str {
+    match unit {
+        "bytes" => "By",
+        "seconds" => "s",
+        "milliseconds" => "ms",
+        "microseconds" => "us",
+        "ratios" => "1",
+        unit => unit,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use serde_json::json;
+
+    use super::{encode, resource_attributes};
+
+    #[test]
+    fn test_encode() {
+        let metrics = "\
+# HELP zenoh_build Zenoh build version.
+# TYPE zenoh_build info
+zenoh_build_info{local_id=\"r1\",version=\"v1.0\"} 1
+# HELP zenoh_tx_bytes Count of transport messages bytes sent.
+# TYPE zenoh_tx_bytes counter
+# UNIT zenoh_tx_bytes bytes
+zenoh_tx_bytes_total{local_id=\"r1\",remote_id=\"a \\\"b\\\"\"} 10
+# HELP zenoh_transports_opened Count of transports currently opened.
+# TYPE zenoh_transports_opened gauge
+zenoh_transports_opened{local_id=\"r1\"} 2
+# HELP zenoh_tx_payload Histogram of network messages payload sent.
+# TYPE zenoh_tx_payload histogram
+zenoh_tx_payload_sum{space=\"user\"} 40.0
+zenoh_tx_payload_count{space=\"user\"} 3
+zenoh_tx_payload_bucket{le=\"0.0\",space=\"user\"} 1
+zenoh_tx_payload_bucket{le=\"32.0\",space=\"user\"} 3
+zenoh_tx_payload_bucket{le=\"+Inf\",space=\"user\"} 3
+# EOF
+";
+        let mut attributes = Vec::new();
+        resource_attributes(
+            &mut attributes,
+            "",
+            &json!({"name": "strawberry", "host": {"cpus": 4, "tags": ["a"]}, "none": null}),
+        );
+        let start = UNIX_EPOCH + Duration::from_secs(1);
+        let time = UNIX_EPOCH + Duration::from_secs(2);
+        let request = encode(metrics, attributes, "v1.0", start, time);
+        let resource_metrics = &request["resourceMetrics"][0];
+        assert_eq!(
+            resource_metrics["resource"]["attributes"],
+            json!([
+                {"key": "host.cpus", "value": {"intValue": "4"}},
+                {"key": "host.tags", "value": {"arrayValue": {"values": [{"stringValue": "a"}]}}},
+                {"key": "name", "value": {"stringValue": "strawberry"}},
+            ])
+        );
+        let metrics = &resource_metrics["scopeMetrics"][0]["metrics"];
+        assert_eq!(metrics.as_array().unwrap().len(), 4);
+
+        assert_eq!(metrics[0]["name"], "zenoh_build");
+        assert_eq!(metrics[0]["gauge"]["dataPoints"][0]["asInt"], "1");
+
+        assert_eq!(
+            metrics[1],
+            json!({
+                "name": "zenoh_tx_bytes",
+                "description": "Count of transport messages bytes sent.",
+                "unit": "By",
+                "sum": {
+                    "dataPoints": [{
+                        "attributes": [
+                            {"key": "local_id", "value": {"stringValue": "r1"}},
+                            {"key": "remote_id", "value": {"stringValue": "a \"b\""}},
+                        ],
+                        "startTimeUnixNano": "1000000000",
+                        "timeUnixNano": "2000000000",
+                        "asInt": "10",
+                    }],
+                    "aggregationTemporality": 2,
+                    "isMonotonic": true,
+                },
+            })
+        );
+
+        assert_eq!(metrics[2]["gauge"]["dataPoints"][0]["asInt"], "2");
+
+        let histogram = &metrics[3]["histogram"];
+        assert_eq!(histogram["aggregationTemporality"], 2);
+        let point = &histogram["dataPoints"][0];
+        assert_eq!(
+            point["attributes"],
+            json!([{"key": "space", "value": {"stringValue": "user"}}])
+        );
+        assert_eq!(point["count"], "3");
+        assert_eq!(point["sum"], 40.0);
+        assert_eq!(point["explicitBounds"], json!([0.0, 32.0]));
+        assert_eq!(point["bucketCounts"], json!(["1", "2", "0"]));
+    }
+}
@@ -4,6 +4,7 @@ use std::{
     hash::Hash,
     iter,
     sync::{Arc, RwLock},
+    time::SystemTime,
 };
 
 use prometheus_client::{
@@ -16,6 +17,7 @@ use prometheus_client::{
     },
     registry::{Registry, Unit},
 };
+use serde_json::json;
 use zenoh_keyexpr::keyexpr;
 use zenoh_protocol::core::{WhatAmI, ZenohIdProto};
 
@@ -24,13 +26,15 @@ use crate::{
         TransportFamily, TransportFamilyCollector, TransportMetric, COLLECT_DISCONNECTED,
         COLLECT_PER_KEY, COLLECT_PER_LINK, COLLECT_PER_TRANSPORT,
     },
-    histogram::{Histogram, HistogramBuckets, PAYLOAD_SIZE_BUCKETS},
+    histogram::{Histogram, HistogramBuckets, LATENCY_BUCKETS, PAYLOAD_SIZE_BUCKETS},
     keys::{HistogramPerKey, StatsKeysRegistry},
     labels::{
-        BytesLabels, LinkLabels, LocalityLabel, NetworkMessageDroppedPayloadLabels,
-        NetworkMessageLabels, NetworkMessagePayloadLabels, ProtocolLabels, ResourceDeclaredLabels,
-        ResourceLabel, TransportLabels, TransportMessageLabels,
+        BytesLabels, LatencyLabels, LinkLabels, LocalityLabel, NetworkMessageDroppedPayloadLabels,
+        NetworkMessageLabels, NetworkMessagePayloadLabels, NetworkMessageScheduledLabels,
+        ProtocolLabels, ResourceDeclaredLabels, ResourceLabel, TransportLabels,
+        TransportMessageLabels,
     },
+    otlp,
     stats::{init_stats, StatsPath},
     Rx, StatsDirection, StatsKeysTree, TransportStats, Tx,
 };
@@ -40,6 +44,7 @@ pub struct StatsRegistry(Arc<StatsRegistryInner>);
 
 impl StatsRegistry {
     pub fn new(zid: ZenohIdProto, whatami: WhatAmI, build_version: impl Into<String>) -> Self {
+        let build_version = build_version.into();
         let stats_keys = StatsKeysRegistry::default();
         let mut registry = Registry::with_prefix_and_labels(
             "zenoh",
@@ -52,7 +57,7 @@ impl StatsRegistry {
         registry.register(
             "build",
             "Zenoh build version",
-            Info::new([("version", build_version.into())]),
+            Info::new([("version", build_version.clone())]),
         );
         let transports_opened = Gauge::default();
         registry.register(
@@ -82,6 +87,30 @@ impl StatsRegistry {
         let network_message_payload_per_key = array::from_fn(|_dir| {
             TransportFamily::new_with_constructor((PAYLOAD_SIZE_BUCKETS, stats_keys.clone()))
         });
+        let publication_latency_per_key =
+            TransportFamily::new_with_constructor((LATENCY_BUCKETS, stats_keys.clone()));
+        registry.register_collector(Box::new(TransportFamilyCollector {
+            name: "publication_latency_per_key".into(),
+            help: "Histogram of the latency between the publication and the delivery of the samples per key".into(),
+            unit: Some(Unit::Other("microseconds".into())),
+            family: publication_latency_per_key.clone(),
+        }));
+        let query_round_trip_time_per_key =
+            TransportFamily::new_with_constructor((LATENCY_BUCKETS, stats_keys.clone()));
+        registry.register_collector(Box::new(TransportFamilyCollector {
+            name: "query_round_trip_time_per_key".into(),
+            help: "Histogram of the round-trip time between the queries and their replies per key"
+                .into(),
+            unit: Some(Unit::Other("microseconds".into())),
+            family: query_round_trip_time_per_key.clone(),
+        }));
+        let tx_network_message_scheduled = TransportFamily::default();
+        registry.register_collector(Box::new(TransportFamilyCollector {
+            name: "tx_network_message_scheduled".into(),
+            help: "Count of network messages scheduled on the links of multilink transports".into(),
+            unit: None,
+            family: tx_network_message_scheduled.clone(),
+        }));
         for dir in [Tx, Rx] {
             let action = match dir {
                 Tx => "sent",
@@ -126,6 +155,10 @@ impl StatsRegistry {
         }
         Self(Arc::new(StatsRegistryInner {
             registry: RwLock::new(registry),
+            zid,
+            whatami,
+            build_version,
+            start_time: SystemTime::now(),
             transports_opened,
             links_opened,
             resources_declared,
@@ -135,6 +168,9 @@ impl StatsRegistry {
             network_message_payload,
             network_message_dropped_payload,
             network_message_payload_per_key,
+            publication_latency_per_key,
+            query_round_trip_time_per_key,
+            tx_network_message_scheduled,
             stats_keys,
         }))
     }
@@ -166,6 +202,40 @@ impl StatsRegistry {
         Ok(())
     }
 
+    /// Encodes the metrics in the JSON encoding of an OTLP `ExportMetricsServiceRequest`.
+    ///
+    /// The resource of the metrics is identified by the `service.*` attributes of the registry, and
+    /// `attributes` is a JSON object of additional resource attributes, e.g. the metadata of the node.
+    pub fn encode_otlp(
+        &self,
+        attributes: &serde_json::Value,
+        per_transport: bool,
+        per_link: bool,
+        per_key: bool,
+    ) -> serde_json::Value {
+        let mut metrics = String::new();
+        self.encode_metrics(&mut metrics, per_transport, per_link, false, per_key)
+            .expect("metrics should be encodable");
+        let mut resource = Vec::new();
+        let service = json!({
+            "service": {
+                "name": "zenoh",
+                "instance": {"id": self.0.zid.to_string()},
+                "version": self.0.build_version,
+            },
+            "zenoh": {"whatami": self.0.whatami.to_string()},
+        });
+        otlp::resource_attributes(&mut resource, "", &service);
+        otlp::resource_attributes(&mut resource, "", attributes);
+        otlp::encode(
+            &metrics,
+            resource,
+            &self.0.build_version,
+            self.0.start_time,
+            SystemTime::now(),
+        )
+    }
+
     pub(crate) fn bytes(
         &self,
         direction: StatsDirection,
@@ -212,17 +282,48 @@ impl StatsRegistry {
         &self.0.network_message_payload_per_key[direction as usize]
     }
 
+    pub(crate) fn publication_latency_per_key(
+        &self,
+    ) -> &TransportFamily<LatencyLabels, HistogramPerKey, (HistogramBuckets, StatsKeysRegistry)>
+    {
+        &self.0.publication_latency_per_key
+    }
+
+    pub(crate) fn query_round_trip_time_per_key(
+        &self,
+    ) -> &TransportFamily<LatencyLabels, HistogramPerKey, (HistogramBuckets, StatsKeysRegistry)>
+    {
+        &self.0.query_round_trip_time_per_key
+    }
+
+    pub(crate) fn tx_network_message_scheduled(
+        &self,
+    ) -> &TransportFamily<NetworkMessageScheduledLabels, Counter> {
+        &self.0.tx_network_message_scheduled
+    }
+
     fn families(&self) -> impl Iterator<Item = (StatsDirection, &dyn TransportFamilyAny)> {
-        [Tx, Rx].into_iter().flat_map(|dir| {
-            iter::repeat(dir).zip([
-                &self.0.bytes[dir as usize] as &dyn TransportFamilyAny,
-                &self.0.transport_message[dir as usize],
-                &self.0.network_message[dir as usize],
-                &self.0.network_message_payload[dir as usize],
-                &self.0.network_message_dropped_payload[dir as usize],
-                &self.0.network_message_payload_per_key[dir as usize],
-            ])
-        })
+        [Tx, Rx]
+            .into_iter()
+            .flat_map(|dir| {
+                iter::repeat(dir).zip([
+                    &self.0.bytes[dir as usize] as &dyn TransportFamilyAny,
+                    &self.0.transport_message[dir as usize],
+                    &self.0.network_message[dir as usize],
+                    &self.0.network_message_payload[dir as usize],
+                    &self.0.network_message_dropped_payload[dir as usize],
+                    &self.0.network_message_payload_per_key[dir as usize],
+                ])
+            })
+            // latencies are observed on the reception of the samples and replies
+            .chain(iter::repeat(Rx).zip([
+                &self.0.publication_latency_per_key as &dyn TransportFamilyAny,
+                &self.0.query_round_trip_time_per_key,
+            ]))
+            .chain(iter::once((
+                Tx,
+                &self.0.tx_network_message_scheduled as &dyn TransportFamilyAny,
+            )))
     }
 
     pub fn merge_stats(&self, json: &mut serde_json::Value) {
@@ -285,6 +386,10 @@ impl StatsRegistry {
 #[derive(Debug)]
 struct StatsRegistryInner {
     registry: RwLock<Registry>,
+    zid: ZenohIdProto,
+    whatami: WhatAmI,
+    build_version: String,
+    start_time: SystemTime,
     transports_opened: Gauge,
     links_opened: Family<ProtocolLabels, Gauge>,
     resources_declared: Family<ResourceDeclaredLabels, Gauge>,
@@ -303,6 +408,11 @@ struct StatsRegistryInner {
         HistogramPerKey,
         (HistogramBuckets, StatsKeysRegistry),
     >; StatsDirection::NUM],
+    publication_latency_per_key:
+        TransportFamily<LatencyLabels, HistogramPerKey, (HistogramBuckets, StatsKeysRegistry)>,
+    query_round_trip_time_per_key:
+        TransportFamily<LatencyLabels, HistogramPerKey, (HistogramBuckets, StatsKeysRegistry)>,
+    tx_network_message_scheduled: TransportFamily<NetworkMessageScheduledLabels, Counter>,
     stats_keys: StatsKeysRegistry,
 }
 
@@ -5,9 +5,9 @@ use crate::{
     histogram::Histogram,
     keys::HistogramPerKey,
     labels::{
-        BytesLabels, LinkLabels, MessageLabel, NetworkMessageDroppedPayloadLabels,
-        NetworkMessageLabels, NetworkMessagePayloadLabels, SpaceLabel, TransportLabels,
-        TransportMessageLabels,
+        BytesLabels, LatencyLabels, LinkLabels, MessageLabel, NetworkMessageDroppedPayloadLabels,
+        NetworkMessageLabels, NetworkMessagePayloadLabels, NetworkMessageScheduledLabels,
+        SpaceLabel, TransportLabels, TransportMessageLabels,
     },
     ReasonLabel, Rx, StatsDirection, Tx,
 };
@@ -254,6 +254,19 @@ impl StatsPath<Counter> for NetworkMessageLabels {
     }
 }
 
+// Scheduling decisions have no counterpart in the JSON stats
+impl StatsPath<Counter> for NetworkMessageScheduledLabels {
+    fn incr_stats(
+        _direction: StatsDirection,
+        _transport: Option<&TransportLabels>,
+        _link: Option<&LinkLabels>,
+        _labels: &Self,
+        _collected: <Counter as TransportMetric>::Collected,
+        _json: &mut serde_json::Value,
+    ) {
+    }
+}
+
 impl StatsPath<Histogram> for NetworkMessagePayloadLabels {
     fn incr_stats(
         direction: StatsDirection,
@@ -290,6 +303,19 @@ impl StatsPath<HistogramPerKey> for NetworkMessagePayloadLabels {
     }
 }
 
+// Latencies have no counterpart in the JSON stats
+impl StatsPath<HistogramPerKey> for LatencyLabels {
+    fn incr_stats(
+        _direction: StatsDirection,
+        _transport: Option<&TransportLabels>,
+        _link: Option<&LinkLabels>,
+        _labels: &Self,
+        _collected: <HistogramPerKey as TransportMetric>::Collected,
+        _json: &mut serde_json::Value,
+    ) {
+    }
+}
+
 impl StatsPath<Histogram> for NetworkMessageDroppedPayloadLabels {
     fn incr_stats(
         direction: StatsDirection,
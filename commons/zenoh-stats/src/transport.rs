@@ -1,4 +1,7 @@
-use std::sync::{Arc, OnceLock};
+use std::{
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
 
 use zenoh_protocol::{
     core::{Locator, Priority, WhatAmI, ZenohIdProto},
@@ -9,8 +12,8 @@ use crate::{
     histogram::Histogram,
     keys::HistogramPerKey,
     labels::{
-        MessageLabel, NetworkMessageDroppedPayloadLabels, NetworkMessagePayloadLabels,
-        ProtocolLabel, SpaceLabel, TransportLabels, SHM_NUM,
+        LatencyLabels, MessageLabel, NetworkMessageDroppedPayloadLabels,
+        NetworkMessagePayloadLabels, ProtocolLabel, SpaceLabel, TransportLabels, SHM_NUM,
     },
     LinkStats, ReasonLabel, StatsDirection, StatsKeys, StatsRegistry, Tx,
 };
@@ -42,6 +45,8 @@ impl TransportStats {
             registry,
             transport,
             network_message_payload: Default::default(),
+            publication_latency: Default::default(),
+            query_round_trip_time: Default::default(),
             tx_no_link,
         }))
     }
@@ -119,6 +124,58 @@ impl TransportStats {
         histogram_per_key.observe(keys, payload_size as u64);
     }
 
+    /// Observes the latency between the publication of a sample received from this transport and
+    /// its delivery.
+    pub fn observe_publication_latency(
+        &self,
+        message: MessageLabel,
+        priority: Priority,
+        space: SpaceLabel,
+        keys: &StatsKeys,
+        latency: Duration,
+    ) {
+        if keys.is_empty() {
+            return;
+        }
+        self.0.publication_latency[priority as usize][message as usize][space as usize]
+            .get_or_init(|| {
+                let labels = LatencyLabels {
+                    space,
+                    priority: priority.into(),
+                    message,
+                };
+                self.registry()
+                    .publication_latency_per_key()
+                    .get_or_create_owned(self.transport(), None, &labels)
+            })
+            .observe(keys, latency.as_micros() as u64);
+    }
+
+    /// Observes the round-trip time between a query and a reply received from this transport.
+    pub fn observe_query_round_trip_time(
+        &self,
+        priority: Priority,
+        space: SpaceLabel,
+        keys: &StatsKeys,
+        round_trip_time: Duration,
+    ) {
+        if keys.is_empty() {
+            return;
+        }
+        self.0.query_round_trip_time[priority as usize][space as usize]
+            .get_or_init(|| {
+                let labels = LatencyLabels {
+                    space,
+                    priority: priority.into(),
+                    message: MessageLabel::Query,
+                };
+                self.registry()
+                    .query_round_trip_time_per_key()
+                    .get_or_create_owned(self.transport(), None, &labels)
+            })
+            .observe(keys, round_trip_time.as_micros() as u64);
+    }
+
     pub fn tx_observe_no_link(&self, msg: NetworkMessageRef) {
         self.0
             .tx_no_link
@@ -133,6 +190,9 @@ pub struct TransportStatsInner {
     #[allow(clippy::type_complexity)]
     network_message_payload: [[[[[OnceLock<(Histogram, HistogramPerKey)>; SpaceLabel::NUM]; SHM_NUM];
         MessageLabel::NUM]; Priority::NUM]; StatsDirection::NUM],
+    publication_latency:
+        [[[OnceLock<HistogramPerKey>; SpaceLabel::NUM]; MessageLabel::NUM]; Priority::NUM],
+    query_round_trip_time: [[OnceLock<HistogramPerKey>; SpaceLabel::NUM]; Priority::NUM],
     tx_no_link: DropStats,
 }
 
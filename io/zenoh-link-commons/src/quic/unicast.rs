@@ -41,6 +41,7 @@ use crate::{
         QuicMtuConfig, QuicTransportConfigurator, TlsClientConfig, TlsServerConfig,
         PROTOCOL_LEGACY, PROTOCOL_MULTI_STREAM, PROTOCOL_SINGLE_STREAM,
     },
+    tls::reload::CertificateWatcher,
     LinkUnicast, LinkUnicastTrait, NewLinkChannelSender,
 };
 
@@ -242,38 +243,11 @@ impl<F: AcceptorCallback> QuicServer<F> {
         let host = get_quic_host(&epaddr)?;
 
         // Server config
-        let mut server_crypto = TlsServerConfig::new(&epconf, is_secure)
-            .await
-            .map_err(|e| zerror!("Cannot create a new QUIC listener on {addr}: {e}"))?;
-
-        let multistream = if is_streamed {
-            Some(MultiStreamConfig::new(endpoint.metadata())?)
-        } else {
-            None
-        };
-        server_crypto.server_config.alpn_protocols = multistream
-            .as_ref()
-            .map(|m| m.alpn_protocols())
-            .unwrap_or(vec![PROTOCOL_LEGACY.into()]);
-
-        let quic_config: QuicServerConfig = server_crypto
-            .server_config
-            .try_into()
-            .map_err(|e| zerror!("Can not create a new QUIC listener on {addr}: {e}"))?;
+        let (server_config, tls_close_link_on_expiration) =
+            quic_server_config(endpoint, is_streamed, is_secure)
+                .await
+                .map_err(|e| zerror!("Cannot create a new QUIC listener on {addr}: {e}"))?;
 
-        let mut server_config = quinn::ServerConfig::with_crypto({
-            if is_secure {
-                Arc::new(quic_config)
-            } else {
-                Arc::new(PlainTextServerConfig::new(quic_config.into()))
-            }
-        });
-        {
-            let transport_config = Arc::get_mut(&mut server_config.transport).unwrap();
-            QuicTransportConfigurator(transport_config)
-                .configure_max_concurrent_streams(multistream.as_ref())
-                .configure_mtu(&QuicMtuConfig::try_from(&epconf)?);
-        }
         // Initialize the Endpoint
         let quic_endpoint = async {
             let socket = QuicSocketConfig::new(&epconf)
@@ -305,10 +279,30 @@ impl<F: AcceptorCallback> QuicServer<F> {
             endpoint.metadata(),
         )?;
 
+        if is_secure {
+            if let Some(watcher) = CertificateWatcher::new(&epconf)? {
+                let endpoint = endpoint.clone();
+                let quic_endpoint = quic_endpoint.clone();
+                zenoh_runtime::ZRuntime::Acceptor.spawn(watcher.run(
+                    acceptor_params.token.clone(),
+                    move || {
+                        let endpoint = endpoint.clone();
+                        let quic_endpoint = quic_endpoint.clone();
+                        async move {
+                            let (server_config, _) =
+                                quic_server_config(&endpoint, is_streamed, is_secure).await?;
+                            quic_endpoint.set_server_config(Some(server_config));
+                            Ok(())
+                        }
+                    },
+                ));
+            }
+        }
+
         Ok(Self {
             quic_acceptor: QuicAcceptor {
                 quic_endpoint,
-                tls_close_link_on_expiration: server_crypto.tls_close_link_on_expiration,
+                tls_close_link_on_expiration,
                 is_streamed,
                 inner: acceptor_params,
             },
@@ -318,6 +312,47 @@ impl<F: AcceptorCallback> QuicServer<F> {
     }
 }
 
+/// Builds the QUIC server config of the endpoint, returning it with its
+/// `close_link_on_expiration` setting.
+async fn quic_server_config(
+    endpoint: &EndPoint,
+    is_streamed: bool,
+    is_secure: bool,
+) -> ZResult<(quinn::ServerConfig, bool)> {
+    let epconf = endpoint.config();
+    let mut server_crypto = TlsServerConfig::new(&epconf, is_secure).await?;
+
+    let multistream = if is_streamed {
+        Some(MultiStreamConfig::new(endpoint.metadata())?)
+    } else {
+        None
+    };
+    server_crypto.server_config.alpn_protocols = multistream
+        .as_ref()
+        .map(|m| m.alpn_protocols())
+        .unwrap_or(vec![PROTOCOL_LEGACY.into()]);
+
+    let quic_config: QuicServerConfig = server_crypto
+        .server_config
+        .try_into()
+        .map_err(|e| zerror!("{e}"))?;
+
+    let mut server_config = quinn::ServerConfig::with_crypto({
+        if is_secure {
+            Arc::new(quic_config)
+        } else {
+            Arc::new(PlainTextServerConfig::new(quic_config.into()))
+        }
+    });
+    {
+        let transport_config = Arc::get_mut(&mut server_config.transport).unwrap();
+        QuicTransportConfigurator(transport_config)
+            .configure_max_concurrent_streams(multistream.as_ref())
+            .configure_mtu(&QuicMtuConfig::try_from(&epconf)?);
+    }
+    Ok((server_config, server_crypto.tls_close_link_on_expiration))
+}
+
 pub struct QuicClientBuilder<'a> {
     endpoint: &'a EndPoint,
     is_streamed: bool,
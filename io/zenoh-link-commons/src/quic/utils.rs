@@ -24,7 +24,6 @@ use quinn::TransportConfig;
 use rustls::{
     crypto::CryptoProvider,
     pki_types::{CertificateDer, PrivateKeyDer, TrustAnchor},
-    server::WebPkiClientVerifier,
     version::TLS13,
     ClientConfig, RootCertStore, ServerConfig,
 };
@@ -44,7 +43,10 @@ use crate::{
         plaintext::{SkipServerVerification, SELF_SIGNED_CERT},
         unicast::MultiStreamConfig,
     },
-    tls::{config::*, WebPkiVerifierAnyServerName},
+    tls::{
+        client_cert_verifier, config::*, load_crls, server_cert_verifier,
+        WebPkiVerifierAnyServerName,
+    },
     ConfigurationInspector, LinkAuthId,
 };
 
@@ -170,6 +172,16 @@ impl ConfigurationInspector<ZenohConfig> for TlsConfigurator {
             false => ps.push((TLS_CLOSE_LINK_ON_EXPIRATION, "false")),
         }
 
+        if let Some(crl) = c.crl() {
+            ps.push((TLS_CRL_FILE, crl));
+        }
+
+        let reload_interval_ms;
+        if let Some(interval) = c.reload_interval_ms() {
+            reload_interval_ms = interval.to_string();
+            ps.push((TLS_RELOAD_INTERVAL_MS, &reload_interval_ms));
+        }
+
         Ok(parameters::from_iter(ps.drain(..)))
     }
 }
@@ -254,7 +266,7 @@ impl TlsServerConfig {
                 || Err(zerror!("Missing root certificates while mTLS is enabled.")),
                 Ok,
             )?;
-            let client_auth = WebPkiClientVerifier::builder(root_cert_store.into()).build()?;
+            let client_auth = client_cert_verifier(root_cert_store, load_crls(config).await?)?;
             ServerConfig::builder_with_protocol_versions(&[&TLS13])
                 .with_client_cert_verifier(client_auth)
                 .with_single_cert(certs, keys.remove(0))
@@ -372,6 +384,7 @@ impl TlsClientConfig {
             tracing::debug!("Loading user-generated certificates.");
             root_cert_store.extend(custom_root_cert.roots);
         }
+        let crls = load_crls(config).await?;
 
         let cc = if tls_client_server_auth {
             tracing::debug!("Loading client authentication key and certificate...");
@@ -412,14 +425,14 @@ impl TlsClientConfig {
 
             if tls_server_name_verification {
                 builder
-                    .with_root_certificates(root_cert_store)
+                    .with_webpki_verifier(server_cert_verifier(root_cert_store, crls)?)
                     .with_client_auth_cert(certs, keys.remove(0))
             } else {
                 builder
                     .dangerous()
-                    .with_custom_certificate_verifier(Arc::new(WebPkiVerifierAnyServerName::new(
-                        root_cert_store,
-                    )))
+                    .with_custom_certificate_verifier(Arc::new(
+                        WebPkiVerifierAnyServerName::new(root_cert_store).with_crls(crls)?,
+                    ))
                     .with_client_auth_cert(certs, keys.remove(0))
             }
             .map_err(|e| zerror!("Bad certificate/key: {}", e))?
@@ -427,14 +440,14 @@ impl TlsClientConfig {
             let builder = ClientConfig::builder();
             if tls_server_name_verification {
                 builder
-                    .with_root_certificates(root_cert_store)
+                    .with_webpki_verifier(server_cert_verifier(root_cert_store, crls)?)
                     .with_no_client_auth()
             } else {
                 builder
                     .dangerous()
-                    .with_custom_certificate_verifier(Arc::new(WebPkiVerifierAnyServerName::new(
-                        root_cert_store,
-                    )))
+                    .with_custom_certificate_verifier(Arc::new(
+                        WebPkiVerifierAnyServerName::new(root_cert_store).with_crls(crls)?,
+                    ))
                     .with_no_client_auth()
             }
         };
@@ -1,16 +1,23 @@
-use alloc::vec::Vec;
+use alloc::{sync::Arc, vec::Vec};
+use std::io::Cursor;
 
 use rustls::{
+    client::WebPkiServerVerifier,
     client::{
         danger::{ServerCertVerified, ServerCertVerifier},
         verify_server_cert_signed_by_trust_anchor,
     },
     crypto::{verify_tls12_signature, verify_tls13_signature},
-    pki_types::{CertificateDer, ServerName, UnixTime},
-    server::ParsedCertificate,
-    RootCertStore,
+    pki_types::{CertificateDer, CertificateRevocationListDer, ServerName, UnixTime},
+    server::{danger::ClientCertVerifier, ParsedCertificate, WebPkiClientVerifier},
+    CertificateError, OtherError, RootCertStore,
 };
-use webpki::ALL_VERIFICATION_ALGS;
+use webpki::{
+    CertRevocationList, EndEntityCert, KeyUsage, OwnedCertRevocationList, RevocationCheckDepth,
+    RevocationOptionsBuilder, UnknownStatusPolicy, ALL_VERIFICATION_ALGS,
+};
+use zenoh_protocol::core::endpoint::Config;
+use zenoh_result::{zerror, ZResult};
 
 pub mod config {
     pub const TLS_ROOT_CA_CERTIFICATE_FILE: &str = "root_ca_certificate_file";
@@ -42,15 +49,70 @@ pub mod config {
     pub const TLS_CLOSE_LINK_ON_EXPIRATION: &str = "close_link_on_expiration";
     pub const TLS_CLOSE_LINK_ON_EXPIRATION_DEFAULT: bool = false;
 
+    pub const TLS_CRL_FILE: &str = "crl_file";
+    pub const TLS_CRL_RAW: &str = "crl_raw";
+
+    /// The interval in milliseconds to check the certificate files of a listener for
+    /// modifications, the files not being watched if `0`.
+    pub const TLS_RELOAD_INTERVAL_MS: &str = "reload_interval_ms";
+    pub const TLS_RELOAD_INTERVAL_MS_DEFAULT: u64 = 0;
+
     /// The time duration in milliseconds to wait for the TLS handshake to complete.
     pub const TLS_HANDSHAKE_TIMEOUT_MS: &str = "tls_handshake_timeout_ms";
     pub const TLS_HANDSHAKE_TIMEOUT_MS_DEFAULT: u64 = 10_000;
 }
 
+/// Loads the certificate revocation lists of the endpoint configuration, if any.
+pub async fn load_crls(config: &Config<'_>) -> ZResult<Vec<CertificateRevocationListDer<'static>>> {
+    let pem = if let Some(value) = config.get(config::TLS_CRL_RAW) {
+        value.as_bytes().to_vec()
+    } else if let Some(value) = config.get(config::TLS_CRL_FILE) {
+        tokio::fs::read(value)
+            .await
+            .map_err(|e| zerror!("Invalid TLS CRL file: {}", e))?
+    } else {
+        return Ok(Vec::new());
+    };
+    let crls = rustls_pemfile::crls(&mut Cursor::new(pem))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| zerror!("Error processing CRL: {err}."))?;
+    if crls.is_empty() {
+        return Err(zerror!("No certificate revocation list found.").into());
+    }
+    Ok(crls)
+}
+
+/// Builds the verifier of the client certificates for mTLS, checking the revocation status of the
+/// end-entity certificates against `crls`.
+pub fn client_cert_verifier(
+    roots: RootCertStore,
+    crls: Vec<CertificateRevocationListDer<'static>>,
+) -> ZResult<Arc<dyn ClientCertVerifier>> {
+    Ok(WebPkiClientVerifier::builder(roots.into())
+        .with_crls(crls)
+        .only_check_end_entity_revocation()
+        .allow_unknown_revocation_status()
+        .build()?)
+}
+
+/// Builds the verifier of the server certificates, checking the revocation status of the
+/// end-entity certificates against `crls`.
+pub fn server_cert_verifier(
+    roots: RootCertStore,
+    crls: Vec<CertificateRevocationListDer<'static>>,
+) -> ZResult<Arc<WebPkiServerVerifier>> {
+    Ok(WebPkiServerVerifier::builder(roots.into())
+        .with_crls(crls)
+        .only_check_end_entity_revocation()
+        .allow_unknown_revocation_status()
+        .build()?)
+}
+
 impl ServerCertVerifier for WebPkiVerifierAnyServerName {
     /// Will verify the certificate is valid in the following ways:
     /// - Signed by a  trusted `RootCertStore` CA
     /// - Not Expired
+    /// - Not revoked by the end-entity CRLs, if any
     fn verify_server_cert(
         &self,
         end_entity: &CertificateDer<'_>,
@@ -67,6 +129,9 @@ impl ServerCertVerifier for WebPkiVerifierAnyServerName {
             now,
             ALL_VERIFICATION_ALGS,
         )?;
+        if !self.crls.is_empty() {
+            self.verify_revocation(end_entity, intermediates, now)?;
+        }
         Ok(ServerCertVerified::assertion())
     }
 
@@ -110,6 +175,7 @@ impl ServerCertVerifier for WebPkiVerifierAnyServerName {
 #[derive(Debug)]
 pub struct WebPkiVerifierAnyServerName {
     roots: RootCertStore,
+    crls: Vec<CertRevocationList<'static>>,
 }
 
 #[allow(unreachable_pub)]
@@ -118,7 +184,157 @@ impl WebPkiVerifierAnyServerName {
     ///
     /// `roots` is the set of trust anchors to trust for issuing server certs.
     pub fn new(roots: RootCertStore) -> Self {
-        Self { roots }
+        Self {
+            roots,
+            crls: Vec::new(),
+        }
+    }
+
+    /// Verifies the revocation status of the server certs against `crls`, allowing the certs
+    /// whose issuer has no CRL.
+    pub fn with_crls(
+        mut self,
+        crls: impl IntoIterator<Item = CertificateRevocationListDer<'static>>,
+    ) -> ZResult<Self> {
+        for crl in crls {
+            let crl = OwnedCertRevocationList::from_der(&crl)
+                .map_err(|e| zerror!("Invalid certificate revocation list: {e}"))?;
+            self.crls.push(crl.into());
+        }
+        Ok(self)
+    }
+
+    fn verify_revocation(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        now: UnixTime,
+    ) -> Result<(), rustls::Error> {
+        let crls: Vec<&CertRevocationList> = self.crls.iter().collect();
+        let revocation = RevocationOptionsBuilder::new(&crls)
+            .map_err(|_| rustls::Error::General("Missing certificate revocation lists".into()))?
+            .with_depth(RevocationCheckDepth::EndEntity)
+            .with_status_policy(UnknownStatusPolicy::Allow)
+            .build();
+        let cert = EndEntityCert::try_from(end_entity)
+            .map_err(|_| rustls::Error::InvalidCertificate(CertificateError::BadEncoding))?;
+        cert.verify_for_usage(
+            ALL_VERIFICATION_ALGS,
+            &self.roots.roots,
+            intermediates,
+            now,
+            KeyUsage::server_auth(),
+            Some(revocation),
+            None,
+        )
+        .map_err(|e| match e {
+            webpki::Error::CertRevoked => CertificateError::Revoked,
+            e => CertificateError::Other(OtherError(Arc::new(e))),
+        })?;
+        Ok(())
+    }
+}
+
+pub mod reload {
+    use std::{
+        future::Future,
+        path::{Path, PathBuf},
+        time::SystemTime,
+    };
+
+    use tokio::time::Duration;
+    use tokio_util::sync::CancellationToken;
+    use zenoh_protocol::core::endpoint::Config;
+    use zenoh_result::{zerror, ZResult};
+
+    use super::config::*;
+
+    /// Watches the certificate files of a listener, to reload its TLS configuration for the new
+    /// connections when they are modified.
+    #[derive(Debug)]
+    pub struct CertificateWatcher {
+        files: Vec<(PathBuf, Option<(SystemTime, u64)>)>,
+        interval: Duration,
+    }
+
+    impl CertificateWatcher {
+        /// Returns `None` if no reload interval is configured, or if the listener has no
+        /// certificate file to watch.
+        pub fn new(config: &Config<'_>) -> ZResult<Option<Self>> {
+            let interval_ms = match config.get(TLS_RELOAD_INTERVAL_MS) {
+                Some(s) => s
+                    .parse()
+                    .map_err(|_| zerror!("Unknown reload interval argument: {}", s))?,
+                None => TLS_RELOAD_INTERVAL_MS_DEFAULT,
+            };
+            if interval_ms == 0 {
+                return Ok(None);
+            }
+            let files: Vec<_> = [
+                TLS_LISTEN_CERTIFICATE_FILE,
+                TLS_LISTEN_PRIVATE_KEY_FILE,
+                TLS_ROOT_CA_CERTIFICATE_FILE,
+                TLS_CRL_FILE,
+            ]
+            .iter()
+            .filter_map(|key| config.get(key))
+            .map(|path| {
+                let path = PathBuf::from(path);
+                let version = Self::version(&path);
+                (path, version)
+            })
+            .collect();
+            if files.is_empty() {
+                return Ok(None);
+            }
+            Ok(Some(Self {
+                files,
+                interval: Duration::from_millis(interval_ms),
+            }))
+        }
+
+        fn version(path: &Path) -> Option<(SystemTime, u64)> {
+            let metadata = std::fs::metadata(path).ok()?;
+            Some((metadata.modified().ok()?, metadata.len()))
+        }
+
+        /// Waits for a watched file to be modified.
+        pub async fn changed(&mut self) {
+            loop {
+                tokio::time::sleep(self.interval).await;
+                let mut changed = false;
+                for (path, version) in self.files.iter_mut() {
+                    let current = Self::version(path);
+                    if current != *version {
+                        *version = current;
+                        changed = true;
+                    }
+                }
+                if changed {
+                    return;
+                }
+            }
+        }
+
+        /// Calls `reload` each time a watched file is modified, until `token` is cancelled.
+        ///
+        /// The previous configuration is kept if `reload` fails, e.g. while the files are
+        /// partially written.
+        pub async fn run<F, Fut>(mut self, token: CancellationToken, mut reload: F)
+        where
+            F: FnMut() -> Fut,
+            Fut: Future<Output = ZResult<()>>,
+        {
+            loop {
+                tokio::select! {
+                    _ = token.cancelled() => break,
+                    _ = self.changed() => match reload().await {
+                        Ok(()) => tracing::info!("Reloaded TLS certificates of {:?}", self.files.iter().map(|(p, _)| p).collect::<Vec<_>>()),
+                        Err(e) => tracing::warn!("Cannot reload TLS certificates, keeping the previous ones: {e}"),
+                    },
+                }
+            }
+        }
     }
 }
 
@@ -77,7 +77,10 @@ use zenoh_link_vsock::{LinkManagerUnicastVsock, VsockLocatorInspector, VSOCK_LOC
 #[cfg(feature = "transport_ws")]
 pub use zenoh_link_ws as ws;
 #[cfg(feature = "transport_ws")]
-use zenoh_link_ws::{LinkManagerUnicastWs, WsLocatorInspector, WS_LOCATOR_PREFIX};
+use zenoh_link_ws::{
+    LinkManagerUnicastWs, WsLocatorInspector, WssConfigurator, WssLocatorInspector,
+    WSS_LOCATOR_PREFIX, WS_LOCATOR_PREFIX,
+};
 pub use zenoh_protocol::core::{EndPoint, Locator};
 use zenoh_result::{bail, ZResult};
 
@@ -93,6 +96,7 @@ pub enum LinkKind {
     UnixsockStream,
     Vscock,
     Ws,
+    Wss,
 }
 
 impl LinkKind {
@@ -120,6 +124,8 @@ impl LinkKind {
                 UNIXSOCKSTREAM_LOCATOR_PREFIX => supported_links.push(LinkKind::UnixsockStream),
                 #[cfg(feature = "transport_ws")]
                 WS_LOCATOR_PREFIX => supported_links.push(LinkKind::Ws),
+                #[cfg(feature = "transport_ws")]
+                WSS_LOCATOR_PREFIX => supported_links.push(LinkKind::Wss),
                 #[cfg(feature = "transport_serial")]
                 SERIAL_LOCATOR_PREFIX => supported_links.push(LinkKind::Serial),
                 #[cfg(feature = "transport_unixpipe")]
@@ -174,6 +180,8 @@ impl TryFrom<&Locator> for LinkKind {
             UNIXSOCKSTREAM_LOCATOR_PREFIX => Ok(LinkKind::UnixsockStream),
             #[cfg(feature = "transport_ws")]
             WS_LOCATOR_PREFIX => Ok(LinkKind::Ws),
+            #[cfg(feature = "transport_ws")]
+            WSS_LOCATOR_PREFIX => Ok(LinkKind::Wss),
             #[cfg(feature = "transport_serial")]
             SERIAL_LOCATOR_PREFIX => Ok(LinkKind::Serial),
             #[cfg(feature = "transport_unixpipe")]
@@ -209,6 +217,8 @@ pub const ALL_SUPPORTED_LINKS: &[LinkKind] = &[
     LinkKind::Udp,
     #[cfg(feature = "transport_ws")]
     LinkKind::Ws,
+    #[cfg(feature = "transport_ws")]
+    LinkKind::Wss,
     #[cfg(all(feature = "transport_unixsock-stream", target_family = "unix"))]
     LinkKind::UnixsockStream,
     #[cfg(feature = "transport_serial")]
@@ -233,6 +243,8 @@ pub struct LocatorInspector {
     udp_inspector: UdpLocatorInspector,
     #[cfg(feature = "transport_ws")]
     ws_inspector: WsLocatorInspector,
+    #[cfg(feature = "transport_ws")]
+    wss_inspector: WssLocatorInspector,
     #[cfg(all(feature = "transport_unixsock-stream", target_family = "unix"))]
     unixsock_stream_inspector: UnixSockStreamLocatorInspector,
     #[cfg(feature = "transport_serial")]
@@ -261,6 +273,8 @@ impl LocatorInspector {
             LinkKind::UnixsockStream => self.unixsock_stream_inspector.is_reliable(locator),
             #[cfg(feature = "transport_ws")]
             LinkKind::Ws => self.ws_inspector.is_reliable(locator),
+            #[cfg(feature = "transport_ws")]
+            LinkKind::Wss => self.wss_inspector.is_reliable(locator),
             #[cfg(feature = "transport_serial")]
             LinkKind::Serial => self.serial_inspector.is_reliable(locator),
             #[cfg(feature = "transport_unixpipe")]
@@ -290,6 +304,8 @@ impl LocatorInspector {
             LinkKind::UnixsockStream => self.unixsock_stream_inspector.is_multicast(locator).await,
             #[cfg(feature = "transport_ws")]
             LinkKind::Ws => self.ws_inspector.is_multicast(locator).await,
+            #[cfg(feature = "transport_ws")]
+            LinkKind::Wss => self.wss_inspector.is_multicast(locator).await,
             #[cfg(feature = "transport_serial")]
             LinkKind::Serial => self.serial_inspector.is_multicast(locator).await,
             #[cfg(feature = "transport_unixpipe")]
@@ -313,6 +329,8 @@ pub struct LinkConfigurator {
     tls_inspector: TlsConfigurator,
     #[cfg(feature = "transport_unixpipe")]
     unixpipe_inspector: UnixPipeConfigurator,
+    #[cfg(feature = "transport_ws")]
+    wss_inspector: WssConfigurator,
 }
 
 impl LinkConfigurator {
@@ -360,6 +378,10 @@ impl LinkConfigurator {
                 self.unixpipe_inspector.inspect_config(config),
             );
         }
+        #[cfg(feature = "transport_ws")]
+        {
+            insert_config(LinkKind::Wss, self.wss_inspector.inspect_config(config));
+        }
         (configs, errors)
     }
 }
@@ -396,6 +418,10 @@ impl LinkManagerBuilderUnicast {
             )),
             #[cfg(feature = "transport_ws")]
             LinkKind::Ws => Ok(std::sync::Arc::new(LinkManagerUnicastWs::new(_manager))),
+            #[cfg(feature = "transport_ws")]
+            LinkKind::Wss => Ok(std::sync::Arc::new(LinkManagerUnicastWs::new_secure(
+                _manager,
+            ))),
             #[cfg(feature = "transport_serial")]
             LinkKind::Serial => Ok(std::sync::Arc::new(LinkManagerUnicastSerial::new(_manager))),
             #[cfg(feature = "transport_unixpipe")]
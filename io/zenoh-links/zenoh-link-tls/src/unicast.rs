@@ -16,24 +16,28 @@ use std::{
     convert::TryInto,
     fmt::{self, Debug},
     net::SocketAddr,
-    sync::Arc,
+    sync::{Arc, RwLock},
     time::Duration,
 };
 
 use async_trait::async_trait;
+use rustls::ServerConfig;
 use time::OffsetDateTime;
 use tokio::{
-    io::{AsyncReadExt, AsyncWriteExt},
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
     net::{TcpListener, TcpStream},
     sync::Mutex as AsyncMutex,
 };
 use tokio_rustls::{TlsAcceptor, TlsConnector, TlsStream};
 use tokio_util::sync::CancellationToken;
 use x509_parser::prelude::{FromDer, X509Certificate};
-use zenoh_core::{bail, zasynclock};
+use zenoh_core::{bail, zasynclock, zread, zwrite};
 use zenoh_link_commons::{
     get_ip_interface_names,
-    tls::expiration::{LinkCertExpirationManager, LinkWithCertExpiration},
+    tls::{
+        expiration::{LinkCertExpirationManager, LinkWithCertExpiration},
+        reload::CertificateWatcher,
+    },
     LinkAuthId, LinkManagerUnicastTrait, LinkUnicast, LinkUnicastTrait, ListenersUnicastIP,
     NewLinkChannelSender, BIND_INTERFACE, BIND_SOCKET,
 };
@@ -419,8 +423,24 @@ impl LinkManagerUnicastTrait for LinkManagerUnicastTls {
         // Initialize the TlsAcceptor
         let token = self.listeners.token.child_token();
 
+        let server_config = Arc::new(RwLock::new(Arc::new(tls_server_config.server_config)));
+        if let Some(watcher) = CertificateWatcher::new(&epconf)? {
+            let endpoint = endpoint.clone();
+            let server_config = server_config.clone();
+            zenoh_runtime::ZRuntime::Acceptor.spawn(watcher.run(token.clone(), move || {
+                let endpoint = endpoint.clone();
+                let server_config = server_config.clone();
+                async move {
+                    let epconf = endpoint.config();
+                    let config = TlsServerConfig::new(&epconf).await?;
+                    *zwrite!(server_config) = Arc::new(config.server_config);
+                    Ok(())
+                }
+            }));
+        }
+
         let task = {
-            let acceptor = TlsAcceptor::from(Arc::new(tls_server_config.server_config));
+            let acceptor = ReloadableTlsAcceptor(server_config);
             let token = token.clone();
             let manager = self.manager.clone();
 
@@ -471,9 +491,24 @@ impl LinkManagerUnicastTrait for LinkManagerUnicastTls {
     }
 }
 
+/// A [`TlsAcceptor`] whose server config can be replaced for the new connections, the
+/// established ones being kept.
+#[derive(Clone)]
+struct ReloadableTlsAcceptor(Arc<RwLock<Arc<ServerConfig>>>);
+
+impl<C: AsyncRead + AsyncWrite + Unpin> tls_listener::AsyncTls<C> for ReloadableTlsAcceptor {
+    type Stream = tokio_rustls::server::TlsStream<C>;
+    type Error = std::io::Error;
+    type AcceptFuture = tokio_rustls::Accept<C>;
+
+    fn accept(&self, stream: C) -> Self::AcceptFuture {
+        TlsAcceptor::from(zread!(self.0).clone()).accept(stream)
+    }
+}
+
 async fn accept_task(
     socket: TcpListener,
-    acceptor: TlsAcceptor,
+    acceptor: ReloadableTlsAcceptor,
     token: CancellationToken,
     manager: NewLinkChannelSender,
     tls_handshake_timeout: Duration,
@@ -23,7 +23,6 @@ use std::{
 
 use rustls::{
     pki_types::{CertificateDer, PrivateKeyDer, TrustAnchor},
-    server::WebPkiClientVerifier,
     version::TLS13,
     ClientConfig, RootCertStore, ServerConfig,
 };
@@ -35,8 +34,9 @@ use zenoh_link_commons::{
     parse_dscp,
     tcp::TcpSocketConfig,
     tls::{
+        client_cert_verifier,
         config::{self, *},
-        WebPkiVerifierAnyServerName,
+        load_crls, server_cert_verifier, WebPkiVerifierAnyServerName,
     },
     ConfigurationInspector, BIND_INTERFACE, BIND_SOCKET, TCP_SO_RCV_BUF, TCP_SO_SND_BUF,
 };
@@ -156,6 +156,16 @@ impl ConfigurationInspector<ZenohConfig> for TlsConfigurator {
             false => ps.push((TLS_CLOSE_LINK_ON_EXPIRATION, "false")),
         }
 
+        if let Some(crl) = c.crl() {
+            ps.push((TLS_CRL_FILE, crl));
+        }
+
+        let reload_interval_ms;
+        if let Some(interval) = c.reload_interval_ms() {
+            reload_interval_ms = interval.to_string();
+            ps.push((TLS_RELOAD_INTERVAL_MS, &reload_interval_ms));
+        }
+
         let rx_buffer_size;
         if let Some(size) = c.so_rcvbuf() {
             rx_buffer_size = size.to_string();
@@ -240,7 +250,7 @@ impl<'a> TlsServerConfig<'a> {
                 || Err(zerror!("Missing root certificates while mTLS is enabled.")),
                 Ok,
             )?;
-            let client_auth = WebPkiClientVerifier::builder(root_cert_store.into()).build()?;
+            let client_auth = client_cert_verifier(root_cert_store, load_crls(config).await?)?;
             ServerConfig::builder_with_protocol_versions(&[&TLS13])
                 .with_client_cert_verifier(client_auth)
                 .with_single_cert(certs, keys.remove(0))
@@ -356,6 +366,7 @@ impl<'a> TlsClientConfig<'a> {
             tracing::debug!("Loading user-generated certificates.");
             root_cert_store.extend(custom_root_cert.roots);
         }
+        let crls = load_crls(config).await?;
 
         // Install ring based rustls CryptoProvider.
         rustls::crypto::ring::default_provider()
@@ -406,14 +417,14 @@ impl<'a> TlsClientConfig<'a> {
 
             if tls_server_name_verification {
                 builder
-                    .with_root_certificates(root_cert_store)
+                    .with_webpki_verifier(server_cert_verifier(root_cert_store, crls)?)
                     .with_client_auth_cert(certs, keys.remove(0))
             } else {
                 builder
                     .dangerous()
-                    .with_custom_certificate_verifier(Arc::new(WebPkiVerifierAnyServerName::new(
-                        root_cert_store,
-                    )))
+                    .with_custom_certificate_verifier(Arc::new(
+                        WebPkiVerifierAnyServerName::new(root_cert_store).with_crls(crls)?,
+                    ))
                     .with_client_auth_cert(certs, keys.remove(0))
             }
             .map_err(|e| zerror!("Bad certificate/key: {}", e))?
@@ -421,14 +432,14 @@ impl<'a> TlsClientConfig<'a> {
             let builder = ClientConfig::builder();
             if tls_server_name_verification {
                 builder
-                    .with_root_certificates(root_cert_store)
+                    .with_webpki_verifier(server_cert_verifier(root_cert_store, crls)?)
                     .with_no_client_auth()
             } else {
                 builder
                     .dangerous()
-                    .with_custom_certificate_verifier(Arc::new(WebPkiVerifierAnyServerName::new(
-                        root_cert_store,
-                    )))
+                    .with_custom_certificate_verifier(Arc::new(
+                        WebPkiVerifierAnyServerName::new(root_cert_store).with_crls(crls)?,
+                    ))
                     .with_no_client_auth()
             }
         };
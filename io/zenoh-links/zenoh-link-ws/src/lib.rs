@@ -29,7 +29,9 @@ use zenoh_protocol::{
 };
 use zenoh_result::{bail, ZResult};
 mod unicast;
+mod utils;
 pub use unicast::*;
+pub use utils::WssConfigurator;
 
 // Default MTU (WSS PDU) in bytes.
 // NOTE: Since TCP is a byte-stream oriented transport, theoretically it has
@@ -40,9 +42,23 @@ pub use unicast::*;
 const WS_MAX_MTU: BatchSize = BatchSize::MAX;
 
 pub const WS_LOCATOR_PREFIX: &str = "ws";
+pub const WSS_LOCATOR_PREFIX: &str = "wss";
 
 const IS_RELIABLE: bool = true;
 
+fn is_reliable(locator: &Locator) -> ZResult<bool> {
+    if let Some(reliability) = locator
+        .metadata()
+        .get(Metadata::RELIABILITY)
+        .map(Reliability::from_str)
+        .transpose()?
+    {
+        Ok(reliability == Reliability::Reliable)
+    } else {
+        Ok(IS_RELIABLE)
+    }
+}
+
 #[derive(Default, Clone, Copy)]
 pub struct WsLocatorInspector;
 #[async_trait]
@@ -55,25 +71,53 @@ impl LocatorInspector for WsLocatorInspector {
     }
 
     fn is_reliable(&self, locator: &Locator) -> ZResult<bool> {
-        if let Some(reliability) = locator
-            .metadata()
-            .get(Metadata::RELIABILITY)
-            .map(Reliability::from_str)
-            .transpose()?
-        {
-            Ok(reliability == Reliability::Reliable)
-        } else {
-            Ok(IS_RELIABLE)
-        }
+        is_reliable(locator)
+    }
+}
+
+#[derive(Default, Clone, Copy)]
+pub struct WssLocatorInspector;
+#[async_trait]
+impl LocatorInspector for WssLocatorInspector {
+    fn protocol(&self) -> &str {
+        WSS_LOCATOR_PREFIX
+    }
+    async fn is_multicast(&self, _locator: &Locator) -> ZResult<bool> {
+        Ok(false)
+    }
+
+    fn is_reliable(&self, locator: &Locator) -> ZResult<bool> {
+        is_reliable(locator)
     }
 }
 
+pub mod config {
+    /// The HTTP path of the WebSocket handshake, e.g. `/zenoh`.
+    ///
+    /// Listeners reject the handshakes on other paths, and accept any path when unset.
+    pub const WS_PATH: &str = "path";
+    pub const WS_PATH_DEFAULT: &str = "/";
+
+    /// The extra HTTP headers of the WebSocket handshake as a `|`-separated list of `name:value`,
+    /// sent in the requests of the connecting side and in the responses of the listening side.
+    pub const WS_HEADERS: &str = "headers";
+
+    /// The WebSocket subprotocols (i.e. `Sec-WebSocket-Protocol`) as a `|`-separated list.
+    ///
+    /// The connecting side offers them all, and the listening side selects the first offered one
+    /// it supports, rejecting the handshakes without any.
+    pub const WS_PROTOCOLS: &str = "protocols";
+}
+
 zconfigurable! {
     // Default MTU (TCP PDU) in bytes.
     static ref WS_DEFAULT_MTU: BatchSize = WS_MAX_MTU;
     // Amount of time in microseconds to throttle the accept loop upon an error.
     // Default set to 100 ms.
     static ref TCP_ACCEPT_THROTTLE_TIME: u64 = 100_000;
+    // Amount of time in milliseconds to wait for the WebSocket handshake of an accepted connection.
+    // Default set to 10 s.
+    static ref WS_HANDSHAKE_TIMEOUT_MS: u64 = 10_000;
 }
 
 pub async fn get_ws_addr(address: Address<'_>) -> ZResult<SocketAddr> {
@@ -83,12 +127,11 @@ pub async fn get_ws_addr(address: Address<'_>) -> ZResult<SocketAddr> {
     }
 }
 
-pub async fn get_ws_url(address: Address<'_>) -> ZResult<Url> {
-    match Url::parse(&format!(
-        "{}://{}",
-        WS_LOCATOR_PREFIX,
-        get_ws_addr(address).await?
-    )) {
+pub fn get_ws_url(protocol: &str, address: Address<'_>, path: &str) -> ZResult<Url> {
+    if !path.starts_with('/') {
+        bail!("Invalid WebSocket path {}: it must start with '/'", path);
+    }
+    match Url::parse(&format!("{}://{}{}", protocol, address, path)) {
         Ok(url) => Ok(url),
         Err(e) => bail!(
             "Couldn't resolve WebSocket locator address: {}: {}",
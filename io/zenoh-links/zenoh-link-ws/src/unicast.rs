@@ -16,7 +16,8 @@ use std::{
     collections::HashMap,
     fmt,
     net::{Ipv4Addr, Ipv6Addr, SocketAddr},
-    sync::Arc,
+    str::FromStr,
+    sync::{Arc, RwLock},
     time::Duration,
 };
 
@@ -25,31 +26,58 @@ use futures_util::{
     stream::{SplitSink, SplitStream},
     SinkExt, StreamExt,
 };
+use rustls::ServerConfig;
 use tokio::{
+    io::{AsyncRead, AsyncWrite},
     net::{TcpListener, TcpStream},
     sync::{Mutex as AsyncMutex, RwLock as AsyncRwLock},
     task::JoinHandle,
 };
-use tokio_tungstenite::{accept_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
+use tokio_rustls::{TlsAcceptor, TlsConnector};
+use tokio_tungstenite::{
+    accept_hdr_async, client_async,
+    tungstenite::{
+        client::IntoClientRequest,
+        handshake::{
+            client::Request as ClientRequest,
+            server::{ErrorResponse, Request, Response},
+        },
+        http::{header::SEC_WEBSOCKET_PROTOCOL, HeaderName, HeaderValue, StatusCode},
+        Message,
+    },
+    WebSocketStream,
+};
 use tokio_util::sync::CancellationToken;
-use zenoh_core::{zasynclock, zasyncread, zasyncwrite};
+use zenoh_core::{zasynclock, zasyncread, zasyncwrite, zread, zwrite};
 use zenoh_link_commons::{
-    get_ip_interface_names, LinkAuthId, LinkManagerUnicastTrait, LinkUnicast, LinkUnicastTrait,
-    NewLinkChannelSender,
+    get_ip_interface_names, tls::reload::CertificateWatcher, LinkAuthId, LinkManagerUnicastTrait,
+    LinkUnicast, LinkUnicastTrait, NewLinkChannelSender,
 };
 use zenoh_protocol::{
-    core::{EndPoint, Locator, Priority},
+    core::{
+        endpoint::{Address, Config},
+        EndPoint, Locator, Priority,
+    },
     transport::BatchSize,
 };
 use zenoh_result::{bail, zerror, ZResult};
 
-use super::{get_ws_addr, get_ws_url, TCP_ACCEPT_THROTTLE_TIME, WS_DEFAULT_MTU, WS_LOCATOR_PREFIX};
+use super::{
+    config::*,
+    get_ws_addr, get_ws_url,
+    utils::{get_wss_server_name, TlsClientConfig, TlsServerConfig},
+    TCP_ACCEPT_THROTTLE_TIME, WSS_LOCATOR_PREFIX, WS_DEFAULT_MTU, WS_HANDSHAKE_TIMEOUT_MS,
+    WS_LOCATOR_PREFIX,
+};
 
-pub struct LinkUnicastWs {
+pub struct LinkUnicastWs<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
     // The inbound message stream as returned from the futures_util::stream::StreamExt::split method
-    recv: AsyncMutex<SplitStream<WebSocketStream<MaybeTlsStream<TcpStream>>>>,
+    recv: AsyncMutex<SplitStream<WebSocketStream<S>>>,
     // // The outbound message stream as returned from the futures_util::stream::StreamExt::split method
-    send: AsyncMutex<SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, Message>>,
+    send: AsyncMutex<SplitSink<WebSocketStream<S>, Message>>,
     // The source socket address of this link (address used on the local host)
     src_addr: SocketAddr,
     src_locator: Locator,
@@ -60,22 +88,16 @@ pub struct LinkUnicastWs {
     leftovers: AsyncMutex<Option<(Vec<u8>, usize, usize)>>,
 }
 
-impl LinkUnicastWs {
+impl<S> LinkUnicastWs<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
     fn new(
-        socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
+        socket: WebSocketStream<S>,
         src_addr: SocketAddr,
         dst_addr: SocketAddr,
-    ) -> LinkUnicastWs {
-        // Set the TCP nodelay option
-        if let Err(err) = get_stream(&socket).set_nodelay(true) {
-            tracing::warn!(
-                "Unable to set NODEALY option on TCP link {} => {}: {}",
-                src_addr,
-                dst_addr,
-                err
-            );
-        }
-
+        protocol: &str,
+    ) -> LinkUnicastWs<S> {
         let (send, recv) = socket.split();
         let send = AsyncMutex::new(send);
         let recv = AsyncMutex::new(recv);
@@ -86,9 +108,9 @@ impl LinkUnicastWs {
             recv,
             send,
             src_addr,
-            src_locator: Locator::new(WS_LOCATOR_PREFIX, src_addr.to_string(), "").unwrap(),
+            src_locator: Locator::new(protocol, src_addr.to_string(), "").unwrap(),
             dst_addr,
-            dst_locator: Locator::new(WS_LOCATOR_PREFIX, dst_addr.to_string(), "").unwrap(),
+            dst_locator: Locator::new(protocol, dst_addr.to_string(), "").unwrap(),
             leftovers: AsyncMutex::new(None),
         }
     }
@@ -128,7 +150,10 @@ impl LinkUnicastWs {
 }
 
 #[async_trait]
-impl LinkUnicastTrait for LinkUnicastWs {
+impl<S> LinkUnicastTrait for LinkUnicastWs<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + Sync,
+{
     async fn close(&self) -> ZResult<()> {
         tracing::trace!("Closing WebSocket link: {}", self);
         let mut guard = zasynclock!(self.send);
@@ -232,7 +257,10 @@ impl LinkUnicastTrait for LinkUnicastWs {
     }
 }
 
-impl Drop for LinkUnicastWs {
+impl<S> Drop for LinkUnicastWs<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
     fn drop(&mut self) {
         zenoh_runtime::ZRuntime::Acceptor.block_in_place(async {
             let mut guard = zasynclock!(self.send);
@@ -244,14 +272,20 @@ impl Drop for LinkUnicastWs {
     }
 }
 
-impl fmt::Display for LinkUnicastWs {
+impl<S> fmt::Display for LinkUnicastWs<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{} => {}", self.src_addr, self.dst_addr)?;
         Ok(())
     }
 }
 
-impl fmt::Debug for LinkUnicastWs {
+impl<S> fmt::Debug for LinkUnicastWs<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Tcp")
             .field("src", &self.src_addr)
@@ -260,6 +294,136 @@ impl fmt::Debug for LinkUnicastWs {
     }
 }
 
+/*************************************/
+/*          HANDSHAKE                */
+/*************************************/
+/// The HTTP options of the WebSocket handshake, see [`config`](crate::config).
+#[derive(Clone, Default)]
+struct WsHandshakeConfig {
+    path: Option<String>,
+    headers: Vec<(HeaderName, HeaderValue)>,
+    protocols: Vec<String>,
+}
+
+impl WsHandshakeConfig {
+    fn new(config: &Config<'_>) -> ZResult<Self> {
+        let path = config.get(WS_PATH).map(str::to_string);
+        if let Some(path) = path.as_ref().filter(|p| !p.starts_with('/')) {
+            bail!("Invalid WebSocket path {}: it must start with '/'", path);
+        }
+
+        let headers = config
+            .values(WS_HEADERS)
+            .filter(|h| !h.is_empty())
+            .map(|h| {
+                let (name, value) = h.split_once(':').ok_or_else(|| {
+                    zerror!("Invalid WebSocket header {}: expected name:value", h)
+                })?;
+                let name = HeaderName::from_str(name.trim())
+                    .map_err(|e| zerror!("Invalid WebSocket header name {}: {}", name, e))?;
+                let value = HeaderValue::from_str(value.trim())
+                    .map_err(|e| zerror!("Invalid WebSocket header value {}: {}", value, e))?;
+                Ok((name, value))
+            })
+            .collect::<ZResult<Vec<_>>>()?;
+
+        let protocols = config
+            .values(WS_PROTOCOLS)
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .map(|p| {
+                if p.contains(',') || HeaderValue::from_str(p).is_err() {
+                    bail!("Invalid WebSocket subprotocol {}", p);
+                }
+                Ok(p.to_string())
+            })
+            .collect::<ZResult<Vec<_>>>()?;
+
+        Ok(Self {
+            path,
+            headers,
+            protocols,
+        })
+    }
+
+    /// Builds the handshake request of a link connecting to `address`.
+    fn request(&self, protocol: &str, address: Address<'_>) -> ZResult<ClientRequest> {
+        let path = self.path.as_deref().unwrap_or(WS_PATH_DEFAULT);
+        let url = get_ws_url(protocol, address, path)?;
+        let mut request = url
+            .as_str()
+            .into_client_request()
+            .map_err(|e| zerror!("Invalid WebSocket request to {}: {}", url, e))?;
+        for (name, value) in self.headers.iter() {
+            request.headers_mut().append(name.clone(), value.clone());
+        }
+        if !self.protocols.is_empty() {
+            // The values have been validated when parsing the config
+            let protocols = HeaderValue::from_str(&self.protocols.join(","))
+                .map_err(|e| zerror!("Invalid WebSocket subprotocols: {}", e))?;
+            request
+                .headers_mut()
+                .insert(SEC_WEBSOCKET_PROTOCOL, protocols);
+        }
+        Ok(request)
+    }
+
+    /// Checks the handshake request of an accepted link, completing its `response`.
+    // The signature is the one of the tungstenite handshake callback
+    #[allow(clippy::result_large_err)]
+    fn respond(
+        &self,
+        request: &Request,
+        mut response: Response,
+    ) -> Result<Response, ErrorResponse> {
+        fn error(status: StatusCode, reason: String) -> ErrorResponse {
+            tracing::debug!("Rejecting WebSocket handshake: {}", reason);
+            let mut response = ErrorResponse::new(Some(reason));
+            *response.status_mut() = status;
+            response
+        }
+
+        if let Some(path) = self.path.as_ref() {
+            if request.uri().path() != path {
+                return Err(error(
+                    StatusCode::NOT_FOUND,
+                    format!("Unknown path {}", request.uri().path()),
+                ));
+            }
+        }
+
+        if !self.protocols.is_empty() {
+            let offered = request
+                .headers()
+                .get_all(SEC_WEBSOCKET_PROTOCOL)
+                .iter()
+                .filter_map(|v| v.to_str().ok())
+                .flat_map(|v| v.split(','))
+                .map(str::trim)
+                .collect::<Vec<_>>();
+            let protocol = self
+                .protocols
+                .iter()
+                .find(|p| offered.contains(&p.as_str()))
+                .and_then(|p| HeaderValue::from_str(p).ok())
+                .ok_or_else(|| {
+                    error(
+                        StatusCode::BAD_REQUEST,
+                        format!("No supported subprotocol in {:?}", offered),
+                    )
+                })?;
+            response
+                .headers_mut()
+                .insert(SEC_WEBSOCKET_PROTOCOL, protocol);
+        }
+
+        for (name, value) in self.headers.iter() {
+            response.headers_mut().append(name.clone(), value.clone());
+        }
+        Ok(response)
+    }
+}
+
 /*************************************/
 /*          LISTENER                 */
 /*************************************/
@@ -290,6 +454,8 @@ impl ListenerUnicastWs {
 pub struct LinkManagerUnicastWs {
     manager: NewLinkChannelSender,
     listeners: Arc<AsyncRwLock<HashMap<SocketAddr, ListenerUnicastWs>>>,
+    // Whether the links are secured with TLS, i.e. `wss` instead of `ws`
+    secure: bool,
 }
 
 impl LinkManagerUnicastWs {
@@ -297,6 +463,22 @@ impl LinkManagerUnicastWs {
         Self {
             manager,
             listeners: Arc::new(AsyncRwLock::new(HashMap::new())),
+            secure: false,
+        }
+    }
+
+    pub fn new_secure(manager: NewLinkChannelSender) -> Self {
+        Self {
+            secure: true,
+            ..Self::new(manager)
+        }
+    }
+
+    fn protocol(&self) -> &'static str {
+        if self.secure {
+            WSS_LOCATOR_PREFIX
+        } else {
+            WS_LOCATOR_PREFIX
         }
     }
 }
@@ -304,19 +486,13 @@ impl LinkManagerUnicastWs {
 #[async_trait]
 impl LinkManagerUnicastTrait for LinkManagerUnicastWs {
     async fn new_link(&self, endpoint: EndPoint) -> ZResult<LinkUnicast> {
-        let dst_url = get_ws_url(endpoint.address()).await?;
+        let epconf = endpoint.config();
+        let request =
+            WsHandshakeConfig::new(&epconf)?.request(self.protocol(), endpoint.address())?;
+        let dst_url = request.uri().to_string();
+        let dst_addr = get_ws_addr(endpoint.address()).await?;
 
-        let (stream, _) = tokio_tungstenite::connect_async(dst_url.as_str())
-            .await
-            .map_err(|e| {
-                zerror!(
-                    "Can not create a new WebSocket link bound to {}: {}",
-                    dst_url,
-                    e
-                )
-            })?;
-
-        let src_addr = get_stream(&stream).local_addr().map_err(|e| {
+        let stream = TcpStream::connect(dst_addr).await.map_err(|e| {
             zerror!(
                 "Can not create a new WebSocket link bound to {}: {}",
                 dst_url,
@@ -324,7 +500,7 @@ impl LinkManagerUnicastTrait for LinkManagerUnicastWs {
             )
         })?;
 
-        let dst_addr = get_stream(&stream).peer_addr().map_err(|e| {
+        let src_addr = stream.local_addr().map_err(|e| {
             zerror!(
                 "Can not create a new WebSocket link bound to {}: {}",
                 dst_url,
@@ -332,7 +508,57 @@ impl LinkManagerUnicastTrait for LinkManagerUnicastWs {
             )
         })?;
 
-        let link = Arc::new(LinkUnicastWs::new(stream, src_addr, dst_addr));
+        set_nodelay(&stream, &src_addr, &dst_addr);
+
+        let link: Arc<dyn LinkUnicastTrait> = if self.secure {
+            let client_config = TlsClientConfig::new(&epconf).await.map_err(|e| {
+                zerror!(
+                    "Can not create a new WebSocket link bound to {}: {}",
+                    dst_url,
+                    e
+                )
+            })?;
+            let connector = TlsConnector::from(Arc::new(client_config.client_config));
+            let epaddr = endpoint.address();
+            let server_name = get_wss_server_name(&epaddr)?;
+            let stream = connector
+                .connect(server_name.to_owned(), stream)
+                .await
+                .map_err(|e| {
+                    zerror!(
+                        "Can not create a new WebSocket link bound to {}: {}",
+                        dst_url,
+                        e
+                    )
+                })?;
+            let (stream, _) = client_async(request, stream).await.map_err(|e| {
+                zerror!(
+                    "Can not create a new WebSocket link bound to {}: {}",
+                    dst_url,
+                    e
+                )
+            })?;
+            Arc::new(LinkUnicastWs::new(
+                stream,
+                src_addr,
+                dst_addr,
+                WSS_LOCATOR_PREFIX,
+            ))
+        } else {
+            let (stream, _) = client_async(request, stream).await.map_err(|e| {
+                zerror!(
+                    "Can not create a new WebSocket link bound to {}: {}",
+                    dst_url,
+                    e
+                )
+            })?;
+            Arc::new(LinkUnicastWs::new(
+                stream,
+                src_addr,
+                dst_addr,
+                WS_LOCATOR_PREFIX,
+            ))
+        };
 
         Ok(LinkUnicast(link))
     }
@@ -340,6 +566,24 @@ impl LinkManagerUnicastTrait for LinkManagerUnicastWs {
     async fn new_listener(&self, mut endpoint: EndPoint) -> ZResult<Locator> {
         let addr = get_ws_addr(endpoint.address()).await?;
 
+        let epconf = endpoint.config();
+        let handshake_config = WsHandshakeConfig::new(&epconf)?;
+        let (server_config, handshake_timeout) = if self.secure {
+            let server_config = TlsServerConfig::new(&epconf).await.map_err(|e| {
+                zerror!(
+                    "Can not create a new TLS (WebSocket) listener on {}: {}",
+                    addr,
+                    e
+                )
+            })?;
+            (
+                Some(Arc::new(RwLock::new(Arc::new(server_config.server_config)))),
+                server_config.tls_handshake_timeout,
+            )
+        } else {
+            (None, Duration::from_millis(*WS_HANDSHAKE_TIMEOUT_MS))
+        };
+
         // Bind the TCP socket
         let socket = TcpListener::bind(addr).await.map_err(|e| {
             zerror!(
@@ -368,6 +612,23 @@ impl LinkManagerUnicastTrait for LinkManagerUnicastWs {
         // Spawn the accept loop for the listener
         let token = CancellationToken::new();
 
+        if let Some(server_config) = server_config.as_ref() {
+            if let Some(watcher) = CertificateWatcher::new(&endpoint.config())? {
+                let endpoint = endpoint.clone();
+                let server_config = server_config.clone();
+                zenoh_runtime::ZRuntime::Acceptor.spawn(watcher.run(token.clone(), move || {
+                    let endpoint = endpoint.clone();
+                    let server_config = server_config.clone();
+                    async move {
+                        let epconf = endpoint.config();
+                        let config = TlsServerConfig::new(&epconf).await?;
+                        *zwrite!(server_config) = Arc::new(config.server_config);
+                        Ok(())
+                    }
+                }));
+            }
+        }
+
         let task = {
             let token = token.clone();
             let manager = self.manager.clone();
@@ -376,7 +637,15 @@ impl LinkManagerUnicastTrait for LinkManagerUnicastWs {
 
             async move {
                 // Wait for the accept loop to terminate
-                let res = accept_task(socket, token, manager).await;
+                let res = accept_task(
+                    socket,
+                    server_config,
+                    handshake_config,
+                    handshake_timeout,
+                    token,
+                    manager,
+                )
+                .await;
                 zasyncwrite!(listeners).remove(&addr);
                 res
             }
@@ -430,7 +699,7 @@ impl LinkManagerUnicastTrait for LinkManagerUnicastWs {
                         for ipaddr in ipaddrs {
                             if !ipaddr.is_loopback() && !ipaddr.is_multicast() && ipaddr.is_ipv4() {
                                 let l = Locator::new(
-                                    WS_LOCATOR_PREFIX,
+                                    self.protocol(),
                                     SocketAddr::new(ipaddr, key.port()).to_string(),
                                     value.endpoint.metadata(),
                                 )
@@ -447,7 +716,7 @@ impl LinkManagerUnicastTrait for LinkManagerUnicastWs {
                         for ipaddr in ipaddrs {
                             if !ipaddr.is_loopback() && !ipaddr.is_multicast() && ipaddr.is_ipv6() {
                                 let l = Locator::new(
-                                    WS_LOCATOR_PREFIX,
+                                    self.protocol(),
                                     SocketAddr::new(ipaddr, key.port()).to_string(),
                                     value.endpoint.metadata(),
                                 )
@@ -470,6 +739,9 @@ impl LinkManagerUnicastTrait for LinkManagerUnicastWs {
 
 async fn accept_task(
     socket: TcpListener,
+    server_config: Option<Arc<RwLock<Arc<ServerConfig>>>>,
+    handshake_config: WsHandshakeConfig,
+    handshake_timeout: Duration,
     token: CancellationToken,
     manager: NewLinkChannelSender,
 ) -> ZResult<()> {
@@ -526,32 +798,103 @@ async fn accept_task(
             dst_addr
         );
 
-        let stream = accept_async(MaybeTlsStream::Plain(stream))
-            .await
-            .map_err(|e| {
-                let e = zerror!("Error when creating the WebSocket session: {}", e);
-                tracing::trace!("{}", e);
-                e
-            })?;
-        // Create the new link object
-        let link = Arc::new(LinkUnicastWs::new(stream, src_addr, dst_addr));
+        set_nodelay(&stream, &src_addr, &dst_addr);
+
+        // Perform the handshakes in a separate task not to block the accept loop, with the
+        // current server config that may have been reloaded
+        let acceptor = server_config
+            .as_ref()
+            .map(|server_config| TlsAcceptor::from(zread!(server_config).clone()));
+        let handshake_config = handshake_config.clone();
+        let manager = manager.clone();
+        zenoh_runtime::ZRuntime::Acceptor.spawn(async move {
+            let handshake = accept_link(stream, acceptor, &handshake_config, src_addr, dst_addr);
+            let link = match tokio::time::timeout(handshake_timeout, handshake).await {
+                Ok(Ok(link)) => link,
+                Ok(Err(e)) => {
+                    tracing::debug!("{}", e);
+                    return;
+                }
+                Err(_) => {
+                    tracing::debug!(
+                        "WebSocket handshake timed out on {:?}: {:?}",
+                        src_addr,
+                        dst_addr
+                    );
+                    return;
+                }
+            };
 
-        // Communicate the new link to the initial transport manager
-        if let Err(e) = manager.send_async(LinkUnicast(link)).await {
-            tracing::error!("{}-{}: {}", file!(), line!(), e)
-        }
+            // Communicate the new link to the initial transport manager
+            if let Err(e) = manager.send_async(LinkUnicast(link)).await {
+                tracing::error!("{}-{}: {}", file!(), line!(), e)
+            }
+        });
     }
 
     Ok(())
 }
 
-fn get_stream(ws_stream: &WebSocketStream<MaybeTlsStream<TcpStream>>) -> &TcpStream {
-    match ws_stream.get_ref() {
-        MaybeTlsStream::Plain(s) => s,
-        // This two are available only if the TLS features are enabled for
-        // tokio_tungstenite.
-        // MaybeTlsStream::NativeTls(s) => s.get_ref().get_ref(),
-        // MaybeTlsStream::Rustls(s) => s.get_ref().0
-        _ => panic!(),
+async fn accept_link(
+    stream: TcpStream,
+    acceptor: Option<TlsAcceptor>,
+    handshake_config: &WsHandshakeConfig,
+    src_addr: SocketAddr,
+    dst_addr: SocketAddr,
+) -> ZResult<Arc<dyn LinkUnicastTrait>> {
+    let callback =
+        |request: &Request, response: Response| handshake_config.respond(request, response);
+
+    let link: Arc<dyn LinkUnicastTrait> = match acceptor {
+        Some(acceptor) => {
+            let stream = acceptor.accept(stream).await.map_err(|e| {
+                zerror!(
+                    "Error when creating the TLS session with {}: {}",
+                    dst_addr,
+                    e
+                )
+            })?;
+            let stream = accept_hdr_async(stream, callback).await.map_err(|e| {
+                zerror!(
+                    "Error when creating the WebSocket session with {}: {}",
+                    dst_addr,
+                    e
+                )
+            })?;
+            Arc::new(LinkUnicastWs::new(
+                stream,
+                src_addr,
+                dst_addr,
+                WSS_LOCATOR_PREFIX,
+            ))
+        }
+        None => {
+            let stream = accept_hdr_async(stream, callback).await.map_err(|e| {
+                zerror!(
+                    "Error when creating the WebSocket session with {}: {}",
+                    dst_addr,
+                    e
+                )
+            })?;
+            Arc::new(LinkUnicastWs::new(
+                stream,
+                src_addr,
+                dst_addr,
+                WS_LOCATOR_PREFIX,
+            ))
+        }
+    };
+    Ok(link)
+}
+
+fn set_nodelay(stream: &TcpStream, src_addr: &SocketAddr, dst_addr: &SocketAddr) {
+    // Set the TCP nodelay option
+    if let Err(err) = stream.set_nodelay(true) {
+        tracing::warn!(
+            "Unable to set NODEALY option on TCP link {} => {}: {}",
+            src_addr,
+            dst_addr,
+            err
+        );
     }
 }
@@ -32,7 +32,11 @@ use zenoh_protocol::{
 };
 use zenoh_result::{zerror, ZResult};
 #[cfg(feature = "transport_compression")]
-use {std::sync::Arc, zenoh_protocol::common::imsg};
+use {
+    super::compression::{Compressor, Decompressor},
+    std::sync::Arc,
+    zenoh_protocol::common::imsg,
+};
 
 const L_LEN: usize = (BatchSize::BITS / 8) as usize;
 const H_LEN: usize = BatchHeader::SIZE;
@@ -134,6 +138,10 @@ impl BatchHeader {
     const SIZE: usize = 1;
     #[cfg(feature = "transport_compression")]
     const COMPRESSION: u8 = 1; // 1 << 0
+    #[cfg(feature = "transport_compression")]
+    pub(crate) const ZSTD: u8 = 1 << 1;
+    #[cfg(feature = "transport_compression")]
+    pub(crate) const DICTIONARY: u8 = 1 << 2;
 
     #[cfg(feature = "transport_compression")]
     const fn new(h: u8) -> Self {
@@ -151,6 +159,20 @@ impl BatchHeader {
     pub fn is_compression(&self) -> bool {
         imsg::has_flag(self.as_u8(), Self::COMPRESSION)
     }
+
+    /// Verify that the compressed [`WBatch`] payload is compressed with zstd rather than LZ4.
+    #[cfg(feature = "transport_compression")]
+    #[inline(always)]
+    pub fn is_zstd(&self) -> bool {
+        imsg::has_flag(self.as_u8(), Self::ZSTD)
+    }
+
+    /// Verify that the compressed [`WBatch`] payload is compressed with the shared zstd dictionary.
+    #[cfg(feature = "transport_compression")]
+    #[inline(always)]
+    pub fn is_dictionary(&self) -> bool {
+        imsg::has_flag(self.as_u8(), Self::DICTIONARY)
+    }
 }
 
 // WRITE BATCH
@@ -171,6 +193,7 @@ impl WBatchStats {
 #[derive(Debug)]
 pub enum Finalize {
     Batch,
+    #[cfg(feature = "transport_compression")]
     Buffer,
 }
 
@@ -294,26 +317,31 @@ impl WBatch {
         zsplit_mut!(buffer, config)
     }
 
-    pub fn finalize(&mut self, mut buffer: Option<&mut BBuf>) -> ZResult<Finalize> {
+    pub fn finalize(
+        &mut self,
+        #[cfg(feature = "transport_compression")] mut compressor: Option<&mut Compressor>,
+    ) -> ZResult<Finalize> {
         #[allow(unused_mut)]
         let mut res = Finalize::Batch;
 
         #[cfg(feature = "transport_compression")]
         if let Some(h) = self.config.header() {
             if h.is_compression() {
-                let buffer = buffer
+                let compressor = compressor
                     .as_mut()
-                    .ok_or_else(|| zerror!("Support buffer not provided"))?;
-                res = self.compress(buffer)?;
+                    .ok_or_else(|| zerror!("Compressor not provided"))?;
+                res = self.compress(compressor)?;
             }
         }
 
         if self.config.is_streamed {
             let buff = match res {
                 Finalize::Batch => self.buffer.as_mut_slice(),
-                Finalize::Buffer => buffer
+                #[cfg(feature = "transport_compression")]
+                Finalize::Buffer => compressor
                     .as_mut()
-                    .ok_or_else(|| zerror!("Support buffer not provided"))?
+                    .ok_or_else(|| zerror!("Compressor not provided"))?
+                    .buffer
                     .as_mut_slice(),
             };
             let (length, header, payload) = Self::split_mut(buff, &self.config);
@@ -325,26 +353,21 @@ impl WBatch {
     }
 
     #[cfg(feature = "transport_compression")]
-    fn compress(&mut self, support: &mut BBuf) -> ZResult<Finalize> {
+    fn compress(&mut self, compressor: &mut Compressor) -> ZResult<Finalize> {
         // Write the initial bytes for the batch
-        support.clear();
-        Self::init(support, &self.config);
+        compressor.buffer.clear();
+        Self::init(&mut compressor.buffer, &self.config);
 
         // Compress the actual content
         let (_length, _header, payload) = Self::split(self.buffer.as_slice(), &self.config);
-        let writer = support.writer();
-        // SAFETY: assertion ensures `with_slot` precondition
-        unsafe {
-            writer.with_slot(writer.remaining(), |b| {
-                let len = lz4_flex::block::compress_into(payload, b).unwrap_or(0);
-                assert!(len <= b.len());
-                len
-            })
-        }
-        .map_err(|_| zerror!("Compression error"))?;
+        let flags = compressor.compress(payload)?;
 
         // Verify whether the resulting compressed data is smaller than the initial input
-        if support.len() < self.buffer.len() {
+        if compressor.buffer.len() < self.buffer.len() {
+            // Signal the algorithm used to compress the batch in the header
+            let (_l, h, _p) = Self::split_mut(compressor.buffer.as_mut_slice(), &self.config);
+            let h = h.first_mut().ok_or_else(|| zerror!("Empty BatchHeader"))?;
+            *h |= flags;
             Ok(Finalize::Buffer)
         } else {
             // Keep the original uncompressed buffer and unset the compression flag from the header
@@ -457,7 +480,11 @@ impl RBatch {
         zsplit!(buffer, config)
     }
 
-    pub fn initialize<C, T>(&mut self, #[allow(unused_variables)] buff: C) -> ZResult<()>
+    pub fn initialize<C, T>(
+        &mut self,
+        #[allow(unused_variables)] buff: C,
+        #[cfg(feature = "transport_compression")] decompressor: &mut Decompressor,
+    ) -> ZResult<()>
     where
         C: Fn() -> T + Copy,
         T: AsMut<[u8]> + ZSliceBuffer + 'static,
@@ -474,7 +501,7 @@ impl RBatch {
                 let header = BatchHeader::new(b);
 
                 if header.is_compression() {
-                    let zslice = self.decompress(p, buff)?;
+                    let zslice = Self::decompress(header, p, buff, decompressor)?;
                     self.buffer = zslice;
                     return Ok(());
                 }
@@ -490,13 +517,17 @@ impl RBatch {
     }
 
     #[cfg(feature = "transport_compression")]
-    fn decompress<T>(&self, payload: &[u8], mut buff: impl FnMut() -> T) -> ZResult<ZSlice>
+    fn decompress<T>(
+        header: BatchHeader,
+        payload: &[u8],
+        mut buff: impl FnMut() -> T,
+        decompressor: &mut Decompressor,
+    ) -> ZResult<ZSlice>
     where
         T: AsMut<[u8]> + ZSliceBuffer + 'static,
     {
         let mut into = (buff)();
-        let n = lz4_flex::block::decompress_into(payload, into.as_mut())
-            .map_err(|_| zerror!("Decompression error"))?;
+        let n = decompressor.decompress(header, payload, into.as_mut())?;
         let zslice = ZSlice::new(Arc::new(into), 0, n)
             .map_err(|_| zerror!("Invalid decompression buffer length"))?;
         Ok(zslice)
@@ -543,7 +574,6 @@ mod tests {
     use std::vec;
 
     use rand::Rng;
-    use zenoh_core::zcondfeat;
     use zenoh_protocol::{
         core::{CongestionControl, Priority, Reliability, WireExpr},
         network::{ext, NetworkMessage, NetworkMessageExt, Push},
@@ -554,6 +584,10 @@ mod tests {
     };
 
     use super::*;
+    #[cfg(feature = "transport_compression")]
+    use crate::common::compression::{
+        CompressionAlgorithm, CompressionConfig, CompressionDictionary,
+    };
 
     #[test]
     fn rw_batch() {
@@ -576,27 +610,42 @@ mod tests {
                 wbatch.encode(&msg_in).unwrap();
                 println!("Encoded WBatch: {wbatch:?}");
 
-                let mut buffer = zcondfeat!(
-                    "transport_compression",
-                    config.is_compression.then_some(BBuf::with_capacity(
-                        lz4_flex::block::get_maximum_output_size(wbatch.as_slice().len()),
-                    )),
-                    None
-                );
-
-                let res = wbatch.finalize(buffer.as_mut()).unwrap();
+                #[cfg(feature = "transport_compression")]
+                let compression = {
+                    let algorithm = [
+                        CompressionAlgorithm::Lz4,
+                        CompressionAlgorithm::Zstd,
+                        CompressionAlgorithm::ZstdDictionary,
+                    ][rng.gen_range(0..3)];
+                    let dictionary = CompressionDictionary::new(vec![0u8; 1_024]).unwrap();
+                    CompressionConfig::new(algorithm, 3, Some(dictionary)).unwrap()
+                };
+                #[cfg(feature = "transport_compression")]
+                let mut compressor = config
+                    .is_compression
+                    .then(|| Compressor::new(compression.clone(), config.mtu));
+
+                let res = wbatch
+                    .finalize(
+                        #[cfg(feature = "transport_compression")]
+                        compressor.as_mut(),
+                    )
+                    .unwrap();
                 let bytes = match res {
                     Finalize::Batch => wbatch.as_slice(),
-                    Finalize::Buffer => buffer.as_mut().unwrap().as_slice(),
+                    #[cfg(feature = "transport_compression")]
+                    Finalize::Buffer => compressor.as_ref().unwrap().as_slice(),
                 };
                 println!("Finalized WBatch: {bytes:02x?}");
 
                 let mut rbatch = RBatch::new(config, bytes.to_vec().into_boxed_slice());
                 println!("Decoded RBatch: {rbatch:?}");
                 rbatch
-                    .initialize(|| {
-                        zenoh_buffers::vec::uninit(config.mtu as usize).into_boxed_slice()
-                    })
+                    .initialize(
+                        || zenoh_buffers::vec::uninit(config.mtu as usize).into_boxed_slice(),
+                        #[cfg(feature = "transport_compression")]
+                        &mut Decompressor::new(compression.dictionary),
+                    )
                     .unwrap();
                 println!("Initialized RBatch: {rbatch:?}");
                 let msg_out: TransportMessage = rbatch.decode().unwrap();
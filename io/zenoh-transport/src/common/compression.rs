@@ -0,0 +1,333 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{fmt, path::Path, sync::Arc};
+
+use sha3::{
+    digest::{ExtendableOutput, Update, XofReader},
+    Shake128,
+};
+use zenoh_buffers::{
+    writer::{HasWriter, Writer},
+    BBuf,
+};
+pub use zenoh_config::CompressionAlgorithm;
+use zenoh_protocol::transport::BatchSize;
+use zenoh_result::{bail, zerror, ZResult};
+
+use super::batch::BatchHeader;
+
+/// A pre-trained zstd dictionary shared by the nodes to compress their batches.
+///
+/// Dictionaries are identified by the ID embedded by `zstd --train`, or by a hash of their
+/// content for raw content dictionaries, the nodes only compressing their batches with the
+/// dictionary when they have the same one.
+#[derive(Clone, PartialEq, Eq)]
+pub struct CompressionDictionary {
+    id: u32,
+    bytes: Arc<[u8]>,
+}
+
+impl CompressionDictionary {
+    pub fn new(bytes: Vec<u8>) -> ZResult<Self> {
+        if bytes.is_empty() {
+            bail!("Empty compression dictionary");
+        }
+        let id = match zstd::zstd_safe::get_dict_id_from_dict(&bytes) {
+            Some(id) => id.get(),
+            None => {
+                let mut hasher = Shake128::default();
+                hasher.update(&bytes);
+                let mut array = 0u32.to_le_bytes();
+                hasher.finalize_xof().read(&mut array);
+                u32::from_le_bytes(array)
+            }
+        };
+        Ok(Self {
+            id,
+            bytes: bytes.into(),
+        })
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> ZResult<Self> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path).map_err(|e| {
+            zerror!(
+                "Invalid compression dictionary file {}: {}.",
+                path.display(),
+                e
+            )
+        })?;
+        Self::new(bytes)
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl fmt::Debug for CompressionDictionary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CompressionDictionary")
+            .field("id", &self.id)
+            .field("len", &self.bytes.len())
+            .finish()
+    }
+}
+
+/// The compression of the batches sent, and received, on a link.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompressionConfig {
+    /// The algorithm used to compress the sent batches.
+    pub algorithm: CompressionAlgorithm,
+    /// The zstd compression level.
+    pub level: i32,
+    /// The dictionary used to compress the sent batches with
+    /// [`CompressionAlgorithm::ZstdDictionary`] and to decompress the received ones.
+    pub dictionary: Option<CompressionDictionary>,
+}
+
+impl CompressionConfig {
+    pub fn new(
+        algorithm: CompressionAlgorithm,
+        level: i32,
+        dictionary: Option<CompressionDictionary>,
+    ) -> ZResult<Self> {
+        if !zstd::compression_level_range().contains(&level) {
+            bail!(
+                "Invalid zstd compression level {level}, it must be in {:?}",
+                zstd::compression_level_range()
+            );
+        }
+        if algorithm == CompressionAlgorithm::ZstdDictionary && dictionary.is_none() {
+            bail!("The 'zstd_dictionary' compression algorithm requires a dictionary");
+        }
+        Ok(Self {
+            algorithm,
+            level,
+            dictionary,
+        })
+    }
+
+    pub fn from_config(
+        algorithm: CompressionAlgorithm,
+        level: i32,
+        dictionary: Option<&str>,
+    ) -> ZResult<Self> {
+        let dictionary = match dictionary {
+            Some(path) => Some(CompressionDictionary::from_file(path)?),
+            None => None,
+        };
+        Self::new(algorithm, level, dictionary)
+    }
+
+    /// Returns the config with `algorithm` being used to compress the sent batches.
+    pub(crate) fn with_algorithm(&self, algorithm: CompressionAlgorithm) -> Self {
+        Self {
+            algorithm,
+            ..self.clone()
+        }
+    }
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            algorithm: CompressionAlgorithm::default(),
+            level: zstd::DEFAULT_COMPRESSION_LEVEL,
+            dictionary: None,
+        }
+    }
+}
+
+/// The buffer, and the zstd context, used to compress a [`WBatch`](super::batch::WBatch) when
+/// finalizing it.
+pub struct Compressor {
+    config: CompressionConfig,
+    pub(crate) buffer: BBuf,
+    zstd: Option<zstd::bulk::Compressor<'static>>,
+}
+
+impl Compressor {
+    pub fn new(config: CompressionConfig, mtu: BatchSize) -> Self {
+        let capacity = match config.algorithm {
+            CompressionAlgorithm::Lz4 => lz4_flex::block::get_maximum_output_size(mtu as usize),
+            CompressionAlgorithm::Zstd | CompressionAlgorithm::ZstdDictionary => {
+                // Reserve space for the batch length and header as well
+                zstd::zstd_safe::compress_bound(mtu as usize) + BatchSize::BITS as usize / 8 + 1
+            }
+        };
+        Self {
+            config,
+            buffer: BBuf::with_capacity(capacity),
+            zstd: None,
+        }
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        self.buffer.as_slice()
+    }
+
+    /// Appends the compressed `payload` to the buffer, returning the [`BatchHeader`] flags of the
+    /// used algorithm.
+    pub(crate) fn compress(&mut self, payload: &[u8]) -> ZResult<u8> {
+        let Self {
+            config,
+            buffer,
+            zstd,
+        } = self;
+
+        let mut zstd = match config.algorithm {
+            CompressionAlgorithm::Lz4 => None,
+            CompressionAlgorithm::Zstd | CompressionAlgorithm::ZstdDictionary => {
+                if zstd.is_none() {
+                    let compressor = match config.dictionary.as_ref() {
+                        Some(dictionary)
+                            if config.algorithm == CompressionAlgorithm::ZstdDictionary =>
+                        {
+                            zstd::bulk::Compressor::with_dictionary(
+                                config.level,
+                                dictionary.as_slice(),
+                            )
+                        }
+                        _ => zstd::bulk::Compressor::new(config.level),
+                    }
+                    .map_err(|e| zerror!("Invalid zstd compression context: {e}"))?;
+                    *zstd = Some(compressor);
+                }
+                zstd.as_mut()
+            }
+        };
+
+        let writer = buffer.writer();
+        let mut res = Ok(());
+        // SAFETY: assertion ensures `with_slot` precondition
+        unsafe {
+            writer.with_slot(writer.remaining(), |b| {
+                let len = match zstd.as_mut() {
+                    Some(zstd) => zstd
+                        .compress_to_buffer(payload, b)
+                        .map_err(|e| res = Err(zerror!("zstd compression error: {e}"))),
+                    None => lz4_flex::block::compress_into(payload, b)
+                        .map_err(|e| res = Err(zerror!("LZ4 compression error: {e}"))),
+                }
+                .unwrap_or(0);
+                assert!(len <= b.len());
+                len
+            })
+        }
+        .map_err(|_| zerror!("Compression error"))?;
+        res?;
+
+        Ok(match config.algorithm {
+            CompressionAlgorithm::Lz4 => 0,
+            CompressionAlgorithm::Zstd => BatchHeader::ZSTD,
+            CompressionAlgorithm::ZstdDictionary => BatchHeader::ZSTD | BatchHeader::DICTIONARY,
+        })
+    }
+}
+
+impl Clone for Compressor {
+    fn clone(&self) -> Self {
+        Self {
+            config: self.config.clone(),
+            buffer: BBuf::with_capacity(self.buffer.capacity()),
+            zstd: None,
+        }
+    }
+}
+
+impl fmt::Debug for Compressor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Compressor")
+            .field("config", &self.config)
+            .field("buffer", &self.buffer.capacity())
+            .finish()
+    }
+}
+
+/// The zstd contexts used to decompress the received [`RBatch`](super::batch::RBatch).
+#[derive(Default)]
+pub struct Decompressor {
+    dictionary: Option<CompressionDictionary>,
+    zstd: Option<zstd::bulk::Decompressor<'static>>,
+    zstd_dictionary: Option<zstd::bulk::Decompressor<'static>>,
+}
+
+impl Decompressor {
+    pub fn new(dictionary: Option<CompressionDictionary>) -> Self {
+        Self {
+            dictionary,
+            zstd: None,
+            zstd_dictionary: None,
+        }
+    }
+
+    /// Decompresses the `payload` of a batch with the given [`BatchHeader`] into `into`,
+    /// returning the number of decompressed bytes.
+    pub(crate) fn decompress(
+        &mut self,
+        header: BatchHeader,
+        payload: &[u8],
+        into: &mut [u8],
+    ) -> ZResult<usize> {
+        if !header.is_zstd() {
+            let n = lz4_flex::block::decompress_into(payload, into)
+                .map_err(|e| zerror!("LZ4 decompression error: {e}"))?;
+            return Ok(n);
+        }
+
+        let (zstd, dictionary) = if header.is_dictionary() {
+            let dictionary = self
+                .dictionary
+                .as_ref()
+                .ok_or_else(|| zerror!("No dictionary to decompress the batch"))?;
+            (&mut self.zstd_dictionary, Some(dictionary))
+        } else {
+            (&mut self.zstd, None)
+        };
+        if zstd.is_none() {
+            let decompressor = match dictionary {
+                Some(dictionary) => {
+                    zstd::bulk::Decompressor::with_dictionary(dictionary.as_slice())
+                }
+                None => zstd::bulk::Decompressor::new(),
+            }
+            .map_err(|e| zerror!("Invalid zstd decompression context: {e}"))?;
+            *zstd = Some(decompressor);
+        }
+        let zstd = zstd.as_mut().ok_or_else(|| zerror!("No zstd context"))?;
+        let n = zstd
+            .decompress_to_buffer(payload, into)
+            .map_err(|e| zerror!("zstd decompression error: {e}"))?;
+        Ok(n)
+    }
+}
+
+impl Clone for Decompressor {
+    fn clone(&self) -> Self {
+        Self::new(self.dictionary.clone())
+    }
+}
+
+impl fmt::Debug for Decompressor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Decompressor")
+            .field("dictionary", &self.dictionary)
+            .finish()
+    }
+}
@@ -11,6 +11,8 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
+#[cfg(feature = "transport_multilink")]
+use std::sync::atomic::AtomicUsize;
 use std::{
     fmt,
     ops::Add,
@@ -152,6 +154,12 @@ impl Current {
             .pending
             .fetch_and(!self.prioflag, Ordering::Relaxed);
     }
+
+    #[cfg(feature = "transport_multilink")]
+    fn notify_queued(&mut self) {
+        self.status
+            .inc_queued(self.prioflag.trailing_zeros() as usize);
+    }
 }
 
 // Inner structure containing mutexes for current serialization batch and SNs
@@ -308,6 +316,8 @@ impl StageIn {
                         None => match self.s_ref.pull() {
                             Some(mut batch) => {
                                 batch.clear();
+                                #[cfg(feature = "transport_multilink")]
+                                c_guard.notify_queued();
                                 self.s_out.atomic_backoff.first_write.store(
                                     LOCAL_EPOCH.elapsed().as_micros() as MicroSeconds,
                                     Ordering::Relaxed,
@@ -472,6 +482,8 @@ impl StageIn {
                         None => match self.s_ref.pull() {
                             Some(mut batch) => {
                                 batch.clear();
+                                #[cfg(feature = "transport_multilink")]
+                                c_guard.notify_queued();
                                 self.s_out.atomic_backoff.first_write.store(
                                     LOCAL_EPOCH.elapsed().as_micros() as MicroSeconds,
                                     Ordering::Relaxed,
@@ -681,6 +693,9 @@ pub(crate) struct TransmissionPipelineConf {
     pub(crate) batching_enabled: bool,
     pub(crate) batching_time_limit: Duration,
     pub(crate) queue_alloc: QueueAllocConf,
+    // Count the queued batches, to schedule the messages on several links
+    #[cfg(feature = "transport_multilink")]
+    pub(crate) count_queued: bool,
 }
 
 // A 2-stage transmission pipeline
@@ -696,6 +711,8 @@ impl TransmissionPipeline {
             disabled: AtomicBool::new(false),
             congested: AtomicU8::new(0),
             pending: AtomicU8::new(0),
+            #[cfg(feature = "transport_multilink")]
+            queued: config.count_queued.then(Default::default),
             waits: Waits {
                 wait_before_drop: config.wait_before_drop,
                 max_wait_before_drop_fragments: config.max_wait_before_drop_fragments,
@@ -812,6 +829,10 @@ struct TransmissionPipelineStatus {
     congested: AtomicU8,
     // Bitflags to indicate the given priority queue has messages waiting to be sent
     pending: AtomicU8,
+    // Batches of the given priority queue in use, i.e. being serialized or waiting to be sent,
+    // only counted if requested by the configuration
+    #[cfg(feature = "transport_multilink")]
+    queued: Option<Box<[QueuedBatches; Priority::NUM]>>,
     // wait parameters
     // Note: this is placed here to optimize TransmissionPipelineProducer memory layout and improve performance
     waits: Waits,
@@ -840,6 +861,32 @@ impl TransmissionPipelineStatus {
         self.congested.load(Ordering::Relaxed) & prioflag != 0
     }
 
+    #[cfg(feature = "transport_multilink")]
+    fn inc_queued(&self, idx: usize) {
+        let Some(queued) = self.queued.as_ref().map(|queued| &queued[idx]) else {
+            return;
+        };
+        // The transmission of a batch is expected from the moment the queue stops being empty
+        if queued.count.fetch_add(1, Ordering::Relaxed) == 0 {
+            queued.progress.store(
+                LOCAL_EPOCH.elapsed().as_micros() as MicroSeconds,
+                Ordering::Relaxed,
+            );
+        }
+    }
+
+    #[cfg(feature = "transport_multilink")]
+    fn dec_queued(&self, idx: usize) {
+        let Some(queued) = self.queued.as_ref().map(|queued| &queued[idx]) else {
+            return;
+        };
+        queued.count.fetch_sub(1, Ordering::Relaxed);
+        queued.progress.store(
+            LOCAL_EPOCH.elapsed().as_micros() as MicroSeconds,
+            Ordering::Relaxed,
+        );
+    }
+
     fn get_pending(&self) -> Option<Priority> {
         let pending = self.pending.load(Ordering::Relaxed);
         let prio = pending.trailing_zeros();
@@ -851,6 +898,14 @@ impl TransmissionPipelineStatus {
     }
 }
 
+#[cfg(feature = "transport_multilink")]
+#[derive(Default)]
+struct QueuedBatches {
+    count: CachePadded<AtomicUsize>,
+    // The last time a batch has been transmitted or the queue has stopped being empty
+    progress: CachePadded<AtomicMicroSeconds>,
+}
+
 #[derive(Clone)]
 struct Waits {
     wait_before_drop: Duration,
@@ -938,6 +993,44 @@ impl TransmissionPipelineProducer {
         queue.push_transport_message(msg)
     }
 
+    #[cfg(feature = "transport_multilink")]
+    #[inline]
+    fn queue_index(&self, priority: Priority) -> usize {
+        // If the queue is not QoS, it means that we only have one priority with index 0.
+        if self.stage_in.len() > 1 {
+            priority as usize
+        } else {
+            0
+        }
+    }
+
+    /// Returns the number of batches of the `priority` queue being serialized or waiting to be sent,
+    /// always 0 if they are not counted.
+    #[cfg(feature = "transport_multilink")]
+    pub(crate) fn queued_batches(&self, priority: Priority) -> usize {
+        self.status.queued.as_ref().map_or(0, |queued| {
+            queued[self.queue_index(priority)]
+                .count
+                .load(Ordering::Relaxed)
+        })
+    }
+
+    /// Returns `true` if the `priority` queue has batches waiting to be sent, but none of them
+    /// has been transmitted for more than `timeout`, always `false` if they are not counted.
+    #[cfg(feature = "transport_multilink")]
+    pub(crate) fn is_stalled(&self, priority: Priority, timeout: Duration) -> bool {
+        let Some(queued) = self.status.queued.as_ref() else {
+            return false;
+        };
+        let queued = &queued[self.queue_index(priority)];
+        if queued.count.load(Ordering::Relaxed) == 0 {
+            return false;
+        }
+        let elapsed = (LOCAL_EPOCH.elapsed().as_micros() as MicroSeconds)
+            .wrapping_sub(queued.progress.load(Ordering::Relaxed));
+        elapsed as u128 > timeout.as_micros()
+    }
+
     pub(crate) fn disable(&self) {
         self.status.set_disabled(true);
 
@@ -1034,6 +1127,8 @@ impl PipelineConsumer for TransmissionPipelineConsumer {
         if !batch.is_ephemeral() {
             self.stage_out[priority as usize].refill(batch);
             self.status.set_congested(priority, false);
+            #[cfg(feature = "transport_multilink")]
+            self.status.dec_queued(priority as usize);
         }
     }
 
@@ -1112,6 +1207,8 @@ impl PipelineConsumer for SplitTransmissionPipelineConsumer {
             debug_assert_eq!(self.priority, priority);
             self.stage_out.refill(batch);
             self.status.set_congested(priority, false);
+            #[cfg(feature = "transport_multilink")]
+            self.status.dec_queued(priority as usize);
         }
     }
 
@@ -1165,6 +1262,8 @@ mod tests {
         queue_alloc: QueueAllocConf {
             mode: QueueAllocMode::Init,
         },
+        #[cfg(feature = "transport_multilink")]
+        count_queued: true,
     };
 
     const CONFIG_NOT_STREAMED: TransmissionPipelineConf = TransmissionPipelineConf {
@@ -1183,6 +1282,8 @@ mod tests {
         queue_alloc: QueueAllocConf {
             mode: QueueAllocMode::Init,
         },
+        #[cfg(feature = "transport_multilink")]
+        count_queued: true,
     };
 
     #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
@@ -1525,4 +1626,38 @@ mod tests {
 
         Ok(())
     }
+
+    #[cfg(feature = "transport_multilink")]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn tx_pipeline_stalled() -> ZResult<()> {
+        // Pipeline
+        let tct = TransportPriorityTx::make(Bits::from(TransportSn::MAX))?;
+        let priorities = vec![tct];
+        let (producer, mut consumer) =
+            TransmissionPipeline::make(CONFIG_NOT_STREAMED, priorities.as_slice(), false);
+        let stall_timeout = Duration::from_millis(50);
+
+        let message = NetworkMessage::from(Push {
+            wire_expr: "test".into(),
+            ext_qos: ext::QoSType::new(Priority::Data, CongestionControl::Drop, true),
+            ..Push::from(vec![42u8])
+        });
+        assert_eq!(producer.queued_batches(Priority::Data), 0);
+        assert!(!producer.is_stalled(Priority::Data, stall_timeout));
+
+        // The batch is queued but not transmitted
+        assert!(producer.push_network_message(message.as_ref())?);
+        assert_eq!(producer.queued_batches(Priority::Data), 1);
+        assert!(!producer.is_stalled(Priority::Data, stall_timeout));
+        tokio::time::sleep(2 * stall_timeout).await;
+        assert!(producer.is_stalled(Priority::Data, stall_timeout));
+
+        // The batch is transmitted
+        let (batch, priority) = timeout(TIMEOUT, consumer.pull()).await?.unwrap();
+        consumer.refill(batch, priority);
+        assert_eq!(producer.queued_batches(Priority::Data), 0);
+        assert!(!producer.is_stalled(Priority::Data, stall_timeout));
+
+        Ok(())
+    }
 }
@@ -11,7 +11,10 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use std::sync::{Arc, Mutex};
+use std::{
+    sync::{Arc, Mutex},
+    time::Instant,
+};
 
 use zenoh_core::zlock;
 use zenoh_protocol::{
@@ -43,10 +46,18 @@ impl TransportChannelTx {
     }
 }
 
+/// The reliable frames missing on a channel, waiting to be retransmitted.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TransportChannelGap {
+    pub(crate) since: Instant,
+    pub(crate) last_nack: Option<Instant>,
+}
+
 #[derive(Debug)]
 pub(crate) struct TransportChannelRx {
     pub(crate) sn: SeqNum,
     pub(crate) defrag: DefragBuffer,
+    pub(crate) gap: Option<TransportChannelGap>,
 }
 
 impl TransportChannelRx {
@@ -57,7 +68,11 @@ impl TransportChannelRx {
     ) -> ZResult<TransportChannelRx> {
         let sn = SeqNum::make(0, resolution)?;
         let defrag = DefragBuffer::make(reliability, resolution, defrag_buff_size)?;
-        let tch = TransportChannelRx { sn, defrag };
+        let tch = TransportChannelRx {
+            sn,
+            defrag,
+            gap: None,
+        };
         Ok(tch)
     }
 
@@ -70,6 +85,7 @@ impl TransportChannelRx {
         };
 
         self.sn.set(sn)?;
+        self.gap = None;
         self.defrag.sync(sn)
     }
 }
@@ -70,6 +70,8 @@ pub(crate) async fn open_link(
             is_compression: manager.config.multicast.is_compression,
             ..Default::default()
         },
+        #[cfg(feature = "transport_compression")]
+        compression: manager.config.multicast.compression.clone(),
     };
     let link = TransportLinkMulticast::new(link, config);
 
@@ -19,18 +19,21 @@ use std::{
 };
 
 use tokio::task::JoinHandle;
-use zenoh_buffers::{BBuf, ZSlice, ZSliceBuffer};
-use zenoh_core::{zcondfeat, zlock};
+use zenoh_buffers::{ZSlice, ZSliceBuffer};
+use zenoh_core::zlock;
 use zenoh_link::{LinkMulticast, Locator};
 use zenoh_protocol::{
     core::{Bits, Priority, Resolution, WhatAmI, ZenohIdProto},
     transport::{
-        join::ext::PatchType, BatchSize, Close, Join, PrioritySn, TransportMessage, TransportSn,
+        join::{self, ext::PatchType},
+        BatchSize, Close, Join, PrioritySn, TransportMessage, TransportSn,
     },
 };
 use zenoh_result::{zerror, ZResult};
 use zenoh_sync::{RecyclingObject, RecyclingObjectPool, Signal};
 
+#[cfg(feature = "transport_compression")]
+use crate::common::compression::{CompressionConfig, Compressor, Decompressor};
 use crate::{
     common::{
         batch::{BatchConfig, Encode, Finalize, RBatch, WBatch},
@@ -40,15 +43,20 @@ use crate::{
         },
         priority::TransportPriorityTx,
     },
-    multicast::transport::TransportMulticastInner,
+    multicast::{
+        nack::{Nack, RetransmissionBuffer},
+        transport::TransportMulticastInner,
+    },
 };
 
 /****************************/
 /* TRANSPORT MULTICAST LINK */
 /****************************/
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Debug)]
 pub(crate) struct TransportLinkMulticastConfig {
     pub(crate) batch: BatchConfig,
+    #[cfg(feature = "transport_compression")]
+    pub(crate) compression: CompressionConfig,
 }
 
 #[derive(Clone, PartialEq, Eq)]
@@ -67,22 +75,20 @@ impl TransportLinkMulticast {
     pub(crate) fn tx(&self) -> TransportLinkMulticastTx {
         TransportLinkMulticastTx {
             inner: self.clone(),
-            buffer: zcondfeat!(
-                "transport_compression",
-                self.config
-                    .batch
-                    .is_compression
-                    .then_some(BBuf::with_capacity(
-                        lz4_flex::block::get_maximum_output_size(self.config.batch.mtu as usize),
-                    )),
-                None
-            ),
+            #[cfg(feature = "transport_compression")]
+            compressor: self
+                .config
+                .batch
+                .is_compression
+                .then(|| Compressor::new(self.config.compression.clone(), self.config.batch.mtu)),
         }
     }
 
     pub(crate) fn rx(&self) -> TransportLinkMulticastRx {
         TransportLinkMulticastRx {
             inner: self.clone(),
+            #[cfg(feature = "transport_compression")]
+            decompressor: Decompressor::new(self.config.compression.dictionary.clone()),
         }
     }
 
@@ -128,21 +134,26 @@ impl fmt::Debug for TransportLinkMulticast {
 
 pub(crate) struct TransportLinkMulticastTx {
     pub(crate) inner: TransportLinkMulticast,
-    pub(crate) buffer: Option<BBuf>,
+    #[cfg(feature = "transport_compression")]
+    pub(crate) compressor: Option<Compressor>,
 }
 
 impl TransportLinkMulticastTx {
-    pub(crate) async fn send_batch(&mut self, batch: &mut WBatch) -> ZResult<()> {
+    pub(crate) async fn send_batch<'a>(&'a mut self, batch: &'a mut WBatch) -> ZResult<&'a [u8]> {
         const ERR: &str = "Write error on link: ";
 
         let res = batch
-            .finalize(self.buffer.as_mut())
+            .finalize(
+                #[cfg(feature = "transport_compression")]
+                self.compressor.as_mut(),
+            )
             .map_err(|_| zerror!("{ERR}{self}"))?;
 
         let bytes = match res {
             Finalize::Batch => batch.as_slice(),
+            #[cfg(feature = "transport_compression")]
             Finalize::Buffer => self
-                .buffer
+                .compressor
                 .as_ref()
                 .ok_or_else(|| zerror!("Invalid buffer finalization"))?
                 .as_slice(),
@@ -151,7 +162,7 @@ impl TransportLinkMulticastTx {
         // Send the message on the link
         self.inner.link.write_all(bytes).await?;
 
-        Ok(())
+        Ok(bytes)
     }
 
     pub(crate) async fn send(&mut self, msg: &TransportMessage) -> ZResult<usize> {
@@ -179,7 +190,7 @@ impl fmt::Debug for TransportLinkMulticastTx {
             .field("config", &self.inner.config);
         #[cfg(feature = "transport_compression")]
         {
-            s.field("buffer", &self.buffer.as_ref().map(|b| b.capacity()));
+            s.field("compressor", &self.compressor);
         }
         s.finish()
     }
@@ -187,10 +198,12 @@ impl fmt::Debug for TransportLinkMulticastTx {
 
 pub(crate) struct TransportLinkMulticastRx {
     pub(crate) inner: TransportLinkMulticast,
+    #[cfg(feature = "transport_compression")]
+    pub(crate) decompressor: Decompressor,
 }
 
 impl TransportLinkMulticastRx {
-    pub async fn recv_batch<C, T>(&self, buff: C) -> ZResult<(RBatch, Locator)>
+    pub async fn recv_batch<C, T>(&mut self, buff: C) -> ZResult<(RBatch, Locator)>
     where
         C: Fn() -> T + Copy,
         T: AsMut<[u8]> + ZSliceBuffer + 'static,
@@ -201,7 +214,13 @@ impl TransportLinkMulticastRx {
         let (n, locator) = self.inner.link.read(into.as_mut()).await?;
         let buffer = ZSlice::new(Arc::new(into), 0, n).map_err(|_| zerror!("Error"))?;
         let mut batch = RBatch::new(self.inner.config.batch, buffer);
-        batch.initialize(buff).map_err(|_| zerror!("{ERR}{self}"))?;
+        batch
+            .initialize(
+                buff,
+                #[cfg(feature = "transport_compression")]
+                &mut self.decompressor,
+            )
+            .map_err(|_| zerror!("{ERR}{self}"))?;
         Ok((batch, locator.into_owned()))
     }
 
@@ -243,6 +262,9 @@ pub(super) struct TransportLinkMulticastConfigUniversal {
     pub(super) join_interval: Duration,
     pub(super) sn_resolution: Bits,
     pub(super) batch_size: BatchSize,
+    pub(super) is_nack: bool,
+    pub(super) retransmission_buffer_size: usize,
+    pub(super) nack_interval: Duration,
 }
 
 // TODO(yuyuan): Introduce TaskTracker or JoinSet and retire handle_tx, handle_rx, and signal_rx.
@@ -252,6 +274,8 @@ pub(super) struct TransportLinkMulticastUniversal {
     pub(super) link: TransportLinkMulticast,
     // The transmission pipeline
     pub(super) pipeline: Option<TransmissionPipelineProducer>,
+    // The NACKs to be served by the TX task
+    pub(super) nack_tx: Option<flume::Sender<Nack>>,
     // The transport this link is associated to
     transport: TransportMulticastInner,
     // The signals to stop TX/RX tasks
@@ -269,6 +293,7 @@ impl TransportLinkMulticastUniversal {
             transport,
             link,
             pipeline: None,
+            nack_tx: None,
             handle_tx: None,
             signal_rx: Signal::new(),
             handle_rx: None,
@@ -318,11 +343,20 @@ impl TransportLinkMulticastUniversal {
                 batching_enabled: self.transport.manager.config.batching,
                 batching_time_limit: self.transport.manager.config.queue_backoff,
                 queue_alloc: self.transport.manager.config.queue_alloc,
+                #[cfg(feature = "transport_multilink")]
+                count_queued: false,
             };
             // The pipeline
             let (producer, consumer) = TransmissionPipeline::make(tpc, &priority_tx, false);
             self.pipeline = Some(producer);
 
+            // The NACKs received from the peers
+            let nack_rx = config.is_nack.then(|| {
+                let (nack_tx, nack_rx) = flume::bounded(Priority::NUM);
+                self.nack_tx = Some(nack_tx);
+                nack_rx
+            });
+
             // Spawn the TX task
             let c_link = self.link.clone();
             let c_transport = self.transport.clone();
@@ -333,6 +367,7 @@ impl TransportLinkMulticastUniversal {
                     c_link.tx(),
                     config,
                     initial_sns,
+                    nack_rx,
                     #[cfg(feature = "stats")]
                     c_transport.link_stats.clone(),
                 )
@@ -416,6 +451,7 @@ async fn tx_task(
     mut link: TransportLinkMulticastTx,
     config: TransportLinkMulticastConfigUniversal,
     mut last_sns: Vec<PrioritySn>,
+    nack_rx: Option<flume::Receiver<Nack>>,
     #[cfg(feature = "stats")] stats: zenoh_stats::LinkStats,
 ) -> ZResult<()> {
     async fn join(last_join: Instant, join_interval: Duration) {
@@ -427,14 +463,37 @@ async fn tx_task(
         }
     }
 
+    async fn nack(nack_rx: Option<&flume::Receiver<Nack>>) -> Nack {
+        if let Some(rx) = nack_rx {
+            if let Ok(nack) = rx.recv_async().await {
+                return nack;
+            }
+        }
+        std::future::pending().await
+    }
+
+    let sn_mask = config.sn_resolution.mask() as TransportSn;
+    let mut retransmission = nack_rx.is_some().then(|| {
+        RetransmissionBuffer::new(
+            config.retransmission_buffer_size,
+            sn_mask,
+            config.nack_interval,
+        )
+    });
     let mut last_join = Instant::now().checked_sub(config.join_interval).unwrap();
     loop {
         tokio::select! {
             res = pipeline.pull() => {
                 match res {
                     Some((mut batch, priority)) => {
+                        let latest_reliable = batch.codec.latest_sn.reliable;
                         // Send the buffer on the link
-                        link.send_batch(&mut batch).await?;
+                        let bytes = link.send_batch(&mut batch).await?;
+                        // Keep the batches with reliable frames for retransmission
+                        if let (Some(buffer), Some(sn)) = (retransmission.as_mut(), latest_reliable) {
+                            let first_sn = (1 + last_sns[priority as usize].reliable) & sn_mask;
+                            buffer.push(priority as usize, first_sn, sn, bytes);
+                        }
                         // Keep track of next SNs
                         if let Some(sn) = batch.codec.latest_sn.reliable {
                             last_sns[priority as usize].reliable = sn;
@@ -454,7 +513,9 @@ async fn tx_task(
                         // Drain the transmission pipeline and write remaining bytes on the wire
                         let mut batches = pipeline.drain();
                         for (mut b, _) in batches.drain(..) {
-                            tokio::time::timeout(config.join_interval, link.send_batch(&mut b))
+                            tokio::time::timeout(config.join_interval, async {
+                                link.send_batch(&mut b).await.map(|_| ())
+                            })
                                 .await
                                 .map_err(|_| {
                                     zerror!(
@@ -476,6 +537,24 @@ async fn tx_task(
                 }
             }
 
+            nack = nack(nack_rx.as_ref()) => {
+                let Some(buffer) = retransmission.as_mut() else {
+                    continue;
+                };
+                // Frames without QoS are all sent on the single priority of the pipeline
+                let priority = if last_sns.len() == Priority::NUM {
+                    nack.priority as usize
+                } else {
+                    0
+                };
+                for bytes in buffer.retransmit(priority, nack.sn) {
+                    tracing::trace!("{}: retransmitting from SN {} upon NACK", link, nack.sn);
+                    link.inner.link.write_all(bytes).await?;
+                    #[cfg(feature = "stats")]
+                    stats.inc_bytes(zenoh_stats::Tx, bytes.len() as u64);
+                }
+            }
+
             _ = join(last_join, config.join_interval) => {
                 let next_sns = last_sns
                     .iter()
@@ -501,6 +580,7 @@ async fn tx_task(
                     next_sn,
                     ext_qos,
                     ext_shm: None,
+                    ext_nack: config.is_nack.then_some(join::ext::Nack::new()),
                     ext_patch: PatchType::CURRENT
                 }
                 .into();
@@ -553,12 +633,24 @@ async fn rx_task(
     }
 
     let pool = RecyclingObjectPool::new(n, || vec![0_u8; mtu].into_boxed_slice());
+    #[cfg(feature = "test")]
+    let rx_loss = transport.manager.config.multicast.rx_loss;
+    #[cfg(feature = "test")]
+    let mut rx_count: usize = 0;
     loop {
         tokio::select! {
             _ = signal.wait() => break,
             res = read(&mut link, &pool) => {
                 let (batch, locator) = res?;
 
+                #[cfg(feature = "test")]
+                {
+                    rx_count = rx_count.wrapping_add(1);
+                    if rx_loss != 0 && rx_count % rx_loss == 0 {
+                        continue;
+                    }
+                }
+
                 #[cfg(feature = "stats")]
                 transport.link_stats.inc_bytes(zenoh_stats::Rx, batch.len() as u64);
 
@@ -16,7 +16,7 @@ use std::{collections::HashMap, sync::Arc, time::Duration};
 use tokio::sync::Mutex;
 #[cfg(feature = "transport_compression")]
 use zenoh_config::CompressionMulticastConf;
-use zenoh_config::{Config, LinkTxConf};
+use zenoh_config::{Config, LinkTxConf, ReliabilityMulticastConf};
 use zenoh_core::zasynclock;
 use zenoh_link::*;
 use zenoh_protocol::{
@@ -25,6 +25,8 @@ use zenoh_protocol::{
 };
 use zenoh_result::{bail, zerror, ZResult};
 
+#[cfg(feature = "transport_compression")]
+use crate::common::compression::CompressionConfig;
 use crate::{
     multicast::{transport::TransportMulticastInner, TransportMulticast},
     TransportManager,
@@ -36,8 +38,16 @@ pub struct TransportManagerConfigMulticast {
     pub join_interval: Duration,
     pub max_sessions: usize,
     pub is_qos: bool,
+    pub is_nack: bool,
+    pub retransmission_buffer_size: usize,
+    pub nack_interval: Duration,
+    pub nack_timeout: Duration,
+    #[cfg(feature = "test")]
+    pub rx_loss: usize,
     #[cfg(feature = "transport_compression")]
     pub is_compression: bool,
+    #[cfg(feature = "transport_compression")]
+    pub compression: CompressionConfig,
 }
 
 pub struct TransportManagerBuilderMulticast {
@@ -46,8 +56,16 @@ pub struct TransportManagerBuilderMulticast {
     join_interval: Duration,
     max_sessions: usize,
     is_qos: bool,
+    is_nack: bool,
+    retransmission_buffer_size: usize,
+    nack_interval: Duration,
+    nack_timeout: Duration,
+    #[cfg(feature = "test")]
+    rx_loss: usize,
     #[cfg(feature = "transport_compression")]
     is_compression: bool,
+    #[cfg(feature = "transport_compression")]
+    compression: CompressionConfig,
 }
 
 pub struct TransportManagerStateMulticast {
@@ -88,12 +106,46 @@ impl TransportManagerBuilderMulticast {
         self
     }
 
+    pub fn nack(mut self, is_nack: bool) -> Self {
+        self.is_nack = is_nack;
+        self
+    }
+
+    pub fn retransmission_buffer_size(mut self, retransmission_buffer_size: usize) -> Self {
+        self.retransmission_buffer_size = retransmission_buffer_size;
+        self
+    }
+
+    pub fn nack_interval(mut self, nack_interval: Duration) -> Self {
+        self.nack_interval = nack_interval;
+        self
+    }
+
+    pub fn nack_timeout(mut self, nack_timeout: Duration) -> Self {
+        self.nack_timeout = nack_timeout;
+        self
+    }
+
+    /// Drops one in `rx_loss` batches received on the link to emulate a lossy network,
+    /// `0` disabling the drops.
+    #[cfg(feature = "test")]
+    pub fn rx_loss(mut self, rx_loss: usize) -> Self {
+        self.rx_loss = rx_loss;
+        self
+    }
+
     #[cfg(feature = "transport_compression")]
     pub fn compression(mut self, is_compression: bool) -> Self {
         self.is_compression = is_compression;
         self
     }
 
+    #[cfg(feature = "transport_compression")]
+    pub fn compression_config(mut self, compression: CompressionConfig) -> Self {
+        self.compression = compression;
+        self
+    }
+
     pub fn from_config(mut self, config: &Config) -> ZResult<TransportManagerBuilderMulticast> {
         self = self.lease(Duration::from_millis(
             *config.transport().link().tx().lease(),
@@ -104,6 +156,21 @@ impl TransportManagerBuilderMulticast {
         ));
         self = self.max_sessions(config.transport().multicast().max_sessions().unwrap());
         self = self.qos(*config.transport().multicast().qos().enabled());
+        let reliability = config.transport().multicast().reliability();
+        self = self.nack(*reliability.enabled());
+        self = self.retransmission_buffer_size(*reliability.retransmission_buffer_size());
+        self = self.nack_interval(Duration::from_millis(*reliability.nack_interval()));
+        self = self.nack_timeout(Duration::from_millis(*reliability.nack_timeout()));
+        #[cfg(feature = "transport_compression")]
+        {
+            let compression = config.transport().multicast().compression();
+            self = self.compression(*compression.enabled());
+            self = self.compression_config(CompressionConfig::from_config(
+                compression.algorithm,
+                *compression.level(),
+                compression.dictionary().as_deref(),
+            )?);
+        }
 
         Ok(self)
     }
@@ -115,8 +182,16 @@ impl TransportManagerBuilderMulticast {
             join_interval: self.join_interval,
             max_sessions: self.max_sessions,
             is_qos: self.is_qos,
+            is_nack: self.is_nack,
+            retransmission_buffer_size: self.retransmission_buffer_size,
+            nack_interval: self.nack_interval,
+            nack_timeout: self.nack_timeout,
+            #[cfg(feature = "test")]
+            rx_loss: self.rx_loss,
             #[cfg(feature = "transport_compression")]
             is_compression: self.is_compression,
+            #[cfg(feature = "transport_compression")]
+            compression: self.compression,
         };
 
         let state = TransportManagerStateMulticast {
@@ -133,6 +208,7 @@ impl TransportManagerBuilderMulticast {
 impl Default for TransportManagerBuilderMulticast {
     fn default() -> TransportManagerBuilderMulticast {
         let link_tx = LinkTxConf::default();
+        let reliability = ReliabilityMulticastConf::default();
         #[cfg(feature = "transport_compression")]
         let compression = CompressionMulticastConf::default();
 
@@ -142,8 +218,16 @@ impl Default for TransportManagerBuilderMulticast {
             join_interval: Duration::from_millis(0),
             max_sessions: 0,
             is_qos: false,
+            is_nack: *reliability.enabled(),
+            retransmission_buffer_size: *reliability.retransmission_buffer_size(),
+            nack_interval: Duration::from_millis(*reliability.nack_interval()),
+            nack_timeout: Duration::from_millis(*reliability.nack_timeout()),
+            #[cfg(feature = "test")]
+            rx_loss: 0,
             #[cfg(feature = "transport_compression")]
             is_compression: *compression.enabled(),
+            #[cfg(feature = "transport_compression")]
+            compression: CompressionConfig::default(),
         };
         tmb.from_config(&Config::default()).unwrap()
     }
@@ -13,6 +13,7 @@
 pub(crate) mod establishment;
 pub(crate) mod link;
 pub(crate) mod manager;
+pub(crate) mod nack;
 pub(crate) mod rx;
 pub(crate) mod transport;
 pub(crate) mod tx;
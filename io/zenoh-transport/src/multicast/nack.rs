@@ -0,0 +1,211 @@
+//
+// Copyright (c) 2023 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use zenoh_buffers::{
+    reader::{DidntRead, HasReader},
+    writer::{DidntWrite, HasWriter},
+    ZBuf,
+};
+use zenoh_codec::{RCodec, WCodec, Zenoh080};
+use zenoh_protocol::{
+    common::ZExtBody,
+    core::{Priority, ZenohIdProto},
+    transport::{oam, Oam, TransportSn},
+};
+
+/// A request for the retransmission of the reliable frames sent by `zid` on `priority`,
+/// starting from the frame with sequence number `sn`.
+///
+/// ```text
+///  7 6 5 4 3 2 1 0
+/// +-+-+-+-+-+-+-+-+
+/// ~      zid      ~ -- ZenohID of the sender of the missing frames
+/// +---------------+
+/// |   priority    |
+/// +---------------+
+/// %      sn       % -- SN of the first missing reliable frame
+/// +---------------+
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Nack {
+    pub(crate) zid: ZenohIdProto,
+    pub(crate) priority: Priority,
+    pub(crate) sn: TransportSn,
+}
+
+impl Nack {
+    pub(crate) fn to_oam(self) -> Result<Oam, DidntWrite> {
+        let codec = Zenoh080::new();
+        let mut buffer = ZBuf::empty();
+        let mut writer = buffer.writer();
+        codec.write(&mut writer, &self.zid)?;
+        codec.write(&mut writer, self.priority as u8)?;
+        codec.write(&mut writer, self.sn)?;
+
+        Ok(Oam {
+            id: oam::id::OAM_NACK,
+            body: ZExtBody::ZBuf(buffer),
+            ext_qos: oam::ext::QoSType::new(Priority::Control),
+        })
+    }
+
+    pub(crate) fn from_oam(oam: &Oam) -> Result<Self, DidntRead> {
+        let ZExtBody::ZBuf(buffer) = &oam.body else {
+            return Err(DidntRead);
+        };
+        if oam.id != oam::id::OAM_NACK {
+            return Err(DidntRead);
+        }
+
+        let codec = Zenoh080::new();
+        let mut reader = buffer.reader();
+        let zid: ZenohIdProto = codec.read(&mut reader)?;
+        let priority: u8 = codec.read(&mut reader)?;
+        let priority = Priority::try_from(priority).map_err(|_| DidntRead)?;
+        let sn: TransportSn = codec.read(&mut reader)?;
+
+        Ok(Self { zid, priority, sn })
+    }
+}
+
+/// A batch kept by the sender until it is evicted by the newer ones.
+struct RetransmissionEntry {
+    priority: usize,
+    first_sn: TransportSn,
+    last_sn: TransportSn,
+    bytes: Box<[u8]>,
+}
+
+/// The bounded buffer of the last batches containing reliable frames, retransmitted upon NACK.
+pub(crate) struct RetransmissionBuffer {
+    entries: VecDeque<RetransmissionEntry>,
+    capacity: usize,
+    sn_mask: TransportSn,
+    nack_interval: Duration,
+    last_nacks: Vec<Option<(TransportSn, Instant)>>,
+}
+
+impl RetransmissionBuffer {
+    pub(crate) fn new(capacity: usize, sn_mask: TransportSn, nack_interval: Duration) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+            sn_mask,
+            nack_interval,
+            last_nacks: vec![None; Priority::NUM],
+        }
+    }
+
+    /// Stores a batch of the pipeline `priority` holding the reliable frames from `first_sn`
+    /// to `last_sn` included.
+    pub(crate) fn push(
+        &mut self,
+        priority: usize,
+        first_sn: TransportSn,
+        last_sn: TransportSn,
+        bytes: &[u8],
+    ) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(RetransmissionEntry {
+            priority,
+            first_sn,
+            last_sn,
+            bytes: bytes.into(),
+        });
+    }
+
+    /// Returns the batches to retransmit for a NACK of `sn` on `priority`, that is the batch
+    /// containing `sn` and all the following ones of the same priority. The NACKs of the same
+    /// frame received within the NACK interval are answered only once, since every receiver
+    /// missing a frame sends its own NACK.
+    pub(crate) fn retransmit(
+        &mut self,
+        priority: usize,
+        sn: TransportSn,
+    ) -> impl Iterator<Item = &[u8]> {
+        let now = Instant::now();
+        let last_nack = &mut self.last_nacks[priority];
+        let is_duplicate = matches!(
+            last_nack,
+            Some((last_sn, instant)) if *last_sn == sn && now.duration_since(*instant) < self.nack_interval
+        );
+        let mask = self.sn_mask;
+        let contains = |e: &RetransmissionEntry| {
+            let offset = sn.wrapping_sub(e.first_sn) & mask;
+            let length = e.last_sn.wrapping_sub(e.first_sn) & mask;
+            offset <= length
+        };
+        let start = if is_duplicate {
+            None
+        } else {
+            self.entries
+                .iter()
+                .position(|e| e.priority == priority && contains(e))
+        };
+        if start.is_some() {
+            *last_nack = Some((sn, now));
+        }
+
+        self.entries
+            .iter()
+            .skip(start.unwrap_or(self.entries.len()))
+            .filter(move |e| e.priority == priority)
+            .map(|e| e.bytes.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nack_oam_codec() {
+        let nack = Nack {
+            zid: ZenohIdProto::rand(),
+            priority: Priority::DataHigh,
+            sn: 42,
+        };
+        let oam = nack.to_oam().unwrap();
+        assert_eq!(Nack::from_oam(&oam).unwrap(), nack);
+    }
+
+    #[test]
+    fn retransmission_buffer() {
+        let mask = (1 << 16) - 1;
+        let mut buffer = RetransmissionBuffer::new(3, mask, Duration::from_secs(60));
+        buffer.push(0, mask - 3, mask - 2, &[0]);
+        buffer.push(0, mask - 1, 0, &[1]);
+        buffer.push(1, 0, 0, &[2]);
+        buffer.push(0, 1, 2, &[3]);
+
+        // The first batch has been evicted
+        assert_eq!(buffer.retransmit(0, mask - 3).count(), 0);
+        // The SN wraps around within the second batch
+        let batches: Vec<&[u8]> = buffer.retransmit(0, mask).collect();
+        assert_eq!(batches, vec![&[1][..], &[3][..]]);
+        // A repeated NACK within the interval is ignored
+        assert_eq!(buffer.retransmit(0, mask).count(), 0);
+        let batches: Vec<&[u8]> = buffer.retransmit(0, 2).collect();
+        assert_eq!(batches, vec![&[3][..]]);
+    }
+}
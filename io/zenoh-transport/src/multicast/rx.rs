@@ -11,7 +11,7 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use std::sync::MutexGuard;
+use std::{sync::MutexGuard, time::Instant};
 
 use zenoh_buffers::ZSlice;
 use zenoh_codec::transport::frame::FrameReader;
@@ -20,15 +20,19 @@ use zenoh_protocol::{
     core::{Locator, Priority, Reliability},
     network::NetworkMessageMut,
     transport::{
-        BatchSize, Close, Fragment, Join, KeepAlive, TransportBody, TransportMessage, TransportSn,
+        BatchSize, Close, Fragment, Join, KeepAlive, Oam, TransportBody, TransportMessage,
+        TransportSn,
     },
 };
 use zenoh_result::{bail, zerror, ZResult};
 
-use super::transport::{TransportMulticastInner, TransportMulticastPeer};
+use super::{
+    nack::Nack,
+    transport::{TransportMulticastInner, TransportMulticastPeer},
+};
 use crate::common::{
     batch::{Decode, RBatch},
-    priority::TransportChannelRx,
+    priority::{TransportChannelGap, TransportChannelRx},
 };
 
 /*************************************/
@@ -82,6 +86,28 @@ impl TransportMulticastInner {
             bail!("{}", e);
         }
 
+        // Detect the loss of the last reliable frames sent by the peer
+        if peer.is_nack {
+            let next_sns = match join.ext_qos.as_ref() {
+                Some(sns) => sns.to_vec(),
+                None => vec![join.next_sn],
+            };
+            for (i, (c, next_sn)) in peer.priority_rx.iter().zip(next_sns).enumerate() {
+                let priority = if peer.is_qos() {
+                    Priority::try_from(i as u8)?
+                } else {
+                    Priority::DEFAULT
+                };
+                let mut guard = zlock!(c.reliable);
+                if guard.sn.next() == next_sn.reliable || !guard.sn.precedes(next_sn.reliable)? {
+                    continue;
+                }
+                if self.handle_gap(peer, priority, &mut guard) {
+                    guard.sync(next_sn.reliable)?;
+                }
+            }
+        }
+
         Ok(())
     }
 
@@ -170,7 +196,14 @@ impl TransportMulticastInner {
             Reliability::BestEffort => zlock!(c.best_effort),
         };
 
-        if !self.verify_sn("Frame", frame.sn, &mut guard)? {
+        if !self.verify_sn(
+            "Frame",
+            frame.sn,
+            frame.reliability,
+            priority,
+            peer,
+            &mut guard,
+        )? {
             // Drop invalid message and continue
             return Ok(());
         }
@@ -211,7 +244,7 @@ impl TransportMulticastInner {
             Reliability::BestEffort => zlock!(c.best_effort),
         };
 
-        if !self.verify_sn("Fragment", sn, &mut guard)? {
+        if !self.verify_sn("Fragment", sn, reliability, priority, peer, &mut guard)? {
             // Drop invalid message and continue
             return Ok(());
         }
@@ -259,6 +292,9 @@ impl TransportMulticastInner {
         &self,
         message_type: &str,
         sn: TransportSn,
+        reliability: Reliability,
+        priority: Priority,
+        peer: &TransportMulticastPeer,
         guard: &mut MutexGuard<'_, TransportChannelRx>,
     ) -> ZResult<bool> {
         let precedes = guard.sn.precedes(sn)?;
@@ -273,13 +309,111 @@ impl TransportMulticastInner {
             return Ok(false);
         }
 
+        // Some reliable frames are missing: drop the out-of-order ones until they
+        // are retransmitted or given up on
+        if peer.is_nack
+            && reliability == Reliability::Reliable
+            && guard.sn.gap(sn)? > 1
+            && !self.handle_gap(peer, priority, guard)
+        {
+            return Ok(false);
+        }
+
         // Set will always return OK because we have already checked
         // with precedes() that the sn has the right resolution
         let _ = guard.sn.set(sn);
+        guard.gap = None;
 
         Ok(true)
     }
 
+    /// Requests the retransmission of the missing reliable frames of a channel, at most once
+    /// per NACK interval. Returns `true` when the missing frames are given up on after the NACK
+    /// timeout.
+    fn handle_gap(
+        &self,
+        peer: &TransportMulticastPeer,
+        priority: Priority,
+        guard: &mut MutexGuard<'_, TransportChannelRx>,
+    ) -> bool {
+        let config = &self.manager.config.multicast;
+        let now = Instant::now();
+        let gap = guard.gap.get_or_insert(TransportChannelGap {
+            since: now,
+            last_nack: None,
+        });
+
+        if now.duration_since(gap.since) >= config.nack_timeout {
+            tracing::debug!(
+                "Transport: {}. Peer: {}. Priority: {:?}. Reliable frames lost from SN {}.",
+                self.manager.config.zid,
+                peer.zid,
+                priority,
+                guard.sn.next()
+            );
+            guard.gap = None;
+            return true;
+        }
+
+        if gap
+            .last_nack
+            .map_or(true, |t| now.duration_since(t) >= config.nack_interval)
+        {
+            gap.last_nack = Some(now);
+            let nack = Nack {
+                zid: peer.zid,
+                priority,
+                sn: guard.sn.next(),
+            };
+            self.send_nack(nack);
+        }
+
+        false
+    }
+
+    fn send_nack(&self, nack: Nack) {
+        let Ok(oam) = nack.to_oam() else {
+            tracing::debug!(
+                "Transport: {}. Failed to encode {:?}.",
+                self.manager.config.zid,
+                nack
+            );
+            return;
+        };
+        let pipeline = zread!(self.link).as_ref().and_then(|l| l.pipeline.clone());
+        if let Some(pipeline) = pipeline {
+            tracing::trace!(
+                "Transport: {}. Sending {:?}.",
+                self.manager.config.zid,
+                nack
+            );
+            let msg: TransportMessage = TransportBody::OAM(oam).into();
+            pipeline.push_transport_message(msg, Priority::Control);
+        }
+    }
+
+    fn handle_oam(&self, oam: Oam) {
+        if !self.manager.config.multicast.is_nack {
+            return;
+        }
+        let Ok(nack) = Nack::from_oam(&oam) else {
+            return;
+        };
+        if nack.zid != self.manager.config.zid {
+            return;
+        }
+        let nack_tx = zread!(self.link).as_ref().and_then(|l| l.nack_tx.clone());
+        if let Some(nack_tx) = nack_tx {
+            if nack_tx.try_send(nack).is_err() {
+                tracing::trace!(
+                    "Transport: {}. Dropping {:?}: too many pending NACKs.",
+                    self.manager.config.zid,
+                    nack
+                );
+            }
+        }
+    }
+
     pub(super) fn read_messages(
         &self,
         mut batch: RBatch,
@@ -322,6 +456,7 @@ impl TransportMulticastInner {
                         }
                         TransportBody::Join(join) => self.handle_join_from_peer(join, peer)?,
                         TransportBody::KeepAlive(KeepAlive { .. }) => {}
+                        TransportBody::OAM(oam) => self.handle_oam(oam),
                         TransportBody::Close(Close { reason, .. }) => {
                             drop(r_guard);
                             self.del_peer(&locator, reason)?;
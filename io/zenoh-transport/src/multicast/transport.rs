@@ -61,6 +61,7 @@ pub(super) struct TransportMulticastPeer {
     pub(super) priority_rx: Box<[TransportPriorityRx]>,
     pub(super) handler: Arc<dyn TransportPeerEventHandler>,
     pub(super) patch: PatchType,
+    pub(super) is_nack: bool,
     #[cfg(feature = "stats")]
     pub(super) stats: zenoh_stats::LinkStats,
 }
@@ -257,6 +258,13 @@ impl TransportMulticastInner {
                     join_interval: self.manager.config.multicast.join_interval,
                     sn_resolution: self.manager.config.resolution.get(Field::FrameSN),
                     batch_size,
+                    is_nack: self.manager.config.multicast.is_nack,
+                    retransmission_buffer_size: self
+                        .manager
+                        .config
+                        .multicast
+                        .retransmission_buffer_size,
+                    nack_interval: self.manager.config.multicast.nack_interval,
                 };
                 l.start_tx(config, self.priority_tx.clone());
                 Ok(())
@@ -372,7 +380,7 @@ impl TransportMulticastInner {
         let priority_rx = priority_rx.into_boxed_slice();
 
         tracing::debug!(
-                "New transport joined on {}: zid {}, whatami {}, resolution {:?}, locator {}, is_qos {}, is_shm {}, initial sn: {:?}",
+                "New transport joined on {}: zid {}, whatami {}, resolution {:?}, locator {}, is_qos {}, is_shm {}, is_nack {}, initial sn: {:?}",
                 self.locator,
                 peer.zid,
                 peer.whatami,
@@ -380,6 +388,7 @@ impl TransportMulticastInner {
                 locator,
                 peer.is_qos,
                 is_shm,
+                join.ext_nack.is_some(),
                 next_sns,
             );
 
@@ -424,6 +433,7 @@ impl TransportMulticastInner {
             priority_rx,
             handler,
             patch: min(PatchType::CURRENT, join.ext_patch),
+            is_nack: self.manager.config.multicast.is_nack && join.ext_nack.is_some(),
             #[cfg(feature = "stats")]
             stats: self
                 .stats
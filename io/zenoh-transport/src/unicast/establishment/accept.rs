@@ -36,6 +36,8 @@ use zenoh_result::ZResult;
 use super::ext::auth::UsrPwdId;
 #[cfg(feature = "shared-memory")]
 use super::ext::shm::AuthSegment;
+#[cfg(feature = "transport_compression")]
+use crate::common::compression::CompressionConfig;
 #[cfg(feature = "shared-memory")]
 use crate::shm::TransportShmConfig;
 use crate::{
@@ -272,7 +274,10 @@ impl<'a, 'b: 'a> AcceptFsm for &'a mut AcceptLink<'b> {
         // Extension Compression
         #[cfg(feature = "transport_compression")]
         self.ext_compression
-            .recv_init_syn((&mut state.link.ext_compression, init_syn.ext_compression))
+            .recv_init_syn((
+                &mut state.link.ext_compression,
+                (init_syn.ext_compression, init_syn.ext_compression_algorithm),
+            ))
             .await
             .map_err(|e| (e, Some(close::reason::GENERIC)))?;
 
@@ -354,13 +359,13 @@ impl<'a, 'b: 'a> AcceptFsm for &'a mut AcceptLink<'b> {
             .map_err(|e| (e, Some(close::reason::GENERIC)))?;
 
         // Extension Compression
-        let ext_compression = zcondfeat!(
+        let (ext_compression, ext_compression_algorithm) = zcondfeat!(
             "transport_compression",
             self.ext_compression
                 .send_init_ack(&state.link.ext_compression)
                 .await
                 .map_err(|e| (e, Some(close::reason::GENERIC)))?,
-            None
+            (None, None)
         );
 
         // Extension Patch
@@ -434,6 +439,7 @@ impl<'a, 'b: 'a> AcceptFsm for &'a mut AcceptLink<'b> {
             ext_mlink,
             ext_lowlatency,
             ext_compression,
+            ext_compression_algorithm,
             ext_patch,
             ext_region_name,
         }
@@ -746,6 +752,8 @@ pub(crate) async fn accept_link(link: LinkUnicast, manager: &TransportManager) -
         },
         priorities: None,
         reliability: None,
+        #[cfg(feature = "transport_compression")]
+        compression: CompressionConfig::default(),
     };
     let mut link = TransportLinkUnicast::new(link, config);
     let mut fsm = AcceptLink {
@@ -765,7 +773,7 @@ pub(crate) async fn accept_link(link: LinkUnicast, manager: &TransportManager) -
         ext_auth: manager.state.unicast.authenticator.fsm(&manager.prng),
         ext_lowlatency: ext::lowlatency::LowLatencyFsm::new(),
         #[cfg(feature = "transport_compression")]
-        ext_compression: ext::compression::CompressionFsm::new(),
+        ext_compression: ext::compression::CompressionFsm::new(&manager.config.unicast.compression),
         ext_patch: ext::patch::PatchFsm::new(),
         ext_south: manager.config.bound_callback.clone(),
         ext_region_name: ext::region_name::RegionNameFsm::new(manager.config.region_name.clone()),
@@ -893,6 +901,12 @@ pub(crate) async fn accept_link(link: LinkUnicast, manager: &TransportManager) -
         },
         priorities: state.transport.ext_qos.priorities(),
         reliability: state.transport.ext_qos.reliability(),
+        #[cfg(feature = "transport_compression")]
+        compression: manager
+            .config
+            .unicast
+            .compression
+            .with_algorithm(state.link.ext_compression.algorithm()),
     };
     let a_link = link.reconfigure(a_config);
     let s_link = format!("{a_link:?}");
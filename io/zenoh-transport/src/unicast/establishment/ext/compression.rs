@@ -11,8 +11,6 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use core::marker::PhantomData;
-
 use async_trait::async_trait;
 use zenoh_buffers::{
     reader::{DidntRead, Reader},
@@ -22,16 +20,61 @@ use zenoh_codec::{RCodec, WCodec, Zenoh080};
 use zenoh_protocol::transport::{init, open};
 use zenoh_result::Error as ZError;
 
-use crate::unicast::establishment::{AcceptFsm, OpenFsm};
+use crate::{
+    common::compression::{CompressionAlgorithm, CompressionConfig},
+    unicast::establishment::{AcceptFsm, OpenFsm},
+};
+
+// The algorithms a node is able to decompress, advertised in the lowest byte of the
+// CompressionAlgorithm extension, the dictionary ID being in the highest 32 bits.
+const LZ4: u64 = 1;
+const ZSTD: u64 = 1 << 1;
+const ZSTD_DICTIONARY: u64 = 1 << 2;
+const DICTIONARY_ID_SHIFT: u32 = 32;
 
 // Extension Fsm
 pub(crate) struct CompressionFsm<'a> {
-    _a: PhantomData<&'a ()>,
+    config: &'a CompressionConfig,
 }
 
-impl CompressionFsm<'_> {
-    pub(crate) const fn new() -> Self {
-        Self { _a: PhantomData }
+impl<'a> CompressionFsm<'a> {
+    pub(crate) const fn new(config: &'a CompressionConfig) -> Self {
+        Self { config }
+    }
+
+    fn to_ext(&self) -> init::ext::CompressionAlgorithm {
+        let mut value = LZ4 | ZSTD;
+        if let Some(dictionary) = self.config.dictionary.as_ref() {
+            value |= ZSTD_DICTIONARY | (dictionary.id() as u64) << DICTIONARY_ID_SHIFT;
+        }
+        init::ext::CompressionAlgorithm::new(value)
+    }
+
+    /// Returns the algorithm to compress the batches sent to the other node, falling back to
+    /// zstd without dictionary when the other node does not have the same dictionary, and to LZ4
+    /// when the other node does not support zstd.
+    fn negotiate(
+        &self,
+        other_ext: Option<init::ext::CompressionAlgorithm>,
+    ) -> CompressionAlgorithm {
+        let Some(other_ext) = other_ext else {
+            return CompressionAlgorithm::Lz4;
+        };
+        let supports_zstd = other_ext.value & ZSTD != 0;
+        let supports_dictionary = other_ext.value & ZSTD_DICTIONARY != 0
+            && self.config.dictionary.as_ref().is_some_and(|dictionary| {
+                dictionary.id() as u64 == other_ext.value >> DICTIONARY_ID_SHIFT
+            });
+
+        match self.config.algorithm {
+            CompressionAlgorithm::ZstdDictionary if supports_dictionary => {
+                CompressionAlgorithm::ZstdDictionary
+            }
+            CompressionAlgorithm::Zstd | CompressionAlgorithm::ZstdDictionary if supports_zstd => {
+                CompressionAlgorithm::Zstd
+            }
+            _ => CompressionAlgorithm::Lz4,
+        }
     }
 }
 
@@ -41,16 +84,24 @@ impl CompressionFsm<'_> {
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(crate) struct StateOpen {
     is_compression: bool,
+    algorithm: CompressionAlgorithm,
 }
 
 impl StateOpen {
     pub(crate) const fn new(is_compression: bool) -> Self {
-        Self { is_compression }
+        Self {
+            is_compression,
+            algorithm: CompressionAlgorithm::Lz4,
+        }
     }
 
     pub(crate) const fn is_compression(&self) -> bool {
         self.is_compression
     }
+
+    pub(crate) const fn algorithm(&self) -> CompressionAlgorithm {
+        self.algorithm
+    }
 }
 
 #[async_trait]
@@ -58,25 +109,37 @@ impl<'a> OpenFsm for &'a CompressionFsm<'a> {
     type Error = ZError;
 
     type SendInitSynIn = &'a StateOpen;
-    type SendInitSynOut = Option<init::ext::Compression>;
+    type SendInitSynOut = (
+        Option<init::ext::Compression>,
+        Option<init::ext::CompressionAlgorithm>,
+    );
     async fn send_init_syn(
         self,
         state: Self::SendInitSynIn,
     ) -> Result<Self::SendInitSynOut, Self::Error> {
-        let output = state
-            .is_compression
-            .then_some(init::ext::Compression::new());
-        Ok(output)
+        if !state.is_compression {
+            return Ok((None, None));
+        }
+        Ok((Some(init::ext::Compression::new()), Some(self.to_ext())))
     }
 
-    type RecvInitAckIn = (&'a mut StateOpen, Option<init::ext::Compression>);
+    type RecvInitAckIn = (
+        &'a mut StateOpen,
+        (
+            Option<init::ext::Compression>,
+            Option<init::ext::CompressionAlgorithm>,
+        ),
+    );
     type RecvInitAckOut = ();
     async fn recv_init_ack(
         self,
         input: Self::RecvInitAckIn,
     ) -> Result<Self::RecvInitAckOut, Self::Error> {
-        let (state, other_ext) = input;
+        let (state, (other_ext, other_ext_algorithm)) = input;
         state.is_compression &= other_ext.is_some();
+        if state.is_compression {
+            state.algorithm = self.negotiate(other_ext_algorithm);
+        }
         Ok(())
     }
 
@@ -105,22 +168,39 @@ impl<'a> OpenFsm for &'a CompressionFsm<'a> {
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(crate) struct StateAccept {
     is_compression: bool,
+    algorithm: CompressionAlgorithm,
 }
 
 impl StateAccept {
     pub(crate) const fn new(is_compression: bool) -> Self {
-        Self { is_compression }
+        Self {
+            is_compression,
+            algorithm: CompressionAlgorithm::Lz4,
+        }
     }
 
     pub(crate) const fn is_compression(&self) -> bool {
         self.is_compression
     }
 
+    pub(crate) const fn algorithm(&self) -> CompressionAlgorithm {
+        self.algorithm
+    }
+
     #[cfg(test)]
     pub(crate) fn rand() -> Self {
-        use rand::Rng;
+        use rand::{seq::SliceRandom, Rng};
         let mut rng = rand::thread_rng();
-        Self::new(rng.gen_bool(0.5))
+        Self {
+            is_compression: rng.gen_bool(0.5),
+            algorithm: *[
+                CompressionAlgorithm::Lz4,
+                CompressionAlgorithm::Zstd,
+                CompressionAlgorithm::ZstdDictionary,
+            ]
+            .choose(&mut rng)
+            .unwrap(),
+        }
     }
 }
 
@@ -134,6 +214,12 @@ where
     fn write(self, writer: &mut W, x: &StateAccept) -> Self::Output {
         let is_compression = u8::from(x.is_compression);
         self.write(&mut *writer, is_compression)?;
+        let algorithm: u8 = match x.algorithm {
+            CompressionAlgorithm::Lz4 => 0,
+            CompressionAlgorithm::Zstd => 1,
+            CompressionAlgorithm::ZstdDictionary => 2,
+        };
+        self.write(&mut *writer, algorithm)?;
         Ok(())
     }
 }
@@ -147,7 +233,17 @@ where
     fn read(self, reader: &mut R) -> Result<StateAccept, Self::Error> {
         let is_compression: u8 = self.read(&mut *reader)?;
         let is_compression = is_compression == 1;
-        Ok(StateAccept { is_compression })
+        let algorithm: u8 = self.read(&mut *reader)?;
+        let algorithm = match algorithm {
+            0 => CompressionAlgorithm::Lz4,
+            1 => CompressionAlgorithm::Zstd,
+            2 => CompressionAlgorithm::ZstdDictionary,
+            _ => return Err(DidntRead),
+        };
+        Ok(StateAccept {
+            is_compression,
+            algorithm,
+        })
     }
 }
 
@@ -155,27 +251,39 @@ where
 impl<'a> AcceptFsm for &'a CompressionFsm<'a> {
     type Error = ZError;
 
-    type RecvInitSynIn = (&'a mut StateAccept, Option<init::ext::Compression>);
+    type RecvInitSynIn = (
+        &'a mut StateAccept,
+        (
+            Option<init::ext::Compression>,
+            Option<init::ext::CompressionAlgorithm>,
+        ),
+    );
     type RecvInitSynOut = ();
     async fn recv_init_syn(
         self,
         input: Self::RecvInitSynIn,
     ) -> Result<Self::RecvInitSynOut, Self::Error> {
-        let (state, other_ext) = input;
+        let (state, (other_ext, other_ext_algorithm)) = input;
         state.is_compression &= other_ext.is_some();
+        if state.is_compression {
+            state.algorithm = self.negotiate(other_ext_algorithm);
+        }
         Ok(())
     }
 
     type SendInitAckIn = &'a StateAccept;
-    type SendInitAckOut = Option<init::ext::Compression>;
+    type SendInitAckOut = (
+        Option<init::ext::Compression>,
+        Option<init::ext::CompressionAlgorithm>,
+    );
     async fn send_init_ack(
         self,
         state: Self::SendInitAckIn,
     ) -> Result<Self::SendInitAckOut, Self::Error> {
-        let output = state
-            .is_compression
-            .then_some(init::ext::Compression::new());
-        Ok(output)
+        if !state.is_compression {
+            return Ok((None, None));
+        }
+        Ok((Some(init::ext::Compression::new()), Some(self.to_ext())))
     }
 
     type RecvOpenSynIn = (&'a mut StateAccept, Option<open::ext::Compression>);
@@ -196,3 +304,115 @@ impl<'a> AcceptFsm for &'a CompressionFsm<'a> {
         Ok(None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use zenoh_result::ZResult;
+
+    use super::{CompressionFsm, StateAccept, StateOpen};
+    use crate::{
+        common::compression::{CompressionAlgorithm, CompressionConfig, CompressionDictionary},
+        unicast::establishment::{AcceptFsm, OpenFsm},
+    };
+
+    fn config(algorithm: CompressionAlgorithm, dictionary: Option<&[u8]>) -> CompressionConfig {
+        let dictionary = dictionary.map(|d| CompressionDictionary::new(d.to_vec()).unwrap());
+        CompressionConfig::new(algorithm, 3, dictionary).unwrap()
+    }
+
+    async fn test_negotiation(
+        config_open: (bool, CompressionConfig),
+        config_accept: (bool, CompressionConfig),
+    ) -> ZResult<(StateOpen, StateAccept)> {
+        let fsm_open = CompressionFsm::new(&config_open.1);
+        let fsm_accept = CompressionFsm::new(&config_accept.1);
+        let mut state_open = StateOpen::new(config_open.0);
+        let mut state_accept = StateAccept::new(config_accept.0);
+
+        let ext = fsm_open.send_init_syn(&state_open).await?;
+        fsm_accept.recv_init_syn((&mut state_accept, ext)).await?;
+
+        let ext = fsm_accept.send_init_ack(&state_accept).await?;
+        fsm_open.recv_init_ack((&mut state_open, ext)).await?;
+
+        assert_eq!(state_open.is_compression(), state_accept.is_compression());
+        Ok((state_open, state_accept))
+    }
+
+    #[tokio::test]
+    async fn test_compression_negotiation_disabled() {
+        let config = config(CompressionAlgorithm::Zstd, None);
+        let (state_open, _) = test_negotiation((true, config.clone()), (false, config))
+            .await
+            .unwrap();
+        assert!(!state_open.is_compression());
+    }
+
+    #[tokio::test]
+    async fn test_compression_negotiation_zstd() {
+        let (state_open, state_accept) = test_negotiation(
+            (true, config(CompressionAlgorithm::Zstd, None)),
+            (true, config(CompressionAlgorithm::Lz4, None)),
+        )
+        .await
+        .unwrap();
+        assert!(state_open.is_compression());
+        assert_eq!(state_open.algorithm(), CompressionAlgorithm::Zstd);
+        assert_eq!(state_accept.algorithm(), CompressionAlgorithm::Lz4);
+    }
+
+    #[tokio::test]
+    async fn test_compression_negotiation_zstd_dictionary() {
+        let dictionary = b"{\"temperature\": 0, \"humidity\": 0}".as_slice();
+        let (state_open, state_accept) = test_negotiation(
+            (
+                true,
+                config(CompressionAlgorithm::ZstdDictionary, Some(dictionary)),
+            ),
+            (
+                true,
+                config(CompressionAlgorithm::ZstdDictionary, Some(dictionary)),
+            ),
+        )
+        .await
+        .unwrap();
+        assert_eq!(state_open.algorithm(), CompressionAlgorithm::ZstdDictionary);
+        assert_eq!(
+            state_accept.algorithm(),
+            CompressionAlgorithm::ZstdDictionary
+        );
+    }
+
+    #[tokio::test]
+    async fn test_compression_negotiation_dictionary_mismatch() {
+        let (state_open, state_accept) = test_negotiation(
+            (
+                true,
+                config(CompressionAlgorithm::ZstdDictionary, Some(b"dictionary_1")),
+            ),
+            (
+                true,
+                config(CompressionAlgorithm::ZstdDictionary, Some(b"dictionary_2")),
+            ),
+        )
+        .await
+        .unwrap();
+        assert_eq!(state_open.algorithm(), CompressionAlgorithm::Zstd);
+        assert_eq!(state_accept.algorithm(), CompressionAlgorithm::Zstd);
+    }
+
+    #[tokio::test]
+    async fn test_compression_negotiation_lz4_only_peer() {
+        // Nodes only supporting LZ4 do not send the CompressionAlgorithm extension
+        let config = config(CompressionAlgorithm::Zstd, None);
+        let fsm = CompressionFsm::new(&config);
+        let mut state_open = StateOpen::new(true);
+        let ext = (
+            Some(zenoh_protocol::transport::init::ext::Compression::new()),
+            None,
+        );
+        fsm.recv_init_ack((&mut state_open, ext)).await.unwrap();
+        assert!(state_open.is_compression());
+        assert_eq!(state_open.algorithm(), CompressionAlgorithm::Lz4);
+    }
+}
@@ -30,6 +30,8 @@ use zenoh_result::ZResult;
 
 #[cfg(feature = "shared-memory")]
 use super::ext::shm::AuthSegment;
+#[cfg(feature = "transport_compression")]
+use crate::common::compression::CompressionConfig;
 #[cfg(feature = "shared-memory")]
 use crate::shm::TransportShmConfig;
 #[cfg(feature = "auth_usrpwd")]
@@ -191,13 +193,13 @@ impl<'a, 'b: 'a> OpenFsm for &'a mut OpenLink<'b> {
             .map_err(|e| (e, Some(close::reason::GENERIC)))?;
 
         // Extension Compression
-        let ext_compression = zcondfeat!(
+        let (ext_compression, ext_compression_algorithm) = zcondfeat!(
             "transport_compression",
             self.ext_compression
                 .send_init_syn(&state.link.ext_compression)
                 .await
                 .map_err(|e| (e, Some(close::reason::GENERIC)))?,
-            None
+            (None, None)
         );
 
         // Extension Patch
@@ -228,6 +230,7 @@ impl<'a, 'b: 'a> OpenFsm for &'a mut OpenLink<'b> {
             ext_mlink,
             ext_lowlatency,
             ext_compression,
+            ext_compression_algorithm,
             ext_patch,
             ext_region_name,
         }
@@ -367,7 +370,10 @@ impl<'a, 'b: 'a> OpenFsm for &'a mut OpenLink<'b> {
         // Extension Compression
         #[cfg(feature = "transport_compression")]
         self.ext_compression
-            .recv_init_ack((&mut state.link.ext_compression, init_ack.ext_compression))
+            .recv_init_ack((
+                &mut state.link.ext_compression,
+                (init_ack.ext_compression, init_ack.ext_compression_algorithm),
+            ))
             .await
             .map_err(|e| (e, Some(close::reason::GENERIC)))?;
 
@@ -634,6 +640,8 @@ pub(crate) async fn open_link(
         },
         priorities: None,
         reliability: None,
+        #[cfg(feature = "transport_compression")]
+        compression: CompressionConfig::default(),
     };
     let mut link = TransportLinkUnicast::new(link, config);
     let mut fsm = OpenLink {
@@ -650,7 +658,7 @@ pub(crate) async fn open_link(
         ext_auth: manager.state.unicast.authenticator.fsm(&manager.prng),
         ext_lowlatency: ext::lowlatency::LowLatencyFsm::new(),
         #[cfg(feature = "transport_compression")]
-        ext_compression: ext::compression::CompressionFsm::new(),
+        ext_compression: ext::compression::CompressionFsm::new(&manager.config.unicast.compression),
         ext_patch: ext::patch::PatchFsm::new(),
         ext_south: manager.config.bound_callback.clone(),
         ext_region_name: ext::region_name::RegionNameFsm::new(manager.config.region_name.clone()),
@@ -768,6 +776,12 @@ pub(crate) async fn open_link(
         },
         priorities: state.transport.ext_qos.priorities(),
         reliability: state.transport.ext_qos.reliability(),
+        #[cfg(feature = "transport_compression")]
+        compression: manager
+            .config
+            .unicast
+            .compression
+            .with_algorithm(state.link.ext_compression.algorithm()),
     };
     let o_link = link.reconfigure(o_config);
     let s_link = format!("{o_link:?}");
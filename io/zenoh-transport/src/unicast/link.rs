@@ -13,7 +13,7 @@
 //
 use std::{fmt, sync::Arc};
 
-use zenoh_buffers::{BBuf, ZSlice, ZSliceBuffer};
+use zenoh_buffers::{ZSlice, ZSliceBuffer};
 use zenoh_core::zcondfeat;
 use zenoh_link::{Link, LinkUnicast};
 use zenoh_protocol::{
@@ -23,6 +23,8 @@ use zenoh_protocol::{
 use zenoh_result::{zerror, ZResult};
 
 use crate::common::batch::{BatchConfig, Decode, Encode, Finalize, RBatch, WBatch};
+#[cfg(feature = "transport_compression")]
+use crate::common::compression::{CompressionConfig, Compressor, Decompressor};
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub(crate) enum TransportLinkUnicastDirection {
@@ -37,6 +39,8 @@ pub(crate) struct TransportLinkUnicastConfig {
     pub(crate) batch: BatchConfig,
     pub(crate) priorities: Option<PriorityRange>,
     pub(crate) reliability: Option<Reliability>,
+    #[cfg(feature = "transport_compression")]
+    pub(crate) compression: CompressionConfig,
 }
 
 #[derive(Clone)]
@@ -70,16 +74,12 @@ impl TransportLinkUnicast {
     pub(crate) fn tx(&self) -> TransportLinkUnicastTx {
         TransportLinkUnicastTx {
             inner: self.clone(),
-            buffer: zcondfeat!(
-                "transport_compression",
-                self.config
-                    .batch
-                    .is_compression
-                    .then_some(BBuf::with_capacity(
-                        lz4_flex::block::get_maximum_output_size(self.config.batch.mtu as usize),
-                    )),
-                None
-            ),
+            #[cfg(feature = "transport_compression")]
+            compressor: self
+                .config
+                .batch
+                .is_compression
+                .then(|| Compressor::new(self.config.compression.clone(), self.config.batch.mtu)),
         }
     }
 
@@ -87,6 +87,8 @@ impl TransportLinkUnicast {
         TransportLinkUnicastRx {
             link: self.link.clone(),
             config: self.config.clone(),
+            #[cfg(feature = "transport_compression")]
+            decompressor: Decompressor::new(self.config.compression.dictionary.clone()),
         }
     }
 
@@ -139,7 +141,8 @@ impl PartialEq<Link> for TransportLinkUnicast {
 #[derive(Clone)]
 pub(crate) struct TransportLinkUnicastTx {
     pub(crate) inner: TransportLinkUnicast,
-    pub(crate) buffer: Option<BBuf>,
+    #[cfg(feature = "transport_compression")]
+    pub(crate) compressor: Option<Compressor>,
 }
 
 impl TransportLinkUnicastTx {
@@ -153,13 +156,17 @@ impl TransportLinkUnicastTx {
         // tracing::trace!("WBatch: {:?}", batch);
 
         let res = batch
-            .finalize(self.buffer.as_mut())
+            .finalize(
+                #[cfg(feature = "transport_compression")]
+                self.compressor.as_mut(),
+            )
             .map_err(|_| zerror!("{ERR}{self}"))?;
 
         let bytes = match res {
             Finalize::Batch => batch.as_slice(),
+            #[cfg(feature = "transport_compression")]
             Finalize::Buffer => self
-                .buffer
+                .compressor
                 .as_ref()
                 .ok_or_else(|| zerror!("Invalid buffer finalization"))?
                 .as_slice(),
@@ -197,11 +204,14 @@ impl fmt::Display for TransportLinkUnicastTx {
 
 impl fmt::Debug for TransportLinkUnicastTx {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("TransportLinkUnicastRx")
-            .field("link", &self.inner.link)
-            .field("config", &self.inner.config)
-            .field("buffer", &self.buffer.as_ref().map(|b| b.capacity()))
-            .finish()
+        let mut s = f.debug_struct("TransportLinkUnicastRx");
+        s.field("link", &self.inner.link)
+            .field("config", &self.inner.config);
+        #[cfg(feature = "transport_compression")]
+        {
+            s.field("compressor", &self.compressor);
+        }
+        s.finish()
     }
 }
 
@@ -209,6 +219,8 @@ impl fmt::Debug for TransportLinkUnicastTx {
 pub(crate) struct TransportLinkUnicastRx {
     pub(crate) link: LinkUnicast,
     pub(crate) config: TransportLinkUnicastConfig,
+    #[cfg(feature = "transport_compression")]
+    pub(crate) decompressor: Decompressor,
 }
 
 impl TransportLinkUnicastRx {
@@ -244,7 +256,11 @@ impl TransportLinkUnicastRx {
             .map_err(|_| zerror!("{ERR}{self}. ZSlice index(es) out of bounds"))?;
         let mut batch = RBatch::new(self.config.batch, buffer);
         batch
-            .initialize(buff)
+            .initialize(
+                buff,
+                #[cfg(feature = "transport_compression")]
+                &mut self.decompressor,
+            )
             .map_err(|e| zerror!("{ERR}{self}. {e}."))?;
 
         // tracing::trace!("RBatch: {:?}", batch);
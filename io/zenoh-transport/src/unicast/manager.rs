@@ -23,6 +23,8 @@ use std::{
 use tokio::sync::{Mutex as AsyncMutex, MutexGuard as AsyncMutexGuard};
 #[cfg(feature = "transport_compression")]
 use zenoh_config::CompressionUnicastConf;
+#[cfg(feature = "transport_multilink")]
+use zenoh_config::MultilinkUnicastConf;
 use zenoh_config::{Config, LinkTxConf, QoSUnicastConf, TransportUnicastConf};
 use zenoh_core::{zasynclock, zcondfeat};
 use zenoh_crypto::PseudoRng;
@@ -34,6 +36,8 @@ use zenoh_protocol::{
 use zenoh_result::{bail, zerror, ZResult};
 
 use super::{link::LinkUnicastWithOpenAck, transport_unicast_inner::InitTransportResult};
+#[cfg(feature = "transport_compression")]
+use crate::common::compression::CompressionConfig;
 #[cfg(feature = "transport_auth")]
 use crate::unicast::establishment::ext::auth::Auth;
 #[cfg(feature = "transport_multilink")]
@@ -62,8 +66,12 @@ pub struct TransportManagerConfigUnicast {
     pub is_lowlatency: bool,
     #[cfg(feature = "transport_multilink")]
     pub max_links: usize,
+    #[cfg(feature = "transport_multilink")]
+    pub multilink: MultilinkUnicastConf,
     #[cfg(feature = "transport_compression")]
     pub is_compression: bool,
+    #[cfg(feature = "transport_compression")]
+    pub compression: CompressionConfig,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -132,11 +140,15 @@ pub struct TransportManagerBuilderUnicast {
     pub(super) is_qos: bool,
     #[cfg(feature = "transport_multilink")]
     pub(super) max_links: usize,
+    #[cfg(feature = "transport_multilink")]
+    pub(super) multilink: MultilinkUnicastConf,
     #[cfg(feature = "transport_auth")]
     pub(super) authenticator: Auth,
     pub(super) is_lowlatency: bool,
     #[cfg(feature = "transport_compression")]
     pub(super) is_compression: bool,
+    #[cfg(feature = "transport_compression")]
+    pub(super) compression: CompressionConfig,
 }
 
 impl TransportManagerBuilderUnicast {
@@ -186,6 +198,12 @@ impl TransportManagerBuilderUnicast {
         self
     }
 
+    #[cfg(feature = "transport_multilink")]
+    pub fn multilink(mut self, multilink: MultilinkUnicastConf) -> Self {
+        self.multilink = multilink;
+        self
+    }
+
     #[cfg(feature = "transport_auth")]
     pub fn authenticator(mut self, authenticator: Auth) -> Self {
         self.authenticator = authenticator;
@@ -198,6 +216,12 @@ impl TransportManagerBuilderUnicast {
         self
     }
 
+    #[cfg(feature = "transport_compression")]
+    pub fn compression_config(mut self, compression: CompressionConfig) -> Self {
+        self.compression = compression;
+        self
+    }
+
     pub async fn from_config(mut self, config: &Config) -> ZResult<TransportManagerBuilderUnicast> {
         self = self.lease(Duration::from_millis(
             *config.transport().link().tx().lease(),
@@ -217,6 +241,7 @@ impl TransportManagerBuilderUnicast {
         #[cfg(feature = "transport_multilink")]
         {
             self = self.max_links(*config.transport().unicast().max_links());
+            self = self.multilink(config.transport().unicast().multilink().clone());
         }
         #[cfg(feature = "transport_auth")]
         {
@@ -224,7 +249,13 @@ impl TransportManagerBuilderUnicast {
         }
         #[cfg(feature = "transport_compression")]
         {
-            self = self.compression(*config.transport().unicast().compression().enabled());
+            let compression = config.transport().unicast().compression();
+            self = self.compression(*compression.enabled());
+            self = self.compression_config(CompressionConfig::from_config(
+                compression.algorithm,
+                *compression.level(),
+                compression.dictionary().as_deref(),
+            )?);
         }
 
         Ok(self)
@@ -248,9 +279,13 @@ impl TransportManagerBuilderUnicast {
             is_qos: self.is_qos,
             #[cfg(feature = "transport_multilink")]
             max_links: self.max_links,
+            #[cfg(feature = "transport_multilink")]
+            multilink: self.multilink,
             is_lowlatency: self.is_lowlatency,
             #[cfg(feature = "transport_compression")]
             is_compression: self.is_compression,
+            #[cfg(feature = "transport_compression")]
+            compression: self.compression,
         };
 
         let state = TransportManagerStateUnicast {
@@ -287,11 +322,15 @@ impl Default for TransportManagerBuilderUnicast {
             is_qos: *qos.enabled(),
             #[cfg(feature = "transport_multilink")]
             max_links: *transport.max_links(),
+            #[cfg(feature = "transport_multilink")]
+            multilink: transport.multilink().clone(),
             #[cfg(feature = "transport_auth")]
             authenticator: Auth::default(),
             is_lowlatency: *transport.lowlatency(),
             #[cfg(feature = "transport_compression")]
             is_compression: *compression.enabled(),
+            #[cfg(feature = "transport_compression")]
+            compression: CompressionConfig::default(),
         }
     }
 }
@@ -24,7 +24,11 @@ use std::{
 use futures::{future::select_all, task::AtomicWaker};
 use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
+#[cfg(feature = "transport_multilink")]
+use zenoh_config::{MultilinkScheduling, MultilinkWeightConf};
 use zenoh_link::Link;
+#[cfg(feature = "transport_multilink")]
+use zenoh_link::LinkUnicast;
 use zenoh_protocol::{
     core::Priority,
     transport::{KeepAlive, TransportMessage},
@@ -35,7 +39,7 @@ use zenoh_sync::RecyclingObjectPool;
 use zenoh_sync::{event, Notifier, Waiter};
 use zenoh_task::TaskController;
 
-use super::transport::TransportUnicastUniversal;
+use super::{rx::LinkDefragBuffers, transport::TransportUnicastUniversal};
 use crate::{
     common::{
         batch::{BatchConfig, RBatch},
@@ -65,6 +69,9 @@ pub(super) struct TransportLinkUnicastUniversal {
     pub block_first_waiters: [Waiter; Priority::NUM],
     #[cfg(feature = "stats")]
     pub(super) stats: zenoh_stats::LinkStats,
+    #[cfg(feature = "transport_multilink")]
+    // The weight of the link when scheduling messages on several links
+    pub(super) weight: u16,
 }
 
 impl TransportLinkUnicastUniversal {
@@ -89,6 +96,13 @@ impl TransportLinkUnicastUniversal {
             batching_enabled: transport.manager.config.batching,
             batching_time_limit: transport.manager.config.queue_backoff,
             queue_alloc: transport.manager.config.queue_alloc,
+            // Only the scheduling policies other than "first" and the failover need them
+            #[cfg(feature = "transport_multilink")]
+            count_queued: {
+                let multilink = &transport.manager.config.unicast.multilink;
+                multilink.scheduling != MultilinkScheduling::First
+                    || multilink.failover_timeout().is_some()
+            },
         };
 
         // The pipeline
@@ -116,6 +130,12 @@ impl TransportLinkUnicastUniversal {
             block_first_waiters.push(waiter);
         }
 
+        #[cfg(feature = "transport_multilink")]
+        let weight = link_weight(
+            &link.link,
+            transport.manager.config.unicast.multilink.weights(),
+        );
+
         let result = Self {
             link,
             pipeline: producer,
@@ -126,6 +146,8 @@ impl TransportLinkUnicastUniversal {
             block_first_waiters: block_first_waiters.try_into().ok().unwrap(),
             #[cfg(feature = "stats")]
             stats,
+            #[cfg(feature = "transport_multilink")]
+            weight,
         };
 
         (result, consumer)
@@ -225,6 +247,24 @@ impl TransportLinkUnicastUniversal {
     }
 }
 
+/// Returns the weight of the first item matching the protocol and the interfaces of `link`.
+#[cfg(feature = "transport_multilink")]
+fn link_weight(link: &LinkUnicast, weights: &[MultilinkWeightConf]) -> u16 {
+    let protocol = link.get_dst().protocol().as_str().to_string();
+    let interfaces = link.get_interface_names();
+    weights
+        .iter()
+        .find(|item| {
+            item.protocols
+                .as_ref()
+                .map_or(true, |protocols| protocols.contains(&protocol))
+                && item.interfaces.as_ref().map_or(true, |item_interfaces| {
+                    interfaces.iter().any(|i| item_interfaces.contains(i))
+                })
+        })
+        .map_or(1, |item| item.weight.get())
+}
+
 /*************************************/
 /*              TASKS                */
 /*************************************/
@@ -432,6 +472,10 @@ async fn read_loop<F: Fn() -> Box<[u8]>>(
         link.config.priorities.clone(),
         link.config.reliability,
     );
+    let mut defrag = LinkDefragBuffers::make(
+        transport.config.sn_resolution,
+        transport.manager.config.defrag_buff_size,
+    )?;
     loop {
         tokio::select! {
             batch = read(link, priority, pool) => {
@@ -442,7 +486,7 @@ async fn read_loop<F: Fn() -> Box<[u8]>>(
                     let header_bytes = if l.is_streamed { 2 } else { 0 };
                     stats.inc_bytes(zenoh_stats::Rx, header_bytes + batch.len() as u64);
                 }
-                transport.read_messages(batch, &l, #[cfg(feature = "stats")] &stats)?;
+                transport.read_messages(batch, &l, &mut defrag, #[cfg(feature = "stats")] &stats)?;
             }
             _ = lease_tracker.wait_if(priority.unwrap_or(Priority::Control) == Priority::Control) => {
                 bail!("{link}: expired after {} milliseconds", lease_tracker.timeout().as_millis());
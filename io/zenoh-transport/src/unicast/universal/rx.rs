@@ -11,6 +11,8 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
+#[cfg(feature = "transport_multilink")]
+use std::sync::atomic::Ordering;
 use std::sync::MutexGuard;
 
 use zenoh_buffers::ZSlice;
@@ -18,7 +20,7 @@ use zenoh_codec::transport::frame::FrameReader;
 use zenoh_core::{zlock, zread};
 use zenoh_link::Link;
 use zenoh_protocol::{
-    core::{Priority, Reliability},
+    core::{Bits, Priority, Reliability},
     network::NetworkMessageMut,
     transport::{Close, Fragment, KeepAlive, TransportBody, TransportMessage, TransportSn},
 };
@@ -28,12 +30,43 @@ use super::transport::TransportUnicastUniversal;
 use crate::{
     common::{
         batch::{Decode, RBatch},
+        defragmentation::DefragBuffer,
         priority::TransportChannelRx,
     },
     unicast::transport_unicast_inner::TransportUnicastTrait,
     TransportPeerEventHandler,
 };
 
+/// The defragmentation buffers of a link, per priority and reliability.
+///
+/// The fragments of a message are all sent on the same link, but the fragments of messages sent
+/// on different links of a multilink transport may interleave, so they are reassembled per link.
+pub(super) struct LinkDefragBuffers {
+    reliable: Box<[DefragBuffer]>,
+    best_effort: Box<[DefragBuffer]>,
+}
+
+impl LinkDefragBuffers {
+    pub(super) fn make(resolution: Bits, capacity: usize) -> ZResult<LinkDefragBuffers> {
+        let make = |reliability| {
+            (0..Priority::NUM)
+                .map(|_| DefragBuffer::make(reliability, resolution, capacity))
+                .collect::<ZResult<Box<[_]>>>()
+        };
+        Ok(LinkDefragBuffers {
+            reliable: make(Reliability::Reliable)?,
+            best_effort: make(Reliability::BestEffort)?,
+        })
+    }
+
+    fn get(&mut self, priority: usize, reliability: Reliability) -> &mut DefragBuffer {
+        match reliability {
+            Reliability::Reliable => &mut self.reliable[priority],
+            Reliability::BestEffort => &mut self.best_effort[priority],
+        }
+    }
+}
+
 /*************************************/
 /*            TRANSPORT RX           */
 /*************************************/
@@ -104,7 +137,7 @@ impl TransportUnicastUniversal {
             Reliability::BestEffort => zlock!(c.best_effort),
         };
 
-        if !self.verify_sn("Frame", frame.sn, &mut guard)? {
+        if !self.verify_sn("Frame", frame.sn, frame.reliability, &mut guard)? {
             // Drop invalid message and continue
             return Ok(());
         }
@@ -131,6 +164,7 @@ impl TransportUnicastUniversal {
     fn handle_fragment(
         &self,
         fragment: Fragment,
+        defrag: &mut LinkDefragBuffers,
         #[cfg(feature = "stats")] stats: &zenoh_stats::LinkStats,
     ) -> ZResult<()> {
         let Fragment {
@@ -143,10 +177,10 @@ impl TransportUnicastUniversal {
             payload,
         } = fragment;
 
-        let c = if self.is_qos() {
-            &self.priority_rx[qos.priority() as usize]
+        let priority = if self.is_qos() {
+            qos.priority() as usize
         } else if qos.priority() == Priority::DEFAULT {
-            &self.priority_rx[0]
+            0
         } else {
             bail!(
                 "Transport: {}. Unknown priority: {:?}.",
@@ -154,20 +188,22 @@ impl TransportUnicastUniversal {
                 qos.priority()
             );
         };
+        let c = &self.priority_rx[priority];
+        let defrag = defrag.get(priority, reliability);
 
         let mut guard = match reliability {
             Reliability::Reliable => zlock!(c.reliable),
             Reliability::BestEffort => zlock!(c.best_effort),
         };
 
-        if !self.verify_sn("Fragment", sn, &mut guard)? {
+        if !self.verify_sn("Fragment", sn, reliability, &mut guard)? {
             // Drop invalid message and continue
             return Ok(());
         }
         if self.config.patch.has_fragmentation_markers() {
             if ext_first.is_some() {
-                guard.defrag.clear();
-            } else if guard.defrag.is_empty() {
+                defrag.clear();
+            } else if defrag.is_empty() {
                 tracing::trace!(
                     "Transport: {}. First fragment received without start marker.",
                     self.manager.config.zid,
@@ -175,21 +211,21 @@ impl TransportUnicastUniversal {
                 return Ok(());
             }
             if ext_drop.is_some() {
-                guard.defrag.clear();
+                defrag.clear();
                 return Ok(());
             }
         }
-        if guard.defrag.is_empty() {
-            let _ = guard.defrag.sync(sn);
+        if defrag.is_empty() {
+            let _ = defrag.sync(sn);
         }
-        if let Err(e) = guard.defrag.push(sn, payload) {
+        if let Err(e) = defrag.push(sn, payload) {
             // Defrag errors don't close transport
             tracing::trace!("{}", e);
             return Ok(());
         }
         if !more {
             // When shared-memory feature is disabled, msg does not need to be mutable
-            if let Some(mut msg) = guard.defrag.defragment() {
+            if let Some(mut msg) = defrag.defragment() {
                 let callback = zread!(self.callback).clone();
                 if let Some(callback) = callback.as_ref() {
                     return self.trigger_callback(
@@ -217,10 +253,19 @@ impl TransportUnicastUniversal {
         &self,
         message_type: &str,
         sn: TransportSn,
+        #[allow(unused_variables)] // When feature "transport_multilink" is not enabled
+        reliability: Reliability,
         guard: &mut MutexGuard<'_, TransportChannelRx>,
     ) -> ZResult<bool> {
         let precedes = guard.sn.roll(sn)?;
         if !precedes {
+            // Best-effort messages are striped on the links of a multilink transport by the
+            // load-balancing policies, so they are accepted out of order in that case
+            #[cfg(feature = "transport_multilink")]
+            if reliability == Reliability::BestEffort && self.is_striped.load(Ordering::Relaxed) {
+                return Ok(true);
+            }
+
             tracing::trace!(
                 "Transport: {}. {} with invalid SN dropped: {}. Expected: {}.",
                 self.config.zid,
@@ -238,6 +283,7 @@ impl TransportUnicastUniversal {
         &self,
         mut batch: RBatch,
         link: &Link,
+        defrag: &mut LinkDefragBuffers,
         #[cfg(feature = "stats")] stats: &zenoh_stats::LinkStats,
     ) -> ZResult<()> {
         while !batch.is_empty() {
@@ -269,6 +315,7 @@ impl TransportUnicastUniversal {
                 TransportBody::Frame(_) => unreachable!(),
                 TransportBody::Fragment(fragment) => self.handle_fragment(
                     fragment,
+                    defrag,
                     #[cfg(feature = "stats")]
                     stats,
                 )?,
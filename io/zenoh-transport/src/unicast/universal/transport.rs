@@ -11,6 +11,8 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
+#[cfg(feature = "transport_multilink")]
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::{
     fmt::DebugStruct,
     sync::{Arc, RwLock},
@@ -19,6 +21,8 @@ use std::{
 
 use async_trait::async_trait;
 use tokio::sync::{Mutex as AsyncMutex, MutexGuard as AsyncMutexGuard};
+#[cfg(feature = "transport_multilink")]
+use zenoh_config::MultilinkScheduling;
 use zenoh_core::{zasynclock, zcondfeat, zread, zwrite};
 use zenoh_link::Link;
 use zenoh_protocol::{
@@ -59,6 +63,12 @@ pub(crate) struct TransportUnicastUniversal {
     pub(super) shm_context: Option<UnicastTransportShmContext>,
     // The links associated to the channel
     pub(super) links: Arc<RwLock<Box<[TransportLinkUnicastUniversal]>>>,
+    // The turn of the round-robin scheduling on the links
+    #[cfg(feature = "transport_multilink")]
+    pub(super) round_robin: Arc<AtomicUsize>,
+    // The best-effort messages are striped on several links, and may be received out of order
+    #[cfg(feature = "transport_multilink")]
+    pub(super) is_striped: Arc<AtomicBool>,
     // The callback
     pub(super) callback: Arc<RwLock<Option<Arc<dyn TransportPeerEventHandler>>>>,
     // Mutex for notification
@@ -104,7 +114,10 @@ impl TransportUnicastUniversal {
             priority_tx: priority_tx.into_boxed_slice().into(),
             priority_rx: priority_rx.into_boxed_slice().into(),
             links: Arc::new(RwLock::new(vec![].into_boxed_slice())),
-
+            #[cfg(feature = "transport_multilink")]
+            round_robin: Arc::new(AtomicUsize::new(0)),
+            #[cfg(feature = "transport_multilink")]
+            is_striped: Arc::new(AtomicBool::new(false)),
             callback: Arc::new(RwLock::new(None)),
             status: Arc::new(AsyncMutex::new(TransportStatus::Uninitialized)),
             #[cfg(feature = "stats")]
@@ -116,6 +129,14 @@ impl TransportUnicastUniversal {
         Ok(t)
     }
 
+    /// Updates whether the best-effort messages are striped, once the transport has `links`.
+    #[cfg(feature = "transport_multilink")]
+    fn update_striped(&self, links: &[TransportLinkUnicastUniversal]) {
+        let is_striped = links.len() > 1
+            && self.manager.config.unicast.multilink.scheduling != MultilinkScheduling::First;
+        self.is_striped.store(is_striped, Ordering::Relaxed);
+    }
+
     /*************************************/
     /*           TERMINATION             */
     /*************************************/
@@ -137,6 +158,8 @@ impl TransportUnicastUniversal {
             let mut l_guard = zwrite!(self.links);
             let links = l_guard.to_vec();
             *l_guard = vec![].into_boxed_slice();
+            #[cfg(feature = "transport_multilink")]
+            self.update_striped(&l_guard);
             links
         };
         for l in links.drain(..) {
@@ -173,6 +196,8 @@ impl TransportUnicastUniversal {
                 let mut links = guard.to_vec();
                 let stl = links.remove(index);
                 *guard = links.into_boxed_slice();
+                #[cfg(feature = "transport_multilink")]
+                self.update_striped(&guard);
                 (guard.is_empty(), stl)
             } else {
                 bail!(
@@ -293,6 +318,8 @@ impl TransportUnicastTrait for TransportUnicastUniversal {
         links.extend_from_slice(&guard);
         links.push(link.clone());
         *guard = links.into_boxed_slice();
+        #[cfg(feature = "transport_multilink")]
+        self.update_striped(&guard);
 
         drop(guard);
 
@@ -12,6 +12,11 @@
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
 
+#[cfg(feature = "transport_multilink")]
+use std::{sync::atomic::Ordering, time::Duration};
+
+#[cfg(feature = "transport_multilink")]
+use zenoh_config::MultilinkScheduling;
 #[cfg(feature = "unstable")]
 use zenoh_protocol::core::CongestionControl;
 use zenoh_protocol::{
@@ -20,12 +25,34 @@ use zenoh_protocol::{
     transport::close,
 };
 use zenoh_result::ZResult;
+#[cfg(all(feature = "stats", feature = "transport_multilink"))]
+use zenoh_stats::SchedulingLabel;
 
-use super::transport::TransportUnicastUniversal;
+use super::{link::TransportLinkUnicastUniversal, transport::TransportUnicastUniversal};
 #[cfg(feature = "shared-memory")]
 use crate::shm::map_zmsg_to_partner;
 use crate::unicast::transport_unicast_inner::TransportUnicastTrait;
 
+/// A link matching the [`Reliability`]-[`PriorityRange`] pair of a message to schedule.
+#[cfg(feature = "transport_multilink")]
+#[derive(Debug, Clone, Copy)]
+struct LinkCandidate {
+    index: usize,
+    weight: u16,
+    queued_batches: usize,
+    is_stalled: bool,
+}
+
+/// The way a link has been picked to send a message on.
+#[cfg(feature = "transport_multilink")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PickedBy {
+    First,
+    RoundRobin,
+    LeastQueued,
+    Failover,
+}
+
 impl TransportUnicastUniversal {
     /// Returns the index of the best matching [`Reliability`]-[`PriorityRange`] pair.
     ///
@@ -72,6 +99,132 @@ impl TransportUnicastUniversal {
         match_.full.or(match_.partial).or(match_.any)
     }
 
+    /// Returns the [`Reliability`]-[`PriorityRange`] pair of a link.
+    fn link_pair(link: &TransportLinkUnicastUniversal) -> (Reliability, Option<PriorityRange>) {
+        (
+            link.link
+                .config
+                .reliability
+                .unwrap_or(Reliability::from(link.link.link.is_reliable())),
+            link.link.config.priorities.clone(),
+        )
+    }
+
+    /// Returns the index of the link to send `msg` on, among the links matching the same
+    /// [`Reliability`]-[`PriorityRange`] pair as the `selected` one.
+    ///
+    /// Best-effort messages are striped on the matching links according to the configured
+    /// [`MultilinkScheduling`], while reliable messages stick to the `selected` link to preserve
+    /// their ordering. If failover is enabled, the stalled links are skipped by the best-effort
+    /// messages only: the reliable batches queued on a stalled link would otherwise be received
+    /// after the following ones, and dropped as out of order.
+    #[cfg(feature = "transport_multilink")]
+    fn schedule_link(
+        &self,
+        links: &[TransportLinkUnicastUniversal],
+        selected: usize,
+        msg: NetworkMessageRef,
+    ) -> usize {
+        let config = &self.manager.config.unicast.multilink;
+        let priority = msg.priority();
+        let failover_timeout = config
+            .failover_timeout()
+            .filter(|_| !msg.is_reliable())
+            .map(Duration::from_millis);
+        let selected_pair = Self::link_pair(&links[selected]);
+        let candidates = links
+            .iter()
+            .enumerate()
+            .filter(|(_, link)| Self::link_pair(link) == selected_pair)
+            .map(|(index, link)| LinkCandidate {
+                index,
+                weight: link.weight,
+                queued_batches: link.pipeline.queued_batches(priority),
+                is_stalled: failover_timeout
+                    .is_some_and(|timeout| link.pipeline.is_stalled(priority, timeout)),
+            });
+        let scheduling = match config.scheduling {
+            _ if msg.is_reliable() => MultilinkScheduling::First,
+            scheduling => scheduling,
+        };
+
+        #[allow(unused_variables)] // When feature "stats" is not enabled
+        let (index, picked_by) = Self::pick_link(scheduling, selected, candidates, || {
+            self.round_robin.fetch_add(1, Ordering::Relaxed)
+        });
+
+        #[cfg(feature = "stats")]
+        links[index]
+            .stats
+            .inc_network_message_scheduled(match picked_by {
+                PickedBy::First => SchedulingLabel::First,
+                PickedBy::RoundRobin => SchedulingLabel::RoundRobin,
+                PickedBy::LeastQueued => SchedulingLabel::LeastQueued,
+                PickedBy::Failover => SchedulingLabel::Failover,
+            });
+
+        index
+    }
+
+    /// Returns the index of the link picked among the `candidates` with the `scheduling` policy,
+    /// and how it has been picked. The `selected` link is picked by the
+    /// [`MultilinkScheduling::First`] policy, unless it is stalled.
+    ///
+    /// The round-robin policy takes the turn of the links with `next_turn`.
+    #[cfg(feature = "transport_multilink")]
+    fn pick_link<I>(
+        scheduling: MultilinkScheduling,
+        selected: usize,
+        candidates: I,
+        next_turn: impl FnOnce() -> usize,
+    ) -> (usize, PickedBy)
+    where
+        I: Iterator<Item = LinkCandidate> + Clone,
+    {
+        let available = candidates.clone().filter(|link| !link.is_stalled);
+
+        let striped = match scheduling {
+            MultilinkScheduling::First => None,
+            MultilinkScheduling::RoundRobin => {
+                let total: usize = available.clone().map(|link| link.weight as usize).sum();
+                let mut turn = match total {
+                    0 => 0,
+                    _ => next_turn() % total,
+                };
+                available
+                    .clone()
+                    .find(|link| match turn.checked_sub(link.weight as usize) {
+                        Some(next) => {
+                            turn = next;
+                            false
+                        }
+                        None => true,
+                    })
+                    .map(|link| (link.index, PickedBy::RoundRobin))
+            }
+            MultilinkScheduling::LeastQueued => available
+                .clone()
+                .min_by(|a, b| {
+                    // Compare the queued batches relatively to the weights, i.e. qa / wa to qb / wb
+                    let qa = a.queued_batches * b.weight as usize;
+                    let qb = b.queued_batches * a.weight as usize;
+                    qa.cmp(&qb)
+                })
+                .map(|link| (link.index, PickedBy::LeastQueued)),
+        };
+
+        if let Some(picked) = striped {
+            return picked;
+        }
+        let is_selected_stalled = candidates
+            .clone()
+            .any(|link| link.index == selected && link.is_stalled);
+        match available.clone().next() {
+            Some(link) if is_selected_stalled => (link.index, PickedBy::Failover),
+            _ => (selected, PickedBy::First),
+        }
+    }
+
     fn handle_push_result(
         &self,
         msg: NetworkMessageRef,
@@ -119,15 +272,7 @@ impl TransportUnicastUniversal {
             .expect("reading `TransportUnicastUniversal::links` should not fail");
 
         let Some(transport_link_index) = Self::select(
-            transport_links.iter().map(|tl| {
-                (
-                    tl.link
-                        .config
-                        .reliability
-                        .unwrap_or(Reliability::from(tl.link.link.is_reliable())),
-                    tl.link.config.priorities.clone(),
-                )
-            }),
+            transport_links.iter().map(Self::link_pair),
             Reliability::from(msg.is_reliable()),
             msg.priority(),
         ) else {
@@ -141,6 +286,13 @@ impl TransportUnicastUniversal {
             return Ok(false);
         };
 
+        #[cfg(feature = "transport_multilink")]
+        let transport_link_index = if transport_links.len() > 1 {
+            self.schedule_link(&transport_links, transport_link_index, msg)
+        } else {
+            transport_link_index
+        };
+
         let transport_link = transport_links
             .get(transport_link_index)
             .expect("transport link index should be valid");
@@ -205,8 +357,12 @@ impl TransportUnicastUniversal {
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "transport_multilink")]
+    use zenoh_config::MultilinkScheduling;
     use zenoh_protocol::core::{Priority, PriorityRange, Reliability};
 
+    #[cfg(feature = "transport_multilink")]
+    use super::{LinkCandidate, PickedBy};
     use crate::unicast::universal::transport::TransportUnicastUniversal;
 
     macro_rules! priority_range {
@@ -271,4 +427,160 @@ mod tests {
         );
         assert_eq!(selection, Some(0));
     }
+
+    /// Returns the candidates made of the (weight, queued batches, stalled) states of the links.
+    #[cfg(feature = "transport_multilink")]
+    fn candidates(
+        links: &[(u16, usize, bool)],
+    ) -> impl Iterator<Item = LinkCandidate> + Clone + '_ {
+        links
+            .iter()
+            .enumerate()
+            .map(
+                |(index, &(weight, queued_batches, is_stalled))| LinkCandidate {
+                    index,
+                    weight,
+                    queued_batches,
+                    is_stalled,
+                },
+            )
+    }
+
+    /// Returns the links picked by `scheduling` for `count` successive messages.
+    #[cfg(feature = "transport_multilink")]
+    fn pick_links(
+        scheduling: MultilinkScheduling,
+        selected: usize,
+        links: &[(u16, usize, bool)],
+        count: usize,
+    ) -> Vec<(usize, PickedBy)> {
+        (0..count)
+            .map(|turn| {
+                TransportUnicastUniversal::pick_link(
+                    scheduling,
+                    selected,
+                    candidates(links),
+                    || turn,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    #[cfg(feature = "transport_multilink")]
+    /// Tests that the "first" policy sticks to the selected link.
+    fn test_link_scheduling_first() {
+        let picked = pick_links(
+            MultilinkScheduling::First,
+            1,
+            &[(1, 0, false), (1, 10, false)],
+            3,
+        );
+        assert_eq!(picked, [(1, PickedBy::First); 3]);
+    }
+
+    #[test]
+    #[cfg(feature = "transport_multilink")]
+    /// Tests that the "round_robin" policy sends the messages on the links in turn.
+    fn test_link_scheduling_round_robin() {
+        let picked = pick_links(
+            MultilinkScheduling::RoundRobin,
+            0,
+            &[(1, 0, false), (1, 0, false), (1, 0, false)],
+            6,
+        );
+        let indexes: Vec<usize> = picked.iter().map(|(index, _)| *index).collect();
+        assert_eq!(indexes, [0, 1, 2, 0, 1, 2]);
+        assert!(picked
+            .iter()
+            .all(|(_, picked_by)| *picked_by == PickedBy::RoundRobin));
+    }
+
+    #[test]
+    #[cfg(feature = "transport_multilink")]
+    /// Tests that the "round_robin" policy sends on each link a share of the messages
+    /// proportional to its weight.
+    fn test_link_scheduling_round_robin_weights() {
+        let picked = pick_links(
+            MultilinkScheduling::RoundRobin,
+            0,
+            &[(1, 0, false), (3, 0, false)],
+            8,
+        );
+        let indexes: Vec<usize> = picked.iter().map(|(index, _)| *index).collect();
+        assert_eq!(indexes, [0, 1, 1, 1, 0, 1, 1, 1]);
+    }
+
+    #[test]
+    #[cfg(feature = "transport_multilink")]
+    /// Tests that the "least_queued" policy sends on the link with the fewest queued batches
+    /// relatively to its weight.
+    fn test_link_scheduling_least_queued() {
+        let least_queued = |links: &[(u16, usize, bool)]| {
+            TransportUnicastUniversal::pick_link(
+                MultilinkScheduling::LeastQueued,
+                0,
+                candidates(links),
+                || unreachable!(),
+            )
+        };
+        assert_eq!(
+            least_queued(&[(1, 4, false), (1, 1, false)]),
+            (1, PickedBy::LeastQueued)
+        );
+        assert_eq!(
+            least_queued(&[(1, 2, false), (1, 3, false)]),
+            (0, PickedBy::LeastQueued)
+        );
+        // 4 batches on a link of weight 4 weigh less than 2 batches on a link of weight 1
+        assert_eq!(
+            least_queued(&[(1, 2, false), (4, 4, false)]),
+            (1, PickedBy::LeastQueued)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "transport_multilink")]
+    /// Tests that the stalled links are skipped, the messages being failed over to the other
+    /// links.
+    fn test_link_scheduling_failover() {
+        let links = [(1, 8, true), (1, 0, false), (1, 0, false)];
+        assert_eq!(
+            pick_links(MultilinkScheduling::First, 0, &links, 1),
+            [(1, PickedBy::Failover)]
+        );
+        // The selected link is used as long as it is not stalled
+        assert_eq!(
+            pick_links(MultilinkScheduling::First, 2, &links, 1),
+            [(2, PickedBy::First)]
+        );
+        // The selected link is used if all the links are stalled
+        assert_eq!(
+            pick_links(
+                MultilinkScheduling::First,
+                0,
+                &[(1, 8, true), (1, 8, true)],
+                1
+            ),
+            [(0, PickedBy::First)]
+        );
+
+        let picked = pick_links(MultilinkScheduling::RoundRobin, 0, &links, 4);
+        let indexes: Vec<usize> = picked.iter().map(|(index, _)| *index).collect();
+        assert_eq!(indexes, [1, 2, 1, 2]);
+        assert_eq!(
+            pick_links(MultilinkScheduling::LeastQueued, 0, &links[..2], 1),
+            [(1, PickedBy::LeastQueued)]
+        );
+        // Striping policies fail back to the selected link if all the links are stalled
+        assert_eq!(
+            pick_links(
+                MultilinkScheduling::RoundRobin,
+                1,
+                &[(1, 8, true), (1, 8, true)],
+                1
+            ),
+            [(1, PickedBy::First)]
+        );
+    }
 }
@@ -38,6 +38,7 @@ mod tests {
     };
     use zenoh_result::ZResult;
     use zenoh_transport::{
+        common::compression::{CompressionAlgorithm, CompressionConfig, CompressionDictionary},
         multicast::{TransportManagerBuilderMulticast, TransportMulticast},
         unicast::TransportUnicast,
         TransportEventHandler, TransportManager, TransportMulticastEventHandler, TransportPeer,
@@ -136,6 +137,7 @@ mod tests {
 
     async fn open_transport(
         endpoint: &EndPoint,
+        compression: &CompressionConfig,
     ) -> (TransportMulticastPeer, TransportMulticastPeer) {
         // Define peer01 and peer02 IDs
         let peer01_id = ZenohIdProto::try_from([1]).unwrap();
@@ -146,7 +148,11 @@ mod tests {
         let peer01_manager = TransportManager::builder()
             .zid(peer01_id)
             .whatami(WhatAmI::Peer)
-            .multicast(TransportManagerBuilderMulticast::default().compression(true))
+            .multicast(
+                TransportManagerBuilderMulticast::default()
+                    .compression(true)
+                    .compression_config(compression.clone()),
+            )
             .build_test(peer01_handler.clone())
             .unwrap();
 
@@ -155,7 +161,11 @@ mod tests {
         let peer02_manager = TransportManager::builder()
             .zid(peer02_id)
             .whatami(WhatAmI::Peer)
-            .multicast(TransportManagerBuilderMulticast::default().compression(true))
+            .multicast(
+                TransportManagerBuilderMulticast::default()
+                    .compression(true)
+                    .compression_config(compression.clone()),
+            )
             .build_test(peer02_handler.clone())
             .unwrap();
 
@@ -287,18 +297,28 @@ mod tests {
         tokio::time::sleep(SLEEP).await;
     }
 
-    async fn run_single(endpoint: &EndPoint, channel: Channel, msg_size: usize) {
-        let (peer01, peer02) = open_transport(endpoint).await;
+    async fn run_single(
+        endpoint: &EndPoint,
+        channel: Channel,
+        msg_size: usize,
+        compression: &CompressionConfig,
+    ) {
+        let (peer01, peer02) = open_transport(endpoint, compression).await;
         test_transport(&peer01, &peer02, channel, msg_size).await;
 
         close_transport(peer01, peer02, endpoint).await;
     }
 
-    async fn run(endpoints: &[EndPoint], channel: &[Channel], msg_size: &[usize]) {
+    async fn run(
+        endpoints: &[EndPoint],
+        channel: &[Channel],
+        msg_size: &[usize],
+        compression: &CompressionConfig,
+    ) {
         for e in endpoints.iter() {
             for ch in channel.iter() {
                 for ms in msg_size.iter() {
-                    run_single(e, *ch, *ms).await;
+                    run_single(e, *ch, *ms, compression).await;
                 }
             }
         }
@@ -337,6 +357,63 @@ mod tests {
             },
         ];
         // Run
-        run(&endpoints, &channel, &MSG_SIZE_NOFRAG).await;
+        run(
+            &endpoints,
+            &channel,
+            &MSG_SIZE_NOFRAG,
+            &CompressionConfig::default(),
+        )
+        .await;
+    }
+
+    #[cfg(feature = "transport_udp")]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn transport_multicast_compression_zstd_udp_only() {
+        zenoh_util::init_log_from_env_or("error");
+
+        // Define the locator
+        let endpoints: Vec<EndPoint> = vec![format!(
+            "udp/224.{}.{}.{}:21001",
+            rand::random::<u8>(),
+            rand::random::<u8>(),
+            rand::random::<u8>()
+        )
+        .parse()
+        .unwrap()];
+        // Define the reliability and congestion control
+        let channel = [Channel {
+            priority: Priority::DEFAULT,
+            reliability: Reliability::BestEffort,
+        }];
+        // Run
+        let compression = CompressionConfig::new(CompressionAlgorithm::Zstd, 3, None).unwrap();
+        run(&endpoints, &channel, &MSG_SIZE_NOFRAG, &compression).await;
+    }
+
+    #[cfg(feature = "transport_udp")]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn transport_multicast_compression_zstd_dictionary_udp_only() {
+        zenoh_util::init_log_from_env_or("error");
+
+        // Define the locator
+        let endpoints: Vec<EndPoint> = vec![format!(
+            "udp/224.{}.{}.{}:21002",
+            rand::random::<u8>(),
+            rand::random::<u8>(),
+            rand::random::<u8>()
+        )
+        .parse()
+        .unwrap()];
+        // Define the reliability and congestion control
+        let channel = [Channel {
+            priority: Priority::DEFAULT,
+            reliability: Reliability::BestEffort,
+        }];
+        // Run
+        let dictionary = CompressionDictionary::new(vec![0u8; 256]).unwrap();
+        let compression =
+            CompressionConfig::new(CompressionAlgorithm::ZstdDictionary, 3, Some(dictionary))
+                .unwrap();
+        run(&endpoints, &channel, &MSG_SIZE_NOFRAG, &compression).await;
     }
 }
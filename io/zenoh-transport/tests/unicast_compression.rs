@@ -34,6 +34,7 @@ mod tests {
     };
     use zenoh_result::ZResult;
     use zenoh_transport::{
+        common::compression::{CompressionAlgorithm, CompressionConfig, CompressionDictionary},
         multicast::TransportMulticast,
         unicast::{test_helpers::make_transport_manager_builder, TransportUnicast},
         TransportEventHandler, TransportManager, TransportMulticastEventHandler, TransportPeer,
@@ -155,6 +156,8 @@ mod tests {
         client_endpoints: &[EndPoint],
         server_endpoints: &[EndPoint],
         lowlatency_transport: bool,
+        client_compression: &CompressionConfig,
+        router_compression: &CompressionConfig,
     ) -> (
         TransportManager,
         Arc<SHRouter>,
@@ -172,7 +175,8 @@ mod tests {
             server_endpoints.len(),
             lowlatency_transport,
         )
-        .compression(true);
+        .compression(true)
+        .compression_config(router_compression.clone());
         let router_manager = TransportManager::builder()
             .zid(router_id)
             .whatami(WhatAmI::Router)
@@ -192,7 +196,8 @@ mod tests {
             client_endpoints.len(),
             lowlatency_transport,
         )
-        .compression(true);
+        .compression(true)
+        .compression_config(client_compression.clone());
         let client_manager = TransportManager::builder()
             .whatami(WhatAmI::Client)
             .zid(client_id)
@@ -308,14 +313,22 @@ mod tests {
         channel: Channel,
         msg_size: usize,
         lowlatency_transport: bool,
+        compression: &(CompressionConfig, CompressionConfig),
     ) {
         println!(
-            "\n>>> Running test for:  {client_endpoints:?}, {server_endpoints:?}, {channel:?}, {msg_size}"
+            "\n>>> Running test for:  {client_endpoints:?}, {server_endpoints:?}, {channel:?}, {msg_size}, {compression:?}"
         );
 
         #[allow(unused_variables)] // Used when stats feature is enabled
         let (router_manager, router_handler, client_manager, client_transport) =
-            open_transport_unicast(client_endpoints, server_endpoints, lowlatency_transport).await;
+            open_transport_unicast(
+                client_endpoints,
+                server_endpoints,
+                lowlatency_transport,
+                &compression.0,
+                &compression.1,
+            )
+            .await;
 
         test_transport(
             router_handler.clone(),
@@ -340,6 +353,7 @@ mod tests {
         channel: &[Channel],
         msg_size: &[usize],
         lowlatency_transport: bool,
+        compression: &(CompressionConfig, CompressionConfig),
     ) {
         for ch in channel.iter() {
             for ms in msg_size.iter() {
@@ -349,6 +363,7 @@ mod tests {
                     *ch,
                     *ms,
                     lowlatency_transport,
+                    compression,
                 )
                 .await;
             }
@@ -361,7 +376,33 @@ mod tests {
         channel: &[Channel],
         msg_size: &[usize],
     ) {
-        run_internal(client_endpoints, server_endpoints, channel, msg_size, false).await;
+        run_internal(
+            client_endpoints,
+            server_endpoints,
+            channel,
+            msg_size,
+            false,
+            &Default::default(),
+        )
+        .await;
+    }
+
+    async fn run_with_compression_config(
+        client_endpoints: &[EndPoint],
+        server_endpoints: &[EndPoint],
+        channel: &[Channel],
+        msg_size: &[usize],
+        compression: (CompressionConfig, CompressionConfig),
+    ) {
+        run_internal(
+            client_endpoints,
+            server_endpoints,
+            channel,
+            msg_size,
+            false,
+            &compression,
+        )
+        .await;
     }
 
     async fn run_with_lowlatency_transport(
@@ -374,7 +415,23 @@ mod tests {
             println!("LowLatency transport doesn't support more than one link, so this test would produce MAX_LINKS error!");
             panic!();
         }
-        run_internal(client_endpoints, server_endpoints, channel, msg_size, true).await;
+        run_internal(
+            client_endpoints,
+            server_endpoints,
+            channel,
+            msg_size,
+            true,
+            &Default::default(),
+        )
+        .await;
+    }
+
+    fn compression_config(
+        algorithm: CompressionAlgorithm,
+        dictionary: Option<&[u8]>,
+    ) -> CompressionConfig {
+        let dictionary = dictionary.map(|d| CompressionDictionary::new(d.to_vec()).unwrap());
+        CompressionConfig::new(algorithm, 3, dictionary).unwrap()
     }
 
     #[cfg(feature = "transport_tcp")]
@@ -470,4 +527,89 @@ mod tests {
         // Run
         run_with_lowlatency_transport(&endpoints, &endpoints, &channel, &MSG_SIZE_NOFRAG).await;
     }
+
+    #[cfg(feature = "transport_tcp")]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn transport_unicast_compression_zstd_tcp_only() {
+        zenoh_util::init_log_from_env_or("error");
+
+        // Define the locators
+        let endpoints: Vec<EndPoint> = vec![format!("tcp/127.0.0.1:{}", 19020).parse().unwrap()];
+        // Define the reliability and congestion control
+        let channel = [Channel {
+            priority: Priority::DEFAULT,
+            reliability: Reliability::Reliable,
+        }];
+        // Run
+        let compression = (
+            compression_config(CompressionAlgorithm::Zstd, None),
+            compression_config(CompressionAlgorithm::Zstd, None),
+        );
+        run_with_compression_config(&endpoints, &endpoints, &channel, &MSG_SIZE_ALL, compression)
+            .await;
+    }
+
+    #[cfg(feature = "transport_tcp")]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn transport_unicast_compression_zstd_dictionary_tcp_only() {
+        zenoh_util::init_log_from_env_or("error");
+
+        // Define the locators
+        let endpoints: Vec<EndPoint> = vec![format!("tcp/127.0.0.1:{}", 19021).parse().unwrap()];
+        // Define the reliability and congestion control
+        let channel = [Channel {
+            priority: Priority::DEFAULT,
+            reliability: Reliability::Reliable,
+        }];
+        // Run
+        let dictionary = [0u8; 256];
+        let compression = (
+            compression_config(CompressionAlgorithm::ZstdDictionary, Some(&dictionary)),
+            compression_config(CompressionAlgorithm::ZstdDictionary, Some(&dictionary)),
+        );
+        run_with_compression_config(&endpoints, &endpoints, &channel, &MSG_SIZE_ALL, compression)
+            .await;
+    }
+
+    #[cfg(feature = "transport_tcp")]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn transport_unicast_compression_zstd_dictionary_mismatch_tcp_only() {
+        zenoh_util::init_log_from_env_or("error");
+
+        // Define the locators
+        let endpoints: Vec<EndPoint> = vec![format!("tcp/127.0.0.1:{}", 19022).parse().unwrap()];
+        // Define the reliability and congestion control
+        let channel = [Channel {
+            priority: Priority::DEFAULT,
+            reliability: Reliability::Reliable,
+        }];
+        // Run: the nodes fall back to zstd without dictionary
+        let compression = (
+            compression_config(CompressionAlgorithm::ZstdDictionary, Some(&[0u8; 256])),
+            compression_config(CompressionAlgorithm::ZstdDictionary, Some(&[1u8; 256])),
+        );
+        run_with_compression_config(&endpoints, &endpoints, &channel, &MSG_SIZE_ALL, compression)
+            .await;
+    }
+
+    #[cfg(feature = "transport_tcp")]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn transport_unicast_compression_zstd_lz4_tcp_only() {
+        zenoh_util::init_log_from_env_or("error");
+
+        // Define the locators
+        let endpoints: Vec<EndPoint> = vec![format!("tcp/127.0.0.1:{}", 19023).parse().unwrap()];
+        // Define the reliability and congestion control
+        let channel = [Channel {
+            priority: Priority::DEFAULT,
+            reliability: Reliability::Reliable,
+        }];
+        // Run: the client compresses with zstd while the router compresses with LZ4
+        let compression = (
+            compression_config(CompressionAlgorithm::ZstdDictionary, Some(&[0u8; 256])),
+            compression_config(CompressionAlgorithm::Lz4, None),
+        );
+        run_with_compression_config(&endpoints, &endpoints, &channel, &MSG_SIZE_ALL, compression)
+            .await;
+    }
 }
@@ -13,11 +13,25 @@
 //
 #[cfg(feature = "transport_multilink")]
 mod tests {
-    use std::{convert::TryFrom, sync::Arc, time::Duration};
+    use std::{
+        any::Any,
+        convert::TryFrom,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
+        time::Duration,
+    };
 
+    use zenoh_buffers::buffer::SplitBuffer;
+    use zenoh_config::{MultilinkScheduling, MultilinkUnicastConf};
     use zenoh_core::ztimeout;
-    use zenoh_link::EndPoint;
-    use zenoh_protocol::core::{WhatAmI, ZenohIdProto};
+    use zenoh_link::{EndPoint, Link};
+    use zenoh_protocol::{
+        core::{CongestionControl, Priority, Reliability, WhatAmI, ZenohIdProto},
+        network::{push::ext::QoSType, NetworkBodyMut, NetworkMessage, NetworkMessageMut, Push},
+        zenoh::{PushBody, Put},
+    };
     use zenoh_result::ZResult;
     use zenoh_transport::{
         multicast::TransportMulticast, unicast::TransportUnicast, DummyTransportPeerEventHandler,
@@ -475,6 +489,285 @@ mod tests {
         tokio::time::sleep(SLEEP).await;
     }
 
+    // Transport Handler for the router counting the received messages
+    #[derive(Default)]
+    struct SHRouterCount {
+        count: Arc<AtomicUsize>,
+    }
+
+    impl TransportEventHandler for SHRouterCount {
+        fn new_unicast(
+            &self,
+            _peer: TransportPeer,
+            _transport: TransportUnicast,
+        ) -> ZResult<Arc<dyn TransportPeerEventHandler>> {
+            Ok(Arc::new(SCRouterCount {
+                count: self.count.clone(),
+            }))
+        }
+
+        fn new_multicast(
+            &self,
+            _transport: TransportMulticast,
+        ) -> ZResult<Arc<dyn TransportMulticastEventHandler>> {
+            panic!();
+        }
+    }
+
+    // Transport Callback for the router counting the received messages
+    struct SCRouterCount {
+        count: Arc<AtomicUsize>,
+    }
+
+    impl TransportPeerEventHandler for SCRouterCount {
+        fn handle_message(&self, _message: NetworkMessageMut) -> ZResult<()> {
+            self.count.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn new_link(&self, _link: Link) {}
+        fn del_link(&self, _link: Link) {}
+        fn closed(&self) {}
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    // Transport Handler for the router recording the indexes of the received messages
+    #[derive(Default)]
+    struct SHRouterOrder {
+        received: Arc<Mutex<Vec<u64>>>,
+    }
+
+    impl TransportEventHandler for SHRouterOrder {
+        fn new_unicast(
+            &self,
+            _peer: TransportPeer,
+            _transport: TransportUnicast,
+        ) -> ZResult<Arc<dyn TransportPeerEventHandler>> {
+            Ok(Arc::new(SCRouterOrder {
+                received: self.received.clone(),
+            }))
+        }
+
+        fn new_multicast(
+            &self,
+            _transport: TransportMulticast,
+        ) -> ZResult<Arc<dyn TransportMulticastEventHandler>> {
+            panic!();
+        }
+    }
+
+    // Transport Callback for the router recording the indexes of the received messages, slowly
+    // processed for the links of the sender to stall
+    struct SCRouterOrder {
+        received: Arc<Mutex<Vec<u64>>>,
+    }
+
+    impl TransportPeerEventHandler for SCRouterOrder {
+        fn handle_message(&self, message: NetworkMessageMut) -> ZResult<()> {
+            let NetworkBodyMut::Push(Push {
+                payload: PushBody::Put(Put { payload, .. }),
+                ..
+            }) = message.body
+            else {
+                panic!("Unsolicited message");
+            };
+            let mut index = [0u8; 8];
+            index.copy_from_slice(&payload.contiguous()[0..8]);
+            self.received
+                .lock()
+                .unwrap()
+                .push(u64::from_le_bytes(index));
+            std::thread::sleep(Duration::from_millis(1));
+            Ok(())
+        }
+
+        fn new_link(&self, _link: Link) {}
+        fn del_link(&self, _link: Link) {}
+        fn closed(&self) {}
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    async fn multilink_scheduling(endpoints: &[EndPoint], scheduling: MultilinkScheduling) {
+        const MSG_COUNT: usize = 300;
+        println!("\n>>> Running test for: {endpoints:?}, {scheduling:?}");
+
+        let router_id = ZenohIdProto::try_from([1]).unwrap();
+        let client_id = ZenohIdProto::try_from([2]).unwrap();
+        let mut multilink = MultilinkUnicastConf::default();
+        multilink.scheduling = scheduling;
+
+        let router_handler = Arc::new(SHRouterCount::default());
+        let unicast = TransportManager::config_unicast()
+            .max_links(endpoints.len())
+            .multilink(multilink.clone());
+        let router_manager = TransportManager::builder()
+            .whatami(WhatAmI::Router)
+            .zid(router_id)
+            .unicast(unicast)
+            .build_test(router_handler.clone())
+            .unwrap();
+        for e in endpoints.iter() {
+            ztimeout!(router_manager.add_listener(e.clone())).unwrap();
+        }
+
+        let unicast = TransportManager::config_unicast()
+            .max_links(endpoints.len())
+            .multilink(multilink);
+        let client_manager = TransportManager::builder()
+            .whatami(WhatAmI::Client)
+            .zid(client_id)
+            .unicast(unicast)
+            .build_test(Arc::new(SHClientOpenClose::new()))
+            .unwrap();
+        for e in endpoints.iter() {
+            ztimeout!(client_manager.open_transport_unicast(e.clone())).unwrap();
+        }
+        let client_transport = ztimeout!(client_manager.get_transport_unicast(&router_id)).unwrap();
+        assert_eq!(client_transport.get_links().unwrap().len(), endpoints.len());
+
+        // Send best-effort messages, two out of three being fragmented. Striped on the links,
+        // they are received out of order and the fragments of the messages are interleaved.
+        for i in 0..MSG_COUNT {
+            let size = if i % 3 == 0 { 8 } else { 128 * 1024 };
+            let mut msg = NetworkMessage::from(Push {
+                wire_expr: "test".into(),
+                ext_qos: QoSType::new(Priority::DEFAULT, CongestionControl::Block, false),
+                ..Push::from(vec![0u8; size])
+            });
+            msg.reliability = Reliability::BestEffort;
+            assert!(client_transport.schedule(msg.as_mut()).unwrap());
+        }
+
+        // All the messages are received
+        ztimeout!(async {
+            while router_handler.count.load(Ordering::SeqCst) < MSG_COUNT {
+                tokio::time::sleep(SLEEP).await;
+            }
+        });
+        tokio::time::sleep(SLEEP).await;
+        assert_eq!(router_handler.count.load(Ordering::SeqCst), MSG_COUNT);
+
+        ztimeout!(client_transport.close()).unwrap();
+        for e in endpoints.iter() {
+            ztimeout!(router_manager.del_listener(e)).unwrap();
+        }
+        ztimeout!(router_manager.close());
+        ztimeout!(client_manager.close());
+
+        // Wait a little bit
+        tokio::time::sleep(SLEEP).await;
+    }
+
+    #[cfg(feature = "transport_tcp")]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn multilink_scheduling_tcp_only() {
+        zenoh_util::init_log_from_env_or("error");
+
+        for (port, scheduling) in [
+            (18030, MultilinkScheduling::First),
+            (18040, MultilinkScheduling::RoundRobin),
+            (18050, MultilinkScheduling::LeastQueued),
+        ] {
+            let endpoints: Vec<EndPoint> = (port..port + 2)
+                .map(|port| format!("tcp/127.0.0.1:{port}").parse().unwrap())
+                .collect();
+            multilink_scheduling(&endpoints, scheduling).await;
+        }
+    }
+
+    async fn multilink_reliable_failover(endpoints: &[EndPoint]) {
+        const MSG_COUNT: u64 = 300;
+        println!("\n>>> Running test for: {endpoints:?}");
+
+        let router_id = ZenohIdProto::try_from([1]).unwrap();
+        let client_id = ZenohIdProto::try_from([2]).unwrap();
+        // The links are stalled as soon as they don't transmit for 1 ms
+        let mut multilink = MultilinkUnicastConf::default();
+        multilink.scheduling = MultilinkScheduling::RoundRobin;
+        multilink.set_failover_timeout(Some(1)).unwrap();
+
+        let router_handler = Arc::new(SHRouterOrder::default());
+        let unicast = TransportManager::config_unicast()
+            .max_links(endpoints.len())
+            .multilink(multilink.clone());
+        let router_manager = TransportManager::builder()
+            .whatami(WhatAmI::Router)
+            .zid(router_id)
+            .unicast(unicast)
+            .build_test(router_handler.clone())
+            .unwrap();
+        for e in endpoints.iter() {
+            ztimeout!(router_manager.add_listener(e.clone())).unwrap();
+        }
+
+        let unicast = TransportManager::config_unicast()
+            .max_links(endpoints.len())
+            .multilink(multilink);
+        let client_manager = TransportManager::builder()
+            .whatami(WhatAmI::Client)
+            .zid(client_id)
+            .unicast(unicast)
+            .build_test(Arc::new(SHClientOpenClose::new()))
+            .unwrap();
+        for e in endpoints.iter() {
+            ztimeout!(client_manager.open_transport_unicast(e.clone())).unwrap();
+        }
+        let client_transport = ztimeout!(client_manager.get_transport_unicast(&router_id)).unwrap();
+        assert_eq!(client_transport.get_links().unwrap().len(), endpoints.len());
+
+        // Send fragmented reliable messages faster than they are processed, stalling the links
+        for i in 0..MSG_COUNT {
+            let mut payload = vec![0u8; 128 * 1024];
+            payload[0..8].copy_from_slice(&i.to_le_bytes());
+            let mut msg = NetworkMessage::from(Push {
+                wire_expr: "test".into(),
+                ext_qos: QoSType::new(Priority::DEFAULT, CongestionControl::Block, false),
+                ..Push::from(payload)
+            });
+            msg.reliability = Reliability::Reliable;
+            assert!(client_transport.schedule(msg.as_mut()).unwrap());
+        }
+
+        // The reliable messages don't fail over: they are all received, in order
+        ztimeout!(async {
+            while router_handler.received.lock().unwrap().len() < MSG_COUNT as usize {
+                tokio::time::sleep(SLEEP).await;
+            }
+        });
+        tokio::time::sleep(SLEEP).await;
+        assert_eq!(
+            *router_handler.received.lock().unwrap(),
+            (0..MSG_COUNT).collect::<Vec<_>>()
+        );
+
+        ztimeout!(client_transport.close()).unwrap();
+        for e in endpoints.iter() {
+            ztimeout!(router_manager.del_listener(e)).unwrap();
+        }
+        ztimeout!(router_manager.close());
+        ztimeout!(client_manager.close());
+
+        // Wait a little bit
+        tokio::time::sleep(SLEEP).await;
+    }
+
+    #[cfg(feature = "transport_tcp")]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn multilink_reliable_failover_tcp_only() {
+        zenoh_util::init_log_from_env_or("error");
+
+        let endpoints: Vec<EndPoint> = (18060..18062)
+            .map(|port| format!("tcp/127.0.0.1:{port}").parse().unwrap())
+            .collect();
+        multilink_reliable_failover(&endpoints).await;
+    }
+
     #[cfg(feature = "transport_tcp")]
     #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
     async fn multilink_tcp_only() {
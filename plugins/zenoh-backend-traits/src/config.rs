@@ -11,7 +11,7 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use std::{convert::TryFrom, time::Duration};
+use std::{convert::TryFrom, path::PathBuf, time::Duration};
 
 use const_format::concatcp;
 use derive_more::{AsMut, AsRef};
@@ -68,6 +68,11 @@ pub struct StorageConfig {
     pub volume_id: String,
     pub volume_cfg: JsonValue,
     pub garbage_collection_config: GarbageCollectionConfig,
+    // Note: the values older than the ttl are expired (i.e. deleted) by the storage manager
+    pub ttl: Option<Duration>,
+    // Note: if not configured, the updates are applied one by one to the storage
+    pub batch: Option<BatchConfig>,
+    pub snapshot: SnapshotConfig,
     // Note: ReplicaConfig is optional. Alignment will be performed only if it is a replica
     pub replication: Option<ReplicaConfig>,
 }
@@ -79,6 +84,8 @@ pub struct ReplicaConfig {
     pub hot: u64,
     pub warm: u64,
     pub propagation_delay: Duration,
+    pub alignment_batch_size: usize,
+    pub alignment_bandwidth: Option<u64>,
 }
 
 impl StructVersion for VolumeConfig {
@@ -149,6 +156,18 @@ impl Default for ReplicaConfig {
             //
             // ⚠️ THIS VALUE SHOULD BE THE SAME FOR ALL REPLICAS.
             propagation_delay: Duration::from_millis(250),
+            // The maximum number of entries exchanged in a row when aligning: a replica requests
+            // the payloads of at most that many entries per alignment query and, when replying,
+            // checks the `alignment_bandwidth` after sending that many entries.
+            //
+            // Its default value is 100.
+            alignment_batch_size: 100,
+            // The maximum number of bytes of payload, per SECOND, sent by this replica to align
+            // another one. This prevents the alignment of a newly started replica from starving
+            // the live traffic.
+            //
+            // By default, the alignment is not throttled.
+            alignment_bandwidth: None,
         }
     }
 }
@@ -172,6 +191,34 @@ impl Default for GarbageCollectionConfig {
     }
 }
 
+// The configuration for applying the updates received by a storage in batches
+#[derive(JsonSchema, Debug, Clone, PartialEq, Eq)]
+pub struct BatchConfig {
+    // The maximum number of updates in a batch
+    pub max_size: usize,
+    // The maximum duration an update waits for its batch to be applied
+    pub max_delay: Duration,
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        Self {
+            max_size: 100,
+            max_delay: Duration::from_millis(10),
+        }
+    }
+}
+
+// The configuration of the snapshot admin operations of a storage
+#[derive(JsonSchema, Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnapshotConfig {
+    // The directory of the snapshot files: if not configured, snapshots can only be exchanged in
+    // the queries and their replies
+    pub directory: Option<PathBuf>,
+    // Whether snapshots can be imported in the storage
+    pub import: bool,
+}
+
 #[derive(Debug)]
 pub enum ConfigDiff {
     DeleteVolume(VolumeConfig),
@@ -561,6 +608,72 @@ impl StorageConfig {
             }
             None => GarbageCollectionConfig::default(),
         };
+        let ttl = match config.get("ttl") {
+            Some(ttl) => match ttl.to_string().parse::<f64>() {
+                Ok(ttl) if ttl > 0.0 && ttl.is_finite() => Some(Duration::from_secs_f64(ttl)),
+                _ => bail!(
+                    "Invalid value for field `ttl` of storage `{}`. Expecting a strictly \
+                     positive integer or floating point number.",
+                    storage_name
+                ),
+            },
+            None => None,
+        };
+        let batch = match config.get("batch") {
+            Some(s) => {
+                let mut batch = BatchConfig::default();
+                if let Some(max_size) = s.get("max_size") {
+                    match max_size.to_string().parse::<usize>() {
+                        Ok(max_size) if max_size > 0 => batch.max_size = max_size,
+                        _ => bail!(
+                            "Invalid value for field `max_size` in `batch` of storage `{}`. Only \
+                             strictly positive integer values are accepted.",
+                            storage_name
+                        ),
+                    }
+                }
+                if let Some(max_delay) = s.get("max_delay") {
+                    if let Ok(max_delay) = max_delay.to_string().parse::<u64>() {
+                        batch.max_delay = Duration::from_millis(max_delay);
+                    } else {
+                        bail!(
+                            "Invalid type for field `max_delay` in `batch` of storage `{}`. Only \
+                             integer values are accepted.",
+                            storage_name
+                        )
+                    }
+                }
+                Some(batch)
+            }
+            None => None,
+        };
+        let snapshot = match config.get("snapshot") {
+            Some(s) => {
+                let mut snapshot = SnapshotConfig::default();
+                match s.get("directory") {
+                    Some(Value::String(directory)) => {
+                        snapshot.directory = Some(PathBuf::from(directory))
+                    }
+                    Some(_) => bail!(
+                        "Invalid type for field `directory` in `snapshot` of storage `{}`. Only \
+                         strings are accepted.",
+                        storage_name
+                    ),
+                    None => {}
+                }
+                match s.get("import") {
+                    Some(Value::Bool(import)) => snapshot.import = *import,
+                    Some(_) => bail!(
+                        "Invalid type for field `import` in `snapshot` of storage `{}`. Only \
+                         booleans are accepted.",
+                        storage_name
+                    ),
+                    None => {}
+                }
+                snapshot
+            }
+            None => SnapshotConfig::default(),
+        };
         let replication = match config.get("replication") {
             Some(s) => {
                 let mut replication = ReplicaConfig::default();
@@ -634,6 +747,30 @@ impl StorageConfig {
                         )
                     }
                 }
+                if let Some(batch_size) = s.get("alignment_batch_size") {
+                    match batch_size.to_string().parse::<usize>() {
+                        Ok(batch_size) if batch_size > 0 => {
+                            replication.alignment_batch_size = batch_size
+                        }
+                        _ => bail!(
+                            "Invalid value for field `alignment_batch_size` in `replica_config` \
+                             of storage `{}`. Only strictly positive integer values are accepted.",
+                            plugin_name
+                        ),
+                    }
+                }
+                if let Some(bandwidth) = s.get("alignment_bandwidth") {
+                    match bandwidth.to_string().parse::<u64>() {
+                        Ok(bandwidth) if bandwidth > 0 => {
+                            replication.alignment_bandwidth = Some(bandwidth)
+                        }
+                        _ => bail!(
+                            "Invalid value for field `alignment_bandwidth` in `replica_config` \
+                             of storage `{}`. Only strictly positive integer values are accepted.",
+                            plugin_name
+                        ),
+                    }
+                }
                 Some(replication)
             }
             None => None,
@@ -646,6 +783,9 @@ impl StorageConfig {
             volume_id,
             volume_cfg: volume_cfg.into(),
             garbage_collection_config,
+            ttl,
+            batch,
+            snapshot,
             replication,
         })
     }
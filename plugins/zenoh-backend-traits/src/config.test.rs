@@ -17,7 +17,7 @@ use std::time::Duration;
 use serde_json::json;
 
 use super::StorageConfig;
-use crate::config::ReplicaConfig;
+use crate::config::{BatchConfig, ReplicaConfig};
 
 #[test]
 fn test_replica_config() {
@@ -72,7 +72,107 @@ Actual message: {err}",
             sub_intervals: 4,
             hot: 6,
             warm: 60,
-            propagation_delay: Duration::from_millis(250)
+            propagation_delay: Duration::from_millis(250),
+            alignment_batch_size: 100,
+            alignment_bandwidth: None,
         })
     );
+
+    let throttled_config = json!({
+        "key_expr": "test/**",
+        "volume": "memory",
+        "replication": {
+            "alignment_batch_size": 10,
+            "alignment_bandwidth": 1048576,
+        }
+    });
+    let storage_config =
+        StorageConfig::try_from("test-plugin", "test-storage", &throttled_config).unwrap();
+    let replication = storage_config.replication.unwrap();
+    assert_eq!(replication.alignment_batch_size, 10);
+    assert_eq!(replication.alignment_bandwidth, Some(1_048_576));
+
+    for (field, value) in [
+        ("alignment_batch_size", json!(0)),
+        ("alignment_bandwidth", json!(0)),
+        ("alignment_bandwidth", json!("1MB")),
+    ] {
+        let config = json!({
+            "key_expr": "test/**",
+            "volume": "memory",
+            "replication": { field: value }
+        });
+        assert!(StorageConfig::try_from("test-plugin", "test-storage", &config).is_err());
+    }
+}
+
+#[test]
+fn test_ttl_config() {
+    let config = json!({
+        "key_expr": "test/**",
+        "volume": "memory",
+    });
+    let storage_config = StorageConfig::try_from("test-plugin", "test-storage", &config).unwrap();
+    assert_eq!(storage_config.ttl, None);
+
+    let config = json!({
+        "key_expr": "test/**",
+        "volume": "memory",
+        "ttl": 0.5,
+    });
+    let storage_config = StorageConfig::try_from("test-plugin", "test-storage", &config).unwrap();
+    assert_eq!(storage_config.ttl, Some(Duration::from_millis(500)));
+
+    for ttl in [json!(0), json!(-1), json!("1h")] {
+        let config = json!({
+            "key_expr": "test/**",
+            "volume": "memory",
+            "ttl": ttl,
+        });
+        assert!(StorageConfig::try_from("test-plugin", "test-storage", &config).is_err());
+    }
+}
+
+#[test]
+fn test_batch_config() {
+    let config = json!({
+        "key_expr": "test/**",
+        "volume": "memory",
+    });
+    let storage_config = StorageConfig::try_from("test-plugin", "test-storage", &config).unwrap();
+    assert_eq!(storage_config.batch, None);
+
+    let config = json!({
+        "key_expr": "test/**",
+        "volume": "memory",
+        "batch": {},
+    });
+    let storage_config = StorageConfig::try_from("test-plugin", "test-storage", &config).unwrap();
+    assert_eq!(storage_config.batch, Some(BatchConfig::default()));
+
+    let config = json!({
+        "key_expr": "test/**",
+        "volume": "memory",
+        "batch": {
+            "max_size": 1000,
+            "max_delay": 50,
+        },
+    });
+    let storage_config = StorageConfig::try_from("test-plugin", "test-storage", &config).unwrap();
+    assert_eq!(
+        storage_config.batch,
+        Some(BatchConfig {
+            max_size: 1000,
+            max_delay: Duration::from_millis(50),
+        })
+    );
+
+    let config = json!({
+        "key_expr": "test/**",
+        "volume": "memory",
+        "batch": {
+            "max_size": 0,
+        },
+    });
+    assert!(StorageConfig::try_from("test-plugin", "test-storage", &config).is_err());
 }
@@ -135,6 +135,8 @@
 //! }
 //! ```
 
+use std::{borrow::Borrow, cmp::Ordering, ops::Bound, sync::Arc};
+
 use async_trait::async_trait;
 use zenoh::{
     bytes::{Encoding, ZBytes},
@@ -195,6 +197,171 @@ pub struct StoredData {
     pub timestamp: Timestamp,
 }
 
+/// An update to apply to a [`Storage`], see [`Storage::apply_batch`].
+#[derive(Debug, Clone)]
+pub enum StorageOperation {
+    Put {
+        key: Option<OwnedKeyExpr>,
+        payload: ZBytes,
+        encoding: Encoding,
+        timestamp: Timestamp,
+    },
+    Delete {
+        key: Option<OwnedKeyExpr>,
+        timestamp: Timestamp,
+    },
+}
+
+/// Position reached by a scan of the entries of a [`Storage`].
+///
+/// The entries are scanned in the order of their stripped key, compared as strings, the `None` key
+/// (i.e. the key equal to the `strip_prefix`) coming first. A scan resuming from a cursor only
+/// returns the entries whose key is strictly greater than `last_key`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScanCursor {
+    pub last_key: Option<OwnedKeyExpr>,
+}
+
+impl ScanCursor {
+    /// Returns `true` if the provided (stripped) key comes after this cursor.
+    pub fn precedes(&self, key: &Option<OwnedKeyExpr>) -> bool {
+        compare_keys(&self.last_key, key) == Ordering::Less
+    }
+}
+
+/// A stripped key ordered as defined by [`ScanCursor`].
+///
+/// Indexing the entries of a backend by `ScanKey` in an ordered map allows its scans to seek to
+/// their cursor with [`ScanOptions::start`], instead of filtering all the keys at each page.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ScanKey(pub Option<OwnedKeyExpr>);
+
+impl Ord for ScanKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare_keys(&self.0, &other.0)
+    }
+}
+
+impl PartialOrd for ScanKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Options of a scan of the entries of a [`Storage`], see [`Storage::get_matching_entries`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScanOptions {
+    /// The maximum number of keys to return, `None` meaning no limit. A limit of `0` is treated as
+    /// a limit of `1`, a scan always progressing by at least one key.
+    pub limit: Option<usize>,
+    /// The position from which the scan should resume, `None` to start from the first key.
+    pub cursor: Option<ScanCursor>,
+}
+
+impl ScanOptions {
+    /// Filters the provided keys, keeping those following the cursor, and sorts them in the order
+    /// defined by [`ScanCursor`]. Then, if there are more than `limit` keys, truncates them and
+    /// returns the cursor to resume the scan.
+    ///
+    /// All the keys are filtered at each call: backends able to list their keys in order should
+    /// rather use [`ScanOptions::select_sorted`].
+    pub fn select<K: Borrow<Option<OwnedKeyExpr>>>(
+        &self,
+        keys: impl IntoIterator<Item = K>,
+    ) -> (Vec<K>, Option<ScanCursor>) {
+        let mut keys = keys
+            .into_iter()
+            .filter(|key| {
+                self.cursor
+                    .as_ref()
+                    .map_or(true, |cursor| cursor.precedes(key.borrow()))
+            })
+            .collect::<Vec<_>>();
+        let compare = |a: &K, b: &K| compare_keys(a.borrow(), b.borrow());
+
+        let mut cursor = None;
+        match self.limit.map(|limit| limit.max(1)) {
+            Some(limit) if keys.len() > limit => {
+                // Only the selected keys need to be sorted.
+                keys.select_nth_unstable_by(limit - 1, compare);
+                keys.truncate(limit);
+                keys.sort_unstable_by(compare);
+                cursor = keys.last().map(|key| ScanCursor {
+                    last_key: key.borrow().clone(),
+                });
+            }
+            _ => keys.sort_unstable_by(compare),
+        }
+
+        (keys, cursor)
+    }
+
+    /// Returns the bound from which the keys follow the cursor, to scan an ordered map indexed by
+    /// [`ScanKey`].
+    pub fn start(&self) -> Bound<ScanKey> {
+        match &self.cursor {
+            Some(cursor) => Bound::Excluded(ScanKey(cursor.last_key.clone())),
+            None => Bound::Unbounded,
+        }
+    }
+
+    /// Same as [`ScanOptions::select`], for keys already following the cursor and sorted in the
+    /// order defined by [`ScanCursor`], e.g. the range of an ordered map indexed by [`ScanKey`]
+    /// starting at [`ScanOptions::start`].
+    ///
+    /// Only the keys up to the limit, and the next one to know if the scan is complete, are
+    /// consumed.
+    pub fn select_sorted<K: Borrow<Option<OwnedKeyExpr>>>(
+        &self,
+        keys: impl IntoIterator<Item = K>,
+    ) -> (Vec<K>, Option<ScanCursor>) {
+        let mut keys = keys.into_iter();
+        let limit = self.limit.map_or(usize::MAX, |limit| limit.max(1));
+        let selected = keys.by_ref().take(limit).collect::<Vec<_>>();
+        let cursor = match keys.next() {
+            Some(_) => selected.last().map(|key| ScanCursor {
+                last_key: key.borrow().clone(),
+            }),
+            None => None,
+        };
+
+        (selected, cursor)
+    }
+}
+
+/// Result of a scan of the entries of a [`Storage`].
+#[derive(Debug, Clone, Default)]
+pub struct ScanResult {
+    /// The (stripped) keys matching the scanned key expression and their values, in the order
+    /// defined by [`ScanCursor`].
+    pub entries: Vec<(Option<OwnedKeyExpr>, Vec<StoredData>)>,
+    /// The cursor from which to resume the scan if it was interrupted because of the `limit`,
+    /// `None` if all the matching entries were returned.
+    pub cursor: Option<ScanCursor>,
+}
+
+/// Compares two stripped keys following the order defined by [`ScanCursor`].
+pub fn compare_keys(a: &Option<OwnedKeyExpr>, b: &Option<OwnedKeyExpr>) -> Ordering {
+    a.as_ref()
+        .map(|k| k.as_str())
+        .cmp(&b.as_ref().map(|k| k.as_str()))
+}
+
+/// Returns `true` if the stripped key, once prefixed by the `strip_prefix`, intersects with the
+/// provided key expression.
+pub fn stripped_key_intersects(
+    key_expr: &keyexpr,
+    strip_prefix: Option<&OwnedKeyExpr>,
+    stripped_key: Option<&OwnedKeyExpr>,
+) -> bool {
+    match (strip_prefix, stripped_key) {
+        (Some(prefix), Some(key)) => key_expr.intersects(&(prefix / key)),
+        (Some(prefix), None) => key_expr.intersects(prefix),
+        (None, Some(key)) => key_expr.intersects(key),
+        (None, None) => false,
+    }
+}
+
 /// Trait to be implemented by a Backend.
 #[async_trait]
 pub trait Volume: Send + Sync {
@@ -248,6 +415,33 @@ pub trait Storage: Send + Sync {
         timestamp: Timestamp,
     ) -> ZResult<StorageInsertionResult>;
 
+    /// Function called to apply a batch of updates to this storage, in order.
+    ///
+    /// The result of each operation must be returned at the same index as the operation. A backend
+    /// applying the batch in a single transaction that failed should report the error for each
+    /// operation.
+    ///
+    /// The default implementation calls [`Storage::put`] or [`Storage::delete`] for each
+    /// operation. Backends supporting transactions or bulk inserts should override it.
+    async fn apply_batch(
+        &mut self,
+        operations: Vec<StorageOperation>,
+    ) -> Vec<ZResult<StorageInsertionResult>> {
+        let mut results = Vec::with_capacity(operations.len());
+        for operation in operations {
+            results.push(match operation {
+                StorageOperation::Put {
+                    key,
+                    payload,
+                    encoding,
+                    timestamp,
+                } => self.put(key, payload, encoding, timestamp).await,
+                StorageOperation::Delete { key, timestamp } => self.delete(key, timestamp).await,
+            });
+        }
+        results
+    }
+
     /// Function to retrieve the sample associated with a single key.
     /// A key can be `None` if it matches the `strip_prefix` exactly.
     /// In order to avoid data loss, the storage must retrieve the `value` and `timestamp` associated with the `None` key
@@ -262,4 +456,94 @@ pub trait Storage: Send + Sync {
     /// The latest Timestamp corresponding to each key is either the timestamp of the delete or put whichever is the latest.
     /// Remember to fetch the entry corresponding to the `None` key
     async fn get_all_entries(&self) -> ZResult<Vec<(Option<OwnedKeyExpr>, Timestamp)>>;
+
+    /// Function called to get the list of the entries (stripped key, timestamp) whose key, once
+    /// prefixed by the `strip_prefix`, intersects with `key_expr`.
+    ///
+    /// The default implementation filters the result of [`Storage::get_all_entries`]. Backends able
+    /// to perform prefix or range lookups should override it.
+    async fn get_matching_keys(
+        &self,
+        key_expr: &keyexpr,
+        strip_prefix: Option<&OwnedKeyExpr>,
+    ) -> ZResult<Vec<(Option<OwnedKeyExpr>, Timestamp)>> {
+        Ok(self
+            .get_all_entries()
+            .await?
+            .into_iter()
+            .filter(|(key, _)| stripped_key_intersects(key_expr, strip_prefix, key.as_ref()))
+            .collect())
+    }
+
+    /// Function called to retrieve the values of all the entries whose key, once prefixed by the
+    /// `strip_prefix`, intersects with `key_expr`.
+    ///
+    /// The entries must be returned in the order defined by [`ScanCursor`], starting after the
+    /// cursor provided in the `options` and stopping after `limit` keys. If the scan was stopped
+    /// before all the matching entries were returned, a cursor to resume it must be returned.
+    ///
+    /// The `parameters` are those of the query, they should be interpreted the same way as in
+    /// [`Storage::get`].
+    ///
+    /// The default implementation calls [`Storage::get_matching_keys`] then [`Storage::get`] for each
+    /// key. As all the keys are listed again for each page, paginating over `N` keys costs
+    /// `O(N² / limit)`: backends should override it to retrieve the values in a single pass and
+    /// to seek to the cursor, e.g. by indexing their entries by [`ScanKey`].
+    async fn get_matching_entries(
+        &mut self,
+        key_expr: &keyexpr,
+        strip_prefix: Option<&OwnedKeyExpr>,
+        parameters: &str,
+        options: ScanOptions,
+    ) -> ZResult<ScanResult> {
+        let (keys, cursor) = options.select(
+            self.get_matching_keys(key_expr, strip_prefix)
+                .await?
+                .into_iter()
+                .map(|(key, _)| key),
+        );
+
+        let mut entries = Vec::with_capacity(keys.len());
+        for key in keys {
+            match self.get(key.clone(), parameters).await {
+                Ok(data) => entries.push((key, data)),
+                // The entry could have been deleted since the keys were listed.
+                Err(e) => tracing::debug!("Failed to retrieve entry {key:?}: {e}"),
+            }
+        }
+
+        Ok(ScanResult { entries, cursor })
+    }
+
+    /// Returns a reader of this storage that can be used concurrently with the updates of the
+    /// storage, if the backend supports it.
+    ///
+    /// If a reader is returned, the storage manager uses it to reply to the queries in parallel
+    /// with the updates of the storage. Otherwise, the queries and updates are serialized.
+    ///
+    /// The default implementation returns `None`.
+    fn concurrent_reader(&self) -> Option<Arc<dyn StorageReader>> {
+        None
+    }
 }
+
+/// Read access to a [`Storage`] that is safe to use concurrently with the updates of the storage,
+/// see [`Storage::concurrent_reader`].
+#[async_trait]
+pub trait StorageReader: Send + Sync {
+    /// Same as [`Storage::get`].
+    async fn get(&self, key: Option<OwnedKeyExpr>, parameters: &str) -> ZResult<Vec<StoredData>>;
+
+    /// Same as [`Storage::get_matching_entries`].
+    async fn get_matching_entries(
+        &self,
+        key_expr: &keyexpr,
+        strip_prefix: Option<&OwnedKeyExpr>,
+        parameters: &str,
+        options: ScanOptions,
+    ) -> ZResult<ScanResult>;
+}
+
+#[cfg(test)]
+#[path = "lib.test.rs"]
+mod tests;
@@ -0,0 +1,154 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+use std::{collections::BTreeMap, str::FromStr};
+
+use zenoh::key_expr::{keyexpr, OwnedKeyExpr};
+
+use super::{stripped_key_intersects, ScanCursor, ScanKey, ScanOptions};
+
+fn key(key: &str) -> Option<OwnedKeyExpr> {
+    Some(OwnedKeyExpr::from_str(key).unwrap())
+}
+
+#[test]
+fn test_stripped_key_intersects() {
+    let prefix = OwnedKeyExpr::from_str("demo/example").unwrap();
+
+    let key_expr = keyexpr::new("demo/example/**").unwrap();
+    assert!(stripped_key_intersects(
+        key_expr,
+        Some(&prefix),
+        key("a/b").as_ref()
+    ));
+    assert!(stripped_key_intersects(key_expr, Some(&prefix), None));
+    assert!(!stripped_key_intersects(
+        key_expr,
+        None,
+        key("a/b").as_ref()
+    ));
+
+    let key_expr = keyexpr::new("**/b").unwrap();
+    assert!(stripped_key_intersects(
+        key_expr,
+        Some(&prefix),
+        key("a/b").as_ref()
+    ));
+    assert!(!stripped_key_intersects(
+        key_expr,
+        Some(&prefix),
+        key("a/c").as_ref()
+    ));
+    assert!(!stripped_key_intersects(key_expr, Some(&prefix), None));
+
+    assert!(stripped_key_intersects(key_expr, None, key("a/b").as_ref()));
+    assert!(!stripped_key_intersects(key_expr, None, None));
+}
+
+#[test]
+fn test_scan_options_select() {
+    let keys = vec![key("c"), key("a"), None, key("b/a"), key("b")];
+
+    let (selected, cursor) = ScanOptions::default().select(keys.clone());
+    assert_eq!(
+        selected,
+        vec![None, key("a"), key("b"), key("b/a"), key("c")]
+    );
+    assert!(cursor.is_none());
+
+    let options = ScanOptions {
+        limit: Some(2),
+        cursor: None,
+    };
+    let (selected, cursor) = options.select(keys.iter());
+    assert_eq!(selected, vec![&None, &key("a")]);
+    assert_eq!(cursor, Some(ScanCursor { last_key: key("a") }));
+
+    let options = ScanOptions {
+        limit: Some(2),
+        cursor,
+    };
+    let (selected, cursor) = options.select(keys.iter());
+    assert_eq!(selected, vec![&key("b"), &key("b/a")]);
+    assert_eq!(
+        cursor,
+        Some(ScanCursor {
+            last_key: key("b/a")
+        })
+    );
+
+    let options = ScanOptions {
+        limit: Some(2),
+        cursor,
+    };
+    let (selected, cursor) = options.select(keys.iter());
+    assert_eq!(selected, vec![&key("c")]);
+    assert!(cursor.is_none());
+
+    // A limit of 0 still makes the scan progress.
+    let options = ScanOptions {
+        limit: Some(0),
+        cursor: None,
+    };
+    let (selected, cursor) = options.select(keys);
+    assert_eq!(selected, vec![None]);
+    assert_eq!(cursor, Some(ScanCursor { last_key: None }));
+}
+
+#[test]
+fn test_scan_options_select_sorted() {
+    let keys = [key("c"), key("a"), None, key("b/a"), key("b")];
+    let map = keys
+        .iter()
+        .map(|key| (ScanKey(key.clone()), ()))
+        .collect::<BTreeMap<_, _>>();
+    let scan = |options: &ScanOptions| {
+        options.select_sorted(
+            map.range((options.start(), std::ops::Bound::Unbounded))
+                .map(|(key, _)| &key.0),
+        )
+    };
+
+    let (selected, cursor) = scan(&ScanOptions::default());
+    assert_eq!(
+        selected,
+        vec![&None, &key("a"), &key("b"), &key("b/a"), &key("c")]
+    );
+    assert!(cursor.is_none());
+
+    // The pages are the same as with an unordered selection.
+    let mut options = ScanOptions {
+        limit: Some(2),
+        cursor: None,
+    };
+    loop {
+        let (selected, cursor) = scan(&options);
+        let (expected, expected_cursor) = options.select(keys.iter());
+        assert_eq!(selected, expected);
+        assert_eq!(cursor, expected_cursor);
+        match cursor {
+            Some(cursor) => options.cursor = Some(cursor),
+            None => break,
+        }
+    }
+
+    // A limit of 0 still makes the scan progress.
+    let options = ScanOptions {
+        limit: Some(0),
+        cursor: None,
+    };
+    let (selected, cursor) = scan(&options);
+    assert_eq!(selected, vec![&None]);
+    assert_eq!(cursor, Some(ScanCursor { last_key: None }));
+}
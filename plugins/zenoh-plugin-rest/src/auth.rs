@@ -0,0 +1,407 @@
+//
+// Copyright (c) 2023 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{collections::HashMap, sync::Arc};
+
+use base64::Engine;
+use http_types::Method;
+use sha3::{Digest, Sha3_256};
+use subtle::ConstantTimeEq;
+use tide::{Middleware, Next, Request, StatusCode};
+use zenoh::{
+    internal::{
+        access_control::{AclAuthorizer, AclIdentity, AclMessage, InterceptorFlow, Permission},
+        bail, zerror,
+    },
+    key_expr::{keyexpr, KeyExpr},
+    query::Reply,
+    sample::{Sample, SampleKind},
+    Result as ZResult,
+};
+
+use crate::{
+    config::AuthConfig, first_accept, path_to_key_expr, response, route_to_key_expr,
+    tls::ClientCertificate, ws, State, INFO_ROUTE, LIVELINESS_ROUTE, MATCHING_QUERYABLES_ROUTE,
+    MATCHING_SUBSCRIBERS_ROUTE, OPENAPI_ROUTE,
+};
+
+const REALM: &str = "zenoh";
+
+/// Authenticates the clients of the REST API and enforces the `access_control` rules on their
+/// requests.
+///
+/// A client is authenticated by a bearer token, by HTTP basic credentials or by the certificate it
+/// presented if mTLS is enabled. Once authenticated, an [`Authorization`] is attached to each of
+/// its requests so that the handlers can filter the samples and replies sent back to it.
+pub(crate) struct Auth {
+    /// The digests of the bearer tokens, with the usernames they authenticate.
+    tokens: Vec<([u8; 32], String)>,
+    /// Passwords indexed by usernames.
+    passwords: HashMap<String, String>,
+    authorizer: Option<Arc<AclAuthorizer>>,
+}
+
+impl Auth {
+    /// Returns `None` if the clients are neither authenticated by credentials nor by certificates.
+    pub(crate) fn new(
+        conf: Option<&AuthConfig>,
+        mtls: bool,
+        authorizer: Option<AclAuthorizer>,
+    ) -> ZResult<Option<Self>> {
+        if conf.is_none() && !mtls {
+            return Ok(None);
+        }
+        let conf = conf.cloned().unwrap_or_default();
+        let mut tokens: Vec<([u8; 32], String)> = Vec::new();
+        for (user, token) in conf.bearer_tokens {
+            if token.is_empty() {
+                bail!("Empty bearer token for user `{user}`");
+            }
+            let digest = token_digest(&token);
+            if let Some((_, other)) = tokens.iter().find(|(d, _)| *d == digest) {
+                bail!("Users `{other}` and `{user}` have the same bearer token");
+            }
+            tokens.push((digest, user));
+        }
+        let passwords = match &conf.dictionary_file {
+            Some(dictionary_file) => load_dictionary(dictionary_file)?,
+            None => HashMap::new(),
+        };
+        Ok(Some(Auth {
+            tokens,
+            passwords,
+            authorizer: authorizer.map(Arc::new),
+        }))
+    }
+
+    fn identity(&self, req: &Request<State>) -> Result<AclIdentity, &'static str> {
+        let cert_common_name = req
+            .ext::<ClientCertificate>()
+            .and_then(|certificate| certificate.common_name.clone());
+        let username = match req.header("authorization") {
+            Some(authorization) => Some(self.authenticate(authorization.last().as_str())?),
+            None => None,
+        };
+        if username.is_none() && cert_common_name.is_none() {
+            return Err("Missing credentials");
+        }
+        Ok(AclIdentity {
+            username,
+            cert_common_name,
+        })
+    }
+
+    fn authenticate(&self, authorization: &str) -> Result<String, &'static str> {
+        let (scheme, credentials) = authorization
+            .trim()
+            .split_once(' ')
+            .ok_or("Invalid authorization header")?;
+        if scheme.eq_ignore_ascii_case("bearer") {
+            // The digests of the tokens are compared in constant time, and all of them are
+            // compared, to leak neither their content nor their length through timing.
+            let digest = token_digest(credentials.trim());
+            self.tokens
+                .iter()
+                .fold(None, |user, (expected, username)| {
+                    match bool::from(expected.ct_eq(&digest)) {
+                        true => Some(username),
+                        false => user,
+                    }
+                })
+                .cloned()
+                .ok_or("Invalid bearer token")
+        } else if scheme.eq_ignore_ascii_case("basic") {
+            let credentials = base64::engine::general_purpose::STANDARD
+                .decode(credentials.trim())
+                .ok()
+                .and_then(|credentials| String::from_utf8(credentials).ok())
+                .ok_or("Invalid basic credentials")?;
+            let (user, password) = credentials
+                .split_once(':')
+                .ok_or("Invalid basic credentials")?;
+            // The passwords are compared in constant time to not leak their content through timing.
+            match self.passwords.get(user) {
+                Some(expected) if bool::from(expected.as_bytes().ct_eq(password.as_bytes())) => {
+                    Ok(user.to_string())
+                }
+                _ => Err("Invalid username or password"),
+            }
+        } else {
+            Err("Unsupported authorization scheme")
+        }
+    }
+
+    fn challenge(&self) -> String {
+        let mut schemes = Vec::new();
+        if !self.passwords.is_empty() {
+            schemes.push(format!("Basic realm=\"{REALM}\""));
+        }
+        if !self.tokens.is_empty() {
+            schemes.push(format!("Bearer realm=\"{REALM}\""));
+        }
+        schemes.join(", ")
+    }
+}
+
+#[tide::utils::async_trait]
+impl Middleware<State> for Auth {
+    async fn handle(&self, mut req: Request<State>, next: Next<'_, State>) -> tide::Result {
+        let identity = match self.identity(&req) {
+            Ok(identity) => identity,
+            Err(e) => {
+                tracing::debug!(
+                    "Unauthenticated REST request from {:?}: {e}",
+                    req.peer_addr()
+                );
+                let mut res = response(StatusCode::Unauthorized, "text/plain", e);
+                let challenge = self.challenge();
+                if !challenge.is_empty() {
+                    res.insert_header("WWW-Authenticate", challenge);
+                }
+                return Ok(res);
+            }
+        };
+        tracing::trace!("REST request authenticated as {identity}");
+
+        if let Some(authorizer) = &self.authorizer {
+            let authorization = Authorization {
+                identity,
+                authorizer: authorizer.clone(),
+            };
+            if let Some((action, key_expr)) = requested_action(&req) {
+                if !authorization.allows(InterceptorFlow::Ingress, action, &key_expr) {
+                    return Ok(response(
+                        StatusCode::Forbidden,
+                        "text/plain",
+                        &format!(
+                            "{} is unauthorized to {action:?} on {key_expr}",
+                            authorization.identity
+                        ),
+                    ));
+                }
+            }
+            req.set_ext(authorization);
+        }
+        Ok(next.run(req).await)
+    }
+}
+
+/// Returns the action that `req` performs on a key expression, to be checked against the
+/// `access_control` rules.
+///
+/// Invalid key expressions are reported by the handlers, the requests issued over WebSockets are
+/// checked by the WebSocket sessions, and the `/@info` and `/@openapi.json` routes are open to
+/// every authenticated client.
+fn requested_action(req: &Request<State>) -> Option<(AclMessage, KeyExpr<'_>)> {
+    let path = req.url().path();
+    let zid = &req.state().zid;
+    let method = req.method();
+    if path.starts_with(LIVELINESS_ROUTE) {
+        let action = match method {
+            Method::Put | Method::Delete => AclMessage::LivelinessToken,
+            _ => AclMessage::LivelinessQuery,
+        };
+        Some((action, route_to_key_expr(path, LIVELINESS_ROUTE, zid).ok()?))
+    } else if path.starts_with(MATCHING_SUBSCRIBERS_ROUTE) {
+        let key_expr = route_to_key_expr(path, MATCHING_SUBSCRIBERS_ROUTE, zid).ok()?;
+        Some((AclMessage::Put, key_expr))
+    } else if path.starts_with(MATCHING_QUERYABLES_ROUTE) {
+        let key_expr = route_to_key_expr(path, MATCHING_QUERYABLES_ROUTE, zid).ok()?;
+        Some((AclMessage::Query, key_expr))
+    } else if path == INFO_ROUTE
+        || path.starts_with(&format!("{INFO_ROUTE}/"))
+        || path == OPENAPI_ROUTE
+        || ws::is_upgrade(req)
+    {
+        None
+    } else {
+        let action = match method {
+            Method::Put | Method::Patch => AclMessage::Put,
+            Method::Delete => AclMessage::Delete,
+            Method::Get | Method::Post if first_accept(req) == "text/event-stream" => {
+                AclMessage::DeclareSubscriber
+            }
+            Method::Get | Method::Post => AclMessage::Query,
+            _ => return None,
+        };
+        Some((action, path_to_key_expr(path, zid).ok()?))
+    }
+}
+
+/// The identity of an authenticated client, with the access control rules that apply to it.
+#[derive(Clone)]
+pub(crate) struct Authorization {
+    identity: AclIdentity,
+    authorizer: Arc<AclAuthorizer>,
+}
+
+impl Authorization {
+    pub(crate) fn allows(
+        &self,
+        flow: InterceptorFlow,
+        action: AclMessage,
+        key_expr: &keyexpr,
+    ) -> bool {
+        let permission = self
+            .authorizer
+            .authorize(&self.identity, flow, action, key_expr);
+        if permission == Permission::Deny {
+            tracing::trace!(
+                "{} is unauthorized to {action:?} ({flow:?}) on {key_expr}",
+                self.identity
+            );
+        }
+        permission == Permission::Allow
+    }
+
+    /// Returns `true` if `sample` can be sent to the client.
+    pub(crate) fn allows_sample(&self, sample: &Sample) -> bool {
+        let action = match sample.kind() {
+            SampleKind::Put => AclMessage::Put,
+            SampleKind::Delete => AclMessage::Delete,
+        };
+        self.allows(InterceptorFlow::Egress, action, sample.key_expr())
+    }
+
+    /// Returns `true` if `reply` can be sent to the client.
+    pub(crate) fn allows_reply(&self, reply: &Reply) -> bool {
+        self.allows_egress_reply(AclMessage::Reply, reply)
+    }
+
+    /// Returns `true` if `reply`, to a liveliness query, can be sent to the client.
+    pub(crate) fn allows_liveliness_reply(&self, reply: &Reply) -> bool {
+        self.allows_egress_reply(AclMessage::LivelinessToken, reply)
+    }
+
+    fn allows_egress_reply(&self, action: AclMessage, reply: &Reply) -> bool {
+        match reply.result() {
+            Ok(sample) => self.allows(InterceptorFlow::Egress, action, sample.key_expr()),
+            Err(_) => true,
+        }
+    }
+}
+
+/// Loads a user-password dictionary with one `<user>:<password>` entry per line, as done by the
+/// `usrpwd` authentication of the transports.
+fn load_dictionary(path: &str) -> ZResult<HashMap<String, String>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| zerror!("Invalid user-password dictionary file: {e}"))?;
+    let mut passwords = HashMap::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let (user, password) = line
+            .split_once(':')
+            .ok_or_else(|| zerror!("Invalid user-password dictionary file: invalid format."))?;
+        let (user, password) = (user.trim(), password.trim());
+        if user.is_empty() {
+            bail!("Invalid user-password dictionary file: empty user.")
+        }
+        if password.is_empty() {
+            bail!("Invalid user-password dictionary file: empty password.")
+        }
+        passwords.insert(user.to_string(), password.to_string());
+    }
+    Ok(passwords)
+}
+
+fn token_digest(token: &str) -> [u8; 32] {
+    Sha3_256::digest(token.as_bytes()).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use base64::Engine;
+
+    use super::Auth;
+    use crate::config::AuthConfig;
+
+    fn basic(credentials: &str) -> String {
+        format!(
+            "Basic {}",
+            base64::engine::general_purpose::STANDARD.encode(credentials)
+        )
+    }
+
+    #[test]
+    fn test_authenticate() {
+        let dictionary_file = std::env::temp_dir().join("zenoh-test-rest-auth-usrpwd.txt");
+        std::fs::write(&dictionary_file, "alice:alice-pwd\n\n  bob : bob-pwd \n").unwrap();
+        let conf = AuthConfig {
+            bearer_tokens: BTreeMap::from([
+                ("carol".to_string(), "carol-token".to_string()),
+                ("dave".to_string(), "dave-token".to_string()),
+            ]),
+            dictionary_file: Some(dictionary_file.to_string_lossy().into_owned()),
+        };
+        let auth = Auth::new(Some(&conf), false, None).unwrap().unwrap();
+        std::fs::remove_file(&dictionary_file).unwrap();
+
+        assert_eq!(
+            auth.authenticate(&basic("alice:alice-pwd")).unwrap(),
+            "alice"
+        );
+        assert_eq!(auth.authenticate(&basic("bob:bob-pwd")).unwrap(), "bob");
+        assert!(auth.authenticate(&basic("alice:bob-pwd")).is_err());
+        assert!(auth.authenticate(&basic("alice:alice-pw")).is_err());
+        assert!(auth.authenticate(&basic("alice:alice-pwd ")).is_err());
+        assert!(auth.authenticate(&basic("alice:")).is_err());
+        assert!(auth.authenticate(&basic("carol:carol-token")).is_err());
+        assert!(auth.authenticate("Basic not-base64").is_err());
+
+        assert_eq!(auth.authenticate("Bearer carol-token").unwrap(), "carol");
+        assert_eq!(auth.authenticate("bearer  carol-token ").unwrap(), "carol");
+        assert_eq!(auth.authenticate("Bearer dave-token").unwrap(), "dave");
+        assert!(auth.authenticate("Bearer carol-toke").is_err());
+        assert!(auth.authenticate("Bearer carol-token2").is_err());
+        assert!(auth.authenticate("Bearer alice-pwd").is_err());
+        assert!(auth.authenticate("Digest carol-token").is_err());
+        assert!(auth.authenticate("carol-token").is_err());
+
+        assert_eq!(
+            auth.challenge(),
+            r#"Basic realm="zenoh", Bearer realm="zenoh""#
+        );
+    }
+
+    #[test]
+    fn test_no_auth() {
+        assert!(Auth::new(None, false, None).unwrap().is_none());
+        assert!(Auth::new(None, true, None).unwrap().is_some());
+        assert!(Auth::new(Some(&AuthConfig::default()), false, None)
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let conf = AuthConfig {
+            bearer_tokens: BTreeMap::from([
+                ("alice".to_string(), "token".to_string()),
+                ("bob".to_string(), "token".to_string()),
+            ]),
+            dictionary_file: None,
+        };
+        assert!(Auth::new(Some(&conf), false, None).is_err());
+
+        let conf = AuthConfig {
+            bearer_tokens: BTreeMap::new(),
+            dictionary_file: Some("/non/existent/dictionary.txt".to_string()),
+        };
+        assert!(Auth::new(Some(&conf), false, None).is_err());
+    }
+}
@@ -11,7 +11,7 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use std::fmt;
+use std::{collections::BTreeMap, fmt};
 
 use schemars::JsonSchema;
 use serde::{
@@ -33,6 +33,18 @@ pub struct Config {
     pub work_thread_num: usize,
     #[serde(default = "default_max_block_thread_num")]
     pub max_block_thread_num: usize,
+    #[serde(default)]
+    pub tls: Option<TlsConfig>,
+    #[serde(default)]
+    pub auth: Option<AuthConfig>,
+    /// Port on which the metrics of the node are served to Prometheus at `/metrics`.
+    #[serde(default, deserialize_with = "deserialize_metrics_port")]
+    pub metrics_port: Option<String>,
+    /// Origins allowed to open WebSocket connections, e.g. `https://example.com`. The upgrade
+    /// requests sent by browsers from other origins are rejected, so that other sites can't reuse
+    /// the credentials of their visitors. Every origin is allowed if unset.
+    #[serde(default)]
+    pub websocket_allowed_origins: Option<Vec<String>>,
     #[serde(default, deserialize_with = "deserialize_path")]
     __path__: Option<Vec<String>>,
     __required__: Option<bool>,
@@ -40,6 +52,37 @@ pub struct Config {
     __plugin__: Option<String>,
 }
 
+/// Serves the REST API over HTTPS.
+#[derive(JsonSchema, Deserialize, serde::Serialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TlsConfig {
+    /// Path to the PEM certificate chain of the server.
+    pub server_certificate: String,
+    /// Path to the PEM private key of the server.
+    pub server_private_key: String,
+    /// Path to the PEM certificates of the CAs trusted to verify the client certificates.
+    #[serde(default)]
+    pub root_ca_certificate: Option<String>,
+    /// Requires the clients to present a certificate signed by `root_ca_certificate`. The common
+    /// name of the certificate is then the identity of the client.
+    #[serde(default)]
+    pub enable_mtls: bool,
+}
+
+/// Authenticates the clients of the REST API. Once authenticated, the requests of a client are
+/// subject to the `access_control` rules matching its identity.
+#[derive(JsonSchema, Deserialize, serde::Serialize, Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct AuthConfig {
+    /// Static bearer tokens, indexed by the username they authenticate.
+    #[serde(default, skip_serializing)]
+    pub bearer_tokens: BTreeMap<String, String>,
+    /// Path to the user-password dictionary used for HTTP basic authentication, in the format of
+    /// `transport/auth/usrpwd/dictionary_file`: one `<user>:<password>` entry per line.
+    #[serde(default)]
+    pub dictionary_file: Option<String>,
+}
+
 impl From<&Config> for serde_json::Value {
     fn from(c: &Config) -> Self {
         serde_json::to_value(c).unwrap()
@@ -53,6 +96,13 @@ where
     deserializer.deserialize_any(HttpPortVisitor)
 }
 
+fn deserialize_metrics_port<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize_http_port(deserializer).map(Some)
+}
+
 fn default_work_thread_num() -> usize {
     DEFAULT_WORK_THREAD_NUM
 }
@@ -234,4 +284,86 @@ mod tests {
         assert_eq!(__path__, None);
         assert_eq!(__required__, None);
     }
+
+    #[test]
+    fn test_tls_and_auth_fields() {
+        let config = serde_json::from_str::<Config>(
+            r#"{
+                "http_port": 8443,
+                "tls": {
+                    "server_certificate": "server.pem",
+                    "server_private_key": "server.key",
+                    "root_ca_certificate": "ca.pem",
+                    "enable_mtls": true
+                },
+                "auth": {
+                    "bearer_tokens": { "alice": "alice-token" },
+                    "dictionary_file": "users.txt"
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let tls = config.tls.as_ref().unwrap();
+        assert_eq!(tls.server_certificate, "server.pem");
+        assert_eq!(tls.server_private_key, "server.key");
+        assert_eq!(tls.root_ca_certificate.as_deref(), Some("ca.pem"));
+        assert!(tls.enable_mtls);
+        let auth = config.auth.as_ref().unwrap();
+        assert_eq!(auth.bearer_tokens["alice"], "alice-token");
+        assert_eq!(auth.dictionary_file.as_deref(), Some("users.txt"));
+
+        // The bearer tokens must not leak through the admin space
+        let value = serde_json::Value::from(&config);
+        assert!(!value.to_string().contains("alice-token"));
+
+        assert!(serde_json::from_str::<Config>(
+            r#"{"http_port": 8443, "tls": {"server_certificate": "server.pem"}}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_metrics_port() {
+        let config = serde_json::from_str::<Config>(r#"{"http_port": 8000}"#).unwrap();
+        assert_eq!(config.metrics_port, None);
+
+        let config =
+            serde_json::from_str::<Config>(r#"{"http_port": 8000, "metrics_port": 9100}"#).unwrap();
+        assert_eq!(
+            config.metrics_port,
+            Some(format!("{DEFAULT_HTTP_INTERFACE}:9100"))
+        );
+
+        let config = serde_json::from_str::<Config>(
+            r#"{"http_port": 8000, "metrics_port": "127.0.0.1:9100"}"#,
+        )
+        .unwrap();
+        assert_eq!(config.metrics_port.as_deref(), Some("127.0.0.1:9100"));
+
+        assert!(serde_json::from_str::<Config>(
+            r#"{"http_port": 8000, "metrics_port": "localhost:metrics"}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_websocket_allowed_origins() {
+        let config = serde_json::from_str::<Config>(r#"{"http_port": 8000}"#).unwrap();
+        assert_eq!(config.websocket_allowed_origins, None);
+
+        let config = serde_json::from_str::<Config>(
+            r#"{"http_port": 8000, "websocket_allowed_origins": ["https://example.com"]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.websocket_allowed_origins,
+            Some(vec!["https://example.com".to_string()])
+        );
+
+        assert!(serde_json::from_str::<Config>(
+            r#"{"http_port": 8000, "websocket_allowed_origins": "https://example.com"}"#
+        )
+        .is_err());
+    }
 }
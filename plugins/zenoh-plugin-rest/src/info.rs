@@ -0,0 +1,154 @@
+//
+// Copyright (c) 2023 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Routes describing the session of the plugin:
+//!
+//! - `/@info`: its Zenoh ID, the Zenoh IDs of the routers and peers it is connected to and its
+//!   locators;
+//! - `/@info/transports` and `/@info/links`: its transports and their links;
+//! - `/@matching/subscribers/<key_expr>` and `/@matching/queryables/<key_expr>`: whether a
+//!   publication or a query on the key expression would currently reach a subscriber or a
+//!   queryable.
+
+use serde_json::json;
+use tide::{Request, Response, StatusCode};
+use zenoh::{key_expr::KeyExpr, session::SessionInfo, Result as ZResult};
+
+use crate::{
+    options::RequestOptions, response, route_to_key_expr, State, MATCHING_QUERYABLES_ROUTE,
+    MATCHING_SUBSCRIBERS_ROUTE,
+};
+
+fn json_response(value: serde_json::Value) -> Response {
+    response(StatusCode::Ok, "application/json", &value.to_string())
+}
+
+fn session_info(req: &Request<State>) -> SessionInfo {
+    req.state().session.info()
+}
+
+pub(crate) async fn info(req: Request<State>) -> tide::Result<Response> {
+    let info = session_info(&req);
+    let locators = info.locators().await;
+    Ok(json_response(json!({
+        "zid": info.zid().await.to_string(),
+        "routers": info.routers_zid().await.map(|zid| zid.to_string()).collect::<Vec<_>>(),
+        "peers": info.peers_zid().await.map(|zid| zid.to_string()).collect::<Vec<_>>(),
+        "locators": locators.iter().map(ToString::to_string).collect::<Vec<_>>(),
+    })))
+}
+
+pub(crate) async fn transports(req: Request<State>) -> tide::Result<Response> {
+    let transports = session_info(&req)
+        .transports()
+        .await
+        .map(|transport| {
+            json!({
+                "zid": transport.zid().to_string(),
+                "whatami": transport.whatami().to_string(),
+                "qos": transport.is_qos(),
+                "multicast": transport.is_multicast(),
+            })
+        })
+        .collect();
+    Ok(json_response(serde_json::Value::Array(transports)))
+}
+
+pub(crate) async fn links(req: Request<State>) -> tide::Result<Response> {
+    let links = session_info(&req)
+        .links()
+        .await
+        .map(|link| {
+            json!({
+                "zid": link.zid().to_string(),
+                "src": link.src().to_string(),
+                "dst": link.dst().to_string(),
+                "group": link.group().map(ToString::to_string),
+                "mtu": link.mtu(),
+                "streamed": link.is_streamed(),
+                "interfaces": link.interfaces(),
+                "auth_identifier": link.auth_identifier(),
+                "priorities": link.priorities(),
+                "reliability": link.reliability(),
+            })
+        })
+        .collect();
+    Ok(json_response(serde_json::Value::Array(links)))
+}
+
+pub(crate) async fn matching_subscribers(req: Request<State>) -> tide::Result<Response> {
+    let state = req.state();
+    let key_expr = match route_to_key_expr(req.url().path(), MATCHING_SUBSCRIBERS_ROUTE, &state.zid)
+    {
+        Ok(key_expr) => key_expr,
+        Err(e) => {
+            return Ok(response(
+                StatusCode::BadRequest,
+                "text/plain",
+                &e.to_string(),
+            ))
+        }
+    };
+    let matching = async {
+        let publisher = state.session.declare_publisher(&key_expr).await?;
+        let status = publisher.matching_status().await?;
+        publisher.undeclare().await?;
+        ZResult::Ok(status.matching())
+    };
+    Ok(matching_response(&key_expr, matching.await))
+}
+
+pub(crate) async fn matching_queryables(req: Request<State>) -> tide::Result<Response> {
+    let options = match RequestOptions::from_headers(&req) {
+        Ok(options) => options,
+        Err(e) => return Ok(response(StatusCode::BadRequest, "text/plain", &e)),
+    };
+    let state = req.state();
+    let key_expr = match route_to_key_expr(req.url().path(), MATCHING_QUERYABLES_ROUTE, &state.zid)
+    {
+        Ok(key_expr) => key_expr,
+        Err(e) => {
+            return Ok(response(
+                StatusCode::BadRequest,
+                "text/plain",
+                &e.to_string(),
+            ))
+        }
+    };
+    let matching = async {
+        let querier = state
+            .session
+            .declare_querier(&key_expr)
+            .target(options.target.unwrap_or_default())
+            .await?;
+        let status = querier.matching_status().await?;
+        querier.undeclare().await?;
+        ZResult::Ok(status.matching())
+    };
+    Ok(matching_response(&key_expr, matching.await))
+}
+
+fn matching_response(key_expr: &KeyExpr, matching: ZResult<bool>) -> Response {
+    match matching {
+        Ok(matching) => json_response(json!({
+            "key_expr": key_expr.as_str(),
+            "matching": matching,
+        })),
+        Err(e) => response(
+            StatusCode::InternalServerError,
+            "text/plain",
+            &e.to_string(),
+        ),
+    }
+}
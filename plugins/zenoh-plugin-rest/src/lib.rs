@@ -19,40 +19,53 @@
 //! [Click here for Zenoh's documentation](https://docs.rs/zenoh/latest/zenoh)
 use std::{
     borrow::Cow,
+    collections::HashMap,
     convert::TryFrom,
     future::Future,
     str::FromStr,
     sync::{
-        atomic::{AtomicUsize, Ordering},
-        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
     },
     time::Duration,
 };
 
 use base64::Engine;
-use futures::StreamExt;
+use futures::{future, StreamExt, TryStreamExt};
 use http_types::Method;
 use serde::{Deserialize, Serialize};
-use tide::{http::Mime, sse::Sender, Request, Response, Server, StatusCode};
+use tide::{http::Mime, sse::Sender, Body, Request, Response, Server, StatusCode};
 use tokio::{task::JoinHandle, time::timeout};
 use zenoh::{
     bytes::{Encoding, ZBytes},
     internal::{
+        access_control::{AclAuthorizer, AclConfig},
         bail,
         plugins::{RunningPluginTrait, ZenohPlugin},
         runtime::DynamicRuntime,
         zerror,
     },
-    key_expr::{keyexpr, KeyExpr},
-    query::{Parameters, QueryConsolidation, Reply, Selector, ZenohParameters},
+    key_expr::{keyexpr, KeyExpr, OwnedKeyExpr},
+    liveliness::LivelinessToken,
+    query::{ConsolidationMode, Parameters, QueryConsolidation, Reply, Selector, ZenohParameters},
     sample::{Sample, SampleKind},
     session::Session,
     Result as ZResult,
 };
 use zenoh_plugin_trait::{plugin_long_version, plugin_version, Plugin, PluginControl};
 
+mod auth;
 mod config;
+mod info;
+mod liveliness;
+mod metrics;
+mod openapi;
+mod options;
+mod tls;
+mod ws;
+use auth::{Auth, Authorization};
 pub use config::Config;
+use options::RequestOptions;
 use zenoh::query::ReplyError;
 
 const GIT_VERSION: &str = git_version::git_version!(prefix = "v", cargo_prefix = "v");
@@ -60,6 +73,26 @@ lazy_static::lazy_static! {
     static ref LONG_VERSION: String = format!("{} built with {}", GIT_VERSION, env!("RUSTC_VERSION"));
 }
 const RAW_KEY: &str = "_raw";
+const NDJSON: &str = "application/x-ndjson";
+/// The number of replies buffered while a response is written. Once reached, the response is
+/// aborted: the reception of the replies can't be paused until the client reads the response.
+const REPLIES_BUFFER_SIZE: usize = 4096;
+
+const INFO_ROUTE: &str = "/@info";
+const LIVELINESS_ROUTE: &str = "/@liveliness/";
+const MATCHING_SUBSCRIBERS_ROUTE: &str = "/@matching/subscribers/";
+const MATCHING_QUERYABLES_ROUTE: &str = "/@matching/queryables/";
+const OPENAPI_ROUTE: &str = "/@openapi.json";
+
+#[derive(Clone, Debug)]
+struct State {
+    session: Arc<Session>,
+    zid: String,
+    /// The liveliness tokens declared through the `/@liveliness` route.
+    liveliness_tokens: Arc<Mutex<HashMap<OwnedKeyExpr, LivelinessToken>>>,
+    /// The origins allowed to open WebSocket connections, every origin if `None`.
+    websocket_allowed_origins: Option<Arc<[String]>>,
+}
 
 lazy_static::lazy_static! {
     static ref WORKER_THREAD_NUM: AtomicUsize = AtomicUsize::new(config::DEFAULT_WORK_THREAD_NUM);
@@ -112,6 +145,18 @@ struct JSONSample {
     value: serde_json::Value,
     encoding: String,
     timestamp: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    attachment: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    source_info: Option<JSONSourceInfo>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    replier_id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JSONSourceInfo {
+    id: String,
+    sn: u32,
 }
 
 pub fn base64_encode(data: &[u8]) -> String {
@@ -153,33 +198,55 @@ fn sample_to_json(sample: &Sample) -> JSONSample {
         value: payload_to_json(sample.payload(), sample.encoding()),
         encoding: sample.encoding().to_string(),
         timestamp: sample.timestamp().map(|ts| ts.to_string()),
+        attachment: sample
+            .attachment()
+            .map(|attachment| payload_to_json(attachment, &Encoding::TEXT_PLAIN)),
+        source_info: sample.source_info().map(|source_info| JSONSourceInfo {
+            id: options::entity_id_to_string(source_info.source_id()),
+            sn: source_info.source_sn(),
+        }),
+        replier_id: None,
     }
 }
 
-fn result_to_json(sample: Result<&Sample, &ReplyError>) -> JSONSample {
-    match sample {
+fn reply_to_json(reply: &Reply) -> JSONSample {
+    let mut json = match reply.result() {
         Ok(sample) => sample_to_json(sample),
         Err(err) => JSONSample {
             key: "ERROR".into(),
             value: payload_to_json(err.payload(), err.encoding()),
             encoding: err.encoding().to_string(),
             timestamp: None,
+            attachment: None,
+            source_info: None,
+            replier_id: None,
         },
-    }
+    };
+    json.replier_id = reply
+        .replier_id()
+        .map(|id| options::entity_id_to_string(&id));
+    json
 }
 
-async fn to_json(results: flume::Receiver<Reply>) -> String {
-    let values = results
-        .stream()
-        .filter_map(move |reply| async move { Some(result_to_json(reply.result())) })
-        .collect::<Vec<JSONSample>>()
-        .await;
+fn reply_to_json_string(reply: &Reply) -> String {
+    serde_json::to_string(&reply_to_json(reply)).unwrap_or("{}".into())
+}
 
-    serde_json::to_string(&values).unwrap_or("[]".into())
+/// Streams the replies as a JSON array, written progressively as they are received.
+fn to_json_response(results: Replies) -> Response {
+    stream_response(
+        results,
+        "application/json",
+        ("[", ",", "]"),
+        reply_to_json_string,
+    )
 }
 
-async fn to_json_response(results: flume::Receiver<Reply>) -> Response {
-    response(StatusCode::Ok, "application/json", &to_json(results).await)
+/// Streams the replies as newline-delimited JSON, one reply per line.
+fn to_ndjson_response(results: Replies) -> Response {
+    stream_response(results, NDJSON, ("", "", ""), |reply| {
+        reply_to_json_string(reply) + "\n"
+    })
 }
 
 fn sample_to_html(sample: &Sample) -> String {
@@ -202,38 +269,170 @@ fn result_to_html(sample: Result<&Sample, &ReplyError>) -> String {
     }
 }
 
-async fn to_html(results: flume::Receiver<Reply>) -> String {
-    let values = results
-        .stream()
-        .filter_map(move |reply| async move { Some(result_to_html(reply.result())) })
-        .collect::<Vec<String>>()
-        .await
-        .join("\n");
-    format!("<dl>\n{values}\n</dl>\n")
+/// Streams the replies in the format asked by the `accept` header of the request.
+fn to_replies_response(accept: &str, results: Replies) -> Response {
+    match accept {
+        "text/html" => to_html_response(results),
+        NDJSON => to_ndjson_response(results),
+        _ => to_json_response(results),
+    }
 }
 
-async fn to_html_response(results: flume::Receiver<Reply>) -> Response {
-    response(StatusCode::Ok, "text/html", &to_html(results).await)
+fn to_html_response(results: Replies) -> Response {
+    stream_response(
+        results,
+        "text/html",
+        ("<dl>\n", "\n", "\n</dl>\n"),
+        |reply| result_to_html(reply.result()),
+    )
 }
 
-async fn to_raw_response(results: flume::Receiver<Reply>) -> Response {
-    match results.recv_async().await {
-        Ok(reply) => match reply.result() {
-            Ok(sample) => response(
-                StatusCode::Ok,
-                Cow::from(sample.encoding()).as_ref(),
-                &sample.payload().to_bytes(),
-            ),
-            Err(value) => response(
-                StatusCode::Ok,
-                Cow::from(value.encoding()).as_ref(),
-                &value.payload().to_bytes(),
-            ),
+/// Answers with a chunked body in which each reply is written, formatted by `format`, as soon as
+/// it is received. `(prefix, separator, suffix)` delimit the formatted replies. If replies were
+/// dropped, the body is aborted instead of being terminated, for the client not to mistake the
+/// replies it received for the complete ones.
+fn stream_response(
+    results: Replies,
+    content_type: &str,
+    (prefix, separator, suffix): (&'static str, &'static str, &'static str),
+    format: fn(&Reply) -> String,
+) -> Response {
+    let Replies {
+        receiver,
+        overflowed,
+    } = results;
+    let replies = receiver
+        .into_stream()
+        .enumerate()
+        .map(move |(i, reply)| match i {
+            0 => format(&reply),
+            _ => [separator, &format(&reply)].concat(),
+        });
+    let end = futures::stream::once(future::lazy(move |_| {
+        if overflowed.load(Ordering::Acquire) {
+            Err(std::io::Error::other("Client too slow to read the replies"))
+        } else {
+            Ok(suffix.to_string())
+        }
+    }));
+    let body = futures::stream::once(future::ready(prefix.to_string()))
+        .chain(replies)
+        .map(Ok)
+        .chain(end)
+        .map_ok(String::into_bytes)
+        .into_async_read();
+    let mut res = Response::builder(StatusCode::Ok)
+        .header("Access-Control-Allow-Origin", "*")
+        .body(Body::from_reader(body, None))
+        .build();
+    if let Ok(mime) = Mime::from_str(content_type) {
+        res.set_content_type(mime);
+    }
+    res
+}
+
+/// The replies of a query, read while its response is written.
+struct Replies {
+    receiver: flume::Receiver<Reply>,
+    /// Set once replies were dropped, the client being too slow to read them.
+    overflowed: Arc<AtomicBool>,
+}
+
+/// Returns a callback queuing the replies accepted by `filter` without blocking, and the replies
+/// to read. If the client doesn't read the replies as fast as they are received, the channel is
+/// closed and marked as overflowed to abort the response.
+fn replies_channel<F>(filter: F) -> (impl Fn(Reply) + Send + Sync, Replies)
+where
+    F: Fn(&Reply) -> bool + Send + Sync,
+{
+    let (sender, receiver) = flume::bounded(REPLIES_BUFFER_SIZE);
+    let sender = Mutex::new(Some(sender));
+    let overflowed = Arc::new(AtomicBool::new(false));
+    let callback = {
+        let overflowed = overflowed.clone();
+        move |reply: Reply| {
+            if !filter(&reply) {
+                return;
+            }
+            let mut sender = sender.lock().unwrap();
+            if let Some(Err(flume::TrySendError::Full(_))) =
+                sender.as_ref().map(|sender| sender.try_send(reply))
+            {
+                tracing::warn!("Client too slow to read the replies! Abort the response");
+                // Set before the channel is closed, to be seen once the replies are all read
+                overflowed.store(true, Ordering::Release);
+                *sender = None;
+            }
+        }
+    };
+    (
+        callback,
+        Replies {
+            receiver,
+            overflowed,
         },
+    )
+}
+
+async fn to_raw_response(results: Replies) -> Response {
+    match results.receiver.recv_async().await {
+        Ok(reply) => {
+            let mut res = match reply.result() {
+                Ok(sample) => {
+                    let mut res = response(
+                        StatusCode::Ok,
+                        Cow::from(sample.encoding()).as_ref(),
+                        &sample.payload().to_bytes(),
+                    );
+                    insert_sample_headers(&mut res, sample);
+                    res
+                }
+                Err(value) => response(
+                    StatusCode::Ok,
+                    Cow::from(value.encoding()).as_ref(),
+                    &value.payload().to_bytes(),
+                ),
+            };
+            if let Some(replier_id) = reply.replier_id() {
+                res.insert_header(
+                    options::REPLIER_ID,
+                    options::entity_id_to_string(&replier_id),
+                );
+            }
+            res
+        }
         Err(_) => response(StatusCode::Ok, "", ""),
     }
 }
 
+/// Returns the metadata of `sample` in `X-Zenoh-*` headers. The attachment is only returned if it
+/// is a printable ASCII string, as required of header values.
+fn insert_sample_headers(res: &mut Response, sample: &Sample) {
+    if let Some(timestamp) = sample.timestamp() {
+        res.insert_header(options::TIMESTAMP, timestamp.to_string());
+    }
+    if let Some(attachment) = sample.attachment() {
+        match attachment.try_to_string() {
+            Ok(attachment)
+                if attachment
+                    .bytes()
+                    .all(|b| b.is_ascii_graphic() || b == b' ') =>
+            {
+                res.insert_header(options::ATTACHMENT, attachment.as_ref())
+            }
+            _ => tracing::debug!(
+                "Attachment of {} can't be sent in a header",
+                sample.key_expr()
+            ),
+        }
+    }
+    if let Some(source_info) = sample.source_info() {
+        for (name, value) in options::source_info_headers(source_info) {
+            res.insert_header(name, value);
+        }
+    }
+}
+
 fn method_to_kind(method: Method) -> SampleKind {
     match method {
         Method::Put => SampleKind::Put,
@@ -367,10 +566,8 @@ fn with_extended_string<R, F: FnMut(&mut String) -> R>(
     result
 }
 
-async fn query(mut req: Request<(Arc<Session>, String)>) -> tide::Result<Response> {
-    tracing::trace!("Incoming GET request: {:?}", req);
-
-    let first_accept = match req.header("accept") {
+fn first_accept(req: &Request<State>) -> String {
+    match req.header("accept") {
         Some(accept) => accept[0]
             .to_string()
             .split(';')
@@ -381,12 +578,22 @@ async fn query(mut req: Request<(Arc<Session>, String)>) -> tide::Result<Respons
             .unwrap()
             .to_string(),
         None => "application/json".to_string(),
-    };
+    }
+}
+
+async fn query(mut req: Request<State>) -> tide::Result<Response> {
+    tracing::trace!("Incoming GET request: {:?}", req);
+
+    if ws::is_upgrade(&req) {
+        return ws::upgrade(req).await;
+    }
+
+    let first_accept = first_accept(&req);
     if first_accept == "text/event-stream" {
         Ok(tide::sse::upgrade(
             req,
-            move |req: Request<(Arc<Session>, String)>, sender: Sender| async move {
-                let key_expr = match path_to_key_expr(req.url().path(), &req.state().1) {
+            move |req: Request<State>, sender: Sender| async move {
+                let key_expr = match path_to_key_expr(req.url().path(), &req.state().zid) {
                     Ok(ke) => ke.into_owned(),
                     Err(e) => {
                         return Err(tide::Error::new(
@@ -395,12 +602,24 @@ async fn query(mut req: Request<(Arc<Session>, String)>) -> tide::Result<Respons
                         ))
                     }
                 };
+                let authorization = req.ext::<Authorization>().cloned();
                 spawn_runtime(async move {
                     tracing::debug!("Subscribe to {} for SSE stream", key_expr);
                     let sender = &sender;
-                    let sub = req.state().0.declare_subscriber(&key_expr).await.unwrap();
+                    let sub = req
+                        .state()
+                        .session
+                        .declare_subscriber(&key_expr)
+                        .await
+                        .unwrap();
                     loop {
                         let sample = sub.recv_async().await.unwrap();
+                        if !authorization
+                            .as_ref()
+                            .map_or(true, |authorization| authorization.allows_sample(&sample))
+                        {
+                            continue;
+                        }
                         let json_sample =
                             serde_json::to_string(&sample_to_json(&sample)).unwrap_or("{}".into());
 
@@ -434,7 +653,7 @@ async fn query(mut req: Request<(Arc<Session>, String)>) -> tide::Result<Respons
     } else {
         let body = req.body_bytes().await.unwrap_or_default();
         let url = req.url();
-        let key_expr = match path_to_key_expr(url.path(), &req.state().1) {
+        let key_expr = match path_to_key_expr(url.path(), &req.state().zid) {
             Ok(ke) => ke,
             Err(e) => {
                 return Ok(response(
@@ -444,20 +663,40 @@ async fn query(mut req: Request<(Arc<Session>, String)>) -> tide::Result<Respons
                 ))
             }
         };
+        let options = match RequestOptions::from_headers(&req) {
+            Ok(options) => options,
+            Err(e) => return Ok(response(StatusCode::BadRequest, "text/plain", &e)),
+        };
         let query_part = url.query();
         let parameters = Parameters::from(query_part.unwrap_or_default());
-        let consolidation = if parameters.time_range().is_some() {
-            QueryConsolidation::from(zenoh::query::ConsolidationMode::None)
-        } else {
-            QueryConsolidation::from(zenoh::query::ConsolidationMode::Latest)
-        };
+        let consolidation = options.consolidation.unwrap_or({
+            if parameters.time_range().is_some() {
+                ConsolidationMode::None
+            } else {
+                ConsolidationMode::Latest
+            }
+        });
         let raw = parameters.contains_key(RAW_KEY);
+        let authorization = req.ext::<Authorization>().cloned();
+        let (callback, receiver) = replies_channel(move |reply| {
+            authorization
+                .as_ref()
+                .map_or(true, |authorization| authorization.allows_reply(reply))
+        });
         let mut query = req
             .state()
-            .0
+            .session
             .get(Selector::borrowed(&key_expr, &parameters))
-            .consolidation(consolidation)
-            .with(flume::unbounded());
+            .consolidation(QueryConsolidation::from(consolidation));
+        if let Some(target) = options.target {
+            query = query.target(target);
+        }
+        if let Some(timeout) = options.timeout {
+            query = query.timeout(timeout);
+        }
+        let mut query = options
+            .apply_attachment(options.apply_qos(query))
+            .callback(callback);
         if !body.is_empty() {
             let encoding: Encoding = req
                 .content_type()
@@ -466,13 +705,11 @@ async fn query(mut req: Request<(Arc<Session>, String)>) -> tide::Result<Respons
             query = query.payload(body).encoding(encoding);
         }
         match query.await {
-            Ok(receiver) => {
+            Ok(()) => {
                 if raw {
                     Ok(to_raw_response(receiver).await)
-                } else if first_accept == "text/html" {
-                    Ok(to_html_response(receiver).await)
                 } else {
-                    Ok(to_json_response(receiver).await)
+                    Ok(to_replies_response(&first_accept, receiver))
                 }
             }
             Err(e) => Ok(response(
@@ -484,11 +721,11 @@ async fn query(mut req: Request<(Arc<Session>, String)>) -> tide::Result<Respons
     }
 }
 
-async fn write(mut req: Request<(Arc<Session>, String)>) -> tide::Result<Response> {
+async fn write(mut req: Request<State>) -> tide::Result<Response> {
     tracing::trace!("Incoming PUT request: {:?}", req);
     match req.body_bytes().await {
         Ok(bytes) => {
-            let key_expr = match path_to_key_expr(req.url().path(), &req.state().1) {
+            let key_expr = match path_to_key_expr(req.url().path(), &req.state().zid) {
                 Ok(ke) => ke,
                 Err(e) => {
                     return Ok(response(
@@ -499,16 +736,30 @@ async fn write(mut req: Request<(Arc<Session>, String)>) -> tide::Result<Respons
                 }
             };
 
+            let options = match RequestOptions::from_headers(&req) {
+                Ok(options) => options,
+                Err(e) => return Ok(response(StatusCode::BadRequest, "text/plain", &e)),
+            };
+
             let encoding: Encoding = req
                 .content_type()
                 .map(|m| Encoding::from(m.to_string()))
                 .unwrap_or_default();
 
-            // @TODO: Define the right congestion control value
-            let session = &req.state().0;
+            let session = &req.state().session;
             let res = match method_to_kind(req.method()) {
-                SampleKind::Put => session.put(&key_expr, bytes).encoding(encoding).await,
-                SampleKind::Delete => session.delete(&key_expr).await,
+                SampleKind::Put => {
+                    let put = session.put(&key_expr, bytes).encoding(encoding);
+                    options
+                        .apply_timestamp(options.apply_attachment(options.apply_qos(put)))
+                        .await
+                }
+                SampleKind::Delete => {
+                    let delete = session.delete(&key_expr);
+                    options
+                        .apply_timestamp(options.apply_attachment(options.apply_qos(delete)))
+                        .await
+                }
             };
             match res {
                 Ok(_) => Ok(Response::new(StatusCode::Ok)),
@@ -533,10 +784,31 @@ pub async fn run(runtime: DynamicRuntime, conf: Config) -> ZResult<()> {
     // But cannot be done twice in case of static link.
     zenoh::init_log_from_env_or("error");
 
+    let tls_config = conf.tls.as_ref().map(tls::server_config).transpose()?;
+    let mtls = conf.tls.as_ref().is_some_and(|tls| tls.enable_mtls);
+    let authorizer = if conf.auth.is_some() || mtls {
+        let acl_config: AclConfig = runtime.get_config().get_typed("access_control")?;
+        AclAuthorizer::new(&acl_config)?
+    } else {
+        None
+    };
+    let auth = Auth::new(conf.auth.as_ref(), mtls, authorizer)?;
+    // The scrape requests are authenticated as the other requests, but not subject to the ACL
+    let metrics_auth = match conf.metrics_port {
+        Some(_) => Auth::new(conf.auth.as_ref(), mtls, None)?,
+        None => None,
+    };
+
     let zid = runtime.zid().to_string();
     let session = zenoh::session::init(runtime).await.unwrap();
 
-    let mut app = Server::with_state((Arc::new(session), zid));
+    let state = State {
+        session: Arc::new(session),
+        zid,
+        liveliness_tokens: Arc::new(Mutex::new(HashMap::new())),
+        websocket_allowed_origins: conf.websocket_allowed_origins.clone().map(Into::into),
+    };
+    let mut app = Server::with_state(state.clone());
     app.with(
         tide::security::CorsMiddleware::new()
             .allow_methods(
@@ -547,7 +819,33 @@ pub async fn run(runtime: DynamicRuntime, conf: Config) -> ZResult<()> {
             .allow_origin(tide::security::Origin::from("*"))
             .allow_credentials(false),
     );
+    if let Some(auth) = auth {
+        app.with(auth);
+    }
 
+    app.at(INFO_ROUTE).get(info::info);
+    app.at(&format!("{INFO_ROUTE}/transports"))
+        .get(info::transports);
+    app.at(&format!("{INFO_ROUTE}/links")).get(info::links);
+    app.at(&format!("{MATCHING_SUBSCRIBERS_ROUTE}*"))
+        .get(info::matching_subscribers);
+    app.at(&format!("{MATCHING_QUERYABLES_ROUTE}*"))
+        .get(info::matching_queryables);
+    app.at(&format!("{LIVELINESS_ROUTE}*"))
+        .get(liveliness::get)
+        .put(liveliness::declare_token)
+        .delete(liveliness::undeclare_token);
+    let openapi = Arc::new(openapi::document(&conf, GIT_VERSION).to_string());
+    app.at(OPENAPI_ROUTE).get(move |_| {
+        let openapi = openapi.clone();
+        async move {
+            Ok(response(
+                StatusCode::Ok,
+                "application/json",
+                openapi.as_str(),
+            ))
+        }
+    });
     app.at("/")
         .get(query)
         .post(query)
@@ -561,13 +859,48 @@ pub async fn run(runtime: DynamicRuntime, conf: Config) -> ZResult<()> {
         .patch(write)
         .delete(write);
 
-    if let Err(e) = app.listen(conf.http_port).await {
+    let metrics_server = conf.metrics_port.as_ref().map(|metrics_port| {
+        let mut metrics_app = Server::with_state(state);
+        if let Some(auth) = metrics_auth {
+            metrics_app.with(auth);
+        }
+        metrics_app.at(metrics::METRICS_ROUTE).get(metrics::metrics);
+        listen(metrics_app, metrics_port, tls_config.clone())
+    });
+    let res = match metrics_server {
+        Some(metrics_server) => {
+            future::try_join(listen(app, &conf.http_port, tls_config), metrics_server)
+                .await
+                .map(|_| ())
+        }
+        None => listen(app, &conf.http_port, tls_config).await,
+    };
+    if let Err(e) = res {
         tracing::error!("Unable to start http server for REST: {:?}", e);
-        return Err(e.into());
+        return Err(e);
     }
     Ok(())
 }
 
+async fn listen(
+    app: Server<State>,
+    addr: &str,
+    tls_config: Option<Arc<rustls::ServerConfig>>,
+) -> ZResult<()> {
+    match tls_config {
+        Some(tls_config) => tls::listen(app, addr, tls_config).await,
+        None => app.listen(addr).await.map_err(Into::into),
+    }
+}
+
+/// Returns the key expression following `route` in `path`.
+fn route_to_key_expr<'a>(path: &'a str, route: &str, zid: &str) -> ZResult<KeyExpr<'a>> {
+    match path.strip_prefix(route) {
+        Some(path) => path_to_key_expr(path, zid),
+        None => bail!("{path} is not a path of the {route} route"),
+    }
+}
+
 fn path_to_key_expr<'a>(path: &'a str, zid: &str) -> ZResult<KeyExpr<'a>> {
     let path = path.strip_prefix('/').unwrap_or(path);
     if path == "@/local" {
@@ -578,3 +911,64 @@ fn path_to_key_expr<'a>(path: &'a str, zid: &str) -> ZResult<KeyExpr<'a>> {
         KeyExpr::try_from(path)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{atomic::AtomicBool, Arc};
+
+    use zenoh::query::Reply;
+
+    use super::{to_html_response, to_json_response, to_ndjson_response, Replies};
+
+    fn replies_with(count: usize, overflowed: bool) -> Replies {
+        let (sender, receiver) = flume::unbounded();
+        for _ in 0..count {
+            sender.send(Reply::empty()).unwrap();
+        }
+        Replies {
+            receiver,
+            overflowed: Arc::new(AtomicBool::new(overflowed)),
+        }
+    }
+
+    fn replies(count: usize) -> Replies {
+        replies_with(count, false)
+    }
+
+    fn try_body(mut res: tide::Response) -> tide::Result<String> {
+        futures::executor::block_on(res.take_body().into_string())
+    }
+
+    fn body(res: tide::Response) -> String {
+        try_body(res).unwrap()
+    }
+
+    #[test]
+    fn test_streamed_responses() {
+        let reply = r#"{"key":"dummy","value":null,"encoding":"zenoh/bytes","timestamp":null}"#;
+
+        assert_eq!(body(to_json_response(replies(0))), "[]");
+        assert_eq!(
+            body(to_json_response(replies(2))),
+            format!("[{reply},{reply}]")
+        );
+
+        assert_eq!(body(to_ndjson_response(replies(0))), "");
+        assert_eq!(
+            body(to_ndjson_response(replies(2))),
+            format!("{reply}\n{reply}\n")
+        );
+
+        let res = to_html_response(replies(1));
+        assert_eq!(res.content_type().unwrap().essence(), "text/html");
+        assert!(res.len().is_none());
+        assert_eq!(body(res), "<dl>\n<dt>dummy</dt>\n<dd></dd>\n\n</dl>\n");
+    }
+
+    #[test]
+    fn test_overflowed_responses() {
+        assert!(try_body(to_json_response(replies_with(2, true))).is_err());
+        assert!(try_body(to_ndjson_response(replies_with(2, true))).is_err());
+        assert!(try_body(to_html_response(replies_with(0, true))).is_err());
+    }
+}
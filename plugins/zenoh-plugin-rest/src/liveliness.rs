@@ -0,0 +1,148 @@
+//
+// Copyright (c) 2023 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! The `/@liveliness/<key_expr>` route: `GET` queries the liveliness tokens matching the key
+//! expression, `PUT` declares a liveliness token held by the plugin and `DELETE` undeclares it.
+
+use tide::{Request, Response, StatusCode};
+use zenoh::{
+    key_expr::{KeyExpr, OwnedKeyExpr},
+    Result as ZResult,
+};
+
+use crate::{
+    first_accept, options::RequestOptions, replies_channel, response, route_to_key_expr,
+    to_replies_response, Authorization, State, LIVELINESS_ROUTE,
+};
+
+fn key_expr(req: &Request<State>) -> ZResult<KeyExpr<'_>> {
+    route_to_key_expr(req.url().path(), LIVELINESS_ROUTE, &req.state().zid)
+}
+
+pub(crate) async fn get(req: Request<State>) -> tide::Result<Response> {
+    tracing::trace!("Incoming liveliness GET request: {:?}", req);
+    let key_expr = match key_expr(&req) {
+        Ok(key_expr) => key_expr,
+        Err(e) => {
+            return Ok(response(
+                StatusCode::BadRequest,
+                "text/plain",
+                &e.to_string(),
+            ))
+        }
+    };
+    let options = match RequestOptions::from_headers(&req) {
+        Ok(options) => options,
+        Err(e) => return Ok(response(StatusCode::BadRequest, "text/plain", &e)),
+    };
+    let authorization = req.ext::<Authorization>().cloned();
+    let (callback, receiver) = replies_channel(move |reply| {
+        authorization.as_ref().map_or(true, |authorization| {
+            authorization.allows_liveliness_reply(reply)
+        })
+    });
+    let mut query = req.state().session.liveliness().get(&key_expr);
+    if let Some(timeout) = options.timeout {
+        query = query.timeout(timeout);
+    }
+    let res = query.callback(callback).await;
+    match res {
+        Ok(()) => Ok(to_replies_response(&first_accept(&req), receiver)),
+        Err(e) => Ok(response(
+            StatusCode::InternalServerError,
+            "text/plain",
+            &e.to_string(),
+        )),
+    }
+}
+
+pub(crate) async fn declare_token(req: Request<State>) -> tide::Result<Response> {
+    tracing::trace!("Incoming liveliness PUT request: {:?}", req);
+    let key_expr = match key_expr(&req) {
+        Ok(key_expr) => OwnedKeyExpr::from(key_expr),
+        Err(e) => {
+            return Ok(response(
+                StatusCode::BadRequest,
+                "text/plain",
+                &e.to_string(),
+            ))
+        }
+    };
+    let state = req.state();
+    if state
+        .liveliness_tokens
+        .lock()
+        .unwrap()
+        .contains_key(&key_expr)
+    {
+        return Ok(Response::new(StatusCode::Ok));
+    }
+    match state.session.liveliness().declare_token(&key_expr).await {
+        Ok(token) => {
+            tracing::debug!("Declared liveliness token {key_expr}");
+            // A concurrent request might have declared the same token meanwhile: keeping one of
+            // them is enough.
+            state
+                .liveliness_tokens
+                .lock()
+                .unwrap()
+                .entry(key_expr)
+                .or_insert(token);
+            Ok(Response::new(StatusCode::Ok))
+        }
+        Err(e) => Ok(response(
+            StatusCode::InternalServerError,
+            "text/plain",
+            &e.to_string(),
+        )),
+    }
+}
+
+pub(crate) async fn undeclare_token(req: Request<State>) -> tide::Result<Response> {
+    tracing::trace!("Incoming liveliness DELETE request: {:?}", req);
+    let key_expr = match key_expr(&req) {
+        Ok(key_expr) => OwnedKeyExpr::from(key_expr),
+        Err(e) => {
+            return Ok(response(
+                StatusCode::BadRequest,
+                "text/plain",
+                &e.to_string(),
+            ))
+        }
+    };
+    let token = req
+        .state()
+        .liveliness_tokens
+        .lock()
+        .unwrap()
+        .remove(&key_expr);
+    let Some(token) = token else {
+        return Ok(response(
+            StatusCode::NotFound,
+            "text/plain",
+            &format!("No liveliness token declared on {key_expr}"),
+        ));
+    };
+    match token.undeclare().await {
+        Ok(()) => {
+            tracing::debug!("Undeclared liveliness token {key_expr}");
+            Ok(Response::new(StatusCode::Ok))
+        }
+        Err(e) => Ok(response(
+            StatusCode::InternalServerError,
+            "text/plain",
+            &e.to_string(),
+        )),
+    }
+}
@@ -0,0 +1,228 @@
+//
+// Copyright (c) 2023 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Prometheus scrape endpoint, served at `/metrics` on the `metrics_port` of the plugin.
+//!
+//! The metrics are those published in the admin space at `@/<zid>/<whatami>/metrics`. The query
+//! parameters of the scrape request (`per_transport`, `per_link`, `per_key`, `disconnected`,
+//! `descriptors`) are forwarded to the admin space, and `aggregate=true` adds the metrics of all
+//! the routers reachable in the admin space to those of the local node.
+
+use std::collections::HashSet;
+
+use tide::{Request, Response, StatusCode};
+use zenoh::{
+    key_expr::{keyexpr, KeyExpr},
+    query::{ConsolidationMode, Parameters, QueryTarget, Selector},
+};
+
+use crate::{response, State};
+
+pub(crate) const METRICS_ROUTE: &str = "/metrics";
+const METRICS_ENCODING: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+const AGGREGATE_KEY: &str = "aggregate";
+const EOF: &str = "# EOF";
+
+pub(crate) async fn metrics(req: Request<State>) -> tide::Result<Response> {
+    // Unlike the selectors of the other routes, the query of a scrape request follows the URL
+    // syntax, with `&`-separated parameters
+    let mut parameters = Parameters::empty();
+    let mut aggregate = false;
+    for (key, value) in req.url().query_pairs() {
+        match key.as_ref() {
+            AGGREGATE_KEY => aggregate = value == "true",
+            _ => {
+                parameters.insert(key, value);
+            }
+        }
+    }
+    parameters.insert("compression", "false");
+
+    let state = req.state();
+    let mut key_exprs = vec![KeyExpr::try_from(format!("@/{}/*/metrics", state.zid)).unwrap()];
+    if aggregate {
+        key_exprs.push(KeyExpr::from(keyexpr::new("@/*/router/metrics").unwrap()));
+    }
+    let mut replied = HashSet::new();
+    let mut documents = Vec::new();
+    for key_expr in key_exprs {
+        let replies = match state
+            .session
+            .get(Selector::borrowed(&key_expr, &parameters))
+            .consolidation(ConsolidationMode::None)
+            .target(QueryTarget::All)
+            .await
+        {
+            Ok(replies) => replies,
+            Err(e) => {
+                return Ok(response(
+                    StatusCode::InternalServerError,
+                    "text/plain",
+                    &e.to_string(),
+                ))
+            }
+        };
+        while let Ok(reply) = replies.recv_async().await {
+            match reply.result() {
+                Ok(sample) if replied.insert(sample.key_expr().as_str().to_string()) => {
+                    match sample.payload().try_to_string() {
+                        Ok(document) => documents.push(document.into_owned()),
+                        Err(e) => tracing::warn!("Invalid metrics from {}: {e}", sample.key_expr()),
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => tracing::warn!(
+                    "Error reply to metrics query on {key_expr}: {}",
+                    e.payload().try_to_string().unwrap_or_default()
+                ),
+            }
+        }
+    }
+    Ok(response(
+        StatusCode::Ok,
+        METRICS_ENCODING,
+        &merge_metrics(&documents),
+    ))
+}
+
+/// Merges OpenMetrics documents in a single one, in which the samples of a metric family are
+/// grouped after its descriptors, as required by the format. The descriptors of a family are those
+/// of the first document declaring it.
+fn merge_metrics(documents: &[String]) -> String {
+    let mut families: Vec<MetricFamily> = Vec::new();
+    for document in documents {
+        let mut current: Option<&str> = None;
+        for line in document.lines() {
+            if line.trim().is_empty() || line.starts_with(EOF) {
+                continue;
+            }
+            if let Some(descriptor) = line.strip_prefix("# ") {
+                let mut tokens = descriptor.split(' ');
+                let (Some(kind), Some(name)) = (tokens.next(), tokens.next()) else {
+                    continue;
+                };
+                let family = family_mut(&mut families, name);
+                if !family
+                    .descriptors
+                    .iter()
+                    .any(|d| d.split(' ').nth(1) == Some(kind))
+                {
+                    family.descriptors.push(line);
+                }
+                current = Some(name);
+            } else {
+                let metric = line.split(['{', ' ']).next().unwrap_or_default();
+                // Without descriptors, each metric is a family of its own
+                let name = match current {
+                    Some(name) if metric.starts_with(name) => name,
+                    _ => metric,
+                };
+                family_mut(&mut families, name).samples.push(line);
+                current = Some(name);
+            }
+        }
+    }
+    let mut merged = String::new();
+    for family in families {
+        for line in family.descriptors.into_iter().chain(family.samples) {
+            merged.push_str(line);
+            merged.push('\n');
+        }
+    }
+    merged.push_str(EOF);
+    merged.push('\n');
+    merged
+}
+
+struct MetricFamily<'a> {
+    name: &'a str,
+    descriptors: Vec<&'a str>,
+    samples: Vec<&'a str>,
+}
+
+fn family_mut<'a, 'f>(
+    families: &'f mut Vec<MetricFamily<'a>>,
+    name: &'a str,
+) -> &'f mut MetricFamily<'a> {
+    let index = match families.iter().position(|family| family.name == name) {
+        Some(index) => index,
+        None => {
+            families.push(MetricFamily {
+                name,
+                descriptors: Vec::new(),
+                samples: Vec::new(),
+            });
+            families.len() - 1
+        }
+    };
+    &mut families[index]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::merge_metrics;
+
+    #[test]
+    fn test_merge_metrics() {
+        let router1 = "\
+# HELP zenoh_bytes Bytes.
+# TYPE zenoh_bytes counter
+zenoh_bytes_total{local_id=\"r1\"} 10
+# HELP zenoh_build Zenoh build version.
+# TYPE zenoh_build info
+zenoh_build_info{local_id=\"r1\"} 1
+# EOF
+";
+        let router2 = "\
+# HELP zenoh_build Zenoh build version.
+# TYPE zenoh_build info
+zenoh_build_info{local_id=\"r2\"} 1
+# HELP zenoh_bytes Bytes.
+# TYPE zenoh_bytes counter
+zenoh_bytes_total{local_id=\"r2\"} 20
+# HELP zenoh_links Links.
+# TYPE zenoh_links gauge
+zenoh_links{local_id=\"r2\"} 2
+# EOF
+";
+        assert_eq!(
+            merge_metrics(&[router1.to_string(), router2.to_string()]),
+            "\
+# HELP zenoh_bytes Bytes.
+# TYPE zenoh_bytes counter
+zenoh_bytes_total{local_id=\"r1\"} 10
+zenoh_bytes_total{local_id=\"r2\"} 20
+# HELP zenoh_build Zenoh build version.
+# TYPE zenoh_build info
+zenoh_build_info{local_id=\"r1\"} 1
+zenoh_build_info{local_id=\"r2\"} 1
+# HELP zenoh_links Links.
+# TYPE zenoh_links gauge
+zenoh_links{local_id=\"r2\"} 2
+# EOF
+"
+        );
+
+        // Without descriptors
+        assert_eq!(
+            merge_metrics(&[
+                "zenoh_a{local_id=\"r1\"} 1\nzenoh_b 2\n \n# EOF\n".to_string(),
+                "zenoh_b 3\nzenoh_a{local_id=\"r2\"} 4\n# EOF\n".to_string(),
+            ]),
+            "zenoh_a{local_id=\"r1\"} 1\nzenoh_a{local_id=\"r2\"} 4\nzenoh_b 2\nzenoh_b 3\n# EOF\n"
+        );
+
+        assert_eq!(merge_metrics(&[]), "# EOF\n");
+    }
+}
@@ -0,0 +1,365 @@
+//
+// Copyright (c) 2023 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! OpenAPI description of the REST API, served at `/@openapi.json`.
+
+use serde_json::{json, Map, Value};
+
+use crate::{
+    options, Config, INFO_ROUTE, LIVELINESS_ROUTE, MATCHING_QUERYABLES_ROUTE,
+    MATCHING_SUBSCRIBERS_ROUTE, NDJSON, OPENAPI_ROUTE,
+};
+
+const KEY_EXPR_PARAMETER: &str = "{key_expr}";
+
+/// Generates the OpenAPI document describing the REST API served with `conf`.
+pub(crate) fn document(conf: &Config, version: &str) -> Value {
+    let mut components = json!({
+        "schemas": schemas(),
+        "parameters": parameters(),
+    });
+    let mut security = Vec::new();
+    if conf.auth.is_some() {
+        components["securitySchemes"] = json!({
+            "basic": {"type": "http", "scheme": "basic"},
+            "bearer": {"type": "http", "scheme": "bearer"},
+        });
+        security = vec![json!({"basic": []}), json!({"bearer": []})];
+    }
+    if conf.tls.as_ref().is_some_and(|tls| tls.enable_mtls) {
+        components["securitySchemes"]["mutualTLS"] = json!({"type": "mutualTLS"});
+        security.push(json!({"mutualTLS": []}));
+    }
+
+    let mut document = json!({
+        "openapi": "3.1.0",
+        "info": {
+            "title": "Zenoh REST API",
+            "description": "Access to the Zenoh key space and session exposed by the zenoh REST plugin.",
+            "version": version,
+        },
+        "paths": paths(),
+        "components": components,
+    });
+    if !security.is_empty() {
+        document["security"] = Value::Array(security);
+    }
+    document
+}
+
+fn paths() -> Value {
+    let mut paths = Map::new();
+    paths.insert(
+        format!("/{KEY_EXPR_PARAMETER}"),
+        json!({
+            "parameters": [{"$ref": "#/components/parameters/key_expr"}],
+            "get": with_event_stream(query_operation("Query the key expression", &["query_target", "consolidation", "timeout"])),
+            "post": with_event_stream(query_operation("Query the key expression with the request body as payload", &["query_target", "consolidation", "timeout"])),
+            "put": write_operation("Publish the request body on the key expression"),
+            "patch": write_operation("Publish the request body on the key expression"),
+            "delete": write_operation("Publish a deletion on the key expression"),
+        }),
+    );
+    paths.insert(
+        format!("{LIVELINESS_ROUTE}{KEY_EXPR_PARAMETER}"),
+        json!({
+            "parameters": [{"$ref": "#/components/parameters/key_expr"}],
+            "get": query_operation("Query the liveliness tokens matching the key expression", &["timeout"]),
+            "put": {
+                "summary": "Declare a liveliness token on the key expression, held until it is deleted",
+                "responses": {"200": {"description": "The token is declared"}},
+            },
+            "delete": {
+                "summary": "Undeclare the liveliness token declared on the key expression",
+                "responses": {
+                    "200": {"description": "The token is undeclared"},
+                    "404": {"description": "No token is declared on the key expression"},
+                },
+            },
+        }),
+    );
+    paths.insert(
+        INFO_ROUTE.to_string(),
+        get_json_operation(
+            "Zenoh ID, connected routers and peers, and locators of the session",
+            "#/components/schemas/Info",
+        ),
+    );
+    paths.insert(
+        format!("{INFO_ROUTE}/transports"),
+        get_json_array_operation(
+            "Transports of the session",
+            "#/components/schemas/Transport",
+        ),
+    );
+    paths.insert(
+        format!("{INFO_ROUTE}/links"),
+        get_json_array_operation(
+            "Links of the transports of the session",
+            "#/components/schemas/Link",
+        ),
+    );
+    paths.insert(
+        format!("{MATCHING_SUBSCRIBERS_ROUTE}{KEY_EXPR_PARAMETER}"),
+        matching_operation(
+            "Whether a publication on the key expression would reach a subscriber",
+            false,
+        ),
+    );
+    paths.insert(
+        format!("{MATCHING_QUERYABLES_ROUTE}{KEY_EXPR_PARAMETER}"),
+        matching_operation(
+            "Whether a query on the key expression would reach a queryable",
+            true,
+        ),
+    );
+    paths.insert(
+        OPENAPI_ROUTE.to_string(),
+        json!({
+            "get": {
+                "summary": "This document",
+                "responses": {"200": {"description": "The OpenAPI document", "content": {"application/json": {}}}},
+            },
+        }),
+    );
+    Value::Object(paths)
+}
+
+fn header_parameter_refs(names: &[&str]) -> Vec<Value> {
+    names
+        .iter()
+        .map(|name| json!({"$ref": format!("#/components/parameters/{name}")}))
+        .collect()
+}
+
+fn query_operation(summary: &str, options: &[&str]) -> Value {
+    let mut parameters =
+        header_parameter_refs(&["congestion_control", "priority", "express", "attachment"]);
+    parameters.extend(header_parameter_refs(options));
+    let samples = json!({"type": "array", "items": {"$ref": "#/components/schemas/Sample"}});
+    json!({
+        "summary": summary,
+        "parameters": parameters,
+        "responses": {
+            "200": {
+                "description": "The replies, streamed as they are received",
+                "content": {
+                    "application/json": {"schema": samples},
+                    NDJSON: {"schema": {"$ref": "#/components/schemas/Sample"}},
+                    "text/html": {},
+                },
+            },
+            "400": {"description": "Invalid key expression or option"},
+        },
+    })
+}
+
+/// Adds the subscription to the key expression, when the client accepts `text/event-stream`.
+fn with_event_stream(mut operation: Value) -> Value {
+    operation["responses"]["200"]["content"]["text/event-stream"] = json!({
+        "schema": {
+            "type": "string",
+            "description": "Subscription to the key expression: the received samples are sent as events",
+        },
+    });
+    operation
+}
+
+fn write_operation(summary: &str) -> Value {
+    json!({
+        "summary": summary,
+        "parameters": header_parameter_refs(&["congestion_control", "priority", "express", "attachment", "timestamp"]),
+        "requestBody": {"content": {"*/*": {}}},
+        "responses": {
+            "200": {"description": "The sample is published"},
+            "400": {"description": "Invalid key expression or option"},
+        },
+    })
+}
+
+fn get_json_operation(summary: &str, schema: &str) -> Value {
+    json!({
+        "get": {
+            "summary": summary,
+            "responses": {"200": {"description": summary, "content": {"application/json": {"schema": {"$ref": schema}}}}},
+        },
+    })
+}
+
+fn get_json_array_operation(summary: &str, schema: &str) -> Value {
+    json!({
+        "get": {
+            "summary": summary,
+            "responses": {
+                "200": {
+                    "description": summary,
+                    "content": {"application/json": {"schema": {"type": "array", "items": {"$ref": schema}}}},
+                },
+            },
+        },
+    })
+}
+
+fn matching_operation(summary: &str, query_target: bool) -> Value {
+    let mut parameters = vec![json!({"$ref": "#/components/parameters/key_expr"})];
+    if query_target {
+        parameters.extend(header_parameter_refs(&["query_target"]));
+    }
+    json!({
+        "parameters": parameters,
+        "get": {
+            "summary": summary,
+            "responses": {
+                "200": {"description": summary, "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Matching"}}}},
+                "400": {"description": "Invalid key expression"},
+            },
+        },
+    })
+}
+
+fn parameters() -> Value {
+    let header = |name: &str, description: &str, schema: Value| json!({"name": name, "in": "header", "description": description, "schema": schema});
+    json!({
+        "key_expr": {
+            "name": "key_expr",
+            "in": "path",
+            "required": true,
+            "description": "A key expression, possibly containing `/`. `@/local` stands for `@/<zid>`, `<zid>` being the Zenoh ID of the session of the plugin.",
+            "schema": {"type": "string"},
+        },
+        "congestion_control": header(options::CONGESTION_CONTROL, "Congestion control of the message", string_enum(&["drop", "block"])),
+        "priority": header(
+            options::PRIORITY,
+            "Priority of the message, from 1 (real-time) to 7 (background)",
+            string_enum(&["1", "2", "3", "4", "5", "6", "7", "real-time", "interactive-high", "interactive-low", "data-high", "data", "data-low", "background"]),
+        ),
+        "express": header(options::EXPRESS, "Whether the message is sent without batching", json!({"type": "boolean"})),
+        "attachment": header(options::ATTACHMENT, "Attachment of the message", json!({"type": "string"})),
+        "timestamp": header(options::TIMESTAMP, "Timestamp of the sample, as `<NTP64 time>/<id>`", json!({"type": "string"})),
+        "query_target": header(options::QUERY_TARGET, "Queryables targeted by the query", string_enum(&["best_matching", "all", "all_complete"])),
+        "consolidation": header(options::CONSOLIDATION, "Consolidation of the replies", string_enum(&["auto", "none", "monotonic", "latest"])),
+        "timeout": header(options::TIMEOUT, "Timeout of the query, in milliseconds", json!({"type": "integer", "minimum": 0})),
+    })
+}
+
+fn schemas() -> Value {
+    let string = json!({"type": "string"});
+    let optional_string = json!({"type": ["string", "null"]});
+    json!({
+        "Sample": {
+            "type": "object",
+            "required": ["key", "value", "encoding", "timestamp"],
+            "properties": {
+                "key": string,
+                "value": {"description": "The payload: JSON if the encoding is JSON, a string if it is text, base64 otherwise"},
+                "encoding": string,
+                "timestamp": optional_string,
+                "attachment": string,
+                "source_info": {
+                    "type": "object",
+                    "properties": {"id": string, "sn": {"type": "integer"}},
+                },
+                "replier_id": string,
+            },
+        },
+        "Info": {
+            "type": "object",
+            "properties": {
+                "zid": string,
+                "routers": {"type": "array", "items": string},
+                "peers": {"type": "array", "items": string},
+                "locators": {"type": "array", "items": string},
+            },
+        },
+        "Transport": {
+            "type": "object",
+            "properties": {
+                "zid": string,
+                "whatami": string_enum(&["router", "peer", "client"]),
+                "qos": {"type": "boolean"},
+                "multicast": {"type": "boolean"},
+            },
+        },
+        "Link": {
+            "type": "object",
+            "properties": {
+                "zid": string,
+                "src": string,
+                "dst": string,
+                "group": optional_string,
+                "mtu": {"type": "integer"},
+                "streamed": {"type": "boolean"},
+                "interfaces": {"type": "array", "items": string},
+                "auth_identifier": optional_string,
+                "priorities": {"type": ["array", "null"], "items": {"type": "integer"}},
+                "reliability": {"type": ["string", "null"], "enum": ["BestEffort", "Reliable", null]},
+            },
+        },
+        "Matching": {
+            "type": "object",
+            "properties": {
+                "key_expr": string,
+                "matching": {"type": "boolean"},
+            },
+        },
+    })
+}
+
+fn string_enum(values: &[&str]) -> Value {
+    json!({"type": "string", "enum": values})
+}
+
+#[cfg(test)]
+mod tests {
+    use super::document;
+    use crate::Config;
+
+    fn config(json: serde_json::Value) -> Config {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_document() {
+        let doc = document(&config(serde_json::json!({"http_port": 8000})), "v1.0.0");
+        assert_eq!(doc["info"]["version"], "v1.0.0");
+        for path in [
+            "/{key_expr}",
+            "/@liveliness/{key_expr}",
+            "/@info",
+            "/@info/transports",
+            "/@info/links",
+            "/@matching/subscribers/{key_expr}",
+            "/@matching/queryables/{key_expr}",
+            "/@openapi.json",
+        ] {
+            assert!(doc["paths"][path].is_object(), "{path}");
+        }
+        assert!(doc.get("security").is_none());
+
+        // Every referenced component is defined
+        let text = doc.to_string();
+        for reference in text.split("\"$ref\":\"#/components/").skip(1) {
+            let reference = reference.split('"').next().unwrap();
+            let (kind, name) = reference.split_once('/').unwrap();
+            assert!(doc["components"][kind][name].is_object(), "{reference}");
+        }
+
+        let doc = document(
+            &config(serde_json::json!({"http_port": 8000, "auth": {}})),
+            "v1.0.0",
+        );
+        assert_eq!(doc["security"].as_array().unwrap().len(), 2);
+        assert!(doc["components"]["securitySchemes"]["bearer"].is_object());
+    }
+}
@@ -0,0 +1,375 @@
+//
+// Copyright (c) 2023 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Options of the `PUT`, `PATCH`, `DELETE`, `GET` and `POST` requests, set with `X-Zenoh-*` headers,
+//! and metadata of the replies returned with the same headers.
+
+use std::{str::FromStr, time::Duration};
+
+use http_types::headers::Headers;
+use zenoh::{
+    internal::traits::{QoSBuilderTrait, SampleBuilderTrait, TimestampBuilderTrait},
+    qos::{CongestionControl, Priority},
+    query::{ConsolidationMode, QueryTarget},
+    sample::SourceInfo,
+    session::EntityGlobalId,
+    time::Timestamp,
+};
+
+pub(crate) const CONGESTION_CONTROL: &str = "x-zenoh-congestion-control";
+pub(crate) const PRIORITY: &str = "x-zenoh-priority";
+pub(crate) const EXPRESS: &str = "x-zenoh-express";
+pub(crate) const ATTACHMENT: &str = "x-zenoh-attachment";
+pub(crate) const TIMESTAMP: &str = "x-zenoh-timestamp";
+pub(crate) const QUERY_TARGET: &str = "x-zenoh-query-target";
+pub(crate) const CONSOLIDATION: &str = "x-zenoh-consolidation";
+pub(crate) const TIMEOUT: &str = "x-zenoh-timeout";
+pub(crate) const REPLIER_ID: &str = "x-zenoh-replier-id";
+pub(crate) const SOURCE_ID: &str = "x-zenoh-source-id";
+pub(crate) const SOURCE_SN: &str = "x-zenoh-source-sn";
+
+/// The options of a request, as set by its `X-Zenoh-*` headers. The options that don't apply to
+/// the operation of the request are ignored.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct RequestOptions {
+    pub(crate) congestion_control: Option<CongestionControl>,
+    pub(crate) priority: Option<Priority>,
+    pub(crate) express: Option<bool>,
+    pub(crate) attachment: Option<String>,
+    pub(crate) timestamp: Option<Timestamp>,
+    pub(crate) target: Option<QueryTarget>,
+    pub(crate) consolidation: Option<ConsolidationMode>,
+    pub(crate) timeout: Option<Duration>,
+}
+
+impl RequestOptions {
+    pub(crate) fn from_headers(headers: impl AsRef<Headers>) -> Result<Self, String> {
+        let headers = headers.as_ref();
+        Ok(RequestOptions {
+            congestion_control: parse_header(
+                headers,
+                CONGESTION_CONTROL,
+                parse_congestion_control,
+            )?,
+            priority: parse_header(headers, PRIORITY, parse_priority)?,
+            express: parse_header(headers, EXPRESS, |s| bool::from_str(s).ok())?,
+            attachment: header(headers, ATTACHMENT),
+            timestamp: parse_header(headers, TIMESTAMP, |s| Timestamp::from_str(s).ok())?,
+            target: parse_header(headers, QUERY_TARGET, parse_query_target)?,
+            consolidation: parse_header(headers, CONSOLIDATION, parse_consolidation)?,
+            timeout: parse_header(headers, TIMEOUT, |s| {
+                u64::from_str(s).ok().map(Duration::from_millis)
+            })?,
+        })
+    }
+
+    pub(crate) fn apply_qos<B: QoSBuilderTrait>(&self, mut builder: B) -> B {
+        if let Some(congestion_control) = self.congestion_control {
+            builder = builder.congestion_control(congestion_control);
+        }
+        if let Some(priority) = self.priority {
+            builder = builder.priority(priority);
+        }
+        if let Some(express) = self.express {
+            builder = builder.express(express);
+        }
+        builder
+    }
+
+    pub(crate) fn apply_attachment<B: SampleBuilderTrait>(&self, builder: B) -> B {
+        match &self.attachment {
+            Some(attachment) => builder.attachment(attachment.clone()),
+            None => builder,
+        }
+    }
+
+    pub(crate) fn apply_timestamp<B: TimestampBuilderTrait>(&self, builder: B) -> B {
+        match self.timestamp {
+            Some(timestamp) => builder.timestamp(timestamp),
+            None => builder,
+        }
+    }
+}
+
+fn header(headers: &Headers, name: &str) -> Option<String> {
+    headers.get(name).map(|values| values.last().to_string())
+}
+
+fn parse_header<T>(
+    headers: &Headers,
+    name: &str,
+    parse: impl Fn(&str) -> Option<T>,
+) -> Result<Option<T>, String> {
+    header(headers, name)
+        .map(|value| parse(value.trim()).ok_or_else(|| format!("Invalid {name} header: {value}")))
+        .transpose()
+}
+
+fn parse_congestion_control(s: &str) -> Option<CongestionControl> {
+    match s.to_ascii_lowercase().as_str() {
+        "drop" => Some(CongestionControl::Drop),
+        "block" => Some(CongestionControl::Block),
+        _ => None,
+    }
+}
+
+fn parse_priority(s: &str) -> Option<Priority> {
+    if let Ok(priority) = u8::from_str(s) {
+        return Priority::try_from(priority).ok();
+    }
+    match s.to_ascii_lowercase().replace('_', "-").as_str() {
+        "real-time" => Some(Priority::RealTime),
+        "interactive-high" => Some(Priority::InteractiveHigh),
+        "interactive-low" => Some(Priority::InteractiveLow),
+        "data-high" => Some(Priority::DataHigh),
+        "data" => Some(Priority::Data),
+        "data-low" => Some(Priority::DataLow),
+        "background" => Some(Priority::Background),
+        _ => None,
+    }
+}
+
+fn parse_query_target(s: &str) -> Option<QueryTarget> {
+    match s.to_ascii_lowercase().replace('-', "_").as_str() {
+        "best_matching" => Some(QueryTarget::BestMatching),
+        "all" => Some(QueryTarget::All),
+        "all_complete" => Some(QueryTarget::AllComplete),
+        _ => None,
+    }
+}
+
+fn parse_consolidation(s: &str) -> Option<ConsolidationMode> {
+    match s.to_ascii_lowercase().as_str() {
+        "auto" => Some(ConsolidationMode::Auto),
+        "none" => Some(ConsolidationMode::None),
+        "monotonic" => Some(ConsolidationMode::Monotonic),
+        "latest" => Some(ConsolidationMode::Latest),
+        _ => None,
+    }
+}
+
+/// Formats an entity id as `<zid>/<eid>`.
+pub(crate) fn entity_id_to_string(id: &EntityGlobalId) -> String {
+    format!("{}/{}", id.zid(), id.eid())
+}
+
+/// Returns the `X-Zenoh-Source-Id` and `X-Zenoh-Source-Sn` headers describing `source_info`.
+pub(crate) fn source_info_headers(source_info: &SourceInfo) -> [(&'static str, String); 2] {
+    [
+        (SOURCE_ID, entity_id_to_string(source_info.source_id())),
+        (SOURCE_SN, source_info.source_sn().to_string()),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use http_types::{Method, Request};
+    use zenoh::{
+        qos::{CongestionControl, Priority},
+        query::{ConsolidationMode, QueryTarget},
+    };
+
+    use super::RequestOptions;
+
+    fn request(headers: &[(&str, &str)]) -> Request {
+        let mut req = Request::new(Method::Put, "http://localhost:8000/demo/a");
+        for (name, value) in headers {
+            req.insert_header(*name, *value);
+        }
+        req
+    }
+
+    fn parse(name: &str, value: &str) -> Result<RequestOptions, String> {
+        RequestOptions::from_headers(request(&[(name, value)]))
+    }
+
+    #[test]
+    fn test_request_options() {
+        assert_eq!(
+            RequestOptions::from_headers(request(&[])).unwrap(),
+            RequestOptions::default()
+        );
+
+        let options = RequestOptions::from_headers(request(&[
+            ("X-Zenoh-Congestion-Control", "Block"),
+            ("X-Zenoh-Priority", "interactive_high"),
+            ("X-Zenoh-Express", "true"),
+            ("X-Zenoh-Attachment", "some metadata"),
+            (
+                "X-Zenoh-Timestamp",
+                "7386690599959157260/33a2b8e4b4ab4e0a9bc8f0a0f62d7e9f",
+            ),
+            ("X-Zenoh-Query-Target", "all-complete"),
+            ("X-Zenoh-Consolidation", "none"),
+            ("X-Zenoh-Timeout", "2500"),
+            ("X-Zenoh-Unknown", "ignored"),
+        ]))
+        .unwrap();
+        assert_eq!(options.congestion_control, Some(CongestionControl::Block));
+        assert_eq!(options.priority, Some(Priority::InteractiveHigh));
+        assert_eq!(options.express, Some(true));
+        assert_eq!(options.attachment.as_deref(), Some("some metadata"));
+        assert_eq!(
+            options.timestamp.unwrap().to_string(),
+            "7386690599959157260/33a2b8e4b4ab4e0a9bc8f0a0f62d7e9f"
+        );
+        assert_eq!(options.target, Some(QueryTarget::AllComplete));
+        assert_eq!(options.consolidation, Some(ConsolidationMode::None));
+        assert_eq!(options.timeout, Some(Duration::from_millis(2500)));
+    }
+
+    #[test]
+    fn test_congestion_control_option() {
+        for (value, congestion_control) in [
+            ("drop", CongestionControl::Drop),
+            ("Drop", CongestionControl::Drop),
+            ("block", CongestionControl::Block),
+            (" BLOCK ", CongestionControl::Block),
+        ] {
+            let options = parse("X-Zenoh-Congestion-Control", value).unwrap();
+            assert_eq!(options.congestion_control, Some(congestion_control));
+        }
+        for value in ["", "wait", "0", "block_first"] {
+            assert!(
+                parse("X-Zenoh-Congestion-Control", value).is_err(),
+                "{value}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_priority_option() {
+        for (value, priority) in [
+            ("1", Priority::RealTime),
+            ("real-time", Priority::RealTime),
+            ("interactive_high", Priority::InteractiveHigh),
+            ("Interactive-Low", Priority::InteractiveLow),
+            ("data-high", Priority::DataHigh),
+            ("5", Priority::Data),
+            ("data", Priority::Data),
+            ("data_low", Priority::DataLow),
+            ("background", Priority::Background),
+            ("7", Priority::Background),
+        ] {
+            assert_eq!(
+                parse("X-Zenoh-Priority", value).unwrap().priority,
+                Some(priority)
+            );
+        }
+        for value in ["", "0", "8", "-1", "urgent", "realtime"] {
+            assert!(parse("X-Zenoh-Priority", value).is_err(), "{value}");
+        }
+    }
+
+    #[test]
+    fn test_express_option() {
+        assert_eq!(
+            parse("X-Zenoh-Express", "true").unwrap().express,
+            Some(true)
+        );
+        assert_eq!(
+            parse("X-Zenoh-Express", "false").unwrap().express,
+            Some(false)
+        );
+        for value in ["", "yes", "1", "TRUE"] {
+            assert!(parse("X-Zenoh-Express", value).is_err(), "{value}");
+        }
+    }
+
+    #[test]
+    fn test_attachment_option() {
+        // Any value is a valid attachment, the last header being kept
+        let options = RequestOptions::from_headers({
+            let mut req = request(&[("X-Zenoh-Attachment", "first")]);
+            req.append_header("X-Zenoh-Attachment", "last");
+            req
+        })
+        .unwrap();
+        assert_eq!(options.attachment.as_deref(), Some("last"));
+        let options = parse("X-Zenoh-Attachment", "").unwrap();
+        assert_eq!(options.attachment.as_deref(), Some(""));
+    }
+
+    #[test]
+    fn test_timestamp_option() {
+        let timestamp = "7386690599959157260/33a2b8e4b4ab4e0a9bc8f0a0f62d7e9f";
+        let options = parse("X-Zenoh-Timestamp", timestamp).unwrap();
+        assert_eq!(options.timestamp.unwrap().to_string(), timestamp);
+        for value in [
+            "",
+            "now",
+            "7386690599959157260",
+            "7386690599959157260/not-an-id",
+            "-1/33a2b8e4b4ab4e0a9bc8f0a0f62d7e9f",
+        ] {
+            assert!(parse("X-Zenoh-Timestamp", value).is_err(), "{value}");
+        }
+    }
+
+    #[test]
+    fn test_query_target_option() {
+        for (value, target) in [
+            ("best_matching", QueryTarget::BestMatching),
+            ("BEST-MATCHING", QueryTarget::BestMatching),
+            ("all", QueryTarget::All),
+            ("all-complete", QueryTarget::AllComplete),
+            ("all_complete", QueryTarget::AllComplete),
+        ] {
+            assert_eq!(
+                parse("X-Zenoh-Query-Target", value).unwrap().target,
+                Some(target)
+            );
+        }
+        for value in ["", "any", "complete", "allcomplete"] {
+            assert!(parse("X-Zenoh-Query-Target", value).is_err(), "{value}");
+        }
+    }
+
+    #[test]
+    fn test_consolidation_option() {
+        for (value, consolidation) in [
+            ("auto", ConsolidationMode::Auto),
+            ("none", ConsolidationMode::None),
+            ("Monotonic", ConsolidationMode::Monotonic),
+            ("LATEST", ConsolidationMode::Latest),
+        ] {
+            let options = parse("X-Zenoh-Consolidation", value).unwrap();
+            assert_eq!(options.consolidation, Some(consolidation));
+        }
+        for value in ["", "first", "all"] {
+            assert!(parse("X-Zenoh-Consolidation", value).is_err(), "{value}");
+        }
+    }
+
+    #[test]
+    fn test_timeout_option() {
+        for (value, timeout) in [("0", 0), ("2500", 2500), (" 10 ", 10)] {
+            let options = parse("X-Zenoh-Timeout", value).unwrap();
+            assert_eq!(options.timeout, Some(Duration::from_millis(timeout)));
+        }
+        for value in ["", "-1", "1.5", "10s", "18446744073709551616"] {
+            assert!(parse("X-Zenoh-Timeout", value).is_err(), "{value}");
+        }
+    }
+
+    #[test]
+    fn test_invalid_option_error() {
+        assert_eq!(
+            parse("X-Zenoh-Priority", "urgent").unwrap_err(),
+            "Invalid x-zenoh-priority header: urgent"
+        );
+    }
+}
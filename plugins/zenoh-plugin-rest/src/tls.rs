@@ -0,0 +1,193 @@
+//
+// Copyright (c) 2023 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{
+    io::BufReader,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::{AsyncRead, AsyncWrite};
+use rustls::{server::WebPkiClientVerifier, RootCertStore, ServerConfig};
+use rustls_pki_types::{CertificateDer, PrivateKeyDer};
+use tide::Server;
+use tokio::{net::TcpListener, time::timeout};
+use tokio_rustls::{server::TlsStream, TlsAcceptor};
+use tokio_util::compat::{Compat, TokioAsyncReadCompatExt};
+use x509_parser::prelude::{FromDer, X509Certificate};
+use zenoh::{
+    internal::{bail, zerror},
+    Result as ZResult,
+};
+
+use crate::{config::TlsConfig, spawn_runtime};
+
+const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+const ACCEPT_ERROR_DELAY: Duration = Duration::from_millis(500);
+
+/// The verified certificate presented by a client, attached to each of its requests.
+#[derive(Clone, Debug)]
+pub(crate) struct ClientCertificate {
+    pub(crate) common_name: Option<String>,
+}
+
+pub(crate) fn server_config(conf: &TlsConfig) -> ZResult<Arc<ServerConfig>> {
+    let certs = load_certificates(&conf.server_certificate)?;
+    if certs.is_empty() {
+        bail!("No certificate found in {}", conf.server_certificate);
+    }
+    let key: PrivateKeyDer = rustls_pemfile::private_key(&mut BufReader::new(
+        std::fs::File::open(&conf.server_private_key)
+            .map_err(|e| zerror!("Invalid TLS private key file: {e}"))?,
+    ))
+    .map_err(|e| zerror!("Error processing server key: {e}"))?
+    .ok_or_else(|| zerror!("No private key found in {}", conf.server_private_key))?;
+
+    // Install ring based rustls CryptoProvider.
+    rustls::crypto::ring::default_provider()
+        // Ignore the error here: the provider might have already been installed by another plugin
+        // or by the TLS links.
+        .install_default()
+        .ok();
+
+    let builder = ServerConfig::builder();
+    let config = if conf.enable_mtls {
+        let Some(root_ca_certificate) = &conf.root_ca_certificate else {
+            bail!("Missing root certificates while mTLS is enabled.");
+        };
+        let mut root_cert_store = RootCertStore::empty();
+        for cert in load_certificates(root_ca_certificate)? {
+            root_cert_store
+                .add(cert)
+                .map_err(|e| zerror!("Error processing root certificate: {e}"))?;
+        }
+        let client_auth = WebPkiClientVerifier::builder(root_cert_store.into())
+            .build()
+            .map_err(|e| zerror!(e))?;
+        builder.with_client_cert_verifier(client_auth)
+    } else {
+        builder.with_no_client_auth()
+    };
+    let mut config = config
+        .with_single_cert(certs, key)
+        .map_err(|e| zerror!(e))?;
+    config.alpn_protocols = vec![b"http/1.1".to_vec()];
+    Ok(Arc::new(config))
+}
+
+fn load_certificates(path: &str) -> ZResult<Vec<CertificateDer<'static>>> {
+    let file =
+        std::fs::File::open(path).map_err(|e| zerror!("Invalid TLS certificate file: {e}"))?;
+    rustls_pemfile::certs(&mut BufReader::new(file))
+        .collect::<Result<_, _>>()
+        .map_err(|e| zerror!("Error processing PEM certificates: {e}").into())
+}
+
+/// Serves `app` over HTTPS on `addr`. Only returns if `addr` can't be bound.
+pub(crate) async fn listen<State>(
+    app: Server<State>,
+    addr: &str,
+    config: Arc<ServerConfig>,
+) -> ZResult<()>
+where
+    State: Clone + Send + Sync + 'static,
+{
+    let acceptor = TlsAcceptor::from(config);
+    let listener = TcpListener::bind(addr).await?;
+    tracing::info!(
+        "REST server listening on https://{}",
+        listener.local_addr()?
+    );
+    loop {
+        let (stream, peer_addr) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            Err(e) => {
+                tracing::warn!("Unable to accept HTTPS connection: {e}");
+                tokio::time::sleep(ACCEPT_ERROR_DELAY).await;
+                continue;
+            }
+        };
+        let app = app.clone();
+        let acceptor = acceptor.clone();
+        spawn_runtime(async move {
+            let local_addr = stream.local_addr().ok();
+            let stream = match timeout(TLS_HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await {
+                Ok(Ok(stream)) => stream,
+                Ok(Err(e)) => {
+                    tracing::debug!("TLS handshake with {peer_addr} failed: {e}");
+                    return;
+                }
+                Err(_) => {
+                    tracing::debug!("TLS handshake with {peer_addr} timed out");
+                    return;
+                }
+            };
+            let certificate = ClientCertificate {
+                common_name: client_common_name(stream.get_ref().1),
+            };
+            let stream = SharedTlsStream(Arc::new(Mutex::new(stream.compat())));
+            let res = async_h1::accept(stream, |mut req| {
+                req.set_local_addr(local_addr);
+                req.set_peer_addr(Some(peer_addr));
+                req.ext_mut().insert(certificate.clone());
+                app.respond(req)
+            })
+            .await;
+            if let Err(e) = res {
+                tracing::debug!("HTTPS connection with {peer_addr} failed: {e}");
+            }
+        });
+    }
+}
+
+fn client_common_name(conn: &rustls::ServerConnection) -> Option<String> {
+    let cert = conn.peer_certificates()?.first()?;
+    let (_, cert) = X509Certificate::from_der(cert.as_ref()).ok()?;
+    let common_name = cert.subject.iter_common_name().next()?.as_str().ok()?;
+    Some(common_name.to_string())
+}
+
+/// A TLS stream that can be cloned, as required by `async_h1` to read requests while writing
+/// responses.
+#[derive(Clone)]
+struct SharedTlsStream(Arc<Mutex<Compat<TlsStream<tokio::net::TcpStream>>>>);
+
+impl AsyncRead for SharedTlsStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        Pin::new(&mut *self.0.lock().unwrap()).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for SharedTlsStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        Pin::new(&mut *self.0.lock().unwrap()).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut *self.0.lock().unwrap()).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut *self.0.lock().unwrap()).poll_close(cx)
+    }
+}
@@ -0,0 +1,639 @@
+//
+// Copyright (c) 2023 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! WebSocket endpoint of the REST API.
+//!
+//! Over a single connection, a client can subscribe to several key expressions, publish and query,
+//! by exchanging JSON messages. Each request of the client is an object with an `op` field:
+//!
+//! - `{"op": "subscribe", "id": 1, "key_expr": "demo/**", "liveliness": false}`
+//! - `{"op": "unsubscribe", "id": 1}`
+//! - `{"op": "put", "id": 2, "key_expr": "demo/a", "value": "Hello", "encoding": "text/plain"}`
+//! - `{"op": "delete", "id": 3, "key_expr": "demo/a"}`
+//! - `{"op": "get", "id": 4, "selector": "demo/**?arg=1", "value": null, "liveliness": false}`
+//!
+//! Each message of the server is an object with a `type` field and the `id` of the request it
+//! relates to: `ok` and `error` acknowledge the requests, `sample` carries the samples received by a
+//! subscription and `reply` the replies to a query, the end of which is signaled by `final`. The
+//! `id` of `put` and `delete` requests is optional, they are only acknowledged if they have one.
+//!
+//! The messages of the server are buffered up to [`QUEUE_SIZE`]: a client that doesn't read them as
+//! fast as they are produced is disconnected, with a `1008` (policy violation) close code.
+
+use std::{collections::HashMap, pin::pin, sync::Arc, time::Duration};
+
+use futures::{future, SinkExt, StreamExt};
+use http_types::upgrade::Connection;
+use serde::{Deserialize, Serialize};
+use tide::{Request, Response, StatusCode};
+use tokio_tungstenite::{
+    tungstenite::{
+        handshake::derive_accept_key,
+        protocol::{frame::coding::CloseCode, CloseFrame, Role},
+        Message,
+    },
+    WebSocketStream,
+};
+use tokio_util::{
+    compat::{Compat, FuturesAsyncReadCompatExt},
+    sync::CancellationToken,
+};
+use zenoh::{
+    bytes::{Encoding, ZBytes},
+    internal::access_control::{AclMessage, InterceptorFlow},
+    key_expr::KeyExpr,
+    pubsub::Subscriber,
+    query::{Parameters, Selector},
+    sample::Sample,
+    session::Session,
+    Result as ZResult,
+};
+
+use crate::{
+    path_to_key_expr, reply_to_json, sample_to_json, spawn_runtime, Authorization, JSONSample,
+    State, REPLIES_BUFFER_SIZE,
+};
+
+/// The number of messages buffered for a client. Once reached, the client is disconnected.
+const QUEUE_SIZE: usize = 1024;
+/// The time given to the close message to be sent to a disconnected client.
+const CLOSE_TIMEOUT: Duration = Duration::from_secs(1);
+
+#[derive(Deserialize, Debug)]
+#[serde(tag = "op", rename_all = "snake_case", deny_unknown_fields)]
+enum WsRequest {
+    Subscribe {
+        id: u64,
+        key_expr: String,
+        #[serde(default)]
+        liveliness: bool,
+    },
+    Unsubscribe {
+        id: u64,
+    },
+    Put {
+        id: Option<u64>,
+        key_expr: String,
+        value: serde_json::Value,
+        encoding: Option<String>,
+    },
+    Delete {
+        id: Option<u64>,
+        key_expr: String,
+    },
+    Get {
+        id: u64,
+        selector: String,
+        value: Option<serde_json::Value>,
+        encoding: Option<String>,
+        #[serde(default)]
+        liveliness: bool,
+    },
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum WsResponse {
+    Ok {
+        id: Option<u64>,
+    },
+    Error {
+        id: Option<u64>,
+        error: String,
+    },
+    Sample {
+        id: u64,
+        kind: String,
+        #[serde(flatten)]
+        sample: JSONSample,
+    },
+    Reply {
+        id: u64,
+        #[serde(flatten)]
+        sample: JSONSample,
+    },
+    Final {
+        id: u64,
+    },
+}
+
+/// Returns `true` if `req` asks to upgrade the connection to the WebSocket protocol.
+pub(crate) fn is_upgrade(req: &Request<State>) -> bool {
+    req.header("upgrade")
+        .is_some_and(|upgrade| upgrade.last().as_str().eq_ignore_ascii_case("websocket"))
+}
+
+/// Returns `true` if a WebSocket connection may be opened from `origin`.
+///
+/// The requests without `Origin` header are not sent by browsers, which are the only clients
+/// the check protects against, and are allowed.
+fn is_allowed_origin(allowed_origins: Option<&[String]>, origin: Option<&str>) -> bool {
+    match (allowed_origins, origin) {
+        (Some(allowed_origins), Some(origin)) => {
+            let origin = origin.trim().trim_end_matches('/');
+            allowed_origins
+                .iter()
+                .any(|allowed| allowed.trim_end_matches('/').eq_ignore_ascii_case(origin))
+        }
+        _ => true,
+    }
+}
+
+/// Upgrades the connection of `req` to the WebSocket protocol and serves the client over it.
+pub(crate) async fn upgrade(req: Request<State>) -> tide::Result<Response> {
+    let origin = req.header("origin").map(|origin| origin.last().as_str());
+    if !is_allowed_origin(req.state().websocket_allowed_origins.as_deref(), origin) {
+        tracing::debug!("Rejected WebSocket connection from origin {origin:?}");
+        return Ok(Response::builder(StatusCode::Forbidden)
+            .body("Origin not allowed")
+            .build());
+    }
+    let Some(key) = req.header("sec-websocket-key") else {
+        return Ok(Response::builder(StatusCode::BadRequest)
+            .body("Missing Sec-WebSocket-Key header")
+            .build());
+    };
+    let mut res = Response::builder(StatusCode::SwitchingProtocols)
+        .header("upgrade", "websocket")
+        .header("connection", "Upgrade")
+        .header(
+            "sec-websocket-accept",
+            derive_accept_key(key.last().as_str().as_bytes()),
+        )
+        .build();
+
+    let session = req.state().session.clone();
+    let zid = req.state().zid.clone();
+    let authorization = req.ext::<Authorization>().cloned();
+    let upgrade = AsMut::<http_types::Response>::as_mut(&mut res)
+        .recv_upgrade()
+        .await;
+    spawn_runtime(async move {
+        if let Some(connection) = upgrade.await {
+            let stream =
+                WebSocketStream::from_raw_socket(connection.compat(), Role::Server, None).await;
+            WsSession {
+                session,
+                zid,
+                authorization,
+                outbox: None,
+                subscribers: HashMap::new(),
+            }
+            .run(stream)
+            .await;
+        }
+    });
+    Ok(res)
+}
+
+struct WsSession {
+    session: Arc<Session>,
+    zid: String,
+    authorization: Option<Authorization>,
+    outbox: Option<Outbox>,
+    /// The subscriptions of the client, indexed by the id of the requests declaring them.
+    subscribers: HashMap<u64, Subscriber<()>>,
+}
+
+impl WsSession {
+    async fn run(mut self, stream: WebSocketStream<Compat<Connection>>) {
+        let (mut sink, stream) = stream.split();
+        let (sender, receiver) = flume::bounded::<WsResponse>(QUEUE_SIZE);
+        let overflow = CancellationToken::new();
+        self.outbox = Some(Outbox {
+            sender,
+            overflow: overflow.clone(),
+        });
+        let writer = {
+            let overflow = overflow.clone();
+            spawn_runtime(async move {
+                let forward = async {
+                    while let Ok(response) = receiver.recv_async().await {
+                        let text = serde_json::to_string(&response).unwrap_or_default();
+                        if let Err(e) = sink.send(Message::Text(text)).await {
+                            tracing::debug!("WebSocket error ({e})! Terminate");
+                            break;
+                        }
+                    }
+                };
+                // The client may not read anymore: stop writing to it as soon as it overflows
+                future::select(pin!(forward), pin!(overflow.cancelled())).await;
+                if overflow.is_cancelled() {
+                    let close = Message::Close(Some(CloseFrame {
+                        code: CloseCode::Policy,
+                        reason: "Too slow to read the messages".into(),
+                    }));
+                    let _ = tokio::time::timeout(CLOSE_TIMEOUT, sink.send(close)).await;
+                }
+            })
+        };
+
+        let mut stream = pin!(stream.take_until(overflow.cancelled()));
+        while let Some(message) = stream.next().await {
+            let text = match message {
+                Ok(Message::Text(text)) => text,
+                Ok(Message::Binary(bytes)) => match String::from_utf8(bytes) {
+                    Ok(text) => text,
+                    Err(_) => {
+                        self.send(WsResponse::Error {
+                            id: None,
+                            error: "Binary messages must be UTF-8 encoded JSON".into(),
+                        });
+                        continue;
+                    }
+                },
+                Ok(Message::Close(_)) => break,
+                Ok(_) => continue,
+                Err(e) => {
+                    tracing::debug!("WebSocket error ({e})! Terminate");
+                    break;
+                }
+            };
+            match serde_json::from_str::<WsRequest>(&text) {
+                Ok(request) => self.handle(request).await,
+                Err(e) => self.send(WsResponse::Error {
+                    id: None,
+                    error: format!("Invalid request: {e}"),
+                }),
+            }
+        }
+
+        // Undeclare the subscribers, so that no more samples are sent to the writer
+        self.subscribers.clear();
+        self.outbox = None;
+        if let Err(e) = writer.await {
+            tracing::debug!("WebSocket writer failed: {e}");
+        }
+    }
+
+    fn send(&self, response: WsResponse) {
+        if let Some(outbox) = &self.outbox {
+            outbox.send(response);
+        }
+    }
+
+    async fn handle(&mut self, request: WsRequest) {
+        tracing::trace!("Incoming WebSocket request: {:?}", request);
+        let (id, res) = match request {
+            WsRequest::Subscribe {
+                id,
+                key_expr,
+                liveliness,
+            } => (Some(id), self.subscribe(id, &key_expr, liveliness).await),
+            WsRequest::Unsubscribe { id } => (Some(id), self.unsubscribe(id)),
+            WsRequest::Put {
+                id,
+                key_expr,
+                value,
+                encoding,
+            } => (id, self.put(&key_expr, value, encoding).await),
+            WsRequest::Delete { id, key_expr } => (id, self.delete(&key_expr).await),
+            WsRequest::Get {
+                id,
+                selector,
+                value,
+                encoding,
+                liveliness,
+            } => match self.get(id, &selector, value, encoding, liveliness).await {
+                // The replies and the final message acknowledge the query
+                Ok(()) => return,
+                Err(e) => (Some(id), Err(e)),
+            },
+        };
+        match res {
+            // Requests without id are not acknowledged
+            Ok(()) if id.is_none() => {}
+            Ok(()) => self.send(WsResponse::Ok { id }),
+            Err(e) => self.send(WsResponse::Error {
+                id,
+                error: e.to_string(),
+            }),
+        }
+    }
+
+    fn key_expr<'a>(&self, key_expr: &'a str) -> ZResult<KeyExpr<'a>> {
+        path_to_key_expr(key_expr, &self.zid)
+    }
+
+    fn authorize(&self, action: AclMessage, key_expr: &KeyExpr) -> ZResult<()> {
+        match &self.authorization {
+            Some(authorization)
+                if !authorization.allows(InterceptorFlow::Ingress, action, key_expr) =>
+            {
+                Err(zenoh::internal::zerror!("Unauthorized to {action:?} on {key_expr}").into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    async fn subscribe(&mut self, id: u64, key_expr: &str, liveliness: bool) -> ZResult<()> {
+        if self.subscribers.contains_key(&id) {
+            zenoh::internal::bail!("Subscription {id} already exists");
+        }
+        let key_expr = self.key_expr(key_expr)?;
+        let (action, egress_action) = if liveliness {
+            (
+                AclMessage::DeclareLivelinessSubscriber,
+                Some(AclMessage::LivelinessToken),
+            )
+        } else {
+            (AclMessage::DeclareSubscriber, None)
+        };
+        self.authorize(action, &key_expr)?;
+        let outbox = self.outbox.clone();
+        let authorization = self.authorization.clone();
+        let callback = move |sample: Sample| {
+            if let Some(authorization) = &authorization {
+                let allowed = match egress_action {
+                    Some(action) => {
+                        authorization.allows(InterceptorFlow::Egress, action, sample.key_expr())
+                    }
+                    None => authorization.allows_sample(&sample),
+                };
+                if !allowed {
+                    return;
+                }
+            }
+            if let Some(outbox) = &outbox {
+                outbox.send(WsResponse::Sample {
+                    id,
+                    kind: sample.kind().to_string(),
+                    sample: sample_to_json(&sample),
+                });
+            }
+        };
+        let subscriber = if liveliness {
+            self.session
+                .liveliness()
+                .declare_subscriber(&key_expr)
+                .callback(callback)
+                .await?
+        } else {
+            self.session
+                .declare_subscriber(&key_expr)
+                .callback(callback)
+                .await?
+        };
+        self.subscribers.insert(id, subscriber);
+        Ok(())
+    }
+
+    fn unsubscribe(&mut self, id: u64) -> ZResult<()> {
+        match self.subscribers.remove(&id) {
+            Some(_) => Ok(()),
+            None => zenoh::internal::bail!("Unknown subscription {id}"),
+        }
+    }
+
+    async fn put(
+        &self,
+        key_expr: &str,
+        value: serde_json::Value,
+        encoding: Option<String>,
+    ) -> ZResult<()> {
+        let key_expr = self.key_expr(key_expr)?;
+        self.authorize(AclMessage::Put, &key_expr)?;
+        let (payload, encoding) = json_to_payload(value, encoding);
+        self.session
+            .put(&key_expr, payload)
+            .encoding(encoding)
+            .await
+    }
+
+    async fn delete(&self, key_expr: &str) -> ZResult<()> {
+        let key_expr = self.key_expr(key_expr)?;
+        self.authorize(AclMessage::Delete, &key_expr)?;
+        self.session.delete(&key_expr).await
+    }
+
+    async fn get(
+        &self,
+        id: u64,
+        selector: &str,
+        value: Option<serde_json::Value>,
+        encoding: Option<String>,
+        liveliness: bool,
+    ) -> ZResult<()> {
+        let (key_expr, parameters) = selector.split_once('?').unwrap_or((selector, ""));
+        let key_expr = self.key_expr(key_expr)?;
+        let action = if liveliness {
+            AclMessage::LivelinessQuery
+        } else {
+            AclMessage::Query
+        };
+        self.authorize(action, &key_expr)?;
+        let Some(outbox) = self.outbox.clone() else {
+            return Ok(());
+        };
+        // The replies are received in a callback, which must not block: a client too slow to
+        // read them is disconnected
+        let (sender, replies) = flume::bounded(REPLIES_BUFFER_SIZE);
+        let callback = {
+            let outbox = outbox.clone();
+            move |reply| {
+                if let Err(flume::TrySendError::Full(_)) = sender.try_send(reply) {
+                    outbox.disconnect();
+                }
+            }
+        };
+        if liveliness {
+            self.session
+                .liveliness()
+                .get(&key_expr)
+                .callback(callback)
+                .await?
+        } else {
+            let parameters = Parameters::from(parameters);
+            let mut query = self
+                .session
+                .get(Selector::borrowed(&key_expr, &parameters))
+                .callback(callback);
+            if let Some(value) = value {
+                let (payload, encoding) = json_to_payload(value, encoding);
+                query = query.payload(payload).encoding(encoding);
+            }
+            query.await?
+        };
+        let authorization = self.authorization.clone();
+        spawn_runtime(async move {
+            while let Ok(reply) = replies.recv_async().await {
+                if !authorization.as_ref().map_or(true, |authorization| {
+                    if liveliness {
+                        authorization.allows_liveliness_reply(&reply)
+                    } else {
+                        authorization.allows_reply(&reply)
+                    }
+                }) {
+                    continue;
+                }
+                outbox.send(WsResponse::Reply {
+                    id,
+                    sample: reply_to_json(&reply),
+                });
+            }
+            outbox.send(WsResponse::Final { id });
+        });
+        Ok(())
+    }
+}
+
+/// The queue of the messages to send to a client.
+#[derive(Clone)]
+struct Outbox {
+    sender: flume::Sender<WsResponse>,
+    /// Cancelled when the queue overflows, to disconnect the client.
+    overflow: CancellationToken,
+}
+
+impl Outbox {
+    /// Queues `response` without blocking, or disconnects the client if the queue is full.
+    fn send(&self, response: WsResponse) {
+        if let Err(flume::TrySendError::Full(_)) = self.sender.try_send(response) {
+            self.disconnect();
+        }
+    }
+
+    fn disconnect(&self) {
+        if !self.overflow.is_cancelled() {
+            tracing::debug!("WebSocket client too slow to read the messages! Disconnect");
+            self.overflow.cancel();
+        }
+    }
+}
+
+/// Converts the JSON `value` of a request to a payload: strings are sent as is, other values are
+/// serialized as JSON.
+fn json_to_payload(value: serde_json::Value, encoding: Option<String>) -> (ZBytes, Encoding) {
+    let (payload, default_encoding) = match value {
+        serde_json::Value::String(string) => (ZBytes::from(string), Encoding::TEXT_PLAIN),
+        value => (ZBytes::from(value.to_string()), Encoding::APPLICATION_JSON),
+    };
+    (
+        payload,
+        encoding.map(Encoding::from).unwrap_or(default_encoding),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use zenoh::bytes::Encoding;
+
+    use super::{is_allowed_origin, json_to_payload, WsRequest, WsResponse};
+    use crate::JSONSample;
+
+    #[test]
+    fn test_requests() {
+        let request = serde_json::from_str::<WsRequest>(
+            r#"{"op": "subscribe", "id": 1, "key_expr": "demo/**"}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            request,
+            WsRequest::Subscribe { id: 1, ref key_expr, liveliness: false } if key_expr == "demo/**"
+        ));
+
+        let request = serde_json::from_str::<WsRequest>(
+            r#"{"op": "get", "id": 2, "selector": "demo/**?arg=1", "liveliness": true}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            request,
+            WsRequest::Get { id: 2, ref selector, value: None, encoding: None, liveliness: true }
+                if selector == "demo/**?arg=1"
+        ));
+
+        let request =
+            serde_json::from_str::<WsRequest>(r#"{"op": "put", "key_expr": "demo/a", "value": 1}"#)
+                .unwrap();
+        assert!(matches!(request, WsRequest::Put { id: None, .. }));
+
+        // Missing id, unknown operation and unknown field
+        assert!(serde_json::from_str::<WsRequest>(r#"{"op": "unsubscribe"}"#).is_err());
+        assert!(serde_json::from_str::<WsRequest>(r#"{"op": "publish", "id": 1}"#).is_err());
+        assert!(serde_json::from_str::<WsRequest>(
+            r#"{"op": "delete", "key_expr": "demo/a", "value": 1}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_responses() {
+        let response = WsResponse::Sample {
+            id: 1,
+            kind: "PUT".into(),
+            sample: JSONSample {
+                key: "demo/a".into(),
+                value: serde_json::json!({"x": 1}),
+                encoding: "application/json".into(),
+                timestamp: None,
+                attachment: None,
+                source_info: None,
+                replier_id: None,
+            },
+        };
+        assert_eq!(
+            serde_json::to_value(&response).unwrap(),
+            serde_json::json!({
+                "type": "sample",
+                "id": 1,
+                "kind": "PUT",
+                "key": "demo/a",
+                "value": {"x": 1},
+                "encoding": "application/json",
+                "timestamp": null,
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(WsResponse::Final { id: 2 }).unwrap(),
+            serde_json::json!({"type": "final", "id": 2})
+        );
+    }
+
+    #[test]
+    fn test_json_to_payload() {
+        let (payload, encoding) = json_to_payload(serde_json::json!("hello"), None);
+        assert_eq!(payload.try_to_string().unwrap(), "hello");
+        assert_eq!(encoding, Encoding::TEXT_PLAIN);
+
+        let (payload, encoding) = json_to_payload(serde_json::json!({"x": 1}), None);
+        assert_eq!(payload.try_to_string().unwrap(), r#"{"x":1}"#);
+        assert_eq!(encoding, Encoding::APPLICATION_JSON);
+
+        let (_, encoding) =
+            json_to_payload(serde_json::json!("<p/>"), Some("text/html".to_string()));
+        assert_eq!(encoding, Encoding::TEXT_HTML);
+    }
+
+    #[test]
+    fn test_allowed_origins() {
+        let allowed = [
+            "https://example.com".to_string(),
+            "http://localhost:8080/".to_string(),
+        ];
+        let allowed = Some(&allowed[..]);
+        assert!(is_allowed_origin(allowed, Some("https://example.com")));
+        assert!(is_allowed_origin(allowed, Some("HTTPS://Example.com/")));
+        assert!(is_allowed_origin(allowed, Some("http://localhost:8080")));
+        assert!(!is_allowed_origin(allowed, Some("http://example.com")));
+        assert!(!is_allowed_origin(
+            allowed,
+            Some("https://example.com.evil.org")
+        ));
+        assert!(!is_allowed_origin(allowed, Some("http://localhost:8000")));
+        assert!(!is_allowed_origin(allowed, Some("null")));
+        assert!(!is_allowed_origin(Some(&[]), Some("https://example.com")));
+        assert!(is_allowed_origin(allowed, None));
+        assert!(is_allowed_origin(None, Some("https://example.com")));
+    }
+}
@@ -0,0 +1,61 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![allow(dead_code)] // because every test doesn't use the whole common features
+use std::time::Duration;
+
+use tokio::net::TcpStream;
+use tokio_util::compat::TokioAsyncReadCompatExt;
+use zenoh::{internal::runtime::RuntimeBuilder, Config, Session};
+
+pub const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Starts the REST API on `127.0.0.1:<port>`, configured by `rest`, over a started runtime
+/// configured by `config`. Returns a session of this runtime.
+pub async fn start(mut config: Config, port: u16, mut rest: serde_json::Value) -> Session {
+    config
+        .insert_json5("scouting/multicast/enabled", "false")
+        .unwrap();
+    rest["http_port"] = format!("127.0.0.1:{port}").into();
+    let rest = serde_json::from_value(rest).unwrap();
+    let mut runtime = RuntimeBuilder::new(config).build().await.unwrap();
+    tokio::spawn(zenoh_plugin_rest::run(runtime.clone().into(), rest));
+
+    tokio::time::timeout(TIMEOUT, async {
+        while TcpStream::connect(("127.0.0.1", port)).await.is_err() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+    let session = zenoh::session::init(runtime.clone().into()).await.unwrap();
+    runtime.start().await.unwrap();
+    session
+}
+
+/// Sends `req` to the REST API on `port` and returns the status and the body of the response.
+pub async fn send(port: u16, mut req: http_types::Request) -> (http_types::StatusCode, String) {
+    req.url_mut().set_port(Some(port)).unwrap();
+    tokio::time::timeout(TIMEOUT, async {
+        let stream = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+        let mut res = async_h1::connect(stream.compat(), req).await.unwrap();
+        (res.status(), res.body_string().await.unwrap())
+    })
+    .await
+    .unwrap()
+}
+
+/// Returns a request of `method` on `path` of the REST API.
+pub fn request(method: http_types::Method, path: &str) -> http_types::Request {
+    http_types::Request::new(method, format!("http://127.0.0.1{path}").as_str())
+}
@@ -0,0 +1,155 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+// Test the Prometheus scrape endpoint -
+// 1. it serves the metrics of the node, counting the traffic of its transports
+// 2. the query parameters of the scrape request filter the metrics
+mod common;
+
+use std::time::Duration;
+
+use http_types::{Method, StatusCode};
+use serde_json::json;
+use tokio::net::TcpStream;
+use zenoh::Config;
+
+use crate::common::TIMEOUT;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_metrics() {
+    const PORT: u16 = 18593;
+    const METRICS_PORT: u16 = 18594;
+    const ZENOH_PORT: u16 = 18595;
+    const PUTS: usize = 10;
+
+    let mut config = Config::default();
+    config.insert_json5("adminspace/enabled", "true").unwrap();
+    config
+        .insert_json5(
+            "listen/endpoints",
+            &format!(r#"["tcp/127.0.0.1:{ZENOH_PORT}"]"#),
+        )
+        .unwrap();
+    let session = common::start(
+        config,
+        PORT,
+        json!({"metrics_port": format!("127.0.0.1:{METRICS_PORT}")}),
+    )
+    .await;
+    tokio::time::timeout(TIMEOUT, async {
+        while TcpStream::connect(("127.0.0.1", METRICS_PORT))
+            .await
+            .is_err()
+        {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+    let subscriber = session.declare_subscriber("test/metrics/**").await.unwrap();
+
+    let mut config = Config::default();
+    config
+        .insert_json5(
+            "connect/endpoints",
+            &format!(r#"["tcp/127.0.0.1:{ZENOH_PORT}"]"#),
+        )
+        .unwrap();
+    config
+        .insert_json5("scouting/multicast/enabled", "false")
+        .unwrap();
+    let peer = zenoh::open(config).await.unwrap();
+    let publisher = peer.declare_publisher("test/metrics/a").await.unwrap();
+    tokio::time::timeout(TIMEOUT, async {
+        while !publisher.matching_status().await.unwrap().matching() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+    for _ in 0..PUTS {
+        publisher.put("value").await.unwrap();
+    }
+    for _ in 0..PUTS {
+        tokio::time::timeout(TIMEOUT, subscriber.recv_async())
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    let body = scrape(METRICS_PORT, "").await;
+    assert!(body.ends_with("# EOF\n"));
+    let put = r#"message="put""#;
+    let remote = format!(r#"remote_zid="{}""#, peer.zid());
+    assert_eq!(
+        counter(&body, "zenoh_rx_network_message_total", &[put]),
+        Some(PUTS as u64)
+    );
+    assert_eq!(
+        counter(
+            &body,
+            "zenoh_rx_network_message_per_transport_total",
+            &[put, &remote]
+        ),
+        Some(PUTS as u64)
+    );
+
+    // The counters are read at each scrape
+    for _ in 0..PUTS {
+        publisher.put("value").await.unwrap();
+        tokio::time::timeout(TIMEOUT, subscriber.recv_async())
+            .await
+            .unwrap()
+            .unwrap();
+    }
+    let body = scrape(
+        METRICS_PORT,
+        "?per_transport=false&aggregate=true&descriptors=false",
+    )
+    .await;
+    assert_eq!(
+        counter(&body, "zenoh_rx_network_message_total", &[put]),
+        Some(2 * PUTS as u64)
+    );
+    assert_eq!(
+        counter(
+            &body,
+            "zenoh_rx_network_message_per_transport_total",
+            &[put]
+        ),
+        None
+    );
+    assert!(body
+        .lines()
+        .all(|line| !line.starts_with('#') || line == "# EOF"));
+
+    peer.close().await.unwrap();
+}
+
+/// Scrapes the metrics served on `port`, with the query `parameters`.
+async fn scrape(port: u16, parameters: &str) -> String {
+    let path = format!("/metrics{parameters}");
+    let (status, body) = common::send(port, common::request(Method::Get, &path)).await;
+    assert_eq!(status, StatusCode::Ok, "{body}");
+    body
+}
+
+/// Returns the value of the counter sample `name` having all the `labels`, if any.
+fn counter(metrics: &str, name: &str, labels: &[&str]) -> Option<u64> {
+    metrics
+        .lines()
+        .filter_map(|line| line.strip_prefix(name)?.strip_prefix('{'))
+        .find(|sample| labels.iter().all(|label| sample.contains(label)))
+        .map(|sample| sample.rsplit(' ').next().unwrap().parse().unwrap())
+}
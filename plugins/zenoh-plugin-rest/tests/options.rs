@@ -0,0 +1,139 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+// Test the `X-Zenoh-*` request options -
+// 1. they are applied to the publications and the queries of the requests
+// 2. an invalid value is rejected with `400 Bad Request`, without performing the request
+mod common;
+
+use std::time::Duration;
+
+use http_types::{Method, StatusCode};
+use serde_json::{json, Value};
+use zenoh::{
+    bytes::Encoding,
+    qos::{CongestionControl, Priority},
+    Config,
+};
+
+use crate::common::TIMEOUT;
+
+const TIMESTAMP: &str = "7386690599959157260/33a2b8e4b4ab4e0a9bc8f0a0f62d7e9f";
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_put_options() {
+    const PORT: u16 = 18591;
+    let session = common::start(Config::default(), PORT, json!({})).await;
+    let subscriber = session
+        .declare_subscriber("test/options/put")
+        .await
+        .unwrap();
+
+    let mut req = common::request(Method::Put, "/test/options/put");
+    req.insert_header("X-Zenoh-Congestion-Control", "block");
+    req.insert_header("X-Zenoh-Priority", "data-high");
+    req.insert_header("X-Zenoh-Express", "true");
+    req.insert_header("X-Zenoh-Attachment", "some metadata");
+    req.insert_header("X-Zenoh-Timestamp", TIMESTAMP);
+    req.set_body("value");
+    assert_eq!(common::send(PORT, req).await.0, StatusCode::Ok);
+
+    let sample = tokio::time::timeout(TIMEOUT, subscriber.recv_async())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(sample.payload().try_to_string().unwrap(), "value");
+    assert_eq!(sample.congestion_control(), CongestionControl::Block);
+    assert_eq!(sample.priority(), Priority::DataHigh);
+    assert!(sample.express());
+    assert_eq!(
+        sample.attachment().unwrap().try_to_string().unwrap(),
+        "some metadata"
+    );
+    assert_eq!(sample.timestamp().unwrap().to_string(), TIMESTAMP);
+
+    // The invalid options are rejected without publishing
+    for (name, value) in [
+        ("X-Zenoh-Congestion-Control", "wait"),
+        ("X-Zenoh-Priority", "urgent"),
+        ("X-Zenoh-Express", "yes"),
+        ("X-Zenoh-Timestamp", "now"),
+    ] {
+        let mut req = common::request(Method::Put, "/test/options/put");
+        req.insert_header(name, value);
+        req.set_body("invalid");
+        let (status, body) = common::send(PORT, req).await;
+        assert_eq!(status, StatusCode::BadRequest, "{name}: {value}");
+        assert!(body.contains(&name.to_ascii_lowercase()), "{body}");
+    }
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(subscriber.try_recv().unwrap().is_none());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_get_options() {
+    const PORT: u16 = 18592;
+    let session = common::start(Config::default(), PORT, json!({})).await;
+    let queryable = session.declare_queryable("test/options/get").await.unwrap();
+    let (queried, queries) = flume::unbounded();
+    tokio::spawn(async move {
+        while let Ok(query) = queryable.recv_async().await {
+            let attachment = query
+                .attachment()
+                .map(|a| a.try_to_string().unwrap().into_owned());
+            query
+                .reply("test/options/get", "reply")
+                .encoding(Encoding::TEXT_PLAIN)
+                .await
+                .unwrap();
+            queried.send(attachment).unwrap();
+        }
+    });
+
+    let mut req = common::request(Method::Get, "/test/options/get");
+    req.insert_header("X-Zenoh-Attachment", "some metadata");
+    req.insert_header("X-Zenoh-Query-Target", "all");
+    req.insert_header("X-Zenoh-Consolidation", "none");
+    req.insert_header("X-Zenoh-Timeout", "5000");
+    let (status, body) = common::send(PORT, req).await;
+    assert_eq!(status, StatusCode::Ok, "{body}");
+    let replies: Vec<Value> = serde_json::from_str(&body).unwrap();
+    assert_eq!(replies.len(), 1);
+    assert_eq!(replies[0]["value"], "reply");
+    assert!(replies[0]["replier_id"]
+        .as_str()
+        .unwrap()
+        .starts_with(&session.zid().to_string()));
+    let attachment = tokio::time::timeout(TIMEOUT, queries.recv_async())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(attachment.as_deref(), Some("some metadata"));
+
+    // The invalid options are rejected without querying
+    for (name, value) in [
+        ("X-Zenoh-Query-Target", "any"),
+        ("X-Zenoh-Consolidation", "first"),
+        ("X-Zenoh-Timeout", "-1"),
+        ("X-Zenoh-Priority", "0"),
+    ] {
+        let mut req = common::request(Method::Get, "/test/options/get");
+        req.insert_header(name, value);
+        let (status, body) = common::send(PORT, req).await;
+        assert_eq!(status, StatusCode::BadRequest, "{name}: {value}");
+        assert!(body.contains(&name.to_ascii_lowercase()), "{body}");
+    }
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(queries.is_empty());
+}
@@ -0,0 +1,92 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+// Test the replies to the GET requests -
+// 1. the replies are all streamed to a client that reads them
+// 2. a client that doesn't read the replies doesn't block their reception: its response is aborted
+mod common;
+
+use http_types::Method;
+use serde_json::{json, Value};
+use tokio::net::TcpStream;
+use tokio_util::compat::TokioAsyncReadCompatExt;
+use zenoh::{bytes::Encoding, Config, Session};
+
+use crate::common::TIMEOUT;
+
+/// Replies `count` times to the queries on `key_expr`, and signals in the returned channel once done.
+async fn reply(session: &Session, key_expr: &'static str, count: usize) -> flume::Receiver<()> {
+    let queryable = session.declare_queryable(key_expr).await.unwrap();
+    let (done, replied) = flume::unbounded();
+    tokio::spawn(async move {
+        while let Ok(query) = queryable.recv_async().await {
+            let payload = "x".repeat(1_000);
+            for _ in 0..count {
+                query
+                    .reply(key_expr, &payload)
+                    .encoding(Encoding::TEXT_PLAIN)
+                    .await
+                    .unwrap();
+            }
+            drop(query);
+            done.send(()).unwrap();
+        }
+    });
+    replied
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_query_replies() {
+    const PORT: u16 = 18571;
+    const REPLIES: usize = 1_000;
+    let session = common::start(Config::default(), PORT, json!({})).await;
+    let _replied = reply(&session, "test/query/replies", REPLIES).await;
+
+    let mut req = common::request(Method::Get, "/test/query/replies");
+    req.insert_header("X-Zenoh-Consolidation", "none");
+    let (status, body) = common::send(PORT, req).await;
+    assert_eq!(status, http_types::StatusCode::Ok);
+    let replies: Vec<Value> = serde_json::from_str(&body).unwrap();
+    assert_eq!(replies.len(), REPLIES);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_query_slow_client() {
+    const PORT: u16 = 18572;
+    const REPLIES: usize = 30_000;
+    let session = common::start(Config::default(), PORT, json!({})).await;
+    let replied = reply(&session, "test/query/slow", REPLIES).await;
+
+    let stream = TcpStream::connect(("127.0.0.1", PORT)).await.unwrap();
+    let mut req = common::request(Method::Get, "/test/query/slow");
+    req.url_mut().set_port(Some(PORT)).unwrap();
+    req.insert_header("X-Zenoh-Consolidation", "none");
+    let mut res = tokio::time::timeout(TIMEOUT, async_h1::connect(stream.compat(), req))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(res.status(), http_types::StatusCode::Ok);
+
+    // The client doesn't read the body while the replies are sent
+    tokio::time::timeout(TIMEOUT, replied.recv_async())
+        .await
+        .expect("The replies were blocked by the client")
+        .unwrap();
+
+    // The response is aborted once the buffered replies are read, not ended as if complete
+    let body = tokio::time::timeout(TIMEOUT, res.body_string())
+        .await
+        .unwrap();
+    assert!(body.is_err());
+}
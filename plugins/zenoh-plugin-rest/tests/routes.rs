@@ -0,0 +1,234 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+// Test the `/@info`, `/@matching` and `/@liveliness` routes -
+// 1. they describe the session of the plugin, and declare and query its liveliness tokens
+// 2. they are subject to the `access_control` rules of the authenticated clients, except `/@info`
+mod common;
+
+use std::time::Duration;
+
+use http_types::{Method, StatusCode};
+use serde_json::{json, Value};
+use zenoh::{query::Reply, Config};
+
+async fn get_json(port: u16, path: &str) -> Value {
+    let (status, body) = common::send(port, common::request(Method::Get, path)).await;
+    assert_eq!(status, StatusCode::Ok, "{path}: {body}");
+    serde_json::from_str(&body).unwrap()
+}
+
+async fn status(port: u16, method: Method, path: &str, token: Option<&str>) -> StatusCode {
+    let mut req = common::request(method, path);
+    if let Some(token) = token {
+        req.insert_header("Authorization", format!("Bearer {token}"));
+    }
+    common::send(port, req).await.0
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_info_routes() {
+    const PORT: u16 = 18581;
+    let session = common::start(Config::default(), PORT, json!({})).await;
+
+    let info = get_json(PORT, "/@info").await;
+    assert_eq!(info["zid"], session.zid().to_string());
+    assert_eq!(info["routers"], json!([]));
+    assert_eq!(info["peers"], json!([]));
+    assert!(info["locators"].is_array());
+    assert!(get_json(PORT, "/@info/transports").await.is_array());
+    assert!(get_json(PORT, "/@info/links").await.is_array());
+
+    let path = "/@matching/subscribers/test/routes/info/a";
+    assert_eq!(
+        get_json(PORT, path).await,
+        json!({"key_expr": "test/routes/info/a", "matching": false})
+    );
+    let subscriber = session
+        .declare_subscriber("test/routes/info/*")
+        .await
+        .unwrap();
+    assert_eq!(get_json(PORT, path).await["matching"], true);
+    subscriber.undeclare().await.unwrap();
+
+    let path = "/@matching/queryables/test/routes/info/a";
+    assert_eq!(get_json(PORT, path).await["matching"], false);
+    let _queryable = session
+        .declare_queryable("test/routes/info/**")
+        .await
+        .unwrap();
+    assert_eq!(get_json(PORT, path).await["matching"], true);
+
+    assert_eq!(
+        status(PORT, Method::Get, "/@matching/subscribers/a//b", None).await,
+        StatusCode::BadRequest
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_liveliness_routes() {
+    const PORT: u16 = 18582;
+    let session = common::start(Config::default(), PORT, json!({})).await;
+
+    let path = "/@liveliness/test/routes/liveliness/token";
+    assert_eq!(status(PORT, Method::Put, path, None).await, StatusCode::Ok);
+    // Declaring the same token twice is idempotent
+    assert_eq!(status(PORT, Method::Put, path, None).await, StatusCode::Ok);
+    let replies: Vec<Reply> = session
+        .liveliness()
+        .get("test/routes/liveliness/**")
+        .await
+        .unwrap()
+        .into_iter()
+        .collect();
+    assert_eq!(replies.len(), 1);
+
+    let _token = session
+        .liveliness()
+        .declare_token("test/routes/liveliness/other")
+        .await
+        .unwrap();
+    let tokens = get_json(PORT, "/@liveliness/test/routes/liveliness/**").await;
+    let mut keys: Vec<_> = tokens
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|token| token["key"].as_str().unwrap().to_string())
+        .collect();
+    keys.sort();
+    assert_eq!(
+        keys,
+        [
+            "test/routes/liveliness/other",
+            "test/routes/liveliness/token"
+        ]
+    );
+
+    assert_eq!(
+        status(PORT, Method::Delete, path, None).await,
+        StatusCode::Ok
+    );
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let tokens = get_json(PORT, "/@liveliness/test/routes/liveliness/token").await;
+    assert_eq!(tokens, json!([]));
+    assert_eq!(
+        status(PORT, Method::Delete, path, None).await,
+        StatusCode::NotFound
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_routes_access_control() {
+    const PORT: u16 = 18583;
+    let mut config = Config::default();
+    config
+        .insert_json5(
+            "access_control",
+            r#"{
+                enabled: true,
+                default_permission: "deny",
+                rules: [
+                    {
+                        id: "liveliness_query",
+                        messages: ["liveliness_query"],
+                        flows: ["ingress"],
+                        permission: "allow",
+                        key_exprs: ["test/routes/acl/**"],
+                    },
+                    {
+                        id: "liveliness_token",
+                        messages: ["liveliness_token"],
+                        flows: ["ingress", "egress"],
+                        permission: "allow",
+                        key_exprs: ["test/routes/acl/allowed/**"],
+                    },
+                ],
+                subjects: [{ id: "alice", usernames: ["alice"] }],
+                policies: [{ rules: ["liveliness_query", "liveliness_token"], subjects: ["alice"] }],
+            }"#,
+        )
+        .unwrap();
+    let _session = common::start(
+        config,
+        PORT,
+        json!({"auth": {"bearer_tokens": {"alice": "alice-token"}}}),
+    )
+    .await;
+    let alice = Some("alice-token");
+
+    // The requests must be authenticated, even on `/@info`
+    assert_eq!(
+        status(PORT, Method::Get, "/@info", None).await,
+        StatusCode::Unauthorized
+    );
+    // `/@info` is not subject to the rules
+    assert_eq!(
+        status(PORT, Method::Get, "/@info", alice).await,
+        StatusCode::Ok
+    );
+    assert_eq!(
+        status(PORT, Method::Get, "/@info/links", alice).await,
+        StatusCode::Ok
+    );
+
+    // The liveliness tokens are declared and undeclared as `liveliness_token` messages
+    let path = "/@liveliness/test/routes/acl/token";
+    assert_eq!(
+        status(PORT, Method::Put, path, alice).await,
+        StatusCode::Forbidden
+    );
+    assert_eq!(
+        status(PORT, Method::Delete, path, alice).await,
+        StatusCode::Forbidden
+    );
+    let path = "/@liveliness/test/routes/acl/allowed/token";
+    assert_eq!(status(PORT, Method::Put, path, alice).await, StatusCode::Ok);
+
+    // The liveliness queries are `liveliness_query` messages
+    assert_eq!(
+        status(PORT, Method::Get, "/@liveliness/test/routes/acl/**", alice).await,
+        StatusCode::Ok
+    );
+    assert_eq!(
+        status(PORT, Method::Get, "/@liveliness/test/routes/**", alice).await,
+        StatusCode::Forbidden
+    );
+
+    assert_eq!(
+        status(PORT, Method::Delete, path, alice).await,
+        StatusCode::Ok
+    );
+
+    // The matching status of subscribers and queryables are `put` and `query` messages
+    assert_eq!(
+        status(
+            PORT,
+            Method::Get,
+            "/@matching/subscribers/test/routes/acl/a",
+            alice
+        )
+        .await,
+        StatusCode::Forbidden
+    );
+    assert_eq!(
+        status(
+            PORT,
+            Method::Get,
+            "/@matching/queryables/test/routes/acl/a",
+            alice
+        )
+        .await,
+        StatusCode::Forbidden
+    );
+}
@@ -0,0 +1,241 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+// Test the WebSocket endpoint over a real connection -
+// 1. the requests of the protocol are served and acknowledged
+// 2. a client that doesn't read its messages is disconnected, without blocking the publications
+// 3. the connections from origins that aren't allowed are rejected before the upgrade
+mod common;
+
+use std::time::Duration;
+
+use futures::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use tokio::net::TcpStream;
+use tokio_tungstenite::{
+    connect_async,
+    tungstenite::{client::IntoClientRequest, protocol::frame::coding::CloseCode, Error, Message},
+    MaybeTlsStream, WebSocketStream,
+};
+use zenoh::{bytes::Encoding, Config, Wait};
+
+use crate::common::TIMEOUT;
+
+type Client = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+async fn connect(port: u16) -> Client {
+    let (client, _) = connect_async(format!("ws://127.0.0.1:{port}/"))
+        .await
+        .unwrap();
+    client
+}
+
+async fn request(client: &mut Client, request: Value) {
+    client
+        .send(Message::Text(request.to_string()))
+        .await
+        .unwrap();
+}
+
+async fn response(client: &mut Client) -> Value {
+    loop {
+        match tokio::time::timeout(TIMEOUT, client.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap()
+        {
+            Message::Text(text) => return serde_json::from_str(&text).unwrap(),
+            Message::Ping(_) | Message::Pong(_) => continue,
+            message => panic!("Unexpected message: {message:?}"),
+        }
+    }
+}
+
+/// Returns the next `count` responses, sorted by type to not depend on the order in which the
+/// samples and the acknowledgments are queued.
+async fn responses(client: &mut Client, count: usize) -> Vec<Value> {
+    let mut responses = Vec::new();
+    for _ in 0..count {
+        responses.push(response(client).await);
+    }
+    responses.sort_by_key(|response| response["type"].as_str().unwrap().to_string());
+    responses
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_ws_protocol() {
+    const PORT: u16 = 18561;
+    let session = common::start(Config::default(), PORT, json!({})).await;
+    let _queryable = session
+        .declare_queryable("test/ws/protocol/q")
+        .callback(|query| {
+            query
+                .reply("test/ws/protocol/q", "answer")
+                .encoding(Encoding::TEXT_PLAIN)
+                .wait()
+                .unwrap()
+        })
+        .await
+        .unwrap();
+
+    let mut client = connect(PORT).await;
+
+    request(
+        &mut client,
+        json!({"op": "subscribe", "id": 1, "key_expr": "test/ws/protocol/**"}),
+    )
+    .await;
+    assert_eq!(response(&mut client).await, json!({"type": "ok", "id": 1}));
+
+    request(
+        &mut client,
+        json!({"op": "put", "id": 2, "key_expr": "test/ws/protocol/a", "value": "hello"}),
+    )
+    .await;
+    let put = responses(&mut client, 2).await;
+    assert_eq!(put[0], json!({"type": "ok", "id": 2}));
+    assert_eq!(put[1]["type"], "sample");
+    assert_eq!(put[1]["id"], 1);
+    assert_eq!(put[1]["kind"], "PUT");
+    assert_eq!(put[1]["key"], "test/ws/protocol/a");
+    assert_eq!(put[1]["value"], "hello");
+
+    request(
+        &mut client,
+        json!({"op": "delete", "key_expr": "test/ws/protocol/a"}),
+    )
+    .await;
+    let delete = response(&mut client).await;
+    assert_eq!(delete["type"], "sample");
+    assert_eq!(delete["kind"], "DELETE");
+
+    request(
+        &mut client,
+        json!({"op": "get", "id": 3, "selector": "test/ws/protocol/q"}),
+    )
+    .await;
+    let reply = response(&mut client).await;
+    assert_eq!(reply["type"], "reply");
+    assert_eq!(reply["id"], 3);
+    assert_eq!(reply["key"], "test/ws/protocol/q");
+    assert_eq!(reply["value"], "answer");
+    assert_eq!(
+        response(&mut client).await,
+        json!({"type": "final", "id": 3})
+    );
+
+    request(&mut client, json!({"op": "unsubscribe", "id": 1})).await;
+    assert_eq!(response(&mut client).await, json!({"type": "ok", "id": 1}));
+    request(&mut client, json!({"op": "unsubscribe", "id": 1})).await;
+    let error = response(&mut client).await;
+    assert_eq!(error["type"], "error");
+    assert_eq!(error["id"], 1);
+
+    request(&mut client, json!({"op": "publish", "id": 4})).await;
+    let error = response(&mut client).await;
+    assert_eq!(error["type"], "error");
+    assert_eq!(error["id"], Value::Null);
+
+    // The samples are no longer sent once unsubscribed
+    request(
+        &mut client,
+        json!({"op": "put", "id": 5, "key_expr": "test/ws/protocol/a", "value": 1}),
+    )
+    .await;
+    assert_eq!(response(&mut client).await, json!({"type": "ok", "id": 5}));
+
+    client.close(None).await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_ws_slow_client() {
+    const PORT: u16 = 18562;
+    const SAMPLES: usize = 20_000;
+    let session = common::start(Config::default(), PORT, json!({})).await;
+
+    let mut client = connect(PORT).await;
+    request(
+        &mut client,
+        json!({"op": "subscribe", "id": 1, "key_expr": "test/ws/slow/**"}),
+    )
+    .await;
+    assert_eq!(response(&mut client).await, json!({"type": "ok", "id": 1}));
+
+    // The client doesn't read the samples while they are published
+    let payload = "x".repeat(1_000);
+    tokio::time::timeout(TIMEOUT, async {
+        for _ in 0..SAMPLES {
+            session.put("test/ws/slow/a", &payload).await.unwrap();
+        }
+    })
+    .await
+    .expect("The publications were blocked by the client");
+
+    // The client is disconnected, after the samples that were buffered
+    let mut received = 0;
+    let end = tokio::time::timeout(TIMEOUT, async {
+        loop {
+            match client.next().await {
+                Some(Ok(Message::Text(_))) => received += 1,
+                Some(Ok(Message::Close(frame))) => return frame.map(|frame| frame.code),
+                Some(Ok(_)) => continue,
+                Some(Err(_)) | None => return None,
+            }
+        }
+    })
+    .await
+    .expect("The client was not disconnected");
+    assert!(received < SAMPLES);
+    assert!(end.is_none() || end == Some(CloseCode::Policy));
+
+    // The REST API keeps serving the other clients
+    let mut client = connect(PORT).await;
+    request(&mut client, json!({"op": "unsubscribe", "id": 1})).await;
+    assert_eq!(response(&mut client).await["type"], "error");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_ws_allowed_origins() {
+    const PORT: u16 = 18563;
+    let _session = common::start(
+        Config::default(),
+        PORT,
+        json!({"websocket_allowed_origins": ["https://example.com"]}),
+    )
+    .await;
+
+    let connect_from = |origin: Option<&'static str>| async move {
+        let mut req = format!("ws://127.0.0.1:{PORT}/")
+            .into_client_request()
+            .unwrap();
+        if let Some(origin) = origin {
+            req.headers_mut().insert("origin", origin.parse().unwrap());
+        }
+        connect_async(req).await
+    };
+
+    let mut client = connect_from(Some("https://example.com")).await.unwrap().0;
+    request(&mut client, json!({"op": "unsubscribe", "id": 1})).await;
+    assert_eq!(response(&mut client).await["type"], "error");
+
+    // The clients that are not browsers don't send any origin
+    assert!(connect_from(None).await.is_ok());
+
+    match connect_from(Some("https://evil.org")).await {
+        Err(Error::Http(res)) => assert_eq!(res.status(), 403),
+        res => panic!("Unexpected connection result: {res:?}"),
+    }
+}
@@ -0,0 +1,545 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! On-disk layout of a durable Storage.
+//!
+//! A durable Storage is a directory containing two files:
+//!
+//! - `data.log`: an append-only log of every put and delete received by the Storage. It starts with
+//!   a header (magic number + generation) followed by framed [Record]s. Each frame is made of the
+//!   length of the record (u32, little endian), its xxh3 checksum (u64, little endian) and the
+//!   bincode serialisation of the record.
+//!
+//! - `data.idx`: a snapshot of the in-memory index, written on clean shutdown. It records the
+//!   generation and the length of the log it covers so that, on start up, only the records appended
+//!   after the snapshot have to be replayed. The generation changes at every compaction.
+//!
+//! Any truncated or corrupted frame found at the end of the log (e.g. following a crash in the
+//! middle of a write) is discarded when the log is opened. As every write is, by default,
+//! synchronised to disk before being acknowledged, a crash can only lose updates that were not yet
+//! acknowledged to the storage manager.
+
+use std::{
+    collections::BTreeMap,
+    fs::{self, File, OpenOptions},
+    io::{BufReader, BufWriter, ErrorKind, Read, Seek, SeekFrom, Write},
+    ops::Bound,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use xxhash_rust::xxh3::xxh3_64;
+use zenoh::{
+    bytes::{Encoding, ZBytes},
+    internal::{bail, zerror},
+    key_expr::OwnedKeyExpr,
+    time::Timestamp,
+    Result as ZResult,
+};
+use zenoh_backend_traits::{ScanKey, StoredData};
+
+const LOG_FILE: &str = "data.log";
+const INDEX_FILE: &str = "data.idx";
+const TMP_SUFFIX: &str = ".tmp";
+
+const MAGIC: &[u8; 8] = b"ZDURLOG1";
+const HEADER_LEN: u64 = (MAGIC.len() + std::mem::size_of::<u64>()) as u64;
+const FRAME_HEADER_LEN: u64 = (std::mem::size_of::<u32>() + std::mem::size_of::<u64>()) as u64;
+
+/// The log is compacted once it holds more than this amount of obsolete bytes *and* more obsolete
+/// bytes than live ones.
+const COMPACTION_THRESHOLD: u64 = 16 * 1024 * 1024;
+
+/// A put (if `value` is `Some`) or a delete (if `value` is `None`) as written in the log.
+#[derive(Debug, Serialize, Deserialize)]
+struct Record {
+    key: Option<OwnedKeyExpr>,
+    timestamp: Timestamp,
+    value: Option<RecordValue>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RecordValue {
+    encoding: String,
+    payload: Vec<u8>,
+}
+
+/// Location, in the log, of the latest value associated to a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct IndexEntry {
+    pub(crate) timestamp: Timestamp,
+    offset: u64,
+    len: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct IndexSnapshot {
+    generation: u64,
+    log_len: u64,
+    garbage: u64,
+    entries: Vec<(Option<OwnedKeyExpr>, IndexEntry)>,
+}
+
+/// Outcome of the insertion of a put or a delete in the [DataLog].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Insertion {
+    /// A more recent value is already stored for that key: nothing was written.
+    Outdated,
+    /// The key was not present (or was deleted).
+    Inserted,
+    /// The key was present and its value replaced (or deleted).
+    Replaced,
+}
+
+pub(crate) struct DataLog {
+    dir: PathBuf,
+    file: File,
+    generation: u64,
+    len: u64,
+    garbage: u64,
+    sync: bool,
+    /// The entries of the keys currently stored, ordered so that the paginated scans seek to
+    /// their cursor.
+    index: BTreeMap<ScanKey, IndexEntry>,
+}
+
+impl DataLog {
+    /// Opens (or creates) the log stored in `dir`, rebuilding its index.
+    ///
+    /// If `sync` is set, every write is synchronised to disk before returning.
+    pub(crate) fn open(dir: &Path, sync: bool) -> ZResult<Self> {
+        fs::create_dir_all(dir)
+            .map_err(|e| zerror!("Failed to create directory {}: {e}", dir.display()))?;
+        let log_path = dir.join(LOG_FILE);
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(&log_path)
+            .map_err(|e| zerror!("Failed to open {}: {e}", log_path.display()))?;
+
+        let file_len = file.metadata()?.len();
+        let generation = if file_len < HEADER_LEN {
+            // Either a brand new log or a crash happened while writing its header: in both cases
+            // there is no data to recover.
+            file.set_len(0)?;
+            let generation = rand::random::<u64>();
+            file.write_all(&header(generation))?;
+            file.sync_all()?;
+            sync_dir(dir);
+            generation
+        } else {
+            let mut buf = [0u8; HEADER_LEN as usize];
+            file.seek(SeekFrom::Start(0))?;
+            file.read_exact(&mut buf)?;
+            if &buf[..MAGIC.len()] != MAGIC {
+                bail!("{} is not a durable storage log", log_path.display());
+            }
+            u64::from_le_bytes(buf[MAGIC.len()..].try_into().unwrap())
+        };
+
+        let mut log = DataLog {
+            dir: dir.to_path_buf(),
+            file,
+            generation,
+            len: HEADER_LEN,
+            garbage: 0,
+            sync,
+            index: BTreeMap::new(),
+        };
+
+        let mut replay_from = HEADER_LEN;
+        if let Some(snapshot) = log.read_index_snapshot() {
+            if snapshot.generation == generation && snapshot.log_len <= file_len {
+                replay_from = snapshot.log_len;
+                log.garbage = snapshot.garbage;
+                log.index = snapshot
+                    .entries
+                    .into_iter()
+                    .map(|(key, entry)| (ScanKey(key), entry))
+                    .collect();
+            }
+        }
+
+        log.replay(replay_from, file_len)?;
+        // The snapshot is now stale: remove it so that a crash does not make us reload an index
+        // that does not cover the records appended from now on.
+        let _ = fs::remove_file(dir.join(INDEX_FILE));
+
+        Ok(log)
+    }
+
+    /// Replays the records located between `from` and `file_len`, truncating the log at the first
+    /// incomplete or corrupted record.
+    fn replay(&mut self, from: u64, file_len: u64) -> ZResult<()> {
+        let mut file = self.file.try_clone()?;
+        file.seek(SeekFrom::Start(from))?;
+        let mut reader = BufReader::new(file);
+        let mut offset = from;
+
+        while offset < file_len {
+            match read_frame(&mut reader) {
+                Ok(Some((record, frame_len))) => {
+                    self.apply(record, offset, frame_len);
+                    offset += frame_len;
+                }
+                Ok(None) => {
+                    tracing::warn!(
+                        "Discarding {} bytes of incomplete or corrupted data at the end of {}",
+                        file_len - offset,
+                        self.dir.join(LOG_FILE).display()
+                    );
+                    break;
+                }
+                Err(e) => bail!("Failed to read {}: {e}", self.dir.join(LOG_FILE).display()),
+            }
+        }
+
+        if offset < file_len {
+            self.file.set_len(offset)?;
+            self.file.sync_all()?;
+        }
+        self.len = offset;
+        Ok(())
+    }
+
+    /// Updates the index following the insertion of `record` at `offset`.
+    fn apply(&mut self, record: Record, offset: u64, frame_len: u64) -> Insertion {
+        let previous = match record.value {
+            Some(_) => self.index.insert(
+                ScanKey(record.key),
+                IndexEntry {
+                    timestamp: record.timestamp,
+                    offset,
+                    len: frame_len,
+                },
+            ),
+            None => {
+                // A tombstone only needs to be kept in the log until the next compaction.
+                self.garbage += frame_len;
+                self.index.remove(&ScanKey(record.key))
+            }
+        };
+
+        match previous {
+            Some(entry) => {
+                self.garbage += entry.len;
+                Insertion::Replaced
+            }
+            None => Insertion::Inserted,
+        }
+    }
+
+    fn is_outdated(&self, key: &Option<OwnedKeyExpr>, timestamp: &Timestamp) -> bool {
+        self.index
+            .get(&ScanKey(key.clone()))
+            .is_some_and(|entry| entry.timestamp > *timestamp)
+    }
+
+    pub(crate) fn put(
+        &mut self,
+        key: Option<OwnedKeyExpr>,
+        payload: &ZBytes,
+        encoding: &Encoding,
+        timestamp: Timestamp,
+    ) -> ZResult<Insertion> {
+        if self.is_outdated(&key, &timestamp) {
+            return Ok(Insertion::Outdated);
+        }
+
+        self.append(Record {
+            key,
+            timestamp,
+            value: Some(RecordValue {
+                encoding: encoding.to_string(),
+                payload: payload.to_bytes().into_owned(),
+            }),
+        })
+    }
+
+    pub(crate) fn delete(
+        &mut self,
+        key: Option<OwnedKeyExpr>,
+        timestamp: Timestamp,
+    ) -> ZResult<Insertion> {
+        if self.is_outdated(&key, &timestamp) {
+            return Ok(Insertion::Outdated);
+        }
+
+        self.append(Record {
+            key,
+            timestamp,
+            value: None,
+        })
+    }
+
+    /// Runs `f`, synchronising the log to disk once at its end rather than after each write.
+    pub(crate) fn batch<T>(&mut self, f: impl FnOnce(&mut Self) -> T) -> ZResult<T> {
+        let sync = std::mem::replace(&mut self.sync, false);
+        let result = f(self);
+        self.sync = sync;
+        if sync {
+            self.file.sync_data().map_err(|e| {
+                zerror!(
+                    "Failed to synchronise {}: {e}",
+                    self.dir.join(LOG_FILE).display()
+                )
+            })?;
+        }
+        Ok(result)
+    }
+
+    fn append(&mut self, record: Record) -> ZResult<Insertion> {
+        let frame = frame(&record)?;
+        let offset = self.len;
+        if let Err(e) = self.file.write_all(&frame) {
+            // Do not leave a partial frame behind us: the next records would be unreadable.
+            let _ = self.file.set_len(offset);
+            bail!(
+                "Failed to append to {}: {e}",
+                self.dir.join(LOG_FILE).display()
+            );
+        }
+        if self.sync {
+            self.file.sync_data()?;
+        }
+        self.len += frame.len() as u64;
+
+        let insertion = self.apply(record, offset, frame.len() as u64);
+        if self.garbage > COMPACTION_THRESHOLD && self.garbage > self.len - self.garbage {
+            if let Err(e) = self.compact() {
+                tracing::error!("Compaction of {} failed: {e:?}", self.dir.display());
+            }
+        }
+
+        Ok(insertion)
+    }
+
+    pub(crate) fn get(&mut self, key: &Option<OwnedKeyExpr>) -> ZResult<Option<StoredData>> {
+        let Some(entry) = self.index.get(&ScanKey(key.clone())).copied() else {
+            return Ok(None);
+        };
+
+        let record = self.read_record(&entry)?;
+        match record.value {
+            Some(value) => Ok(Some(StoredData {
+                payload: ZBytes::from(value.payload),
+                encoding: Encoding::from(value.encoding),
+                timestamp: record.timestamp,
+            })),
+            None => bail!(
+                "Index of {} points to a tombstone for key {key:?}",
+                self.dir.display()
+            ),
+        }
+    }
+
+    fn read_record(&mut self, entry: &IndexEntry) -> ZResult<Record> {
+        self.file.seek(SeekFrom::Start(entry.offset))?;
+        let mut reader = (&self.file).take(entry.len);
+        match read_frame(&mut reader)? {
+            Some((record, _)) => Ok(record),
+            None => bail!(
+                "Corrupted record at offset {} of {}",
+                entry.offset,
+                self.dir.join(LOG_FILE).display()
+            ),
+        }
+    }
+
+    /// Returns an iterator over the keys currently stored and the timestamp of their value.
+    pub(crate) fn entries(&self) -> impl Iterator<Item = (&Option<OwnedKeyExpr>, &Timestamp)> {
+        self.entries_from(Bound::Unbounded)
+    }
+
+    /// Same as [DataLog::entries], for the keys following `start` in the order of [ScanKey].
+    pub(crate) fn entries_from(
+        &self,
+        start: Bound<ScanKey>,
+    ) -> impl Iterator<Item = (&Option<OwnedKeyExpr>, &Timestamp)> {
+        self.index
+            .range((start, Bound::Unbounded))
+            .map(|(key, entry)| (&key.0, &entry.timestamp))
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Size, in bytes, of the log on disk.
+    pub(crate) fn size(&self) -> u64 {
+        self.len
+    }
+
+    /// Rewrites the log, keeping only the latest value of each key.
+    ///
+    /// The new log is fully written and synchronised under a temporary name before atomically
+    /// replacing the current one, so that a crash during compaction leaves the current log intact.
+    pub(crate) fn compact(&mut self) -> ZResult<()> {
+        tracing::debug!(
+            "Compacting {} ({} obsolete bytes out of {})",
+            self.dir.join(LOG_FILE).display(),
+            self.garbage,
+            self.len
+        );
+        let log_path = self.dir.join(LOG_FILE);
+        let tmp_path = self.dir.join(format!("{LOG_FILE}{TMP_SUFFIX}"));
+        let generation = rand::random::<u64>();
+
+        let mut keys = self
+            .index
+            .iter()
+            .map(|(key, entry)| (key.clone(), *entry))
+            .collect::<Vec<_>>();
+        // Reading the records in the order they were written avoids random seeks.
+        keys.sort_unstable_by_key(|(_, entry)| entry.offset);
+
+        let mut new_index = BTreeMap::new();
+        let mut writer = BufWriter::new(File::create(&tmp_path)?);
+        writer.write_all(&header(generation))?;
+        let mut len = HEADER_LEN;
+        for (key, entry) in keys {
+            let frame = frame(&self.read_record(&entry)?)?;
+            writer.write_all(&frame)?;
+            new_index.insert(
+                key,
+                IndexEntry {
+                    timestamp: entry.timestamp,
+                    offset: len,
+                    len: frame.len() as u64,
+                },
+            );
+            len += frame.len() as u64;
+        }
+        writer
+            .into_inner()
+            .map_err(|e| zerror!("Failed to write {}: {e}", tmp_path.display()))?
+            .sync_all()?;
+
+        fs::rename(&tmp_path, &log_path)?;
+        sync_dir(&self.dir);
+
+        self.file = OpenOptions::new().read(true).append(true).open(&log_path)?;
+        self.generation = generation;
+        self.len = len;
+        self.garbage = 0;
+        self.index = new_index;
+
+        Ok(())
+    }
+
+    /// Writes a snapshot of the index to disk, allowing the next [DataLog::open] to skip the replay
+    /// of the records it covers.
+    pub(crate) fn write_index_snapshot(&self) -> ZResult<()> {
+        let snapshot = IndexSnapshot {
+            generation: self.generation,
+            log_len: self.len,
+            garbage: self.garbage,
+            entries: self
+                .index
+                .iter()
+                .map(|(key, entry)| (key.0.clone(), *entry))
+                .collect(),
+        };
+        let body = bincode::serialize(&snapshot)?;
+        let tmp_path = self.dir.join(format!("{INDEX_FILE}{TMP_SUFFIX}"));
+        let mut file = File::create(&tmp_path)?;
+        file.write_all(&xxh3_64(&body).to_le_bytes())?;
+        file.write_all(&body)?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, self.dir.join(INDEX_FILE))?;
+        sync_dir(&self.dir);
+        Ok(())
+    }
+
+    fn read_index_snapshot(&self) -> Option<IndexSnapshot> {
+        let path = self.dir.join(INDEX_FILE);
+        let content = match fs::read(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => return None,
+            Err(e) => {
+                tracing::warn!("Failed to read {}: {e}", path.display());
+                return None;
+            }
+        };
+        if content.len() < std::mem::size_of::<u64>() {
+            return None;
+        }
+        let (checksum, body) = content.split_at(std::mem::size_of::<u64>());
+        if u64::from_le_bytes(checksum.try_into().unwrap()) != xxh3_64(body) {
+            tracing::warn!("Ignoring corrupted index {}", path.display());
+            return None;
+        }
+        bincode::deserialize(body)
+            .map_err(|e| tracing::warn!("Ignoring invalid index {}: {e}", path.display()))
+            .ok()
+    }
+}
+
+fn header(generation: u64) -> Vec<u8> {
+    let mut header = Vec::with_capacity(HEADER_LEN as usize);
+    header.extend_from_slice(MAGIC);
+    header.extend_from_slice(&generation.to_le_bytes());
+    header
+}
+
+fn frame(record: &Record) -> ZResult<Vec<u8>> {
+    let body = bincode::serialize(record)?;
+    let body_len = u32::try_from(body.len())
+        .map_err(|_| zerror!("Record of {} bytes is too large", body.len()))?;
+    let mut frame = Vec::with_capacity(FRAME_HEADER_LEN as usize + body.len());
+    frame.extend_from_slice(&body_len.to_le_bytes());
+    frame.extend_from_slice(&xxh3_64(&body).to_le_bytes());
+    frame.extend_from_slice(&body);
+    Ok(frame)
+}
+
+/// Reads one frame, returning the decoded [Record] and the length of the frame.
+///
+/// Returns `None` if the frame is truncated or corrupted.
+fn read_frame<R: Read>(reader: &mut R) -> std::io::Result<Option<(Record, u64)>> {
+    let mut frame_header = [0u8; FRAME_HEADER_LEN as usize];
+    match reader.read_exact(&mut frame_header) {
+        Ok(()) => {}
+        Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    }
+    let body_len = u32::from_le_bytes(frame_header[..4].try_into().unwrap()) as usize;
+    let checksum = u64::from_le_bytes(frame_header[4..].try_into().unwrap());
+
+    let mut body = Vec::new();
+    let read = reader.take(body_len as u64).read_to_end(&mut body)?;
+    if read != body_len || xxh3_64(&body) != checksum {
+        return Ok(None);
+    }
+
+    Ok(bincode::deserialize(&body)
+        .ok()
+        .map(|record| (record, FRAME_HEADER_LEN + body_len as u64)))
+}
+
+/// Synchronises the directory entry so that a file creation or rename survives a crash.
+fn sync_dir(dir: &Path) {
+    #[cfg(unix)]
+    if let Err(e) = File::open(dir).and_then(|dir| dir.sync_all()) {
+        tracing::warn!("Failed to synchronise directory {}: {e}", dir.display());
+    }
+    #[cfg(not(unix))]
+    let _ = dir;
+}
+
+#[cfg(test)]
+#[path = "tests/data_log.test.rs"]
+mod tests;
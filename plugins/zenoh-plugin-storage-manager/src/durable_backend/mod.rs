@@ -0,0 +1,283 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use serde_json::Value;
+use zenoh::{
+    bytes::{Encoding, ZBytes},
+    internal::{bail, zenoh_home, zerror},
+    key_expr::{keyexpr, OwnedKeyExpr},
+    time::Timestamp,
+    Result as ZResult,
+};
+use zenoh_backend_traits::{
+    config::{StorageConfig, VolumeConfig},
+    *,
+};
+use zenoh_plugin_trait::{plugin_long_version, plugin_version, Plugin};
+use zenoh_util::ffi::JsonValue;
+
+use crate::DURABLE_BACKEND_NAME;
+
+mod data_log;
+use data_log::{DataLog, Insertion};
+
+/// Volume configuration: directory under which each storage creates its own sub-directory.
+const PROP_ROOT: &str = "root";
+/// Storage configuration: name of the sub-directory of the storage (defaults to its name).
+const PROP_DIR: &str = "dir";
+/// Storage configuration: if `false`, writes are not synchronised to disk before being
+/// acknowledged (defaults to `true`).
+const PROP_FSYNC: &str = "fsync";
+
+const DEFAULT_ROOT_DIRNAME: &str = "zenoh_backend_durable";
+
+pub struct DurableBackend {
+    config: VolumeConfig,
+    root: PathBuf,
+}
+
+impl Plugin for DurableBackend {
+    type StartArgs = VolumeConfig;
+    type Instance = VolumeInstance;
+
+    const DEFAULT_NAME: &'static str = DURABLE_BACKEND_NAME;
+    const PLUGIN_VERSION: &'static str = plugin_version!();
+    const PLUGIN_LONG_VERSION: &'static str = plugin_long_version!();
+
+    fn start(name: &str, args: &VolumeConfig) -> ZResult<VolumeInstance> {
+        let root = match args.rest.into_serde_map().get(PROP_ROOT) {
+            Some(Value::String(root)) => PathBuf::from(root),
+            None => zenoh_home().join(DEFAULT_ROOT_DIRNAME),
+            Some(_) => bail!(
+                "Invalid type for field `{PROP_ROOT}` of volume `{name}`. Only strings are \
+                 accepted."
+            ),
+        };
+        tracing::debug!("Durable volume '{}' stores its data in {:?}", name, root);
+
+        Ok(Box::new(DurableBackend {
+            config: args.clone(),
+            root,
+        }))
+    }
+}
+
+#[async_trait]
+impl Volume for DurableBackend {
+    fn get_admin_status(&self) -> JsonValue {
+        self.config.to_json_value().into()
+    }
+
+    fn get_capability(&self) -> Capability {
+        Capability {
+            persistence: Persistence::Durable,
+            history: History::Latest,
+        }
+    }
+
+    async fn create_storage(&self, properties: StorageConfig) -> ZResult<Box<dyn Storage>> {
+        tracing::debug!(
+            "Create Durable Storage with configuration: {:?}",
+            properties
+        );
+        Ok(Box::new(DurableStorage::new(&self.root, properties)?))
+    }
+}
+
+struct DurableStorage {
+    config: StorageConfig,
+    log: DataLog,
+}
+
+impl DurableStorage {
+    fn new(root: &Path, config: StorageConfig) -> ZResult<DurableStorage> {
+        let volume_cfg: Value = (&config.volume_cfg).into();
+        let dir = match volume_cfg.get(PROP_DIR) {
+            Some(Value::String(dir)) => dir.as_str(),
+            None => config.name.as_str(),
+            Some(_) => bail!(
+                "Invalid type for field `{PROP_DIR}` of storage `{}`. Only strings are accepted.",
+                config.name
+            ),
+        };
+        let fsync = match volume_cfg.get(PROP_FSYNC) {
+            Some(Value::Bool(fsync)) => *fsync,
+            None => true,
+            Some(_) => bail!(
+                "Invalid type for field `{PROP_FSYNC}` of storage `{}`. Only booleans are \
+                 accepted.",
+                config.name
+            ),
+        };
+
+        let log = DataLog::open(&root.join(dir), fsync)?;
+        tracing::debug!(
+            "Durable Storage '{}' recovered {} entries",
+            config.name,
+            log.len()
+        );
+
+        Ok(DurableStorage { config, log })
+    }
+}
+
+#[async_trait]
+impl Storage for DurableStorage {
+    fn get_admin_status(&self) -> JsonValue {
+        let mut status = self.config.to_json_value();
+        if let Value::Object(map) = &mut status {
+            map.insert("entries".into(), self.log.len().into());
+            map.insert("size".into(), self.log.size().into());
+        }
+        status.into()
+    }
+
+    async fn put(
+        &mut self,
+        key: Option<OwnedKeyExpr>,
+        payload: ZBytes,
+        encoding: Encoding,
+        timestamp: Timestamp,
+    ) -> ZResult<StorageInsertionResult> {
+        tracing::trace!("put for {:?}", key);
+        self.log
+            .put(key, &payload, &encoding, timestamp)
+            .map(put_result)
+    }
+
+    async fn delete(
+        &mut self,
+        key: Option<OwnedKeyExpr>,
+        timestamp: Timestamp,
+    ) -> ZResult<StorageInsertionResult> {
+        tracing::trace!("delete for {:?}", key);
+        self.log.delete(key, timestamp).map(delete_result)
+    }
+
+    async fn apply_batch(
+        &mut self,
+        operations: Vec<StorageOperation>,
+    ) -> Vec<ZResult<StorageInsertionResult>> {
+        tracing::trace!("apply batch of {} operations", operations.len());
+        let len = operations.len();
+        let results = self.log.batch(|log| {
+            operations
+                .into_iter()
+                .map(|operation| match operation {
+                    StorageOperation::Put {
+                        key,
+                        payload,
+                        encoding,
+                        timestamp,
+                    } => log.put(key, &payload, &encoding, timestamp).map(put_result),
+                    StorageOperation::Delete { key, timestamp } => {
+                        log.delete(key, timestamp).map(delete_result)
+                    }
+                })
+                .collect()
+        });
+        match results {
+            Ok(results) => results,
+            // None of the operations can be considered as durable.
+            Err(e) => (0..len).map(|_| Err(zerror!("{e}").into())).collect(),
+        }
+    }
+
+    async fn get(
+        &mut self,
+        key: Option<OwnedKeyExpr>,
+        _parameters: &str,
+    ) -> ZResult<Vec<StoredData>> {
+        tracing::trace!("get for {:?}", key);
+        match self.log.get(&key)? {
+            Some(data) => Ok(vec![data]),
+            None => Err(format!("Key {key:?} is not present").into()),
+        }
+    }
+
+    async fn get_all_entries(&self) -> ZResult<Vec<(Option<OwnedKeyExpr>, Timestamp)>> {
+        Ok(self
+            .log
+            .entries()
+            .map(|(key, timestamp)| (key.clone(), *timestamp))
+            .collect())
+    }
+
+    async fn get_matching_keys(
+        &self,
+        key_expr: &keyexpr,
+        strip_prefix: Option<&OwnedKeyExpr>,
+    ) -> ZResult<Vec<(Option<OwnedKeyExpr>, Timestamp)>> {
+        Ok(self
+            .log
+            .entries()
+            .filter(|(key, _)| stripped_key_intersects(key_expr, strip_prefix, key.as_ref()))
+            .map(|(key, timestamp)| (key.clone(), *timestamp))
+            .collect())
+    }
+
+    async fn get_matching_entries(
+        &mut self,
+        key_expr: &keyexpr,
+        strip_prefix: Option<&OwnedKeyExpr>,
+        _parameters: &str,
+        options: ScanOptions,
+    ) -> ZResult<ScanResult> {
+        tracing::trace!("get matching entries for {}", key_expr);
+        let (keys, cursor) = options.select_sorted(
+            self.log
+                .entries_from(options.start())
+                .filter(|(key, _)| stripped_key_intersects(key_expr, strip_prefix, key.as_ref()))
+                .map(|(key, _)| key.clone()),
+        );
+
+        let mut entries = Vec::with_capacity(keys.len());
+        for key in keys {
+            if let Some(data) = self.log.get(&key)? {
+                entries.push((key, vec![data]));
+            }
+        }
+
+        Ok(ScanResult { entries, cursor })
+    }
+}
+
+fn put_result(insertion: Insertion) -> StorageInsertionResult {
+    match insertion {
+        Insertion::Outdated => StorageInsertionResult::Outdated,
+        Insertion::Inserted => StorageInsertionResult::Inserted,
+        Insertion::Replaced => StorageInsertionResult::Replaced,
+    }
+}
+
+fn delete_result(insertion: Insertion) -> StorageInsertionResult {
+    match insertion {
+        Insertion::Outdated => StorageInsertionResult::Outdated,
+        Insertion::Inserted | Insertion::Replaced => StorageInsertionResult::Deleted,
+    }
+}
+
+impl Drop for DurableStorage {
+    fn drop(&mut self) {
+        tracing::trace!("DurableStorage::drop()");
+        if let Err(e) = self.log.write_index_snapshot() {
+            tracing::warn!(
+                "Durable Storage '{}' failed to save its index: {e:?}",
+                self.config.name
+            );
+        }
+    }
+}
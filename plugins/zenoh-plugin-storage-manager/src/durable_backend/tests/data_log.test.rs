@@ -0,0 +1,295 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+use std::{path::PathBuf, str::FromStr};
+
+use uhlc::HLC;
+use zenoh::{bytes::Encoding, key_expr::OwnedKeyExpr};
+
+use super::*;
+
+struct TestDir(PathBuf);
+
+impl TestDir {
+    fn new() -> Self {
+        let dir = std::env::temp_dir().join(format!("zenoh-durable-{}", uuid::Uuid::new_v4()));
+        Self(dir)
+    }
+}
+
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+fn key(key: &str) -> Option<OwnedKeyExpr> {
+    Some(OwnedKeyExpr::from_str(key).unwrap())
+}
+
+fn payload(data: &Option<StoredData>) -> String {
+    data.as_ref()
+        .unwrap()
+        .payload
+        .try_to_string()
+        .unwrap()
+        .into_owned()
+}
+
+#[test]
+fn test_put_get_delete() {
+    let dir = TestDir::new();
+    let hlc = HLC::default();
+    let mut log = DataLog::open(&dir.0, true).unwrap();
+
+    let ts_1 = hlc.new_timestamp();
+    assert_eq!(
+        log.put(key("a"), &"1".into(), &Encoding::TEXT_PLAIN, ts_1)
+            .unwrap(),
+        Insertion::Inserted
+    );
+    let data = log.get(&key("a")).unwrap();
+    assert_eq!(payload(&data), "1");
+    assert_eq!(data.as_ref().unwrap().encoding, Encoding::TEXT_PLAIN);
+    assert_eq!(data.unwrap().timestamp, ts_1);
+
+    let ts_2 = hlc.new_timestamp();
+    assert_eq!(
+        log.put(key("a"), &"2".into(), &Encoding::TEXT_PLAIN, ts_2)
+            .unwrap(),
+        Insertion::Replaced
+    );
+    assert_eq!(payload(&log.get(&key("a")).unwrap()), "2");
+
+    // An older value should not override a more recent one.
+    assert_eq!(
+        log.put(key("a"), &"0".into(), &Encoding::TEXT_PLAIN, ts_1)
+            .unwrap(),
+        Insertion::Outdated
+    );
+    assert_eq!(payload(&log.get(&key("a")).unwrap()), "2");
+
+    assert_eq!(
+        log.put(
+            None,
+            &"none".into(),
+            &Encoding::default(),
+            hlc.new_timestamp()
+        )
+        .unwrap(),
+        Insertion::Inserted
+    );
+    assert_eq!(payload(&log.get(&None).unwrap()), "none");
+
+    assert_eq!(
+        log.delete(key("a"), hlc.new_timestamp()).unwrap(),
+        Insertion::Replaced
+    );
+    assert!(log.get(&key("a")).unwrap().is_none());
+    assert_eq!(log.len(), 1);
+}
+
+#[test]
+fn test_recovery_after_crash() {
+    let dir = TestDir::new();
+    let hlc = HLC::default();
+
+    let ts_b = hlc.new_timestamp();
+    {
+        let mut log = DataLog::open(&dir.0, true).unwrap();
+        log.put(
+            key("a"),
+            &"1".into(),
+            &Encoding::default(),
+            hlc.new_timestamp(),
+        )
+        .unwrap();
+        log.put(key("b"), &"2".into(), &Encoding::default(), ts_b)
+            .unwrap();
+        log.delete(key("a"), hlc.new_timestamp()).unwrap();
+        log.put(
+            key("c"),
+            &"3".into(),
+            &Encoding::default(),
+            hlc.new_timestamp(),
+        )
+        .unwrap();
+        // Dropping the log without writing the index snapshot simulates a crash.
+    }
+
+    // Simulate a crash in the middle of the write of the last record.
+    let log_path = dir.0.join(LOG_FILE);
+    let len = fs::metadata(&log_path).unwrap().len();
+    OpenOptions::new()
+        .write(true)
+        .open(&log_path)
+        .unwrap()
+        .set_len(len - 3)
+        .unwrap();
+
+    let mut log = DataLog::open(&dir.0, true).unwrap();
+    assert_eq!(log.len(), 1);
+    assert!(log.get(&key("a")).unwrap().is_none());
+    assert!(log.get(&key("c")).unwrap().is_none());
+    let data = log.get(&key("b")).unwrap();
+    assert_eq!(payload(&data), "2");
+    assert_eq!(data.unwrap().timestamp, ts_b);
+
+    // The log must be writable after having been truncated.
+    log.put(
+        key("d"),
+        &"4".into(),
+        &Encoding::default(),
+        hlc.new_timestamp(),
+    )
+    .unwrap();
+    drop(log);
+
+    let mut log = DataLog::open(&dir.0, true).unwrap();
+    assert_eq!(log.len(), 2);
+    assert_eq!(payload(&log.get(&key("d")).unwrap()), "4");
+}
+
+#[test]
+fn test_index_snapshot() {
+    let dir = TestDir::new();
+    let hlc = HLC::default();
+
+    {
+        let mut log = DataLog::open(&dir.0, true).unwrap();
+        log.put(
+            key("a"),
+            &"1".into(),
+            &Encoding::default(),
+            hlc.new_timestamp(),
+        )
+        .unwrap();
+        log.write_index_snapshot().unwrap();
+        // Appended after the snapshot: must be replayed.
+        log.put(
+            key("b"),
+            &"2".into(),
+            &Encoding::default(),
+            hlc.new_timestamp(),
+        )
+        .unwrap();
+    }
+    assert!(dir.0.join(INDEX_FILE).exists());
+
+    let mut log = DataLog::open(&dir.0, true).unwrap();
+    assert!(!dir.0.join(INDEX_FILE).exists());
+    assert_eq!(log.len(), 2);
+    assert_eq!(payload(&log.get(&key("a")).unwrap()), "1");
+    assert_eq!(payload(&log.get(&key("b")).unwrap()), "2");
+}
+
+#[test]
+fn test_compaction() {
+    let dir = TestDir::new();
+    let hlc = HLC::default();
+
+    let mut log = DataLog::open(&dir.0, true).unwrap();
+    for i in 0..10 {
+        log.put(
+            key("a"),
+            &i.to_string().into(),
+            &Encoding::default(),
+            hlc.new_timestamp(),
+        )
+        .unwrap();
+    }
+    log.put(
+        key("b"),
+        &"b".into(),
+        &Encoding::default(),
+        hlc.new_timestamp(),
+    )
+    .unwrap();
+    log.delete(key("b"), hlc.new_timestamp()).unwrap();
+    // Snapshot taken before the compaction: it must be ignored after it.
+    log.write_index_snapshot().unwrap();
+
+    let size = log.size();
+    log.compact().unwrap();
+    assert!(log.size() < size);
+    assert_eq!(log.len(), 1);
+    assert_eq!(payload(&log.get(&key("a")).unwrap()), "9");
+    log.put(
+        key("c"),
+        &"c".into(),
+        &Encoding::default(),
+        hlc.new_timestamp(),
+    )
+    .unwrap();
+    drop(log);
+
+    let mut log = DataLog::open(&dir.0, true).unwrap();
+    assert_eq!(log.len(), 2);
+    assert_eq!(payload(&log.get(&key("a")).unwrap()), "9");
+    assert_eq!(payload(&log.get(&key("c")).unwrap()), "c");
+    assert!(log.get(&key("b")).unwrap().is_none());
+}
+
+#[test]
+fn test_batch() {
+    let dir = TestDir::new();
+    let hlc = HLC::default();
+
+    {
+        let mut log = DataLog::open(&dir.0, true).unwrap();
+        let insertions = log
+            .batch(|log| {
+                vec![
+                    log.put(
+                        key("a"),
+                        &"1".into(),
+                        &Encoding::default(),
+                        hlc.new_timestamp(),
+                    )
+                    .unwrap(),
+                    log.put(
+                        key("a"),
+                        &"2".into(),
+                        &Encoding::default(),
+                        hlc.new_timestamp(),
+                    )
+                    .unwrap(),
+                    log.put(
+                        key("b"),
+                        &"3".into(),
+                        &Encoding::default(),
+                        hlc.new_timestamp(),
+                    )
+                    .unwrap(),
+                    log.delete(key("b"), hlc.new_timestamp()).unwrap(),
+                ]
+            })
+            .unwrap();
+        assert_eq!(
+            insertions,
+            vec![
+                Insertion::Inserted,
+                Insertion::Replaced,
+                Insertion::Inserted,
+                Insertion::Replaced
+            ]
+        );
+        // The synchronisation of each write is restored after the batch.
+        assert!(log.sync);
+    }
+
+    let mut log = DataLog::open(&dir.0, true).unwrap();
+    assert_eq!(log.len(), 1);
+    assert_eq!(payload(&log.get(&key("a")).unwrap()), "2");
+}
@@ -24,8 +24,10 @@ use std::{
     convert::TryFrom,
     str::FromStr,
     sync::{Arc, Mutex},
+    time::Duration,
 };
 
+use durable_backend::DurableBackend;
 use memory_backend::MemoryBackend;
 use storages_mgt::StorageMessage;
 use tokio::sync::broadcast::Sender;
@@ -48,6 +50,7 @@ use zenoh_plugin_trait::{
     plugin_long_version, plugin_version, Plugin, PluginControl, PluginReport, PluginStatusRec,
 };
 
+mod durable_backend;
 mod memory_backend;
 mod replication;
 mod storages_mgt;
@@ -56,6 +59,8 @@ use zenoh_util::ffi::JsonKeyValueMap;
 
 const WORKER_THREAD_NUM: usize = 2;
 const MAX_BLOCK_THREAD_NUM: usize = 50;
+// The maximum time a storage takes to report its replication status for the metrics.
+const METRICS_TIMEOUT: Duration = Duration::from_secs(1);
 lazy_static::lazy_static! {
     // The global runtime is used in the zenohd case, which we can't get the current runtime
     static ref TOKIO_RUNTIME: tokio::runtime::Runtime = tokio::runtime::Builder::new_multi_thread()
@@ -229,6 +234,21 @@ impl StorageRuntimeInner {
         );
         let declared = if let Some(declared) = self.plugins_manager.plugin_mut(volume_id) {
             declared
+        } else if config.paths().is_none()
+            && [MEMORY_BACKEND_NAME, DURABLE_BACKEND_NAME].contains(&backend_name)
+        {
+            // These backends are statically linked: each volume using them is declared as a
+            // distinct instance of the static plugin.
+            if backend_name == MEMORY_BACKEND_NAME {
+                self.plugins_manager
+                    .declare_static_plugin::<MemoryBackend, &str>(volume_id, config.required);
+            } else {
+                self.plugins_manager
+                    .declare_static_plugin::<DurableBackend, &str>(volume_id, config.required);
+            }
+            self.plugins_manager
+                .plugin_mut(volume_id)
+                .expect("Static plugin should have been declared")
         } else if let Some(paths) = config.paths() {
             self.plugins_manager.declare_dynamic_plugin_by_paths(
                 backend_name,
@@ -392,10 +412,62 @@ impl RunningPluginTrait for StorageRuntime {
         });
         Ok(responses)
     }
+
+    fn metrics(&self) -> ZResult<String> {
+        // The lock is only held to send the requests: the storages reply concurrently, and a
+        // storage that does not reply in time is left out of the metrics.
+        let (zid, whatami, requests) = {
+            let guard = self.0.lock().unwrap();
+            let requests = guard
+                .storages
+                .values()
+                .flatten()
+                .map(|(storage, handle)| {
+                    let (tx, rx) = tokio::sync::mpsc::channel(1);
+                    let _ = handle.send(StorageMessage::GetReplicationStatus(tx));
+                    (storage.clone(), rx)
+                })
+                .collect::<Vec<_>>();
+            (
+                guard.runtime.zid().to_string(),
+                guard.runtime.whatami(),
+                requests,
+            )
+        };
+
+        let statuses = tokio::task::block_in_place(|| {
+            TOKIO_RUNTIME.block_on(futures::future::join_all(requests.into_iter().map(
+                |(storage, mut rx)| async move {
+                    match tokio::time::timeout(METRICS_TIMEOUT, rx.recv()).await {
+                        Ok(status) => status.flatten().map(|status| (storage, status)),
+                        Err(_) => {
+                            tracing::warn!(
+                                "Storage '{storage}' did not report its replication status \
+                                 within {METRICS_TIMEOUT:?}"
+                            );
+                            None
+                        }
+                    }
+                },
+            )))
+        })
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+        let mut metrics = String::new();
+        replication::encode_metrics(
+            &mut metrics,
+            &[("local_id", &zid), ("local_whatami", whatami.to_str())],
+            &statuses,
+        )?;
+        Ok(metrics)
+    }
 }
 
 const BACKEND_LIB_PREFIX: &str = "zenoh_backend_";
 const MEMORY_BACKEND_NAME: &str = "memory";
+const DURABLE_BACKEND_NAME: &str = "durable";
 
 fn with_extended_string<R, F: FnMut(&mut String) -> R>(
     prefix: &mut String,
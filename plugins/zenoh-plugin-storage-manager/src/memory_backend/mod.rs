@@ -11,13 +11,21 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{btree_map::Entry, BTreeMap},
+    ops::Bound,
+    sync::Arc,
+    time::SystemTime,
+};
 
 use async_trait::async_trait;
+use serde_json::Value;
 use tokio::sync::RwLock;
 use zenoh::{
     bytes::{Encoding, ZBytes},
-    key_expr::OwnedKeyExpr,
+    internal::bail,
+    key_expr::{keyexpr, OwnedKeyExpr},
+    query::{Parameters, TimeRange, ZenohParameters},
     time::Timestamp,
     Result as ZResult,
 };
@@ -30,8 +38,13 @@ use zenoh_util::ffi::JsonValue;
 
 use crate::MEMORY_BACKEND_NAME;
 
+/// Volume configuration: either "latest" (default) to only keep the latest value of each key, or
+/// "all" to keep all of them.
+const PROP_HISTORY: &str = "history";
+
 pub struct MemoryBackend {
     config: VolumeConfig,
+    history: History,
 }
 
 impl Plugin for MemoryBackend {
@@ -42,9 +55,20 @@ impl Plugin for MemoryBackend {
     const PLUGIN_VERSION: &'static str = plugin_version!();
     const PLUGIN_LONG_VERSION: &'static str = plugin_long_version!();
 
-    fn start(_: &str, args: &VolumeConfig) -> ZResult<VolumeInstance> {
+    fn start(name: &str, args: &VolumeConfig) -> ZResult<VolumeInstance> {
+        let history = match args.rest.into_serde_map().get(PROP_HISTORY) {
+            None => History::Latest,
+            Some(Value::String(history)) if history == "latest" => History::Latest,
+            Some(Value::String(history)) if history == "all" => History::All,
+            Some(_) => bail!(
+                "Invalid value for field `{PROP_HISTORY}` of volume `{name}`. Only \"latest\" or \
+                 \"all\" are accepted."
+            ),
+        };
+
         Ok(Box::new(MemoryBackend {
             config: args.clone(),
+            history,
         }))
     }
 }
@@ -58,13 +82,15 @@ impl Volume for MemoryBackend {
     fn get_capability(&self) -> Capability {
         Capability {
             persistence: Persistence::Volatile,
-            history: History::Latest,
+            history: self.history.clone(),
         }
     }
 
     async fn create_storage(&self, properties: StorageConfig) -> ZResult<Box<dyn Storage>> {
         tracing::debug!("Create Memory Storage with configuration: {:?}", properties);
-        Ok(Box::new(MemoryStorage::new(properties).await?))
+        Ok(Box::new(
+            MemoryStorage::new(properties, self.history.clone()).await?,
+        ))
     }
 }
 
@@ -75,16 +101,30 @@ impl Drop for MemoryBackend {
     }
 }
 
+/// The values stored for a key, ordered by timestamp. With [History::Latest], it contains at most
+/// one value.
+type Versions = BTreeMap<Timestamp, StoredData>;
+
 struct MemoryStorage {
     config: StorageConfig,
-    map: Arc<RwLock<HashMap<Option<OwnedKeyExpr>, StoredData>>>,
+    data: Arc<MemoryData>,
+}
+
+/// The content of a [MemoryStorage], shared with its concurrent reader. The keys are ordered so
+/// that the paginated scans seek to their cursor.
+struct MemoryData {
+    history: History,
+    map: RwLock<BTreeMap<ScanKey, Versions>>,
 }
 
 impl MemoryStorage {
-    async fn new(properties: StorageConfig) -> ZResult<MemoryStorage> {
+    async fn new(properties: StorageConfig, history: History) -> ZResult<MemoryStorage> {
         Ok(MemoryStorage {
             config: properties,
-            map: Arc::new(RwLock::new(HashMap::new())),
+            data: Arc::new(MemoryData {
+                history,
+                map: RwLock::new(BTreeMap::new()),
+            }),
         })
     }
 }
@@ -103,22 +143,22 @@ impl Storage for MemoryStorage {
         timestamp: Timestamp,
     ) -> ZResult<StorageInsertionResult> {
         tracing::trace!("put for {:?}", key);
-        let mut map = self.map.write().await;
-        match map.entry(key) {
-            std::collections::hash_map::Entry::Occupied(mut e) => {
-                e.insert(StoredData {
-                    payload,
-                    encoding,
-                    timestamp,
-                });
+        let mut map = self.data.map.write().await;
+        let data = StoredData {
+            payload,
+            encoding,
+            timestamp,
+        };
+        match map.entry(ScanKey(key)) {
+            Entry::Occupied(mut e) => {
+                if self.data.history == History::Latest {
+                    e.get_mut().clear();
+                }
+                e.get_mut().insert(timestamp, data);
                 return Ok(StorageInsertionResult::Replaced);
             }
-            std::collections::hash_map::Entry::Vacant(e) => {
-                e.insert(StoredData {
-                    payload,
-                    encoding,
-                    timestamp,
-                });
+            Entry::Vacant(e) => {
+                e.insert(BTreeMap::from([(timestamp, data)]));
                 return Ok(StorageInsertionResult::Inserted);
             }
         }
@@ -127,34 +167,149 @@ impl Storage for MemoryStorage {
     async fn delete(
         &mut self,
         key: Option<OwnedKeyExpr>,
-        _timestamp: Timestamp,
+        timestamp: Timestamp,
     ) -> ZResult<StorageInsertionResult> {
         tracing::trace!("delete for {:?}", key);
-        self.map.write().await.remove_entry(&key);
+        let mut map = self.data.map.write().await;
+        let key = ScanKey(key);
+        match self.data.history {
+            History::Latest => {
+                map.remove_entry(&key);
+            }
+            // A delete only removes the values it supersedes: the history of the key after the
+            // delete is kept.
+            History::All => {
+                if let Some(versions) = map.get_mut(&key) {
+                    *versions = versions.split_off(&timestamp);
+                    versions.remove(&timestamp);
+                    if versions.is_empty() {
+                        map.remove(&key);
+                    }
+                }
+            }
+        }
         return Ok(StorageInsertionResult::Deleted);
     }
 
     async fn get(
         &mut self,
         key: Option<OwnedKeyExpr>,
-        _parameters: &str,
+        parameters: &str,
     ) -> ZResult<Vec<StoredData>> {
-        tracing::trace!("get for {:?}", key);
-        // @TODO: use parameters???
-        match self.map.read().await.get(&key) {
-            Some(v) => Ok(vec![v.clone()]),
-            None => Err(format!("Key {key:?} is not present").into()),
-        }
+        self.data.get(key, parameters).await
     }
 
     async fn get_all_entries(&self) -> ZResult<Vec<(Option<OwnedKeyExpr>, Timestamp)>> {
-        let map = self.map.read().await;
+        let map = self.data.map.read().await;
         let mut result = Vec::with_capacity(map.len());
-        for (k, v) in map.iter() {
-            result.push((k.clone(), v.timestamp));
+        for (k, versions) in map.iter() {
+            if let Some(latest) = versions.keys().last() {
+                result.push((k.0.clone(), *latest));
+            }
         }
         Ok(result)
     }
+
+    async fn get_matching_keys(
+        &self,
+        key_expr: &keyexpr,
+        strip_prefix: Option<&OwnedKeyExpr>,
+    ) -> ZResult<Vec<(Option<OwnedKeyExpr>, Timestamp)>> {
+        let map = self.data.map.read().await;
+        Ok(map
+            .iter()
+            .filter(|(k, _)| stripped_key_intersects(key_expr, strip_prefix, k.0.as_ref()))
+            .filter_map(|(k, versions)| versions.keys().last().map(|latest| (k.0.clone(), *latest)))
+            .collect())
+    }
+
+    async fn get_matching_entries(
+        &mut self,
+        key_expr: &keyexpr,
+        strip_prefix: Option<&OwnedKeyExpr>,
+        parameters: &str,
+        options: ScanOptions,
+    ) -> ZResult<ScanResult> {
+        self.data
+            .get_matching_entries(key_expr, strip_prefix, parameters, options)
+            .await
+    }
+
+    fn concurrent_reader(&self) -> Option<Arc<dyn StorageReader>> {
+        Some(self.data.clone())
+    }
+}
+
+#[async_trait]
+impl StorageReader for MemoryData {
+    async fn get(&self, key: Option<OwnedKeyExpr>, parameters: &str) -> ZResult<Vec<StoredData>> {
+        tracing::trace!("get for {:?}", key);
+        let time_range = self.time_range(parameters)?;
+        let map = self.map.read().await;
+        let key = ScanKey(key);
+        match map.get(&key) {
+            Some(versions) => Ok(self.select(versions, time_range.as_ref())),
+            None => bail!("Key {:?} is not present", key.0),
+        }
+    }
+
+    async fn get_matching_entries(
+        &self,
+        key_expr: &keyexpr,
+        strip_prefix: Option<&OwnedKeyExpr>,
+        parameters: &str,
+        options: ScanOptions,
+    ) -> ZResult<ScanResult> {
+        tracing::trace!("get matching entries for {}", key_expr);
+        let time_range = self.time_range(parameters)?;
+        let map = self.map.read().await;
+        let (keys, cursor) = options.select_sorted(
+            map.range((options.start(), Bound::Unbounded))
+                .map(|(k, _)| &k.0)
+                .filter(|k| stripped_key_intersects(key_expr, strip_prefix, k.as_ref())),
+        );
+        let entries = keys
+            .into_iter()
+            .map(|k| {
+                let key = ScanKey(k.clone());
+                let versions = self.select(&map[&key], time_range.as_ref());
+                (key.0, versions)
+            })
+            .collect();
+
+        Ok(ScanResult { entries, cursor })
+    }
+}
+
+impl MemoryData {
+    /// Returns the time range of the `_time` parameter, if any and if this storage keeps the
+    /// history of the keys.
+    fn time_range(&self, parameters: &str) -> ZResult<Option<TimeRange<SystemTime>>> {
+        if self.history == History::Latest {
+            return Ok(None);
+        }
+        match Parameters::from(parameters).time_range() {
+            Some(Ok(time_range)) => Ok(Some(time_range.resolve())),
+            Some(Err(e)) => bail!("Invalid `_time` parameter: {e}"),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the values within the time range or, without a time range, the latest value.
+    fn select(
+        &self,
+        versions: &Versions,
+        time_range: Option<&TimeRange<SystemTime>>,
+    ) -> Vec<StoredData> {
+        match time_range {
+            Some(time_range) => versions
+                .values()
+                .filter(|data| time_range.contains(data.timestamp.get_time().to_system_time()))
+                .cloned()
+                .collect(),
+            None => versions.values().last().cloned().into_iter().collect(),
+        }
+    }
 }
 
 impl Drop for MemoryStorage {
@@ -55,6 +55,9 @@ impl Configuration {
     /// Creates a new [Configuration] based on the provided [ReplicaConfig].
     ///
     /// This constructor also computes its [Fingerprint].
+    ///
+    /// The `alignment_batch_size` and `alignment_bandwidth` only affect how this Replica aligns:
+    /// they can differ between Replicas and are thus not part of the [Fingerprint].
     pub fn new(
         storage_key_expr: OwnedKeyExpr,
         prefix: Option<OwnedKeyExpr>,
@@ -14,10 +14,11 @@
 
 mod aligner_query;
 mod aligner_reply;
+mod throttle;
 
 use std::{
     collections::{HashMap, HashSet},
-    sync::Arc,
+    sync::{Arc, Mutex},
     time::{Duration, Instant, SystemTime},
 };
 
@@ -25,20 +26,23 @@ use rand::Rng;
 use tokio::{sync::RwLock, task::JoinHandle};
 use tracing::{debug_span, Instrument};
 use zenoh::{
+    internal::{bail, zerror},
     key_expr::{
         format::{kedefine, keformat},
         OwnedKeyExpr,
     },
-    query::{ConsolidationMode, Selector},
-    sample::{Locality, SampleKind},
+    query::{ConsolidationMode, Reply, Selector},
+    sample::{Locality, Sample, SampleKind},
+    session::ZenohId,
     time::Timestamp,
-    Session,
+    Result as ZResult, Session,
 };
 
 use self::aligner_reply::AlignmentReply;
-use super::{digest::Digest, log::LogLatest, Action, Event, LogLatestKey};
+pub(crate) use self::throttle::Throttle;
+use super::{digest::Digest, log::LogLatest, Action, Event, LogLatestKey, ReplicationMetrics};
 use crate::{
-    replication::core::aligner_query::AlignmentQuery,
+    replication::core::aligner_query::{AlignmentQuery, PageCursor},
     storages_mgt::{LatestUpdates, StorageService},
 };
 
@@ -54,11 +58,14 @@ pub(crate) struct Replication {
     pub(crate) storage_key_expr: OwnedKeyExpr,
     pub(crate) latest_updates: Arc<RwLock<LatestUpdates>>,
     pub(crate) storage_service: Arc<StorageService>,
+    pub(crate) metrics: Arc<ReplicationMetrics>,
+    pub(crate) throttle: Arc<Mutex<Throttle>>,
 }
 
 impl Replication {
     /// Performs an initial alignment, skipping the comparison of Digest, asking directly the first
-    /// discovered Replica for all its entries.
+    /// discovered Replica for all its entries, one page of at most `alignment_batch_size` Events
+    /// after the other.
     ///
     /// # ⚠️ Assumption: empty Storage
     ///
@@ -70,26 +77,25 @@ impl Replication {
     ///
     /// To discover a Replica, this method will craft a specific [AlignmentQuery] using the
     /// [Discovery] variant.
-    pub(crate) async fn initial_alignment(&self) {
-        let ke_all_replicas = match keformat!(
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the content of the Replica could not be entirely retrieved,
+    /// i.e. if a Query failed, timed out or received an erroneous reply before the last page.
+    pub(crate) async fn initial_alignment(&self) -> ZResult<()> {
+        let (hash_configuration, page_size) = {
+            let log = self.replication_log.read().await;
+            (
+                *log.configuration.fingerprint(),
+                log.configuration.alignment_batch_size,
+            )
+        };
+        let ke_all_replicas = keformat!(
             aligner_key_expr_formatter::formatter(),
-            hash_configuration = *self
-                .replication_log
-                .read()
-                .await
-                .configuration
-                .fingerprint(),
+            hash_configuration,
             zid = "*",
-        ) {
-            Ok(ke) => ke,
-            Err(e) => {
-                tracing::error!(
-                    "Failed to generate key expression to query all Replicas: {e:?}. Skipping \
-                     initial alignment."
-                );
-                return;
-            }
-        };
+        )
+        .map_err(|e| zerror!("Failed to generate key expression to query all Replicas: {e:?}"))?;
 
         // NOTE: As discussed with @OlivierHecart, the plugins do not wait for the duration of the
         // "scouting delay" before performing any Zenoh operation. Hence, we manually enforce this
@@ -101,14 +107,113 @@ impl Replication {
             .unwrap_or(500);
         tokio::time::sleep(Duration::from_millis(delay)).await;
 
-        if let Err(e) = self
-            .spawn_query_replica_aligner(ke_all_replicas, AlignmentQuery::Discovery)
-            .await
-        {
-            tracing::error!("Initial alignment failed with: {e:?}");
+        let Some(replica_zid) = self.discover_replica(ke_all_replicas).await? else {
+            tracing::debug!("Found no Replica to perform the initial alignment with");
+            return Ok(());
+        };
+        let replica_aligner_ke = keformat!(
+            aligner_key_expr_formatter::formatter(),
+            hash_configuration,
+            zid = replica_zid,
+        )
+        .map_err(|e| zerror!("Failed to generate a valid Aligner key expression: {e:?}"))?;
+        tracing::debug!("Performing initial alignment with Replica < {replica_zid} >");
+
+        let default_timeout = Duration::from_millis(
+            self.zenoh_session
+                .config()
+                .get_typed::<u64>("queries_default_timeout")
+                .unwrap_or(10_000),
+        );
+        let mut cursor = None;
+        let mut timeout = default_timeout;
+        loop {
+            let alignment_query = AlignmentQuery::All { cursor, page_size };
+            let (next_cursor, delay) = self
+                .query_replica_page(&replica_aligner_ke, alignment_query, timeout)
+                .await?;
+            match next_cursor {
+                Some(next_cursor) => cursor = Some(next_cursor),
+                None => return Ok(()),
+            }
+            // The Replica waits for its `alignment_bandwidth` to be available before sending the
+            // next page.
+            timeout = default_timeout + delay;
         }
     }
 
+    /// Returns the Zenoh ID of the first Replica replying to a `Discovery` Query, if any.
+    async fn discover_replica(&self, ke_all_replicas: OwnedKeyExpr) -> ZResult<Option<ZenohId>> {
+        // NOTE: `Monotonic` means that Zenoh will forward the first answer it receives (and ensure
+        //       that later answers are with a higher timestamp — we do not care about that last
+        //       aspect).
+        //
+        //       By setting the consolidation to this value when performing the initial alignment,
+        //       we select the most reactive Replica (hopefully the closest as well).
+        let replies = self
+            .zenoh_session
+            .get(Into::<Selector>::into(ke_all_replicas.clone()))
+            .attachment(bincode::serialize(&AlignmentQuery::Discovery)?)
+            .consolidation(ConsolidationMode::Monotonic)
+            .await?;
+
+        // The consolidation mode `Monotonic` will keep on sending replies. We only want to
+        // discover / align with a single Replica so we return after the first one.
+        while let Ok(reply) = replies.recv_async().await {
+            match decode_alignment_reply(reply) {
+                Ok((AlignmentReply::Discovery(replica_zid), _)) => return Ok(Some(replica_zid)),
+                Ok((alignment_reply, _)) => {
+                    tracing::debug!("Skipping unexpected reply to Discovery: {alignment_reply:?}")
+                }
+                Err(e) => {
+                    tracing::warn!("Skipping reply to query to < {ke_all_replicas} >: {e:?}")
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Retrieves a page of the content of the Replica, returning the cursor of the next page, if
+    /// any, and how long the Replica will wait before sending it.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the Query failed, received an erroneous reply or ended
+    /// before the `EndOfPage` reply of the Replica, e.g. because it timed out.
+    async fn query_replica_page(
+        &self,
+        replica_aligner_ke: &OwnedKeyExpr,
+        alignment_query: AlignmentQuery,
+        timeout: Duration,
+    ) -> ZResult<(Option<PageCursor>, Duration)> {
+        let replies = self
+            .zenoh_session
+            .get(Into::<Selector>::into(replica_aligner_ke.clone()))
+            .attachment(bincode::serialize(&alignment_query)?)
+            .consolidation(ConsolidationMode::None)
+            .timeout(timeout)
+            .await?;
+
+        while let Ok(reply) = replies.recv_async().await {
+            match decode_alignment_reply(reply)? {
+                (AlignmentReply::EndOfPage { next_cursor, delay }, _) => {
+                    return Ok((next_cursor, delay))
+                }
+                (alignment_reply, sample) => {
+                    self.process_alignment_reply(
+                        replica_aligner_ke.clone(),
+                        alignment_reply,
+                        sample,
+                    )
+                    .await
+                }
+            }
+        }
+
+        bail!("Query to < {replica_aligner_ke} > ended before the end of the page (timeout: {timeout:?})")
+    }
+
     /// Spawns a task that periodically publishes the [Digest] of the Replication [Log].
     ///
     /// This task will perform the following steps:
@@ -373,7 +478,12 @@ impl Replication {
                             }
                         };
 
-                        if let Some(digest_diff) = digest.diff(other_digest) {
+                        let digest_diff = digest.diff(other_digest);
+                        replication
+                            .metrics
+                            .digest_received(source_zid.as_str(), digest_diff.is_none());
+
+                        if let Some(digest_diff) = digest_diff {
                             tracing::debug!("Potential misalignment detected: {digest_diff:?}");
 
                             let replica_aligner_ke = match keformat!(
@@ -499,24 +609,11 @@ impl Replication {
             //
             //       When we retrieve Samples from a Replica, each Sample is sent in a separate
             //       reply. Hence the need to have no consolidation.
-            let mut consolidation = ConsolidationMode::None;
-
-            if matches!(alignment_query, AlignmentQuery::Discovery) {
-                // NOTE: `Monotonic` means that Zenoh will forward the first answer it receives (and
-                //       ensure that later answers are with a higher timestamp — we do not care
-                //       about that last aspect).
-                //
-                //       By setting the consolidation to this value when performing the initial
-                //       alignment, we select the most reactive Replica (hopefully the closest as
-                //       well).
-                consolidation = ConsolidationMode::Monotonic;
-            }
-
             match replication
                 .zenoh_session
                 .get(Into::<Selector>::into(replica_aligner_ke.clone()))
                 .attachment(attachment)
-                .consolidation(consolidation)
+                .consolidation(ConsolidationMode::None)
                 .await
             {
                 Err(e) => {
@@ -524,8 +621,8 @@ impl Replication {
                 }
                 Ok(reply_receiver) => {
                     while let Ok(reply) = reply_receiver.recv_async().await {
-                        let sample = match reply.into_result() {
-                            Ok(sample) => sample,
+                        let (alignment_reply, sample) = match decode_alignment_reply(reply) {
+                            Ok(alignment_reply) => alignment_reply,
                             Err(e) => {
                                 tracing::warn!(
                                     "Skipping reply to query to < {replica_aligner_ke} >: {e:?}"
@@ -534,26 +631,6 @@ impl Replication {
                             }
                         };
 
-                        let alignment_reply = match sample.attachment() {
-                            None => {
-                                tracing::debug!("Skipping reply without attachment");
-                                continue;
-                            }
-                            Some(attachment) => {
-                                match bincode::deserialize::<AlignmentReply>(&attachment.to_bytes())
-                                {
-                                    Err(e) => {
-                                        tracing::error!(
-                                            "Failed to deserialize attachment as AlignmentReply: \
-                                             {e:?}"
-                                        );
-                                        continue;
-                                    }
-                                    Ok(alignment_reply) => alignment_reply,
-                                }
-                            }
-                        };
-
                         replication
                             .process_alignment_reply(
                                 replica_aligner_ke.clone(),
@@ -561,13 +638,6 @@ impl Replication {
                                 sample,
                             )
                             .await;
-
-                        // The consolidation mode `Monotonic`, used for sending out an
-                        // `AlignmentQuery::Discovery`, will keep on sending replies. We only want
-                        // to discover / align with a single Replica so we break here.
-                        if matches!(alignment_query, AlignmentQuery::Discovery) {
-                            return;
-                        }
                     }
                 }
             }
@@ -575,6 +645,19 @@ impl Replication {
     }
 }
 
+/// Returns the [AlignmentReply], carried in the attachment of the reply, and its [Sample].
+fn decode_alignment_reply(reply: Reply) -> ZResult<(AlignmentReply, Sample)> {
+    let sample = reply
+        .into_result()
+        .map_err(|e| zerror!("Received an error reply: {e:?}"))?;
+    let attachment = sample
+        .attachment()
+        .ok_or_else(|| zerror!("Received a reply without attachment"))?;
+    let alignment_reply = bincode::deserialize::<AlignmentReply>(&attachment.to_bytes())
+        .map_err(|e| zerror!("Failed to deserialize attachment as AlignmentReply: {e:?}"))?;
+    Ok((alignment_reply, sample))
+}
+
 /// This function will search through the `events` structure and remove all event(s) that are
 /// "impacted" by the wildcard.
 ///
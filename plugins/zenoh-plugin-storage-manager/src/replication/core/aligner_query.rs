@@ -12,13 +12,17 @@
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
 
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    time::Instant,
+};
 
 use serde::{Deserialize, Serialize};
 use zenoh::{
     bytes::{Encoding, ZBytes},
     key_expr::keyexpr_tree::IKeyExprTree,
     query::Query,
+    time::Timestamp,
 };
 
 use super::aligner_reply::AlignmentReply;
@@ -47,13 +51,17 @@ use crate::replication::{
 ///
 /// The `Discovery` and `All` variants are used to perform the initial alignment. After receiving a
 /// `Discovery` Query, a Replica will reply with its Zenoh ID. The Replica that replied first will
-/// then receive an `All` Query to transfer all its content.
+/// then receive `All` Queries to transfer all its content, one page after the other.
 #[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
 pub(crate) enum AlignmentQuery {
     /// Ask Replica for their Zenoh ID to perform an initial alignment.
     Discovery,
-    /// Retrieve all the content of a Replica.
-    All,
+    /// Retrieve a page of at most `page_size` Events of the content of a Replica, starting at the
+    /// `cursor` or, if there is none, at the first Event of its Replication Log.
+    All {
+        cursor: Option<PageCursor>,
+        page_size: usize,
+    },
     /// First alignment Query after detecting a potential misalignment.
     Diff(DigestDiff),
     /// Request the Fingerprint(s) of the Sub-Interval(s) contained in the provided Interval(s).
@@ -64,6 +72,19 @@ pub(crate) enum AlignmentQuery {
     Events(Vec<EventMetadata>),
 }
 
+/// The position, in the Replication Log, of a page of the content of a Replica.
+///
+/// Within a [SubInterval], the Events are sent in the order of their [Timestamp]: the page starts
+/// at the first Event of the Sub-Interval following the `after` [Timestamp], if any.
+///
+/// [SubInterval]: crate::replication::classification::SubInterval
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub(crate) struct PageCursor {
+    interval: IntervalIdx,
+    sub_interval: SubIntervalIdx,
+    after: Option<Timestamp>,
+}
+
 impl Replication {
     /// Replies with the information requested by the Replica.
     ///
@@ -100,38 +121,9 @@ impl Replication {
                 )
                 .await;
             }
-            AlignmentQuery::All => {
+            AlignmentQuery::All { cursor, page_size } => {
                 tracing::trace!("Processing `AlignmentQuery::All`");
-
-                let idx_intervals = self
-                    .replication_log
-                    .read()
-                    .await
-                    .intervals
-                    .keys()
-                    .copied()
-                    .collect::<Vec<_>>();
-
-                for interval_idx in idx_intervals {
-                    let mut events_to_retrieve = Vec::default();
-                    if let Some(interval) = self
-                        .replication_log
-                        .read()
-                        .await
-                        .intervals
-                        .get(&interval_idx)
-                    {
-                        interval.sub_intervals().for_each(|(_, sub_interval)| {
-                            events_to_retrieve.extend(sub_interval.events().map(Into::into));
-                        });
-                    }
-
-                    // NOTE: As we took the lock in the `if let` block, it is released here,
-                    // diminishing contention.
-                    for event_to_retrieve in events_to_retrieve {
-                        self.reply_event_retrieval(&query, event_to_retrieve).await;
-                    }
-                }
+                self.reply_page(&query, cursor, page_size).await;
             }
             AlignmentQuery::Diff(digest_diff) => {
                 tracing::trace!("Processing `AlignmentQuery::Diff`");
@@ -264,15 +256,126 @@ impl Replication {
         reply_to_query(query, reply, None).await;
     }
 
+    /// Replies to the [Query] with the [EventMetadata] and [Value] of the Events of the page
+    /// starting at the `cursor`, followed by an `EndOfPage` reply.
+    ///
+    /// The `alignment_bandwidth` is enforced before sending the page, not while sending it: the
+    /// `EndOfPage` reply tells the Replica how long this Replica will wait before sending the next
+    /// page, such that it can wait for it without timing out.
+    pub(crate) async fn reply_page(
+        &self,
+        query: &Query,
+        cursor: Option<PageCursor>,
+        page_size: usize,
+    ) {
+        let delay = self.throttle.lock().unwrap().delay(Instant::now());
+        if let Some(delay) = delay {
+            tracing::trace!("Throttling the alignment for {} ms", delay.as_millis());
+            tokio::time::sleep(delay).await;
+        }
+
+        // The page size is chosen by the Replica: it is not trusted to preallocate the page.
+        let page_size = page_size.max(1);
+        let mut events_to_retrieve = Vec::new();
+        let mut next_cursor = None;
+        {
+            let log = self.replication_log.read().await;
+            'intervals: for (interval_idx, interval) in &log.intervals {
+                for (sub_interval_idx, sub_interval) in interval.sub_intervals() {
+                    let position = (*interval_idx, *sub_interval_idx);
+                    let after = match &cursor {
+                        Some(cursor) if position < (cursor.interval, cursor.sub_interval) => {
+                            continue
+                        }
+                        Some(cursor) if position == (cursor.interval, cursor.sub_interval) => {
+                            cursor.after
+                        }
+                        _ => None,
+                    };
+
+                    let mut events = sub_interval
+                        .events()
+                        .map(EventMetadata::from)
+                        .filter(|event| after.map_or(true, |after| *event.timestamp() > after))
+                        .collect::<Vec<_>>();
+                    events.sort_by_key(|event| *event.timestamp());
+
+                    let mut last = after;
+                    for event in events {
+                        if events_to_retrieve.len() >= page_size {
+                            next_cursor = Some(PageCursor {
+                                interval: *interval_idx,
+                                sub_interval: *sub_interval_idx,
+                                after: last,
+                            });
+                            break 'intervals;
+                        }
+                        last = Some(*event.timestamp());
+                        events_to_retrieve.push(event);
+                    }
+                }
+            }
+        }
+
+        for event_to_retrieve in events_to_retrieve {
+            if let Some(payload_len) = self.send_event(query, event_to_retrieve).await {
+                self.throttle
+                    .lock()
+                    .unwrap()
+                    .record(payload_len, Instant::now());
+            }
+        }
+
+        let delay = self
+            .throttle
+            .lock()
+            .unwrap()
+            .delay(Instant::now())
+            .unwrap_or_default();
+        reply_to_query(
+            query,
+            AlignmentReply::EndOfPage { next_cursor, delay },
+            None,
+        )
+        .await;
+    }
+
     /// Replies to the [Query] with the [EventMetadata] and [Value] identified as missing.
     ///
     /// Depending on the associated action, this method will fetch the [Value] either from the
     /// Storage or from the wildcard updates.
+    ///
+    /// Once the reply is sent, this method waits if the `alignment_bandwidth` of the Replica is
+    /// exceeded (see [Throttle]).
+    ///
+    /// [Throttle]: super::Throttle
     pub(crate) async fn reply_event_retrieval(
         &self,
         query: &Query,
         event_to_retrieve: EventMetadata,
     ) {
+        let Some(payload_len) = self.send_event(query, event_to_retrieve).await else {
+            return;
+        };
+
+        // Waiting here delays the replies to all the alignment queries, including the ones
+        // processed by other tasks, as they share the same `Throttle`.
+        let delay = self
+            .throttle
+            .lock()
+            .unwrap()
+            .record(payload_len, Instant::now());
+        if let Some(delay) = delay {
+            tracing::trace!("Throttling the alignment for {} ms", delay.as_millis());
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Replies to the [Query] with the [EventMetadata] and its [Value], fetched either from the
+    /// Storage or from the wildcard updates, and returns the size of the payload sent.
+    ///
+    /// If the [Value] is no longer available, no reply is sent and `None` is returned.
+    async fn send_event(&self, query: &Query, event_to_retrieve: EventMetadata) -> Option<usize> {
         let value = match &event_to_retrieve.action {
             // For a Delete or WildcardDelete there is no associated `Value`.
             Action::Delete | Action::WildcardDelete(_) => None,
@@ -290,7 +393,7 @@ impl Replication {
                                 "Failed to retrieve data associated to key < {:?} >: {e:?}",
                                 event_to_retrieve.key_expr()
                             );
-                            return;
+                            return None;
                         }
                     }
                 };
@@ -312,7 +415,7 @@ impl Replication {
                             event_to_retrieve.key_expr(),
                             event_to_retrieve.timestamp()
                         );
-                        return;
+                        return None;
                     }
                 }
             }
@@ -326,12 +429,15 @@ impl Replication {
                     tracing::error!(
                         "Ignoring Wildcard Update < {wildcard_ke} >: found no associated `Update`."
                     );
-                    return;
+                    return None;
                 }
             }
         };
 
+        let payload_len = value.as_ref().map_or(0, |(payload, _)| payload.len());
         reply_to_query(query, AlignmentReply::Retrieval(event_to_retrieve), value).await;
+        self.metrics.inc_entries_pushed();
+        Some(payload_len)
     }
 }
 
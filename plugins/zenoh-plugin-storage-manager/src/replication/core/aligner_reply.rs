@@ -12,13 +12,16 @@
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
 
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
 
 use serde::{Deserialize, Serialize};
 use tokio::sync::RwLockWriteGuard;
 use zenoh::{
     bytes::{Encoding, ZBytes},
-    key_expr::{format::keformat, keyexpr_tree::IKeyExprTreeMut, OwnedKeyExpr},
+    key_expr::{keyexpr_tree::IKeyExprTreeMut, OwnedKeyExpr},
     sample::{Sample, SampleFields, SampleKind},
     session::ZenohId,
     Result as ZResult,
@@ -28,7 +31,10 @@ use zenoh_backend_traits::{StorageInsertionResult, StoredData};
 use crate::{
     replication::{
         classification::{EventRemoval, IntervalIdx, SubIntervalIdx},
-        core::{aligner_key_expr_formatter, aligner_query::AlignmentQuery, Replication},
+        core::{
+            aligner_query::{AlignmentQuery, PageCursor},
+            Replication,
+        },
         digest::Fingerprint,
         log::{Action, EventMetadata},
         Event, LogLatest,
@@ -45,12 +51,18 @@ use crate::{
 ///
 /// Not all replies are made, it depends on the Era where a misalignment was detected.
 ///
-/// The `Discovery` Reply is used to perform the initial alignment. The Replica sends its Zenoh ID
-/// such that the newly joined Replica can retrieve all the content without having to go through
-/// an exchange of Digest.
+/// The `Discovery` and `EndOfPage` Replies are used to perform the initial alignment. The Replica
+/// sends its Zenoh ID such that the newly joined Replica can retrieve all the content without
+/// having to go through an exchange of Digest. It then sends its content one page after the other,
+/// each page ending with an `EndOfPage` Reply giving the cursor of the next page, if any, and how
+/// long the Replica will wait before sending it.
 #[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
 pub(crate) enum AlignmentReply {
     Discovery(ZenohId),
+    EndOfPage {
+        next_cursor: Option<PageCursor>,
+        delay: Duration,
+    },
     Intervals(HashMap<IntervalIdx, Fingerprint>),
     SubIntervals(HashMap<IntervalIdx, HashMap<SubIntervalIdx, Fingerprint>>),
     EventsMetadata(Vec<EventMetadata>),
@@ -103,38 +115,10 @@ impl Replication {
         sample: Sample,
     ) {
         match alignment_reply {
-            AlignmentReply::Discovery(replica_zid) => {
-                let parsed_ke = match aligner_key_expr_formatter::parse(&replica_aligner_ke) {
-                    Ok(ke) => ke,
-                    Err(e) => {
-                        tracing::error!(
-                            "Failed to parse < {replica_aligner_ke} > as a valid Aligner key \
-                             expression: {e:?}"
-                        );
-                        return;
-                    }
-                };
-
-                let replica_aligner_ke = match keformat!(
-                    aligner_key_expr_formatter::formatter(),
-                    hash_configuration = parsed_ke.hash_configuration(),
-                    zid = replica_zid,
-                ) {
-                    Ok(ke) => ke,
-                    Err(e) => {
-                        tracing::error!("Failed to generate a valid Aligner key expression: {e:?}");
-                        return;
-                    }
-                };
-
-                tracing::debug!("Performing initial alignment with Replica < {replica_zid} >");
-
-                if let Err(e) = self
-                    .spawn_query_replica_aligner(replica_aligner_ke, AlignmentQuery::All)
-                    .await
-                {
-                    tracing::error!("Error returned while performing the initial alignment: {e:?}");
-                }
+            AlignmentReply::Discovery(_) | AlignmentReply::EndOfPage { .. } => {
+                // These replies are processed by the initial alignment, see
+                // `Replication::initial_alignment`.
+                tracing::debug!("Ignoring reply only expected during the initial alignment");
             }
             AlignmentReply::Intervals(replica_intervals) => {
                 tracing::trace!("Processing `AlignmentReply::Intervals`");
@@ -215,11 +199,24 @@ impl Replication {
                     }
                 }
 
-                if !diff_events.is_empty() {
-                    self.spawn_query_replica_aligner(
-                        replica_aligner_ke,
-                        AlignmentQuery::Events(diff_events),
-                    );
+                // The payloads are requested by batches of `alignment_batch_size` Events, one batch
+                // after the other, to not overwhelm the remote Replica and the network.
+                let batch_size = self
+                    .replication_log
+                    .read()
+                    .await
+                    .configuration
+                    .alignment_batch_size;
+                for events in diff_events.chunks(batch_size) {
+                    if let Err(e) = self
+                        .spawn_query_replica_aligner(
+                            replica_aligner_ke.clone(),
+                            AlignmentQuery::Events(events.to_vec()),
+                        )
+                        .await
+                    {
+                        tracing::error!("Failed to retrieve a batch of Events: {e:?}");
+                    }
                 }
             }
             AlignmentReply::Retrieval(replica_event) => {
@@ -302,6 +299,7 @@ impl Replication {
         }
 
         replication_log_guard.insert_event_unchecked(replica_event.clone().into());
+        self.metrics.inc_entries_pulled();
         None
     }
 
@@ -406,6 +404,7 @@ impl Replication {
         // NOTE: We can only safely call `insert_event_unchecked` because we called earlier
         // `replication_log_guard.remove_older`.
         replication_log_guard.insert_event_unchecked(replica_event.into());
+        self.metrics.inc_entries_pulled();
     }
 
     /// Returns `true` if the provided `replica_event` requires more processing.
@@ -0,0 +1,64 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+use std::time::{Duration, Instant};
+
+use super::Throttle;
+
+#[test]
+fn test_unlimited() {
+    let mut throttle = Throttle::new(None, 1);
+    let now = Instant::now();
+    for _ in 0..10 {
+        assert_eq!(throttle.record(1_000_000, now), None);
+    }
+}
+
+#[test]
+fn test_bandwidth() {
+    // 1000 bytes per second, waiting every 2 payloads.
+    let mut throttle = Throttle::new(Some(1_000), 2);
+    let now = Instant::now();
+
+    assert_eq!(throttle.record(250, now), None);
+    assert_eq!(throttle.record(250, now), Some(Duration::from_millis(500)));
+
+    // Having waited, the next batch is delayed by its own size only.
+    let now = now + Duration::from_millis(500);
+    assert_eq!(throttle.record(500, now), None);
+    assert_eq!(throttle.record(500, now), Some(Duration::from_secs(1)));
+
+    // An idle period does not allow a burst.
+    let now = now + Duration::from_secs(60);
+    assert_eq!(throttle.record(100, now), None);
+    assert_eq!(throttle.record(100, now), Some(Duration::from_millis(200)));
+}
+
+#[test]
+fn test_delay() {
+    let mut throttle = Throttle::new(Some(1_000), 10);
+    let now = Instant::now();
+    assert_eq!(throttle.delay(now), None);
+
+    // The delay does not depend on the batches.
+    assert_eq!(throttle.record(250, now), None);
+    assert_eq!(throttle.delay(now), Some(Duration::from_millis(250)));
+    assert_eq!(
+        throttle.delay(now + Duration::from_millis(100)),
+        Some(Duration::from_millis(150))
+    );
+    assert_eq!(throttle.delay(now + Duration::from_millis(250)), None);
+
+    assert_eq!(Throttle::new(None, 10).delay(now), None);
+}
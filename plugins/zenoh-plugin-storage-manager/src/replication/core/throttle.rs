@@ -0,0 +1,71 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+use std::time::{Duration, Instant};
+
+/// The `Throttle` limits the bandwidth used by a Replica to send the payloads of its Events to the
+/// remote Replicas it is aligning.
+///
+/// Each payload sent pushes back the instant at which the bandwidth is available again by the time
+/// it takes to send it at the configured bandwidth. Every `batch_size` Events, the Aligner waits
+/// until that instant: the Events of a batch are thus sent in a row, and the batches spaced such
+/// that the average bandwidth does not exceed the configured one.
+///
+/// As the instant is never moved before the current time, a Replica that has not aligned another
+/// one for a while cannot send a burst exceeding its bandwidth.
+#[derive(Debug)]
+pub(crate) struct Throttle {
+    bandwidth: Option<u64>,
+    batch_size: usize,
+    available_at: Instant,
+    batch_len: usize,
+}
+
+impl Throttle {
+    pub(crate) fn new(bandwidth: Option<u64>, batch_size: usize) -> Self {
+        Self {
+            bandwidth,
+            batch_size,
+            available_at: Instant::now(),
+            batch_len: 0,
+        }
+    }
+
+    /// Records that a payload of `bytes` was sent at `now` and returns how long the Aligner
+    /// should wait before sending the next one, if it needs to wait.
+    pub(crate) fn record(&mut self, bytes: usize, now: Instant) -> Option<Duration> {
+        let bandwidth = self.bandwidth?;
+
+        self.available_at =
+            self.available_at.max(now) + Duration::from_secs_f64(bytes as f64 / bandwidth as f64);
+        self.batch_len += 1;
+        if self.batch_len < self.batch_size {
+            return None;
+        }
+
+        self.batch_len = 0;
+        self.delay(now)
+    }
+
+    /// Returns how long the Aligner should wait, from `now`, for the bandwidth to be available
+    /// again, if it needs to wait.
+    pub(crate) fn delay(&self, now: Instant) -> Option<Duration> {
+        self.bandwidth?;
+        Some(self.available_at.saturating_duration_since(now)).filter(|delay| !delay.is_zero())
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/throttle.test.rs"]
+mod tests;
@@ -26,6 +26,7 @@ use super::{
     classification::{EventLookup, EventRemoval, Interval, IntervalIdx},
     configuration::Configuration,
     digest::{Digest, Fingerprint},
+    metrics::{EraStatus, ErasStatus},
 };
 
 /// The `Action` enumeration facilitates dealing with Wildcard Updates. It is a super-set of
@@ -355,6 +356,15 @@ impl LogLatest {
         }
     }
 
+    /// Returns an iterator over all the [Event]s of the Replication Log.
+    pub(crate) fn events(&self) -> impl Iterator<Item = &Event> {
+        self.intervals.values().flat_map(|interval| {
+            interval
+                .sub_intervals()
+                .flat_map(|(_, sub_interval)| sub_interval.events())
+        })
+    }
+
     /// Returns the [Configuration] associated with the [LogLatest].
     pub fn configuration(&self) -> &Configuration {
         &self.configuration
@@ -589,6 +599,51 @@ impl LogLatest {
         }
     }
 
+    /// Returns the number of Intervals, Sub-Intervals and Events contained in each Era of the
+    /// [LogLatest].
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if the index of the last elapsed Interval could not be
+    /// computed. See [Configuration::last_elapsed_interval] for more details.
+    pub(crate) fn eras_status(&self) -> ZResult<ErasStatus> {
+        let last_elapsed_interval = self.configuration.last_elapsed_interval()?;
+
+        Ok(self.eras_status_from(last_elapsed_interval))
+    }
+
+    /// Considering the upper bound of the hot era, returns the content of each Era of the
+    /// [LogLatest].
+    ///
+    /// The Eras are delimited as in [LogLatest::digest_from].
+    fn eras_status_from(&self, hot_era_upper_bound: IntervalIdx) -> ErasStatus {
+        let hot_era_lower_bound = self.configuration.hot_era_lower_bound(hot_era_upper_bound);
+        let warm_era_lower_bound = self.configuration.warm_era_lower_bound(hot_era_upper_bound);
+
+        let mut eras_status = ErasStatus::default();
+        for (interval_idx, interval) in self
+            .intervals
+            .iter()
+            .filter(|(&idx, _)| idx <= hot_era_upper_bound)
+        {
+            let era_status: &mut EraStatus = if *interval_idx < warm_era_lower_bound {
+                &mut eras_status.cold
+            } else if *interval_idx < hot_era_lower_bound {
+                &mut eras_status.warm
+            } else {
+                &mut eras_status.hot
+            };
+
+            era_status.intervals += 1;
+            for (_, sub_interval) in interval.sub_intervals() {
+                era_status.sub_intervals += 1;
+                era_status.events += sub_interval.events().count();
+            }
+        }
+
+        eras_status
+    }
+
     /// Removes and returns the [Event]s overridden by the provided Wildcard Update from the
     /// Replication Log.
     ///
@@ -0,0 +1,308 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Progress of the replication of a Storage and divergence with the remote Replicas.
+//!
+//! The [ReplicationMetrics] are updated by the tasks of the Replication and gathered, together with
+//! the current [Digest] and the content of the Eras of the Replication Log, in a
+//! [ReplicationStatus]. The latter is published in the admin status of the Storage and encoded as
+//! OpenMetrics by [encode_metrics].
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Write},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::Serialize;
+
+use super::digest::Digest;
+
+const METRICS_PREFIX: &str = "zenoh_storage_replication";
+
+/// Number of Intervals, Sub-Intervals and Events of the Replication Log contained in an Era.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub(crate) struct EraStatus {
+    pub(crate) intervals: usize,
+    pub(crate) sub_intervals: usize,
+    pub(crate) events: usize,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub(crate) struct ErasStatus {
+    pub(crate) hot: EraStatus,
+    pub(crate) warm: EraStatus,
+    pub(crate) cold: EraStatus,
+}
+
+impl ErasStatus {
+    fn iter(&self) -> impl Iterator<Item = (&'static str, &EraStatus)> {
+        [
+            ("hot", &self.hot),
+            ("warm", &self.warm),
+            ("cold", &self.cold),
+        ]
+        .into_iter()
+    }
+}
+
+/// Alignment with a remote Replica, as assessed from the last [Digest] it published.
+///
+/// The times are expressed in seconds since [UNIX_EPOCH].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct ReplicaStatus {
+    /// Reception time of the last Digest of the Replica.
+    pub(crate) last_digest: f64,
+    /// Reception time of the last Digest of the Replica that did not differ from ours, i.e. the
+    /// last time both Replicas were found aligned.
+    pub(crate) last_alignment: Option<f64>,
+    /// `true` if the last Digest of the Replica did not differ from ours.
+    pub(crate) aligned: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ReplicationStatus {
+    /// `false` while the initial alignment of the Storage is in progress: until then, the Storage
+    /// might miss data published before it started.
+    pub(crate) initial_sync_complete: bool,
+    pub(crate) digest: Digest,
+    pub(crate) eras: ErasStatus,
+    /// The status of the alignment with each remote Replica, indexed by their Zenoh ID.
+    pub(crate) replicas: BTreeMap<String, ReplicaStatus>,
+    /// Number of Events received from remote Replicas and applied to the Storage.
+    pub(crate) entries_pulled: u64,
+    /// Number of Events sent to remote Replicas, in reply to their alignment queries.
+    pub(crate) entries_pushed: u64,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct ReplicationMetrics {
+    initial_sync_complete: AtomicBool,
+    replicas: Mutex<BTreeMap<String, ReplicaStatus>>,
+    entries_pulled: AtomicU64,
+    entries_pushed: AtomicU64,
+}
+
+impl ReplicationMetrics {
+    /// Records the reception of a Digest published by the Replica `replica_zid`, `aligned`
+    /// indicating if it differs from the Digest of this Replica.
+    pub(crate) fn digest_received(&self, replica_zid: &str, aligned: bool) {
+        let now = seconds_since_epoch();
+        let mut replicas = self.replicas.lock().unwrap();
+        let last_alignment = aligned.then_some(now).or_else(|| {
+            replicas
+                .get(replica_zid)
+                .and_then(|replica| replica.last_alignment)
+        });
+        replicas.insert(
+            replica_zid.to_string(),
+            ReplicaStatus {
+                last_digest: now,
+                last_alignment,
+                aligned,
+            },
+        );
+    }
+
+    /// Records that the initial alignment of the Storage is complete, or that it was not needed.
+    pub(crate) fn initial_sync_completed(&self) {
+        self.initial_sync_complete.store(true, Ordering::Relaxed);
+    }
+
+    pub(crate) fn inc_entries_pulled(&self) {
+        self.entries_pulled.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn inc_entries_pushed(&self) {
+        self.entries_pushed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn status(&self, digest: Digest, eras: ErasStatus) -> ReplicationStatus {
+        ReplicationStatus {
+            initial_sync_complete: self.initial_sync_complete.load(Ordering::Relaxed),
+            digest,
+            eras,
+            replicas: self.replicas.lock().unwrap().clone(),
+            entries_pulled: self.entries_pulled.load(Ordering::Relaxed),
+            entries_pushed: self.entries_pushed.load(Ordering::Relaxed),
+        }
+    }
+}
+
+fn seconds_since_epoch() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs_f64()
+}
+
+/// Encodes the [ReplicationStatus] of the provided Storages as OpenMetrics families.
+///
+/// `labels` are added to every sample, before the `storage` label. The `# EOF` marker is not
+/// written: the families are meant to be appended to the metrics of the Zenoh runtime.
+pub(crate) fn encode_metrics(
+    writer: &mut impl Write,
+    labels: &[(&str, &str)],
+    statuses: &[(String, ReplicationStatus)],
+) -> fmt::Result {
+    if statuses.is_empty() {
+        return Ok(());
+    }
+
+    let mut base_labels = String::new();
+    for (name, value) in labels {
+        write!(base_labels, "{name}=\"{}\",", escape(value))?;
+    }
+
+    write_descriptor(
+        writer,
+        "initial_sync_complete",
+        "Whether the initial alignment of the storage is complete",
+        "gauge",
+        None,
+    )?;
+    for (storage, status) in statuses {
+        writeln!(
+            writer,
+            "{METRICS_PREFIX}_initial_sync_complete{{{base_labels}storage=\"{}\"}} {}",
+            escape(storage),
+            u8::from(status.initial_sync_complete)
+        )?;
+    }
+
+    for (name, help, value) in [
+        (
+            "intervals",
+            "Count of intervals of the replication log per era",
+            (|era: &EraStatus| era.intervals) as fn(&EraStatus) -> usize,
+        ),
+        (
+            "sub_intervals",
+            "Count of sub-intervals of the replication log per era",
+            |era| era.sub_intervals,
+        ),
+        (
+            "events",
+            "Count of events of the replication log per era",
+            |era| era.events,
+        ),
+    ] {
+        write_descriptor(writer, name, help, "gauge", None)?;
+        for (storage, status) in statuses {
+            for (era_name, era) in status.eras.iter() {
+                writeln!(
+                    writer,
+                    "{METRICS_PREFIX}_{name}{{{base_labels}storage=\"{}\",era=\"{era_name}\"}} {}",
+                    escape(storage),
+                    value(era)
+                )?;
+            }
+        }
+    }
+
+    for (name, help, value) in [
+        (
+            "entries_pulled",
+            "Count of entries received from remote replicas and applied to the storage",
+            (|status: &ReplicationStatus| status.entries_pulled) as fn(&ReplicationStatus) -> u64,
+        ),
+        (
+            "entries_pushed",
+            "Count of entries sent to remote replicas",
+            |status| status.entries_pushed,
+        ),
+    ] {
+        write_descriptor(writer, name, help, "counter", None)?;
+        for (storage, status) in statuses {
+            writeln!(
+                writer,
+                "{METRICS_PREFIX}_{name}_total{{{base_labels}storage=\"{}\"}} {}",
+                escape(storage),
+                value(status)
+            )?;
+        }
+    }
+
+    write_descriptor(
+        writer,
+        "replica_aligned",
+        "Whether the last digest of the remote replica matched the local one",
+        "gauge",
+        None,
+    )?;
+    for (storage, status) in statuses {
+        for (replica, replica_status) in &status.replicas {
+            writeln!(
+                writer,
+                "{METRICS_PREFIX}_replica_aligned{{{base_labels}storage=\"{}\",replica=\"{}\"}} {}",
+                escape(storage),
+                escape(replica),
+                u8::from(replica_status.aligned)
+            )?;
+        }
+    }
+
+    write_descriptor(
+        writer,
+        "last_alignment_seconds",
+        "Time, since UNIX epoch, at which the remote replica was last found aligned",
+        "gauge",
+        Some("seconds"),
+    )?;
+    for (storage, status) in statuses {
+        for (replica, replica_status) in &status.replicas {
+            if let Some(last_alignment) = replica_status.last_alignment {
+                writeln!(
+                    writer,
+                    "{METRICS_PREFIX}_last_alignment_seconds{{{base_labels}storage=\"{}\",\
+                     replica=\"{}\"}} {last_alignment:.3}",
+                    escape(storage),
+                    escape(replica),
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn write_descriptor(
+    writer: &mut impl Write,
+    name: &str,
+    help: &str,
+    metric_type: &str,
+    unit: Option<&str>,
+) -> fmt::Result {
+    writeln!(writer, "# HELP {METRICS_PREFIX}_{name} {help}.")?;
+    writeln!(writer, "# TYPE {METRICS_PREFIX}_{name} {metric_type}")?;
+    if let Some(unit) = unit {
+        writeln!(writer, "# UNIT {METRICS_PREFIX}_{name} {unit}")?;
+    }
+    Ok(())
+}
+
+fn escape(label_value: &str) -> String {
+    label_value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+#[path = "tests/metrics.test.rs"]
+mod tests;
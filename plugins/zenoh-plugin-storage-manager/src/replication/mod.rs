@@ -31,7 +31,9 @@ mod configuration;
 mod core;
 mod digest;
 mod log;
+mod metrics;
 mod service;
 
 pub(crate) use log::{Action, Event, LogLatest, LogLatestKey};
+pub(crate) use metrics::{encode_metrics, ReplicationMetrics, ReplicationStatus};
 pub(crate) use service::ReplicationService;
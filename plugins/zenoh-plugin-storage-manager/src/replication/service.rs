@@ -12,7 +12,7 @@
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
 
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use tokio::{
     sync::{broadcast::Receiver, RwLock},
@@ -20,7 +20,10 @@ use tokio::{
 };
 use zenoh::{key_expr::OwnedKeyExpr, session::Session};
 
-use super::{core::Replication, LogLatest};
+use super::{
+    core::{Replication, Throttle},
+    LogLatest,
+};
 use crate::storages_mgt::{LatestUpdates, StorageMessage, StorageService};
 
 pub(crate) struct ReplicationService {
@@ -38,6 +41,10 @@ impl ReplicationService {
     /// performed: if a Replica is detected, a query will be made to retrieve the entire content of
     /// its Storage.
     ///
+    /// The initial alignment is performed in the background: the Storage receives publications and
+    /// replies to queries in the meantime. Its completion is indicated by the
+    /// `initial_sync_complete` field of the replication status of the Storage.
+    ///
     /// # Tasks spawned
     ///
     /// This function will spawn four long-lived tasks:
@@ -47,6 +54,8 @@ impl ReplicationService {
     /// 4. One to wait on the provided [Receiver] in order to stop the Replication Service,
     ///    attempting to abort all the tasks that were spawned, once a Stop message has been
     ///    received.
+    ///
+    /// The first three tasks are only spawned once the initial alignment is complete.
     pub async fn spawn_start(
         zenoh_session: Arc<Session>,
         storage_service: Arc<StorageService>,
@@ -55,40 +64,63 @@ impl ReplicationService {
         latest_updates: Arc<RwLock<LatestUpdates>>,
         mut rx: Receiver<StorageMessage>,
     ) {
+        let configuration = replication_log.read().await.configuration.clone();
         let replication = Replication {
             zenoh_session,
             replication_log,
             storage_key_expr,
             latest_updates,
+            metrics: storage_service
+                .replication_metrics
+                .clone()
+                .unwrap_or_default(),
+            throttle: Arc::new(Mutex::new(Throttle::new(
+                configuration.alignment_bandwidth,
+                configuration.alignment_batch_size,
+            ))),
             storage_service,
         };
 
-        if replication
-            .replication_log
-            .read()
-            .await
-            .intervals
-            .is_empty()
-        {
-            replication.initial_alignment().await;
-        }
-
         tokio::task::spawn(async move {
+            if replication
+                .replication_log
+                .read()
+                .await
+                .intervals
+                .is_empty()
+            {
+                tokio::select! {
+                    result = replication.initial_alignment() => match result {
+                        Ok(()) => replication.metrics.initial_sync_completed(),
+                        // The Replica will still be aligned through the exchange of Digests.
+                        Err(e) => tracing::error!("Initial alignment failed with: {e:?}"),
+                    },
+                    _ = Self::wait_stop(&mut rx) => return,
+                }
+            } else {
+                replication.metrics.initial_sync_completed();
+            }
+
             let replication_service = Self {
                 digest_publisher_handle: replication.spawn_digest_publisher(),
                 digest_subscriber_handle: replication.spawn_digest_subscriber(),
                 aligner_queryable_handle: replication.spawn_aligner_queryable(),
             };
 
-            while let Ok(storage_message) = rx.recv().await {
-                if matches!(storage_message, StorageMessage::Stop) {
-                    replication_service.stop();
-                    return;
-                }
-            }
+            Self::wait_stop(&mut rx).await;
+            replication_service.stop();
         });
     }
 
+    /// Returns once a Stop message has been received or the Storage Service is gone.
+    async fn wait_stop(rx: &mut Receiver<StorageMessage>) {
+        while let Ok(storage_message) = rx.recv().await {
+            if matches!(storage_message, StorageMessage::Stop) {
+                return;
+            }
+        }
+    }
+
     /// Stops all the long-lived tasks spawned by the `ReplicationService`.
     pub fn stop(self) {
         self.digest_publisher_handle.abort();
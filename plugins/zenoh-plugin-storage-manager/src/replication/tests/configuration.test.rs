@@ -29,6 +29,7 @@ fn test_lower_bounds() {
             hot: 1,
             warm: 5,
             propagation_delay: Duration::from_millis(250),
+            ..Default::default()
         },
     );
 
@@ -50,6 +51,7 @@ fn test_difference() {
         hot: 1,
         warm: 5,
         propagation_delay: Duration::from_millis(250),
+        ..Default::default()
     };
 
     let configuration_a = Configuration::new(
@@ -86,6 +88,7 @@ fn test_get_classification() {
             hot: 1,
             warm: 5,
             propagation_delay: Duration::from_millis(250),
+            ..Default::default()
         },
     );
 
@@ -23,6 +23,7 @@ use crate::replication::{
     classification::{Interval, IntervalIdx, SubInterval, SubIntervalIdx},
     digest::{Digest, Fingerprint},
     log::{Action, EventInsertion},
+    metrics::{EraStatus, ErasStatus},
 };
 
 fn generate_timestamp_matching(
@@ -54,6 +55,7 @@ fn test_insert() {
             hot: 1,
             warm: 5,
             propagation_delay: Duration::from_millis(250),
+            ..Default::default()
         },
     );
 
@@ -118,6 +120,7 @@ fn test_digest() {
             hot: 1,
             warm: 5,
             propagation_delay: Duration::from_millis(250),
+            ..Default::default()
         },
     );
 
@@ -297,6 +300,62 @@ fn test_digest() {
     assert_eq!(expected_digest, log.digest_from(IntervalIdx(12)));
 }
 
+#[test]
+fn test_eras_status() {
+    let hlc = HLC::default();
+    let mut log = LogLatest::new(
+        OwnedKeyExpr::from_str("replication/test/**").unwrap(),
+        None,
+        ReplicaConfig {
+            interval: Duration::from_secs(10),
+            sub_intervals: 5,
+            hot: 1,
+            warm: 5,
+            propagation_delay: Duration::from_millis(250),
+            ..Default::default()
+        },
+    );
+
+    for (key, interval, sub_interval) in [
+        ("0/0/0", 0, 0),
+        ("5/1/0", 5, 1),
+        ("6/2/0", 6, 2),
+        ("6/3/0", 6, 3),
+        ("10/4/0", 10, 4),
+        ("10/4/1", 10, 4),
+        // Not yet elapsed: ignored.
+        ("11/0/0", 11, 0),
+    ] {
+        log.insert_event(Event::new(
+            Some(OwnedKeyExpr::from_str(key).unwrap()),
+            generate_timestamp_matching(&log, &hlc, interval, sub_interval, 0),
+            &Action::Put,
+        ));
+    }
+
+    // With an upper bound of 10: 10 <= hot <= 10, 5 <= warm <= 9, cold <= 4.
+    assert_eq!(
+        ErasStatus {
+            hot: EraStatus {
+                intervals: 1,
+                sub_intervals: 1,
+                events: 2,
+            },
+            warm: EraStatus {
+                intervals: 2,
+                sub_intervals: 3,
+                events: 3,
+            },
+            cold: EraStatus {
+                intervals: 1,
+                sub_intervals: 1,
+                events: 1,
+            },
+        },
+        log.eras_status_from(IntervalIdx(10))
+    );
+}
+
 #[test]
 fn test_event() {
     let hlc = HLC::default();
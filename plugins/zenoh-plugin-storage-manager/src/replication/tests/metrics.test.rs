@@ -0,0 +1,91 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+use std::collections::HashMap;
+
+use super::*;
+use crate::replication::digest::Fingerprint;
+
+fn empty_digest() -> Digest {
+    Digest {
+        configuration_fingerprint: Fingerprint::default(),
+        cold_era_fingerprint: Fingerprint::default(),
+        warm_era_fingerprints: HashMap::default(),
+        hot_era_fingerprints: HashMap::default(),
+    }
+}
+
+#[test]
+fn test_digest_received() {
+    let metrics = ReplicationMetrics::default();
+
+    metrics.digest_received("a", true);
+    metrics.digest_received("b", false);
+    let status = metrics.status(empty_digest(), ErasStatus::default());
+    assert!(!status.initial_sync_complete);
+    let last_alignment_a = status.replicas["a"].last_alignment;
+    assert!(last_alignment_a.is_some());
+    assert!(status.replicas["a"].aligned);
+    assert_eq!(status.replicas["b"].last_alignment, None);
+    assert!(!status.replicas["b"].aligned);
+
+    // A misalignment keeps the time of the last alignment.
+    metrics.digest_received("a", false);
+    let status = metrics.status(empty_digest(), ErasStatus::default());
+    assert_eq!(status.replicas["a"].last_alignment, last_alignment_a);
+    assert!(!status.replicas["a"].aligned);
+}
+
+#[test]
+fn test_encode_metrics() {
+    let mut text = String::new();
+    encode_metrics(&mut text, &[("local_id", "1")], &[]).unwrap();
+    assert!(text.is_empty());
+
+    let metrics = ReplicationMetrics::default();
+    metrics.inc_entries_pulled();
+    metrics.inc_entries_pulled();
+    metrics.inc_entries_pushed();
+    metrics.digest_received("b", false);
+    let eras = ErasStatus {
+        hot: EraStatus {
+            intervals: 1,
+            sub_intervals: 2,
+            events: 3,
+        },
+        ..Default::default()
+    };
+    metrics.initial_sync_completed();
+    let statuses = [("demo".to_string(), metrics.status(empty_digest(), eras))];
+
+    encode_metrics(&mut text, &[("local_id", "1")], &statuses).unwrap();
+    let lines = text.lines().collect::<Vec<_>>();
+    for expected in [
+        "zenoh_storage_replication_initial_sync_complete{local_id=\"1\",storage=\"demo\"} 1",
+        "# TYPE zenoh_storage_replication_events gauge",
+        "zenoh_storage_replication_events{local_id=\"1\",storage=\"demo\",era=\"hot\"} 3",
+        "zenoh_storage_replication_sub_intervals{local_id=\"1\",storage=\"demo\",era=\"cold\"} 0",
+        "# TYPE zenoh_storage_replication_entries_pulled counter",
+        "zenoh_storage_replication_entries_pulled_total{local_id=\"1\",storage=\"demo\"} 2",
+        "zenoh_storage_replication_entries_pushed_total{local_id=\"1\",storage=\"demo\"} 1",
+        "zenoh_storage_replication_replica_aligned{local_id=\"1\",storage=\"demo\",replica=\"b\"} 0",
+        "# UNIT zenoh_storage_replication_last_alignment_seconds seconds",
+    ] {
+        assert!(lines.contains(&expected), "missing < {expected} > in:\n{text}");
+    }
+    // Replica "b" was never found aligned.
+    assert!(!lines
+        .iter()
+        .any(|line| line.starts_with("zenoh_storage_replication_last_alignment_seconds{")));
+}
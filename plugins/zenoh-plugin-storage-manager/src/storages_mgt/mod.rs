@@ -18,15 +18,20 @@ use tokio::sync::{broadcast::Sender, Mutex, RwLock};
 use zenoh::{internal::bail, session::Session, Result as ZResult};
 use zenoh_backend_traits::{config::StorageConfig, History, VolumeInstance};
 
-use crate::replication::{Action, Event, LogLatest, LogLatestKey, ReplicationService};
+use crate::replication::{
+    Action, Event, LogLatest, LogLatestKey, ReplicationService, ReplicationStatus,
+};
 
+mod pagination;
 pub(crate) mod service;
+mod snapshot;
 pub(crate) use service::StorageService;
 
 #[derive(Clone)]
 pub enum StorageMessage {
     Stop,
     GetStatus(tokio::sync::mpsc::Sender<serde_json::Value>),
+    GetReplicationStatus(tokio::sync::mpsc::Sender<Option<ReplicationStatus>>),
 }
 
 pub(crate) type LatestUpdates = HashMap<LogLatestKey, Event>;
@@ -119,6 +124,7 @@ pub(crate) async fn create_and_start_storage(
                 zenoh_session.clone(),
                 config.clone(),
                 &name,
+                &admin_key,
                 storage,
                 capability,
                 CacheLatest::new(latest_updates.clone(), replication_log.clone()),
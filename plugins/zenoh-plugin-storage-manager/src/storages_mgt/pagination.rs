@@ -0,0 +1,158 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Pagination of the replies to the wildcard queries received by a Storage.
+//!
+//! A query can limit the number of samples it receives with the `_limit=<N>` selector parameter.
+//! If more samples match, all the replies carry, as attachment, an opaque cursor. Sending the same
+//! query with the additional `_cursor=<cursor>` parameter returns the next samples.
+//!
+//! A page always holds `N` samples unless it is the last one: the entries filtered out when
+//! replying (e.g. expired or out of the `_time` range) do not shorten a page, hence a page that
+//! carries a cursor is never empty.
+
+use std::str::FromStr;
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use zenoh::{
+    internal::{bail, zerror},
+    key_expr::OwnedKeyExpr,
+    query::Parameters,
+    time::Timestamp,
+    Result as ZResult,
+};
+use zenoh_backend_traits::ScanCursor;
+
+pub(crate) const LIMIT_PARAM: &str = "_limit";
+pub(crate) const CURSOR_PARAM: &str = "_cursor";
+
+const CURSOR_NONE_KEY: u8 = 0;
+const CURSOR_SOME_KEY: u8 = 1;
+const CURSOR_NONE_KEY_VERSION: u8 = 2;
+const CURSOR_SOME_KEY_VERSION: u8 = 3;
+
+/// Position reached by the replies to a paginated query.
+///
+/// The samples are replied in the order of their key, as defined by [ScanCursor], then of their
+/// timestamp. If `last_timestamp` is set, the next page starts with the versions of `last_key`
+/// more recent than it, otherwise with the key following `last_key`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PageCursor {
+    pub(crate) last_key: Option<OwnedKeyExpr>,
+    pub(crate) last_timestamp: Option<Timestamp>,
+}
+
+impl PageCursor {
+    /// Returns the [ScanCursor] from which the keys following `last_key` are scanned.
+    pub(crate) fn scan_cursor(&self) -> ScanCursor {
+        ScanCursor {
+            last_key: self.last_key.clone(),
+        }
+    }
+}
+
+/// Options of a paginated query, see [page_options].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct PageOptions {
+    /// The maximum number of samples to reply, `None` meaning no limit.
+    pub(crate) limit: Option<usize>,
+    /// The position from which the replies should resume, `None` to start from the first key.
+    pub(crate) cursor: Option<PageCursor>,
+}
+
+/// Returns the [PageOptions] described by the `_limit` and `_cursor` parameters of a query.
+pub(crate) fn page_options(parameters: &Parameters) -> ZResult<PageOptions> {
+    let limit = match parameters.get(LIMIT_PARAM) {
+        Some(limit) => match limit.parse::<usize>() {
+            Ok(limit) if limit > 0 => Some(limit),
+            _ => bail!(
+                "Invalid `{LIMIT_PARAM}` parameter: expected a positive integer, found < {limit} >"
+            ),
+        },
+        None => None,
+    };
+    let cursor = parameters
+        .get(CURSOR_PARAM)
+        .map(decode_cursor)
+        .transpose()?;
+
+    Ok(PageOptions { limit, cursor })
+}
+
+/// Encodes a [PageCursor] such that it can be used as the value of a selector parameter.
+pub(crate) fn encode_cursor(cursor: &PageCursor) -> String {
+    let mut bytes = Vec::new();
+    match &cursor.last_timestamp {
+        Some(timestamp) => {
+            let timestamp = timestamp.to_string();
+            match &cursor.last_key {
+                Some(_) => bytes.push(CURSOR_SOME_KEY_VERSION),
+                None => bytes.push(CURSOR_NONE_KEY_VERSION),
+            }
+            bytes.push(timestamp.len() as u8);
+            bytes.extend_from_slice(timestamp.as_bytes());
+        }
+        None => match &cursor.last_key {
+            Some(_) => bytes.push(CURSOR_SOME_KEY),
+            None => bytes.push(CURSOR_NONE_KEY),
+        },
+    }
+    if let Some(key) = &cursor.last_key {
+        bytes.extend_from_slice(key.as_str().as_bytes());
+    }
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+pub(crate) fn decode_cursor(cursor: &str) -> ZResult<PageCursor> {
+    let invalid =
+        |e: &dyn std::fmt::Display| zerror!("Invalid `{CURSOR_PARAM}` parameter < {cursor} >: {e}");
+
+    let bytes = URL_SAFE_NO_PAD.decode(cursor).map_err(|e| invalid(&e))?;
+    let Some((&tag, mut bytes)) = bytes.split_first() else {
+        bail!("Invalid `{CURSOR_PARAM}` parameter < {cursor} >");
+    };
+
+    let mut last_timestamp = None;
+    if tag == CURSOR_NONE_KEY_VERSION || tag == CURSOR_SOME_KEY_VERSION {
+        let Some((&len, rest)) = bytes.split_first() else {
+            bail!("Invalid `{CURSOR_PARAM}` parameter < {cursor} >");
+        };
+        if rest.len() < len as usize {
+            bail!("Invalid `{CURSOR_PARAM}` parameter < {cursor} >");
+        }
+        let (timestamp, rest) = rest.split_at(len as usize);
+        let timestamp = std::str::from_utf8(timestamp).map_err(|e| invalid(&e))?;
+        last_timestamp =
+            Some(Timestamp::from_str(timestamp).map_err(|e| invalid(&format!("{e:?}")))?);
+        bytes = rest;
+    }
+
+    let last_key = match (tag, bytes) {
+        (CURSOR_NONE_KEY | CURSOR_NONE_KEY_VERSION, []) => None,
+        (CURSOR_SOME_KEY | CURSOR_SOME_KEY_VERSION, key) => {
+            let key = std::str::from_utf8(key).map_err(|e| invalid(&e))?;
+            Some(OwnedKeyExpr::new(key).map_err(|e| invalid(&e))?)
+        }
+        _ => bail!("Invalid `{CURSOR_PARAM}` parameter < {cursor} >"),
+    };
+
+    Ok(PageCursor {
+        last_key,
+        last_timestamp,
+    })
+}
+
+#[cfg(test)]
+#[path = "tests/pagination.test.rs"]
+mod tests;
@@ -13,17 +13,17 @@
 //
 
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     str::{self},
     sync::Arc,
-    time::{SystemTime, UNIX_EPOCH},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 use async_trait::async_trait;
-use tokio::sync::{broadcast::Receiver, Mutex, RwLock, RwLockWriteGuard};
+use tokio::sync::{broadcast::Receiver, Mutex, RwLock, Semaphore};
 use zenoh::{
     bytes::{Encoding, ZBytes},
-    internal::{bail, Timed, TimedEvent, Timer},
+    internal::{bail, zerror, Timed, TimedEvent, Timer},
     key_expr::{
         keyexpr,
         keyexpr_tree::{
@@ -31,22 +31,32 @@ use zenoh::{
         },
         OwnedKeyExpr,
     },
-    sample::{Sample, SampleBuilder, SampleFields, SampleKind},
+    query::ZenohParameters,
+    sample::{Locality, Sample, SampleBuilder, SampleFields, SampleKind},
     session::Session,
     time::{Timestamp, NTP64},
     Result as ZResult,
 };
 use zenoh_backend_traits::{
     config::{GarbageCollectionConfig, StorageConfig},
-    Capability, History, StorageInsertionResult, StoredData,
+    Capability, History, ScanOptions, ScanResult, StorageInsertionResult, StorageOperation,
+    StorageReader, StoredData,
 };
 
-use super::LatestUpdates;
+use super::{
+    pagination::{self, PageCursor},
+    snapshot::{self, Snapshot, SnapshotRecord},
+    LatestUpdates,
+};
 use crate::{
-    replication::{Action, Event},
+    replication::{Action, Event, ReplicationMetrics, ReplicationStatus},
     storages_mgt::{CacheLatest, StorageMessage},
 };
 
+// The maximum number of queries replied concurrently through the reader of a Storage. Beyond it,
+// the queries are replied one at a time, in the loop of the Storage.
+const MAX_CONCURRENT_QUERIES: usize = 64;
+
 #[derive(Clone)]
 pub(crate) struct Update {
     kind: SampleKind,
@@ -77,16 +87,30 @@ impl From<Update> for StoredData {
     }
 }
 
+/// An update of the Storage resulting from a received Sample.
+struct PendingUpdate {
+    key_expr: OwnedKeyExpr,
+    kind: SampleKind,
+    event: Event,
+    operation: StorageOperation,
+}
+
 #[derive(Clone)]
 pub struct StorageService {
     session: Arc<Session>,
     pub(crate) configuration: StorageConfig,
     name: String,
+    admin_key: String,
     pub(crate) storage: Arc<Mutex<Box<dyn zenoh_backend_traits::Storage>>>,
+    // If set, the queries are replied through this reader, without locking the storage.
+    reader: Option<Arc<dyn StorageReader>>,
+    query_permits: Arc<Semaphore>,
     capability: Capability,
     pub(crate) wildcard_deletes: Arc<RwLock<KeBoxTree<Update, UnknownWildness, KeyedSetProvider>>>,
     pub(crate) wildcard_puts: Arc<RwLock<KeBoxTree<Update, UnknownWildness, KeyedSetProvider>>>,
     cache_latest: CacheLatest,
+    // Only set if the Storage is replicated.
+    pub(crate) replication_metrics: Option<Arc<ReplicationMetrics>>,
 }
 
 impl StorageService {
@@ -94,19 +118,29 @@ impl StorageService {
         session: Arc<Session>,
         config: StorageConfig,
         name: &str,
+        admin_key: &str,
         storage: Arc<Mutex<Box<dyn zenoh_backend_traits::Storage>>>,
         capability: Capability,
         cache_latest: CacheLatest,
     ) -> Self {
+        let reader = storage.lock().await.concurrent_reader();
+        let replication_metrics = cache_latest
+            .replication_log
+            .as_ref()
+            .map(|_| Arc::default());
         StorageService {
             session,
             configuration: config,
             name: name.to_string(),
+            admin_key: admin_key.to_string(),
             storage,
+            reader,
+            query_permits: Arc::new(Semaphore::new(MAX_CONCURRENT_QUERIES)),
             capability,
             wildcard_deletes: Arc::new(RwLock::new(KeBoxTree::default())),
             wildcard_puts: Arc::new(RwLock::new(KeBoxTree::default())),
             cache_latest,
+            replication_metrics,
         }
     }
 
@@ -125,6 +159,10 @@ impl StorageService {
             None
         };
 
+        // Expired values are deleted as often as the metadata are garbage collected.
+        let mut expiry_interval = tokio::time::interval(gc_config.period);
+        expiry_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
         let gc = TimedEvent::periodic(
             gc_config.period,
             GarbageCollectionEvent {
@@ -161,12 +199,29 @@ impl StorageService {
             }
         };
 
+        // answer to the admin operations on the storage
+        let admin_queryable = match self
+            .session
+            .declare_queryable(format!("{}/*", self.admin_key))
+            .await
+        {
+            Ok(admin_queryable) => admin_queryable,
+            Err(e) => {
+                tracing::error!("Error starting storage '{}': {}", self.name, e);
+                return;
+            }
+        };
+
         tracing::debug!(
             "Starting storage '{}' on keyexpr '{}'",
             self.name,
             storage_key_expr
         );
 
+        let batch_config = self.configuration.batch.clone();
+        let mut batch = Vec::new();
+        let mut batch_deadline = None;
+
         tokio::task::spawn(async move {
             loop {
                 tokio::select!(
@@ -181,24 +236,85 @@ impl StorageService {
                         };
                         let timestamp = sample.timestamp().cloned().unwrap_or(self.session.new_timestamp());
                         let sample = SampleBuilder::from(sample).timestamp(timestamp).into();
-                        if let Err(e) = self.process_sample(sample).await {
-                            tracing::error!("{e:?}");
+                        match &batch_config {
+                            Some(batch_config) => {
+                                if batch.is_empty() {
+                                    batch_deadline = Some(tokio::time::Instant::now() + batch_config.max_delay);
+                                }
+                                batch.push(sample);
+                                if batch.len() >= batch_config.max_size {
+                                    batch_deadline = None;
+                                    self.process_samples(std::mem::take(&mut batch)).await;
+                                }
+                            }
+                            None => {
+                                if let Err(e) = self.process_sample(sample).await {
+                                    tracing::error!("{e:?}");
+                                }
+                            }
                         }
                     },
+                    // on expiry of the delay of the pending batch
+                    _ = tokio::time::sleep_until(batch_deadline.unwrap_or_else(tokio::time::Instant::now)), if batch_deadline.is_some() => {
+                        batch_deadline = None;
+                        self.process_samples(std::mem::take(&mut batch)).await;
+                    },
                     // on query on key_expr
                     query = storage_queryable.recv_async() => {
-                        self.reply_query(query).await;
+                        // The query does not need to wait for the updates to be applied, nor to
+                        // delay them, unless too many queries are already being replied.
+                        let permit = self
+                            .reader
+                            .as_ref()
+                            .and_then(|_| self.query_permits.clone().try_acquire_owned().ok());
+                        match permit {
+                            Some(permit) => {
+                                let service = self.clone();
+                                tokio::task::spawn(async move {
+                                    service.reply_query(query).await;
+                                    drop(permit);
+                                });
+                            }
+                            None => self.reply_query(query).await,
+                        }
+                    },
+                    // on admin operation
+                    query = admin_queryable.recv_async() => {
+                        self.reply_admin_query(query).await;
+                    },
+                    // on expiry check, if a ttl is configured
+                    _ = expiry_interval.tick(), if self.configuration.ttl.is_some() => {
+                        if let Some(ttl) = self.configuration.ttl {
+                            self.expire_entries(ttl).await;
+                        }
                     },
                     // on storage handle drop
                     Ok(message) = rx.recv() => {
                         match message {
                             StorageMessage::Stop => {
                                 tracing::trace!("Dropping storage '{}'", self.name);
+                                self.process_samples(std::mem::take(&mut batch)).await;
                                 return
                             },
                             StorageMessage::GetStatus(tx) => {
-                                let storage = self.storage.lock().await;
-                                std::mem::drop(tx.send(storage.get_admin_status().into()).await);
+                                let mut status: serde_json::Value =
+                                    self.storage.lock().await.get_admin_status().into();
+                                if let (serde_json::Value::Object(map), Some(replication)) =
+                                    (&mut status, self.replication_status().await)
+                                {
+                                    match serde_json::to_value(replication) {
+                                        Ok(replication) => {
+                                            map.insert("replication".into(), replication);
+                                        }
+                                        Err(e) => tracing::error!(
+                                            "Failed to serialize the replication status: {e:?}"
+                                        ),
+                                    }
+                                }
+                                std::mem::drop(tx.send(status).await);
+                            }
+                            StorageMessage::GetReplicationStatus(tx) => {
+                                std::mem::drop(tx.send(self.replication_status().await).await);
                             }
                         };
                     },
@@ -207,9 +323,58 @@ impl StorageService {
         });
     }
 
+    /// Returns the status of the replication of the Storage, if it is replicated.
+    async fn replication_status(&self) -> Option<ReplicationStatus> {
+        let (replication_log, metrics) = match (
+            &self.cache_latest.replication_log,
+            &self.replication_metrics,
+        ) {
+            (Some(replication_log), Some(metrics)) => (replication_log, metrics),
+            _ => return None,
+        };
+
+        let replication_log_guard = replication_log.read().await;
+        match (
+            replication_log_guard.digest(),
+            replication_log_guard.eras_status(),
+        ) {
+            (Ok(digest), Ok(eras)) => Some(metrics.status(digest, eras)),
+            (Err(e), _) | (_, Err(e)) => {
+                tracing::error!("Failed to compute the replication status: {e:?}");
+                None
+            }
+        }
+    }
+
     // The storage should only simply save the key, sample pair while put and retrieve the same
     // during get the trimming during PUT and GET should be handled by the plugin
     pub(crate) async fn process_sample(&self, sample: Sample) -> ZResult<()> {
+        let updates = self.prepare_updates(sample).await?;
+        self.apply_updates(updates).await;
+        Ok(())
+    }
+
+    /// Processes a batch of Samples, applying the resulting updates to the Storage with as few
+    /// calls to `Storage::apply_batch` as possible.
+    pub(crate) async fn process_samples(&self, samples: Vec<Sample>) {
+        let mut updates = Vec::with_capacity(samples.len());
+        for sample in samples {
+            // A Wildcard Update applies to the keys present in the Storage: the updates of the
+            // previous Samples must be applied before retrieving them.
+            if sample.key_expr().is_wild() {
+                self.apply_updates(std::mem::take(&mut updates)).await;
+            }
+            match self.prepare_updates(sample).await {
+                Ok(sample_updates) => updates.extend(sample_updates),
+                Err(e) => tracing::error!("{e:?}"),
+            }
+        }
+        self.apply_updates(updates).await;
+    }
+
+    /// Returns the updates to apply to the Storage for the provided Sample: one per matching key
+    /// if its key expression is a wildcard, or the Wildcard Update overriding it if any.
+    async fn prepare_updates(&self, sample: Sample) -> ZResult<Vec<PendingUpdate>> {
         tracing::trace!("[STORAGE] Processing sample: {:?}", sample.key_expr());
         let SampleFields {
             key_expr,
@@ -266,6 +431,7 @@ impl StorageService {
 
         let prefix = self.configuration.strip_prefix.as_ref();
 
+        let mut updates = Vec::with_capacity(matching_keys.len());
         for k in matching_keys {
             // there might be the case that the actual update was outdated due to a wild card
             // update, but not stored yet in the storage. get the relevant wild
@@ -311,61 +477,95 @@ impl StorageService {
                 }
             };
 
-            // If the Storage was declared as only keeping the Latest value, we ensure that, for
-            // each received Sample, it is indeed the Latest value that is processed.
-            let new_event = Event::new(stripped_key.clone(), sample_to_store_timestamp, &action);
-            let mut cache_guard = None;
-            if self.capability.history == History::Latest {
-                match self.guard_cache_if_latest(&new_event).await {
-                    Some(guard) => {
-                        cache_guard = Some(guard);
-                    }
-                    None => {
-                        tracing::trace!("Skipping outdated Sample < {} >", k);
-                        continue;
-                    }
-                }
-            }
+            let operation = match kind {
+                SampleKind::Put => StorageOperation::Put {
+                    key: stripped_key.clone(),
+                    payload: sample_to_store.payload().clone(),
+                    encoding: sample_to_store.encoding().clone(),
+                    timestamp: sample_to_store_timestamp,
+                },
+                SampleKind::Delete => StorageOperation::Delete {
+                    key: stripped_key.clone(),
+                    timestamp: sample_to_store_timestamp,
+                },
+            };
 
-            let mut storage = self.storage.lock().await;
-            let storage_result = match sample.kind() {
-                SampleKind::Put => {
-                    storage
-                        .put(
-                            stripped_key.clone(),
-                            sample_to_store.payload().clone(),
-                            sample_to_store.encoding().clone(),
-                            sample_to_store_timestamp,
-                        )
-                        .await
-                }
-                SampleKind::Delete => {
-                    storage
-                        .delete(stripped_key.clone(), sample_to_store_timestamp)
-                        .await
+            updates.push(PendingUpdate {
+                key_expr: k,
+                kind,
+                event: Event::new(stripped_key, sample_to_store_timestamp, &action),
+                operation,
+            });
+        }
+
+        Ok(updates)
+    }
+
+    /// Applies the updates to the Storage, skipping the outdated ones if the Storage only keeps
+    /// the Latest value.
+    async fn apply_updates(&self, updates: Vec<PendingUpdate>) {
+        if updates.is_empty() {
+            return;
+        }
+
+        // If the Storage was declared as only keeping the Latest value, we ensure that, for
+        // each received Sample, it is indeed the Latest value that is processed.
+        //
+        // The guard over the cache must be kept until the Storage has processed the updates and
+        // the Cache has been updated accordingly, see `is_latest`.
+        let mut cache_guard = None;
+        let updates = if self.capability.history == History::Latest {
+            let guard = self.cache_latest.latest_updates.write().await;
+            // Several updates of the same key can be part of the same batch.
+            let mut batch_latest = HashMap::new();
+            let mut latest_updates = Vec::with_capacity(updates.len());
+            for update in updates {
+                let log_key = update.event.log_key();
+                let outdated_in_batch = batch_latest
+                    .get(&log_key)
+                    .is_some_and(|timestamp| *timestamp >= update.event.timestamp);
+                if outdated_in_batch || !self.is_latest(&guard, &update.event).await {
+                    tracing::trace!("Skipping outdated Sample < {} >", update.key_expr);
+                    continue;
                 }
-            };
+                batch_latest.insert(log_key, update.event.timestamp);
+                latest_updates.push(update);
+            }
+            cache_guard = Some(guard);
+            latest_updates
+        } else {
+            updates
+        };
 
-            drop(storage);
+        let operations = updates
+            .iter()
+            .map(|update| update.operation.clone())
+            .collect();
+        let mut storage = self.storage.lock().await;
+        let storage_results = storage.apply_batch(operations).await;
+        drop(storage);
 
+        for (update, storage_result) in updates.into_iter().zip(storage_results) {
             match storage_result {
                 Ok(StorageInsertionResult::Outdated) => {
-                    tracing::trace!("Ignoring `Outdated` sample < {} >", k);
+                    tracing::trace!("Ignoring `Outdated` sample < {} >", update.key_expr);
                 }
                 Ok(_) => {
-                    if let Some(mut cache_guard) = cache_guard {
-                        cache_guard.insert(new_event.log_key(), new_event);
+                    if let Some(cache_guard) = &mut cache_guard {
+                        cache_guard.insert(update.event.log_key(), update.event);
                     }
                 }
                 Err(e) => {
                     // TODO In case of a wildcard update, multiple keys can be updated. What should
                     //      be the behaviour if one or more of these updates fail?
-                    tracing::error!("`{}` on < {} > failed with: {e:?}", sample.kind(), k);
+                    tracing::error!(
+                        "`{}` on < {} > failed with: {e:?}",
+                        update.kind,
+                        update.key_expr
+                    );
                 }
             }
         }
-
-        Ok(())
     }
 
     /// Registers a Wildcard Update, storing it in a dedicated in-memory structure and on disk if
@@ -492,28 +692,24 @@ impl StorageService {
         None
     }
 
-    /// Returns a guard over the cache if the provided [Timestamp] is more recent than what is kept
-    /// in the Storage for the `stripped_key`. Otherwise returns `None`.
+    /// Returns `true` if the provided [Timestamp] is more recent than what is kept in the Storage
+    /// for the `stripped_key`.
     ///
     /// This method will first look up any cached value and if none is found, it will request the
     /// Storage.
     ///
     /// # ⚠️ Race-condition
     ///
-    /// Returning a guard over the cache is not an "innocent" choice: in order to avoid
-    /// race-condition, the guard over the cache must be kept until the Storage has processed the
-    /// Sample and the Cache has been updated accordingly.
+    /// The caller must hold a guard over the cache: in order to avoid race-condition, the guard
+    /// must be kept until the Storage has processed the Sample and the Cache has been updated
+    /// accordingly.
     ///
     /// If the lock is released before both operations are performed, the Cache and Storage could
     /// end up in an inconsistent state (think two updates being processed at the same time).
-    async fn guard_cache_if_latest(
-        &self,
-        new_event: &Event,
-    ) -> Option<RwLockWriteGuard<'_, LatestUpdates>> {
-        let cache_guard = self.cache_latest.latest_updates.write().await;
-        if let Some(event) = cache_guard.get(&new_event.log_key()) {
+    async fn is_latest(&self, cache: &LatestUpdates, new_event: &Event) -> bool {
+        if let Some(event) = cache.get(&new_event.log_key()) {
             if new_event.timestamp > event.timestamp {
-                return Some(cache_guard);
+                return true;
             }
         }
 
@@ -524,7 +720,7 @@ impl StorageService {
                 .lookup_newer(new_event)
                 .is_some()
             {
-                return None;
+                return false;
             }
         } else {
             let mut storage = self.storage.lock().await;
@@ -533,13 +729,13 @@ impl StorageService {
             if let Ok(stored_data) = storage.get(new_event.stripped_key.clone(), "").await {
                 for data in stored_data {
                     if data.timestamp > new_event.timestamp {
-                        return None;
+                        return false;
                     }
                 }
             }
         }
 
-        Some(cache_guard)
+        true
     }
 
     async fn reply_query(&self, query: ZResult<zenoh::query::Query>) {
@@ -552,44 +748,145 @@ impl StorageService {
         };
         tracing::trace!("[STORAGE] Processing query on key_expr: {}", q.key_expr());
 
+        // The time range is resolved once so that the values of all the keys are filtered with the
+        // same bounds.
+        let time_range = match q.parameters().time_range() {
+            Some(Ok(time_range)) => Some(time_range.resolve()),
+            Some(Err(e)) => {
+                tracing::warn!(
+                    "Storage '{}' received a query with an invalid `_time` parameter: {e}",
+                    self.name
+                );
+                if let Err(e) = q.reply_err(format!("Invalid `_time` parameter: {e}")).await {
+                    tracing::warn!(
+                        "Storage '{}' raised an error replying a query: {}",
+                        self.name,
+                        e
+                    )
+                }
+                return;
+            }
+            None => None,
+        };
+        // Expired values that were not yet deleted are not returned.
+        let expiry_limit = self
+            .configuration
+            .ttl
+            .and_then(|ttl| SystemTime::now().checked_sub(ttl));
+        let is_visible = |entry: &StoredData| {
+            let time = entry.timestamp.get_time().to_system_time();
+            expiry_limit.map_or(true, |expiry_limit| time > expiry_limit)
+                && time_range
+                    .as_ref()
+                    .map_or(true, |time_range| time_range.contains(time))
+        };
+
         let prefix = self.configuration.strip_prefix.as_ref();
 
         if q.key_expr().is_wild() {
-            // resolve key expr into individual keys
-            let matching_keys = self.get_matching_keys(q.key_expr()).await;
-            let mut storage = self.storage.lock().await;
-            for key in matching_keys {
-                let stripped_key = match crate::strip_prefix(prefix, &key.clone().into()) {
-                    Ok(k) => k,
-                    Err(e) => {
-                        tracing::error!("{}", e);
-                        // @TODO: return error when it is supported
-                        return;
+            let page_options = match pagination::page_options(q.parameters()) {
+                Ok(page_options) => page_options,
+                Err(e) => {
+                    tracing::warn!("Storage '{}' received an invalid query: {e}", self.name);
+                    if let Err(e) = q.reply_err(e.to_string()).await {
+                        tracing::warn!(
+                            "Storage '{}' raised an error replying a query: {}",
+                            self.name,
+                            e
+                        )
                     }
-                };
-                match storage.get(stripped_key, q.parameters().as_str()).await {
-                    Ok(stored_data) => {
-                        for entry in stored_data {
-                            if let Err(e) = q
-                                .reply(key.clone(), entry.payload.clone())
-                                .encoding(entry.encoding.clone())
-                                .timestamp(entry.timestamp)
-                                .await
-                            {
-                                tracing::warn!(
-                                    "Storage '{}' raised an error replying a query: {}",
-                                    self.name,
-                                    e
-                                )
-                            }
+                    return;
+                }
+            };
+
+            // The storage is only locked while retrieving the matching entries, not while replying.
+            let parameters = q.parameters().as_str();
+            let limit = page_options.limit;
+            let mut replies: Vec<(Option<OwnedKeyExpr>, StoredData)> = Vec::new();
+            let mut next_cursor = None;
+
+            // The entries retrieved and not yet replied, starting with the remaining versions of
+            // the key the previous page stopped in.
+            let mut pending: Vec<(Option<OwnedKeyExpr>, Vec<StoredData>)> = Vec::new();
+            let mut scan_cursor = None;
+            if let Some(cursor) = page_options.cursor {
+                if let Some(last_timestamp) = cursor.last_timestamp {
+                    let stored_data = self
+                        .get_entry(cursor.last_key.clone(), parameters)
+                        .await
+                        .unwrap_or_default()
+                        .into_iter()
+                        .filter(|entry| entry.timestamp > last_timestamp)
+                        .collect();
+                    pending.push((cursor.last_key.clone(), stored_data));
+                }
+                scan_cursor = Some(cursor.scan_cursor());
+            }
+
+            // The keys are scanned until the page is full: the entries that are not visible do not
+            // count, so that a truncated page is never empty and always delivers its cursor.
+            let mut is_scanned = false;
+            'page: loop {
+                for (stripped_key, mut stored_data) in pending.drain(..) {
+                    stored_data.retain(is_visible);
+                    stored_data.sort_by_key(|entry| entry.timestamp);
+                    for entry in stored_data {
+                        if limit.is_some_and(|limit| replies.len() >= limit) {
+                            next_cursor = replies.last().map(|(key, entry)| PageCursor {
+                                last_key: key.clone(),
+                                last_timestamp: Some(entry.timestamp),
+                            });
+                            break 'page;
                         }
+                        replies.push((stripped_key.clone(), entry));
+                    }
+                }
+                if is_scanned {
+                    break;
+                }
+
+                let scan_options = ScanOptions {
+                    limit: limit.map(|limit| limit.saturating_sub(replies.len()).max(1)),
+                    cursor: scan_cursor.take(),
+                };
+                match self
+                    .get_matching_entries(q.key_expr(), prefix, parameters, scan_options)
+                    .await
+                {
+                    Ok(ScanResult { entries, cursor }) => {
+                        pending = entries;
+                        is_scanned = cursor.is_none();
+                        scan_cursor = cursor;
                     }
                     Err(e) => {
-                        tracing::warn!("Storage'{}' raised an error on query: {}", self.name, e)
+                        tracing::warn!("Storage '{}' raised an error on query: {}", self.name, e);
+                        return;
                     }
+                }
+            }
+
+            // If the replies were truncated, they all carry the cursor to retrieve the next ones:
+            // the order in which they are received by the querier is not guaranteed.
+            let cursor = next_cursor.as_ref().map(pagination::encode_cursor);
+            for (stripped_key, entry) in replies {
+                let Ok(key) = crate::prefix(prefix, stripped_key.as_ref()) else {
+                    tracing::error!("Internal error: empty key with no `strip_prefix` configured");
+                    continue;
                 };
+                if let Err(e) = q
+                    .reply(key, entry.payload)
+                    .encoding(entry.encoding)
+                    .timestamp(entry.timestamp)
+                    .attachment(cursor.clone())
+                    .await
+                {
+                    tracing::warn!(
+                        "Storage '{}' raised an error replying a query: {}",
+                        self.name,
+                        e
+                    )
+                }
             }
-            drop(storage);
         } else {
             let stripped_key = match crate::strip_prefix(prefix, q.key_expr()) {
                 Ok(k) => k,
@@ -599,10 +896,10 @@ impl StorageService {
                     return;
                 }
             };
-            let mut storage = self.storage.lock().await;
-            match storage.get(stripped_key, q.parameters().as_str()).await {
+            let parameters = q.parameters().as_str();
+            match self.get_entry(stripped_key, parameters).await {
                 Ok(stored_data) => {
-                    for entry in stored_data {
+                    for entry in stored_data.into_iter().filter(is_visible) {
                         if let Err(e) = q
                             .reply(q.key_expr().clone(), entry.payload.clone())
                             .encoding(entry.encoding.clone())
@@ -624,6 +921,331 @@ impl StorageService {
         }
     }
 
+    /// Retrieves the values of a key, through the concurrent reader if the backend provides one.
+    async fn get_entry(
+        &self,
+        stripped_key: Option<OwnedKeyExpr>,
+        parameters: &str,
+    ) -> ZResult<Vec<StoredData>> {
+        match &self.reader {
+            Some(reader) => reader.get(stripped_key, parameters).await,
+            None => {
+                self.storage
+                    .lock()
+                    .await
+                    .get(stripped_key, parameters)
+                    .await
+            }
+        }
+    }
+
+    /// Scans the entries matching a key expression, through the concurrent reader if the backend
+    /// provides one.
+    async fn get_matching_entries(
+        &self,
+        key_expr: &keyexpr,
+        strip_prefix: Option<&OwnedKeyExpr>,
+        parameters: &str,
+        options: ScanOptions,
+    ) -> ZResult<ScanResult> {
+        match &self.reader {
+            Some(reader) => {
+                reader
+                    .get_matching_entries(key_expr, strip_prefix, parameters, options)
+                    .await
+            }
+            None => {
+                self.storage
+                    .lock()
+                    .await
+                    .get_matching_entries(key_expr, strip_prefix, parameters, options)
+                    .await
+            }
+        }
+    }
+
+    /// Deletes the values that were stored for longer than the `ttl`.
+    ///
+    /// The delete of an expired value is timestamped with its expiry time (i.e. its timestamp
+    /// increased by the `ttl`) so that all the replicas generate the same delete. It is processed
+    /// as any other delete, hence recorded in the Replication Log, and published to notify the
+    /// subscribers of the expiry.
+    async fn expire_entries(&self, ttl: Duration) {
+        let entries = match self.storage.lock().await.get_all_entries().await {
+            Ok(entries) => entries,
+            Err(e) => {
+                tracing::warn!(
+                    "Storage '{}' raised an error while retrieving its entries: {e:?}",
+                    self.name
+                );
+                return;
+            }
+        };
+
+        let now = SystemTime::now();
+        let prefix = self.configuration.strip_prefix.as_ref();
+
+        for (stripped_key, timestamp) in entries {
+            match timestamp.get_time().to_system_time().checked_add(ttl) {
+                Some(expiry) if expiry <= now => {}
+                _ => continue,
+            }
+
+            let Ok(key) = crate::prefix(prefix, stripped_key.as_ref()) else {
+                tracing::error!("Internal error: empty key with no `strip_prefix` configured");
+                continue;
+            };
+            let expiry_timestamp = Timestamp::new(
+                *timestamp.get_time() + NTP64::from(ttl),
+                *timestamp.get_id(),
+            );
+            tracing::trace!("Expiring < {} > at {}", key, expiry_timestamp);
+
+            let sample = SampleBuilder::delete(key.clone())
+                .timestamp(expiry_timestamp)
+                .into();
+            if let Err(e) = self.process_sample(sample).await {
+                tracing::error!("Failed to expire < {} >: {e:?}", key);
+                continue;
+            }
+
+            if let Err(e) = self
+                .session
+                .delete(&key)
+                .timestamp(expiry_timestamp)
+                .allowed_destination(Locality::Remote)
+                .await
+            {
+                tracing::warn!(
+                    "Storage '{}' failed to publish the expiry of < {} >: {e:?}",
+                    self.name,
+                    key
+                );
+            }
+        }
+    }
+
+    /// Replies to the admin operations on the storage: the `export` and `import` of snapshots.
+    async fn reply_admin_query(&self, query: ZResult<zenoh::query::Query>) {
+        let q = match query {
+            Ok(q) => q,
+            Err(e) => {
+                tracing::error!("Error in admin query: {}", e);
+                return;
+            }
+        };
+
+        // Queries on the whole admin space must not trigger the operations.
+        if q.key_expr().is_wild() {
+            return;
+        }
+        let Some(operation) = q
+            .key_expr()
+            .as_str()
+            .strip_prefix(self.admin_key.as_str())
+            .and_then(|operation| operation.strip_prefix('/'))
+        else {
+            return;
+        };
+
+        let result = match operation {
+            snapshot::EXPORT_OPERATION => self.export_snapshot(&q).await,
+            snapshot::IMPORT_OPERATION => self.import_snapshot(&q).await,
+            _ => return,
+        };
+
+        let reply_result = match result {
+            Ok((payload, encoding)) => {
+                q.reply(q.key_expr().clone(), payload)
+                    .encoding(encoding)
+                    .await
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Storage '{}' failed to {operation} a snapshot: {e}",
+                    self.name
+                );
+                q.reply_err(e.to_string()).await
+            }
+        };
+        if let Err(e) = reply_result {
+            tracing::warn!(
+                "Storage '{}' raised an error replying an admin query: {}",
+                self.name,
+                e
+            )
+        }
+    }
+
+    /// Returns the snapshot of the storage or, if the query has a `file` parameter, writes it to
+    /// that file of the snapshot directory.
+    async fn export_snapshot(&self, q: &zenoh::query::Query) -> ZResult<(ZBytes, Encoding)> {
+        let path = q
+            .parameters()
+            .get(snapshot::FILE_PARAM)
+            .map(|file| {
+                snapshot::file_path(self.configuration.snapshot.directory.as_deref(), file)
+                    .map(|path| (file, path))
+            })
+            .transpose()?;
+        let snapshot = self.snapshot().await?;
+        let bytes = snapshot.encode()?;
+
+        match path {
+            Some((file, path)) => {
+                tokio::fs::write(&path, &bytes)
+                    .await
+                    .map_err(|e| zerror!("Failed to write snapshot to {}: {e}", path.display()))?;
+                tracing::info!(
+                    "Storage '{}' exported {} records to {}",
+                    self.name,
+                    snapshot.records.len(),
+                    path.display()
+                );
+                let status = serde_json::json!({
+                    "file": file,
+                    "records": snapshot.records.len(),
+                });
+                Ok((status.to_string().into(), Encoding::APPLICATION_JSON))
+            }
+            None => Ok((bytes.into(), Encoding::TEXT_PLAIN)),
+        }
+    }
+
+    /// Replays the snapshot, read from the `file` parameter of the query or from its payload, with
+    /// the original timestamps of its records.
+    ///
+    /// Importing a snapshot overrides the content of the storage: it must be enabled in the
+    /// configuration of the storage.
+    async fn import_snapshot(&self, q: &zenoh::query::Query) -> ZResult<(ZBytes, Encoding)> {
+        if !self.configuration.snapshot.import {
+            bail!("Importing snapshots is not enabled in the configuration of the storage");
+        }
+        let bytes = match q.parameters().get(snapshot::FILE_PARAM) {
+            Some(file) => {
+                let path =
+                    snapshot::file_path(self.configuration.snapshot.directory.as_deref(), file)?;
+                tokio::fs::read(&path)
+                    .await
+                    .map_err(|e| zerror!("Failed to read snapshot from {}: {e}", path.display()))?
+            }
+            None => match q.payload() {
+                Some(payload) => payload.to_bytes().into_owned(),
+                None => bail!(
+                    "Missing snapshot: expected as payload or in the `{}` parameter",
+                    snapshot::FILE_PARAM
+                ),
+            },
+        };
+        let snapshot = Snapshot::decode(&bytes)?;
+
+        let mut samples = Vec::with_capacity(snapshot.records.len());
+        let mut skipped = 0;
+        for record in &snapshot.records {
+            let sample = record.to_sample()?;
+            if self.configuration.key_expr.intersects(sample.key_expr()) {
+                samples.push(sample);
+            } else {
+                skipped += 1;
+            }
+        }
+        // Samples created from records always have a timestamp.
+        samples.sort_by_key(|sample| sample.timestamp().copied());
+
+        let imported = samples.len();
+        self.process_samples(samples).await;
+        tracing::info!(
+            "Storage '{}' imported {imported} records from a snapshot of storage '{}' ({skipped} \
+             records skipped as outside of its key expression)",
+            self.name,
+            snapshot.header.storage
+        );
+
+        let status = serde_json::json!({
+            "imported": imported,
+            "skipped": skipped,
+        });
+        Ok((status.to_string().into(), Encoding::APPLICATION_JSON))
+    }
+
+    /// Returns a snapshot of the content of the storage: all the values it holds (with their
+    /// history, if kept) along with the deletes and Wildcard Updates known to the storage.
+    async fn snapshot(&self) -> ZResult<Snapshot> {
+        let prefix = self.configuration.strip_prefix.as_ref();
+        let key_expr = &self.configuration.key_expr;
+        // Storages keeping the history of the keys return all their values for this time range.
+        let parameters = "_time=[..]";
+
+        let scan_result = match &self.reader {
+            Some(reader) => {
+                reader
+                    .get_matching_entries(key_expr, prefix, parameters, Default::default())
+                    .await?
+            }
+            None => {
+                self.storage
+                    .lock()
+                    .await
+                    .get_matching_entries(key_expr, prefix, parameters, Default::default())
+                    .await?
+            }
+        };
+
+        let mut records = Vec::new();
+        for (stripped_key, stored_data) in scan_result.entries {
+            let Ok(key) = crate::prefix(prefix, stripped_key.as_ref()) else {
+                tracing::error!("Internal error: empty key with no `strip_prefix` configured");
+                continue;
+            };
+            records.extend(
+                stored_data
+                    .iter()
+                    .map(|data| SnapshotRecord::put(&key, data)),
+            );
+        }
+
+        // The deletes are either in the cache or, if the replication is enabled, in the
+        // Replication Log.
+        let mut deletes = HashMap::<Option<OwnedKeyExpr>, Timestamp>::new();
+        let mut add_delete = |event: &Event| {
+            if event.action() == &Action::Delete {
+                let timestamp = deletes
+                    .entry(event.stripped_key.clone())
+                    .or_insert(event.timestamp);
+                *timestamp = (*timestamp).max(event.timestamp);
+            }
+        };
+        self.cache_latest
+            .latest_updates
+            .read()
+            .await
+            .values()
+            .for_each(&mut add_delete);
+        if let Some(replication_log) = &self.cache_latest.replication_log {
+            replication_log
+                .read()
+                .await
+                .events()
+                .for_each(&mut add_delete);
+        }
+        for (stripped_key, timestamp) in deletes {
+            let Ok(key) = crate::prefix(prefix, stripped_key.as_ref()) else {
+                tracing::error!("Internal error: empty key with no `strip_prefix` configured");
+                continue;
+            };
+            records.push(SnapshotRecord::delete(&key, &timestamp));
+        }
+
+        for (key, update) in self.wildcard_puts.read().await.key_value_pairs() {
+            records.push(SnapshotRecord::put(&key, &update.data));
+        }
+        for (key, update) in self.wildcard_deletes.read().await.key_value_pairs() {
+            records.push(SnapshotRecord::delete(&key, update.timestamp()));
+        }
+
+        Ok(Snapshot::new(&self.configuration.name, key_expr, records))
+    }
+
     async fn get_matching_keys(&self, key_expr: &keyexpr) -> Vec<OwnedKeyExpr> {
         let mut result = Vec::new();
         // @TODO: if cache exists, use that to get the list
@@ -631,20 +1253,16 @@ impl StorageService {
 
         let prefix = self.configuration.strip_prefix.as_ref();
 
-        match storage.get_all_entries().await {
+        match storage.get_matching_keys(key_expr, prefix).await {
             Ok(entries) => {
                 for (k, _ts) in entries {
-                    // @TODO: optimize adding back the prefix (possible inspiration from https://github.com/eclipse-zenoh/zenoh/blob/0.5.0-beta.9/backends/traits/src/utils.rs#L79)
                     let Ok(full_key) = crate::prefix(prefix, k.as_ref()) else {
                         tracing::error!(
                             "Internal error: empty key with no `strip_prefix` configured"
                         );
                         continue;
                     };
-
-                    if key_expr.intersects(&full_key.clone()) {
-                        result.push(full_key);
-                    }
+                    result.push(full_key);
                 }
             }
             Err(e) => tracing::warn!(
@@ -0,0 +1,209 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Snapshots of the content of a Storage, to back it up or move it to another router.
+//!
+//! A snapshot is a JSON Lines document: its first line is a [SnapshotHeader] and each following
+//! line a [SnapshotRecord]. The records hold the complete key expressions (i.e. including the
+//! `strip_prefix`), the original timestamps and the payloads encoded in base64. Deletes
+//! (tombstones) and Wildcard Updates are recorded as well so that importing a snapshot restores
+//! the exact same state.
+
+use std::{
+    path::{Component, Path, PathBuf},
+    str::FromStr,
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use zenoh::{
+    bytes::Encoding,
+    internal::{bail, zerror},
+    key_expr::{keyexpr, OwnedKeyExpr},
+    sample::{Sample, SampleBuilder},
+    time::Timestamp,
+    Result as ZResult,
+};
+use zenoh_backend_traits::StoredData;
+
+/// Admin operation returning the snapshot of a Storage.
+pub(crate) const EXPORT_OPERATION: &str = "export";
+/// Admin operation replaying a snapshot in a Storage.
+pub(crate) const IMPORT_OPERATION: &str = "import";
+/// Parameter of the admin operations: path of the snapshot file, relative to the snapshot
+/// directory of the Storage.
+pub(crate) const FILE_PARAM: &str = "file";
+
+const SNAPSHOT_FORMAT: &str = "zenoh-storage-snapshot";
+const SNAPSHOT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct SnapshotHeader {
+    pub(crate) format: String,
+    pub(crate) version: u32,
+    /// Name of the exported Storage.
+    pub(crate) storage: String,
+    /// Key expression of the exported Storage.
+    pub(crate) key_expr: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub(crate) enum SnapshotRecord {
+    Put {
+        key: String,
+        timestamp: String,
+        encoding: String,
+        payload: String,
+    },
+    Delete {
+        key: String,
+        timestamp: String,
+    },
+}
+
+impl SnapshotRecord {
+    pub(crate) fn put(key: &keyexpr, data: &StoredData) -> Self {
+        Self::Put {
+            key: key.to_string(),
+            timestamp: data.timestamp.to_string(),
+            encoding: data.encoding.to_string(),
+            payload: STANDARD.encode(data.payload.to_bytes()),
+        }
+    }
+
+    pub(crate) fn delete(key: &keyexpr, timestamp: &Timestamp) -> Self {
+        Self::Delete {
+            key: key.to_string(),
+            timestamp: timestamp.to_string(),
+        }
+    }
+
+    /// Returns the Sample to process to replay this record, with its original timestamp.
+    pub(crate) fn to_sample(&self) -> ZResult<Sample> {
+        let (key, timestamp) = match self {
+            Self::Put { key, timestamp, .. } | Self::Delete { key, timestamp } => (key, timestamp),
+        };
+        let key = OwnedKeyExpr::from_str(key)
+            .map_err(|e| zerror!("Invalid key expression < {key} > in snapshot: {e}"))?;
+        let timestamp = Timestamp::from_str(timestamp)
+            .map_err(|e| zerror!("Invalid timestamp < {timestamp} > in snapshot: {e:?}"))?;
+
+        Ok(match self {
+            Self::Put {
+                encoding, payload, ..
+            } => {
+                let payload = STANDARD
+                    .decode(payload)
+                    .map_err(|e| zerror!("Invalid payload of < {key} > in snapshot: {e}"))?;
+                SampleBuilder::put(key, payload)
+                    .encoding(Encoding::from(encoding.as_str()))
+                    .timestamp(timestamp)
+                    .into()
+            }
+            Self::Delete { .. } => SampleBuilder::delete(key).timestamp(timestamp).into(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Snapshot {
+    pub(crate) header: SnapshotHeader,
+    pub(crate) records: Vec<SnapshotRecord>,
+}
+
+impl Snapshot {
+    pub(crate) fn new(storage: &str, key_expr: &keyexpr, records: Vec<SnapshotRecord>) -> Self {
+        Self {
+            header: SnapshotHeader {
+                format: SNAPSHOT_FORMAT.to_string(),
+                version: SNAPSHOT_VERSION,
+                storage: storage.to_string(),
+                key_expr: key_expr.to_string(),
+            },
+            records,
+        }
+    }
+
+    pub(crate) fn encode(&self) -> ZResult<Vec<u8>> {
+        let mut bytes = serde_json::to_vec(&self.header)?;
+        for record in &self.records {
+            bytes.push(b'\n');
+            serde_json::to_writer(&mut bytes, record)?;
+        }
+        bytes.push(b'\n');
+        Ok(bytes)
+    }
+
+    pub(crate) fn decode(bytes: &[u8]) -> ZResult<Self> {
+        let mut lines = bytes
+            .split(|byte| *byte == b'\n')
+            .filter(|line| !line.iter().all(u8::is_ascii_whitespace));
+
+        let header: SnapshotHeader = match lines.next() {
+            Some(line) => {
+                serde_json::from_slice(line).map_err(|e| zerror!("Invalid snapshot header: {e}"))?
+            }
+            None => bail!("Empty snapshot"),
+        };
+        if header.format != SNAPSHOT_FORMAT || header.version != SNAPSHOT_VERSION {
+            bail!(
+                "Unsupported snapshot: expected format < {SNAPSHOT_FORMAT} > version \
+                 < {SNAPSHOT_VERSION} >, found < {} > version < {} >",
+                header.format,
+                header.version
+            );
+        }
+
+        let records = lines
+            .enumerate()
+            .map(|(i, line)| {
+                serde_json::from_slice(line)
+                    .map_err(|e| zerror!("Invalid record {} of snapshot: {e}", i + 1).into())
+            })
+            .collect::<ZResult<_>>()?;
+
+        Ok(Self { header, records })
+    }
+}
+
+/// Returns the path of the snapshot file given in the `file` parameter of an admin operation.
+///
+/// The file must be a relative path, without `..` components, that is resolved in the snapshot
+/// `directory` of the Storage: the admin operations cannot access any other file of the router.
+pub(crate) fn file_path(directory: Option<&Path>, file: &str) -> ZResult<PathBuf> {
+    let Some(directory) = directory else {
+        bail!(
+            "The `{FILE_PARAM}` parameter requires a snapshot `directory` in the configuration \
+             of the storage"
+        );
+    };
+    let file = Path::new(file);
+    if file.as_os_str().is_empty()
+        || !file
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+    {
+        bail!(
+            "Invalid snapshot file < {} >: expected a path relative to the snapshot directory, \
+             without `..` components",
+            file.display()
+        );
+    }
+    Ok(directory.join(file))
+}
+
+#[cfg(test)]
+#[path = "tests/snapshot.test.rs"]
+mod tests;
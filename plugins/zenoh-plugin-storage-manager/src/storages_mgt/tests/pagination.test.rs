@@ -0,0 +1,79 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+use std::str::FromStr;
+
+use zenoh::{key_expr::OwnedKeyExpr, query::Parameters, time::Timestamp};
+
+use super::{decode_cursor, encode_cursor, page_options, PageCursor, PageOptions};
+
+#[test]
+fn test_cursor_encoding() {
+    let timestamp = Timestamp::from_str("7386690599959157260/33").unwrap();
+    for last_key in [
+        None,
+        Some(OwnedKeyExpr::new("demo/example/a").unwrap()),
+        Some(OwnedKeyExpr::new("with;separators=and&co").unwrap()),
+    ] {
+        for last_timestamp in [None, Some(timestamp)] {
+            let cursor = PageCursor {
+                last_key: last_key.clone(),
+                last_timestamp,
+            };
+            let encoded = encode_cursor(&cursor);
+            assert!(!encoded.contains([';', '=', '&', '/']));
+            assert_eq!(decode_cursor(&encoded).unwrap(), cursor);
+        }
+    }
+
+    assert!(decode_cursor("").is_err());
+    assert!(decode_cursor("not a cursor").is_err());
+    // A cursor whose key is not a valid key expression.
+    assert!(decode_cursor(&base64::Engine::encode(
+        &base64::engine::general_purpose::URL_SAFE_NO_PAD,
+        b"\x01a//b"
+    ))
+    .is_err());
+    // A cursor whose timestamp is truncated.
+    assert!(decode_cursor(&base64::Engine::encode(
+        &base64::engine::general_purpose::URL_SAFE_NO_PAD,
+        b"\x03\x10abc"
+    ))
+    .is_err());
+}
+
+#[test]
+fn test_page_options() {
+    assert_eq!(
+        page_options(&Parameters::from("")).unwrap(),
+        PageOptions::default()
+    );
+
+    let cursor = PageCursor {
+        last_key: Some(OwnedKeyExpr::new("a/b").unwrap()),
+        last_timestamp: None,
+    };
+    let parameters = format!("_limit=10;_cursor={}", encode_cursor(&cursor));
+    assert_eq!(
+        page_options(&Parameters::from(parameters)).unwrap(),
+        PageOptions {
+            limit: Some(10),
+            cursor: Some(cursor),
+        }
+    );
+
+    assert!(page_options(&Parameters::from("_limit=0")).is_err());
+    assert!(page_options(&Parameters::from("_limit=-1")).is_err());
+    assert!(page_options(&Parameters::from("_limit=ten")).is_err());
+}
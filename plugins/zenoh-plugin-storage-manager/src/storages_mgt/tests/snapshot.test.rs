@@ -0,0 +1,116 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+use std::path::Path;
+
+use uhlc::HLC;
+use zenoh::{bytes::Encoding, key_expr::keyexpr, sample::SampleKind};
+use zenoh_backend_traits::StoredData;
+
+use super::{file_path, Snapshot, SnapshotRecord};
+
+#[test]
+fn test_snapshot_encoding() {
+    let hlc = HLC::default();
+    let put_timestamp = hlc.new_timestamp();
+    let delete_timestamp = hlc.new_timestamp();
+
+    let snapshot = Snapshot::new(
+        "demo",
+        keyexpr::new("demo/**").unwrap(),
+        vec![
+            SnapshotRecord::put(
+                keyexpr::new("demo/a").unwrap(),
+                &StoredData {
+                    payload: vec![0u8, 159, 146, 150].into(),
+                    encoding: Encoding::APPLICATION_OCTET_STREAM,
+                    timestamp: put_timestamp,
+                },
+            ),
+            SnapshotRecord::delete(keyexpr::new("demo/*").unwrap(), &delete_timestamp),
+        ],
+    );
+
+    let bytes = snapshot.encode().unwrap();
+    // One line for the header, one per record.
+    assert_eq!(
+        bytes
+            .split(|b| *b == b'\n')
+            .filter(|l| !l.is_empty())
+            .count(),
+        3
+    );
+    let decoded = Snapshot::decode(&bytes).unwrap();
+    assert_eq!(decoded, snapshot);
+
+    let put = decoded.records[0].to_sample().unwrap();
+    assert_eq!(put.kind(), SampleKind::Put);
+    assert_eq!(put.key_expr().as_str(), "demo/a");
+    assert_eq!(put.payload().to_bytes().as_ref(), &[0u8, 159, 146, 150]);
+    assert_eq!(put.encoding(), &Encoding::APPLICATION_OCTET_STREAM);
+    assert_eq!(put.timestamp(), Some(&put_timestamp));
+
+    let delete = decoded.records[1].to_sample().unwrap();
+    assert_eq!(delete.kind(), SampleKind::Delete);
+    assert_eq!(delete.key_expr().as_str(), "demo/*");
+    assert_eq!(delete.timestamp(), Some(&delete_timestamp));
+}
+
+#[test]
+fn test_invalid_snapshot() {
+    assert!(Snapshot::decode(b"").is_err());
+    assert!(Snapshot::decode(
+        b"{\"format\":\"other\",\"version\":1,\"storage\":\"s\",\"key_expr\":\"a\"}"
+    )
+    .is_err());
+
+    let header = b"{\"format\":\"zenoh-storage-snapshot\",\"version\":1,\"storage\":\"s\",\"key_expr\":\"a/**\"}\n";
+    assert!(Snapshot::decode(header).unwrap().records.is_empty());
+
+    let mut bytes = header.to_vec();
+    bytes.extend_from_slice(b"{\"kind\":\"delete\",\"key\":\"a/b\"}\n");
+    assert!(Snapshot::decode(&bytes).is_err());
+
+    let mut bytes = header.to_vec();
+    bytes.extend_from_slice(
+        b"{\"kind\":\"delete\",\"key\":\"a/b\",\"timestamp\":\"not a timestamp\"}\n",
+    );
+    let snapshot = Snapshot::decode(&bytes).unwrap();
+    assert!(snapshot.records[0].to_sample().is_err());
+}
+
+#[test]
+fn test_file_path() {
+    let directory = Path::new("/var/lib/zenoh/snapshots");
+
+    assert_eq!(
+        file_path(Some(directory), "demo.snapshot").unwrap(),
+        directory.join("demo.snapshot")
+    );
+    assert_eq!(
+        file_path(Some(directory), "backups/demo.snapshot").unwrap(),
+        directory.join("backups/demo.snapshot")
+    );
+
+    assert!(file_path(None, "demo.snapshot").is_err());
+    for file in [
+        "",
+        "/etc/passwd",
+        "../demo.snapshot",
+        "backups/../../demo.snapshot",
+        "./demo.snapshot",
+    ] {
+        assert!(file_path(Some(directory), file).is_err(), "{file}");
+    }
+}
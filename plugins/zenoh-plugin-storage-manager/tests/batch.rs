@@ -0,0 +1,151 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+// Test the storages applying the received updates in batches -
+// 1. the updates are applied once the batch is full or its delay expired
+// 2. an outdated update of a batch does not override a more recent one
+// 3. a wildcard update applies to the keys updated earlier in the same batch
+
+use std::thread::sleep;
+
+use tokio::runtime::Runtime;
+use zenoh::{
+    internal::zasync_executor_init,
+    query::{ConsolidationMode, Reply},
+    sample::Sample,
+    Config, Session,
+};
+use zenoh_plugin_trait::Plugin;
+
+async fn put_data(session: &Session, key_expr: &str, value: &str) {
+    println!("Putting Data ('{key_expr}': '{value}')...");
+    session.put(key_expr, value).await.unwrap();
+}
+
+async fn get_data(session: &Session, selector: &str) -> Vec<Sample> {
+    let replies: Vec<Reply> = session
+        .get(selector)
+        .consolidation(ConsolidationMode::None)
+        .await
+        .unwrap()
+        .into_iter()
+        .collect();
+    println!("Getting replies on '{selector}': '{replies:?}'...");
+    let mut samples = Vec::new();
+    for reply in replies {
+        if let Ok(sample) = reply.into_result() {
+            samples.push(sample);
+        }
+    }
+    println!("Getting Data on '{selector}': '{samples:?}'...");
+    samples
+}
+
+async fn test_batch() {
+    async {
+        zasync_executor_init!();
+    }
+    .await;
+    let mut config = Config::default();
+    config
+        .insert_json5(
+            "plugins/storage-manager",
+            r#"{
+                    storages: {
+                        batch_test: {
+                            key_expr: "batch/test/**",
+                            volume: {
+                                id: "memory"
+                            },
+                            batch: {
+                                max_size: 4,
+                                max_delay: 100,
+                            }
+                        }
+                    }
+                }"#,
+        )
+        .unwrap();
+    config
+        .insert_json5(
+            "timestamping",
+            r#"{
+                    enabled: {
+                        router: true,
+                        peer: true,
+                        client: true
+                    }
+                }"#,
+        )
+        .unwrap();
+
+    let runtime = zenoh::internal::runtime::RuntimeBuilder::new(config)
+        .build()
+        .await
+        .unwrap()
+        .into();
+    let storage =
+        zenoh_plugin_storage_manager::StoragesPlugin::start("storage-manager", &runtime).unwrap();
+
+    let session = zenoh::session::init(runtime).await.unwrap();
+
+    sleep(std::time::Duration::from_secs(1));
+
+    for i in 0..10 {
+        put_data(&session, &format!("batch/test/{}", i % 3), &i.to_string()).await;
+    }
+
+    sleep(std::time::Duration::from_millis(500));
+
+    let data = get_data(&session, "batch/test/**").await;
+    assert_eq!(data.len(), 3);
+    for (key, value) in [
+        ("batch/test/0", "9"),
+        ("batch/test/1", "7"),
+        ("batch/test/2", "8"),
+    ] {
+        let sample = data.iter().find(|s| s.key_expr().as_str() == key).unwrap();
+        assert_eq!(sample.payload().try_to_string().unwrap(), value);
+    }
+
+    // An update timestamped before the latest one of its key is ignored.
+    let outdated = session.new_timestamp();
+    put_data(&session, "batch/test/3", "new").await;
+    session
+        .put("batch/test/3", "outdated")
+        .timestamp(outdated)
+        .await
+        .unwrap();
+    put_data(&session, "batch/test/4", "4").await;
+    session.put("batch/test/*", "wildcard").await.unwrap();
+
+    sleep(std::time::Duration::from_millis(500));
+
+    let data = get_data(&session, "batch/test/**").await;
+    assert_eq!(data.len(), 5);
+    for sample in data {
+        assert_eq!(sample.payload().try_to_string().unwrap(), "wildcard");
+    }
+
+    let data = get_data(&session, "batch/test/3").await;
+    assert_eq!(data.len(), 1);
+
+    drop(storage);
+}
+
+#[test]
+fn batch_test() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async { test_batch().await });
+}
@@ -0,0 +1,166 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+// Test the queries replied concurrently to the updates of a storage -
+// 1. more queries than the storage replies concurrently are all served while batches of updates
+//    are applied
+// 2. each reply holds every key, with a value that was put and that never goes back in time
+// 3. once the updates are applied, the queries return the latest values
+
+use std::{collections::HashMap, thread::sleep, time::Duration};
+
+use tokio::runtime::Runtime;
+use zenoh::{
+    internal::zasync_executor_init,
+    query::{ConsolidationMode, Reply},
+    Config, Session,
+};
+use zenoh_plugin_trait::Plugin;
+
+const KEYS: usize = 10;
+const ROUNDS: usize = 50;
+const QUERIES: usize = 100;
+
+async fn put_round(session: &Session, round: usize) {
+    for key in 0..KEYS {
+        session
+            .put(format!("concurrent/test/{key}"), round.to_string())
+            .await
+            .unwrap();
+    }
+}
+
+/// Returns the value of each key, as the round that put it.
+async fn get_rounds(session: &Session) -> HashMap<String, usize> {
+    let replies: Vec<Reply> = session
+        .get("concurrent/test/**")
+        .consolidation(ConsolidationMode::None)
+        .await
+        .unwrap()
+        .into_iter()
+        .collect();
+    replies
+        .into_iter()
+        .map(|reply| {
+            let sample = reply.into_result().unwrap();
+            let round = sample.payload().try_to_string().unwrap().parse().unwrap();
+            (sample.key_expr().to_string(), round)
+        })
+        .collect()
+}
+
+async fn test_concurrent() {
+    async {
+        zasync_executor_init!();
+    }
+    .await;
+    let mut config = Config::default();
+    config
+        .insert_json5(
+            "plugins/storage-manager",
+            r#"{
+                    storages: {
+                        concurrent_test: {
+                            key_expr: "concurrent/test/**",
+                            volume: {
+                                id: "memory"
+                            },
+                            batch: {
+                                max_size: 100,
+                                max_delay: 50,
+                            }
+                        }
+                    }
+                }"#,
+        )
+        .unwrap();
+    config
+        .insert_json5(
+            "timestamping",
+            r#"{
+                    enabled: {
+                        router: true,
+                        peer: true,
+                        client: true
+                    }
+                }"#,
+        )
+        .unwrap();
+
+    let runtime = zenoh::internal::runtime::RuntimeBuilder::new(config)
+        .build()
+        .await
+        .unwrap()
+        .into();
+    let storage =
+        zenoh_plugin_storage_manager::StoragesPlugin::start("storage-manager", &runtime).unwrap();
+
+    let session = zenoh::session::init(runtime).await.unwrap();
+
+    sleep(Duration::from_secs(1));
+
+    put_round(&session, 0).await;
+
+    sleep(Duration::from_millis(500));
+
+    let writer = {
+        let session = session.clone();
+        tokio::task::spawn(async move {
+            for round in 1..=ROUNDS {
+                put_round(&session, round).await;
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+    };
+
+    let readers: Vec<_> = (0..QUERIES)
+        .map(|_| {
+            let session = session.clone();
+            tokio::task::spawn(async move {
+                let mut previous = HashMap::new();
+                for _ in 0..5 {
+                    let rounds = get_rounds(&session).await;
+                    assert_eq!(rounds.len(), KEYS);
+                    for (key, round) in rounds {
+                        assert!(round <= ROUNDS);
+                        assert!(round >= previous.get(&key).copied().unwrap_or(0));
+                        previous.insert(key, round);
+                    }
+                }
+            })
+        })
+        .collect();
+
+    for reader in readers {
+        tokio::time::timeout(Duration::from_secs(10), reader)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+    writer.await.unwrap();
+
+    sleep(Duration::from_millis(500));
+
+    let rounds = get_rounds(&session).await;
+    assert_eq!(rounds.len(), KEYS);
+    assert!(rounds.values().all(|round| *round == ROUNDS));
+
+    drop(storage);
+}
+
+#[test]
+fn concurrent_test() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async { test_concurrent().await });
+}
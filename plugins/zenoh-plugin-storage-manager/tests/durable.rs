@@ -0,0 +1,136 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+// Test the durable backend statically linked in the storage manager -
+// 1. a volume using the `durable` backend can be declared in the configuration
+// 2. puts and deletes are stored on disk, in the directory configured for the volume
+
+use std::thread::sleep;
+
+use tokio::runtime::Runtime;
+use zenoh::{internal::zasync_executor_init, query::Reply, sample::Sample, Config, Session};
+use zenoh_plugin_trait::Plugin;
+
+async fn put_data(session: &Session, key_expr: &str, value: &str) {
+    println!("Putting Data ('{key_expr}': '{value}')...");
+    session.put(key_expr, value).await.unwrap();
+}
+
+async fn delete_data(session: &Session, key_expr: &str) {
+    println!("Deleting Data '{key_expr}'...");
+    session.delete(key_expr).await.unwrap();
+}
+
+async fn get_data(session: &Session, key_expr: &str) -> Vec<Sample> {
+    let replies: Vec<Reply> = session.get(key_expr).await.unwrap().into_iter().collect();
+    println!("Getting replies on '{key_expr}': '{replies:?}'...");
+    let mut samples = Vec::new();
+    for reply in replies {
+        if let Ok(sample) = reply.into_result() {
+            samples.push(sample);
+        }
+    }
+    println!("Getting Data on '{key_expr}': '{samples:?}'...");
+    samples
+}
+
+async fn test_durable_volume() {
+    async {
+        zasync_executor_init!();
+    }
+    .await;
+    let root = std::env::temp_dir().join(format!("zenoh-durable-{}", uuid::Uuid::new_v4()));
+    let mut config = Config::default();
+    config
+        .insert_json5(
+            "plugins/storage-manager",
+            &format!(
+                r#"{{
+                    volumes: {{
+                        disk: {{
+                            backend: "durable",
+                            root: "{}"
+                        }}
+                    }},
+                    storages: {{
+                        durable_test: {{
+                            key_expr: "durable/test/**",
+                            strip_prefix: "durable/test",
+                            volume: {{
+                                id: "disk",
+                                dir: "durable_test"
+                            }}
+                        }}
+                    }}
+                }}"#,
+                root.display()
+            ),
+        )
+        .unwrap();
+    config
+        .insert_json5(
+            "timestamping",
+            r#"{
+                    enabled: {
+                        router: true,
+                        peer: true,
+                        client: true
+                    }
+                }"#,
+        )
+        .unwrap();
+
+    let runtime = zenoh::internal::runtime::RuntimeBuilder::new(config)
+        .build()
+        .await
+        .unwrap()
+        .into();
+    let storage =
+        zenoh_plugin_storage_manager::StoragesPlugin::start("storage-manager", &runtime).unwrap();
+
+    let session = zenoh::session::init(runtime).await.unwrap();
+
+    sleep(std::time::Duration::from_secs(1));
+
+    put_data(&session, "durable/test/a", "1").await;
+    put_data(&session, "durable/test/b", "2").await;
+
+    sleep(std::time::Duration::from_millis(10));
+
+    let data = get_data(&session, "durable/test/a").await;
+    assert_eq!(data.len(), 1);
+    assert_eq!(data[0].payload().try_to_string().unwrap(), "1");
+
+    let data = get_data(&session, "durable/test/**").await;
+    assert_eq!(data.len(), 2);
+
+    delete_data(&session, "durable/test/a").await;
+
+    sleep(std::time::Duration::from_millis(10));
+
+    let data = get_data(&session, "durable/test/**").await;
+    assert_eq!(data.len(), 1);
+    assert_eq!(data[0].key_expr().as_str(), "durable/test/b");
+
+    assert!(root.join("durable_test").join("data.log").exists());
+
+    drop(storage);
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn durable_test() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async { test_durable_volume().await });
+}
@@ -0,0 +1,153 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+// Test storages keeping the history of the keys -
+// 1. a memory volume with `history: "all"` keeps every value of a key
+// 2. queries with a `_time` range return all the values in that range, others only the latest one
+
+use std::thread::sleep;
+
+use tokio::runtime::Runtime;
+use zenoh::{
+    internal::zasync_executor_init,
+    query::{ConsolidationMode, Reply},
+    sample::Sample,
+    Config, Session,
+};
+use zenoh_plugin_trait::Plugin;
+
+async fn put_data(session: &Session, key_expr: &str, value: &str) {
+    println!("Putting Data ('{key_expr}': '{value}')...");
+    session.put(key_expr, value).await.unwrap();
+}
+
+async fn delete_data(session: &Session, key_expr: &str) {
+    println!("Deleting Data '{key_expr}'...");
+    session.delete(key_expr).await.unwrap();
+}
+
+async fn get_data(session: &Session, selector: &str) -> Vec<Sample> {
+    let replies: Vec<Reply> = session
+        .get(selector)
+        .consolidation(ConsolidationMode::None)
+        .await
+        .unwrap()
+        .into_iter()
+        .collect();
+    println!("Getting replies on '{selector}': '{replies:?}'...");
+    let mut samples = Vec::new();
+    for reply in replies {
+        if let Ok(sample) = reply.into_result() {
+            samples.push(sample);
+        }
+    }
+    println!("Getting Data on '{selector}': '{samples:?}'...");
+    samples
+}
+
+async fn test_history_all() {
+    async {
+        zasync_executor_init!();
+    }
+    .await;
+    let mut config = Config::default();
+    config
+        .insert_json5(
+            "plugins/storage-manager",
+            r#"{
+                    volumes: {
+                        history: {
+                            backend: "memory",
+                            history: "all"
+                        }
+                    },
+                    storages: {
+                        history_test: {
+                            key_expr: "history/test/**",
+                            volume: {
+                                id: "history"
+                            }
+                        }
+                    }
+                }"#,
+        )
+        .unwrap();
+    config
+        .insert_json5(
+            "timestamping",
+            r#"{
+                    enabled: {
+                        router: true,
+                        peer: true,
+                        client: true
+                    }
+                }"#,
+        )
+        .unwrap();
+
+    let runtime = zenoh::internal::runtime::RuntimeBuilder::new(config)
+        .build()
+        .await
+        .unwrap()
+        .into();
+    let storage =
+        zenoh_plugin_storage_manager::StoragesPlugin::start("storage-manager", &runtime).unwrap();
+
+    let session = zenoh::session::init(runtime).await.unwrap();
+
+    sleep(std::time::Duration::from_secs(1));
+
+    put_data(&session, "history/test/a", "1").await;
+    put_data(&session, "history/test/a", "2").await;
+    put_data(&session, "history/test/b", "3").await;
+    put_data(&session, "history/test/a", "4").await;
+
+    sleep(std::time::Duration::from_millis(10));
+
+    // without a time range, only the latest value is expected
+    let data = get_data(&session, "history/test/a").await;
+    assert_eq!(data.len(), 1);
+    assert_eq!(data[0].payload().try_to_string().unwrap(), "4");
+
+    let data = get_data(&session, "history/test/a?_time=[now(-1m)..]").await;
+    let mut values = data
+        .iter()
+        .map(|sample| sample.payload().try_to_string().unwrap().into_owned())
+        .collect::<Vec<_>>();
+    values.sort();
+    assert_eq!(values, vec!["1", "2", "4"]);
+
+    let data = get_data(&session, "history/test/**?_time=[now(-1m)..]").await;
+    assert_eq!(data.len(), 4);
+
+    // no value in this time range
+    let data = get_data(&session, "history/test/**?_time=[..now(-1m)]").await;
+    assert_eq!(data.len(), 0);
+
+    delete_data(&session, "history/test/a").await;
+
+    sleep(std::time::Duration::from_millis(10));
+
+    let data = get_data(&session, "history/test/**?_time=[now(-1m)..]").await;
+    assert_eq!(data.len(), 1);
+    assert_eq!(data[0].key_expr().as_str(), "history/test/b");
+
+    drop(storage);
+}
+
+#[test]
+fn history_test() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async { test_history_all().await });
+}
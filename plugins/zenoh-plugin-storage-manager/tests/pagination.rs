@@ -0,0 +1,238 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+// Test the pagination of the replies to wildcard queries -
+// 1. a query with `_limit=N` receives at most N samples, each reply carrying a cursor if truncated
+// 2. a query with the `_cursor` parameter resumes where the previous one stopped
+// 3. the entries filtered out by a `_time` range do not truncate the pages
+// 4. the versions of a key kept by a `history: "all"` volume are paginated as any other sample
+
+use std::thread::sleep;
+
+use tokio::runtime::Runtime;
+use zenoh::{
+    internal::zasync_executor_init,
+    query::{ConsolidationMode, Reply},
+    sample::Sample,
+    Config, Session,
+};
+use zenoh_plugin_trait::Plugin;
+
+async fn put_data(session: &Session, key_expr: &str, value: &str) {
+    println!("Putting Data ('{key_expr}': '{value}')...");
+    session.put(key_expr, value).await.unwrap();
+}
+
+async fn get_page(session: &Session, selector: &str) -> (Vec<Sample>, Option<String>) {
+    let replies: Vec<Reply> = session
+        .get(selector)
+        .consolidation(ConsolidationMode::None)
+        .await
+        .unwrap()
+        .into_iter()
+        .collect();
+    println!("Getting replies on '{selector}': '{replies:?}'...");
+    let mut samples = Vec::new();
+    let mut cursors = Vec::new();
+    for reply in replies {
+        let sample = reply.into_result().unwrap();
+        cursors.push(
+            sample
+                .attachment()
+                .map(|attachment| attachment.try_to_string().unwrap().into_owned()),
+        );
+        samples.push(sample);
+    }
+    // All the replies of a page carry the same cursor.
+    cursors.dedup();
+    assert!(cursors.len() <= 1);
+    (samples, cursors.pop().flatten())
+}
+
+async fn test_pagination() {
+    async {
+        zasync_executor_init!();
+    }
+    .await;
+    let mut config = Config::default();
+    config
+        .insert_json5(
+            "plugins/storage-manager",
+            r#"{
+                    volumes: {
+                        history: {
+                            backend: "memory",
+                            history: "all"
+                        }
+                    },
+                    storages: {
+                        pagination_test: {
+                            key_expr: "pagination/test/**",
+                            strip_prefix: "pagination/test",
+                            volume: {
+                                id: "memory"
+                            }
+                        },
+                        pagination_history: {
+                            key_expr: "pagination/history/**",
+                            volume: {
+                                id: "history"
+                            }
+                        }
+                    }
+                }"#,
+        )
+        .unwrap();
+    config
+        .insert_json5(
+            "timestamping",
+            r#"{
+                    enabled: {
+                        router: true,
+                        peer: true,
+                        client: true
+                    }
+                }"#,
+        )
+        .unwrap();
+
+    let runtime = zenoh::internal::runtime::RuntimeBuilder::new(config)
+        .build()
+        .await
+        .unwrap()
+        .into();
+    let storage =
+        zenoh_plugin_storage_manager::StoragesPlugin::start("storage-manager", &runtime).unwrap();
+
+    let session = zenoh::session::init(runtime).await.unwrap();
+
+    sleep(std::time::Duration::from_secs(1));
+
+    put_data(&session, "pagination/test", "prefix").await;
+    for i in 0..5 {
+        put_data(&session, &format!("pagination/test/{i}"), &i.to_string()).await;
+    }
+
+    sleep(std::time::Duration::from_millis(10));
+
+    // without a limit, a single page is expected
+    let (samples, cursor) = get_page(&session, "pagination/test/**").await;
+    assert_eq!(samples.len(), 6);
+    assert!(cursor.is_none());
+
+    let mut keys = Vec::new();
+    let mut selector = "pagination/test/**?_limit=4".to_string();
+    let mut pages = 0;
+    loop {
+        let (samples, cursor) = get_page(&session, &selector).await;
+        assert!(samples.len() <= 4);
+        keys.extend(samples.iter().map(|s| s.key_expr().to_string()));
+        pages += 1;
+        match cursor {
+            Some(cursor) => selector = format!("pagination/test/**?_limit=4;_cursor={cursor}"),
+            None => break,
+        }
+    }
+    assert_eq!(pages, 2);
+    keys.sort();
+    assert_eq!(
+        keys,
+        vec![
+            "pagination/test",
+            "pagination/test/0",
+            "pagination/test/1",
+            "pagination/test/2",
+            "pagination/test/3",
+            "pagination/test/4",
+        ]
+    );
+
+    // a page whose keys are all filtered out by the `_time` range does not end the pagination
+    for i in 0..4 {
+        put_data(&session, &format!("pagination/test/filtered/a{i}"), "old").await;
+    }
+    sleep(std::time::Duration::from_millis(1500));
+    for i in 0..2 {
+        put_data(
+            &session,
+            &format!("pagination/test/filtered/b{i}"),
+            "recent",
+        )
+        .await;
+    }
+    sleep(std::time::Duration::from_millis(10));
+
+    let (samples, cursor) = get_page(
+        &session,
+        "pagination/test/filtered/**?_limit=2;_time=[now(-1s)..]",
+    )
+    .await;
+    let mut keys: Vec<String> = samples.iter().map(|s| s.key_expr().to_string()).collect();
+    keys.sort();
+    assert_eq!(
+        keys,
+        vec!["pagination/test/filtered/b0", "pagination/test/filtered/b1"]
+    );
+    assert!(cursor.is_none());
+
+    // the limit counts the versions of a key, not the keys
+    for i in 0..5 {
+        put_data(&session, "pagination/history/x", &i.to_string()).await;
+    }
+    put_data(&session, "pagination/history/y", "5").await;
+    sleep(std::time::Duration::from_millis(10));
+
+    let mut values = Vec::new();
+    let mut selector = "pagination/history/**?_limit=2;_time=[now(-1m)..]".to_string();
+    let mut pages = 0;
+    loop {
+        let (samples, cursor) = get_page(&session, &selector).await;
+        values.extend(
+            samples
+                .iter()
+                .map(|s| s.payload().try_to_string().unwrap().into_owned()),
+        );
+        pages += 1;
+        match cursor {
+            Some(cursor) => {
+                // a truncated page is always full
+                assert_eq!(samples.len(), 2);
+                selector =
+                    format!("pagination/history/**?_limit=2;_time=[now(-1m)..];_cursor={cursor}");
+            }
+            None => break,
+        }
+    }
+    assert_eq!(pages, 3);
+    values.sort();
+    assert_eq!(values, vec!["0", "1", "2", "3", "4", "5"]);
+
+    // an invalid limit is reported as an error
+    let replies: Vec<Reply> = session
+        .get("pagination/test/**?_limit=zero")
+        .await
+        .unwrap()
+        .into_iter()
+        .collect();
+    assert_eq!(replies.len(), 1);
+    assert!(replies[0].result().is_err());
+
+    drop(storage);
+}
+
+#[test]
+fn pagination_test() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async { test_pagination().await });
+}
@@ -0,0 +1,182 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+// Test the initial alignment of a replicated storage with a throttled Replica -
+// 1. the content of the Replica is retrieved by pages, at the `alignment_bandwidth` of the Replica,
+//    even though the transfer takes longer than the default timeout of the queries
+// 2. the initial sync is only reported as complete once all the content was retrieved
+
+use std::{
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+use tokio::runtime::Runtime;
+use zenoh::{
+    internal::{plugins::RunningPlugin, runtime::Runtime as ZRuntime, zasync_executor_init},
+    query::Reply,
+    Config, Session,
+};
+use zenoh_plugin_trait::Plugin;
+
+const ENDPOINT: &str = "tcp/127.0.0.1:17449";
+const KEYS: usize = 30;
+const PAYLOAD_SIZE: usize = 1_000;
+
+async fn start_replica(endpoints: &str) -> (ZRuntime, Session) {
+    let mut config = Config::default();
+    config
+        .insert_json5(
+            "plugins/storage-manager",
+            r#"{
+                    storages: {
+                        replication_test: {
+                            key_expr: "replication/test/**",
+                            volume: {
+                                id: "memory"
+                            },
+                            replication: {
+                                interval: 1,
+                                sub_intervals: 5,
+                                hot: 6,
+                                warm: 30,
+                                propagation_delay: 100,
+                                alignment_batch_size: 5,
+                                alignment_bandwidth: 10000,
+                            }
+                        }
+                    }
+                }"#,
+        )
+        .unwrap();
+    config
+        .insert_json5(
+            "timestamping",
+            r#"{
+                    enabled: {
+                        router: true,
+                        peer: true,
+                        client: true
+                    }
+                }"#,
+        )
+        .unwrap();
+    config
+        .insert_json5(endpoints, &format!(r#"["{ENDPOINT}"]"#))
+        .unwrap();
+    config
+        .insert_json5("scouting/multicast/enabled", "false")
+        .unwrap();
+    // The whole content of the Replica takes 3 seconds to be transferred at its bandwidth.
+    config
+        .insert_json5("queries_default_timeout", "1000")
+        .unwrap();
+
+    let mut runtime: ZRuntime = zenoh::internal::runtime::RuntimeBuilder::new(config)
+        .build()
+        .await
+        .unwrap();
+    let session = zenoh::session::init(runtime.clone().into()).await.unwrap();
+    runtime.start().await.unwrap();
+    (runtime, session)
+}
+
+async fn export_snapshot(session: &Session) -> Vec<u8> {
+    let selector = format!(
+        "@/{}/peer/status/plugins/storage-manager/storages/replication_test/export",
+        session.zid()
+    );
+    let replies: Vec<Reply> = session.get(selector).await.unwrap().into_iter().collect();
+    assert_eq!(replies.len(), 1);
+    replies
+        .into_iter()
+        .next()
+        .unwrap()
+        .into_result()
+        .unwrap()
+        .payload()
+        .to_bytes()
+        .into_owned()
+}
+
+fn initial_sync_complete(storage: &RunningPlugin) -> bool {
+    storage
+        .metrics()
+        .unwrap()
+        .lines()
+        .find(|line| line.starts_with("zenoh_storage_replication_initial_sync_complete{"))
+        .unwrap()
+        .ends_with(" 1")
+}
+
+async fn test_replication() {
+    async {
+        zasync_executor_init!();
+    }
+    .await;
+
+    let (runtime, session) = start_replica("listen/endpoints").await;
+    let storage =
+        zenoh_plugin_storage_manager::StoragesPlugin::start("storage-manager", &runtime.into())
+            .unwrap();
+
+    sleep(Duration::from_secs(1));
+
+    for i in 0..KEYS {
+        session
+            .put(format!("replication/test/{i}"), vec![b'0'; PAYLOAD_SIZE])
+            .await
+            .unwrap();
+    }
+
+    // The updates are added to the Replication Log at the end of the interval.
+    sleep(Duration::from_secs(2));
+
+    let start = Instant::now();
+    let (runtime, replica_session) = start_replica("connect/endpoints").await;
+    let replica_storage =
+        zenoh_plugin_storage_manager::StoragesPlugin::start("storage-manager", &runtime.into())
+            .unwrap();
+
+    sleep(Duration::from_millis(500));
+    assert!(!initial_sync_complete(&replica_storage));
+
+    while !initial_sync_complete(&replica_storage) {
+        assert!(start.elapsed() < Duration::from_secs(15));
+        sleep(Duration::from_millis(100));
+    }
+    // Only the first page is sent without waiting for the bandwidth to be available.
+    assert!(start.elapsed() >= Duration::from_millis(2500));
+
+    let snapshot = export_snapshot(&replica_session).await;
+    let puts = String::from_utf8(snapshot)
+        .unwrap()
+        .lines()
+        .skip(1)
+        .filter(|line| {
+            let record: serde_json::Value = serde_json::from_str(line).unwrap();
+            record["kind"] == "put"
+        })
+        .count();
+    assert_eq!(puts, KEYS);
+
+    drop(replica_storage);
+    drop(storage);
+}
+
+#[test]
+fn replication_test() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async { test_replication().await });
+}
@@ -0,0 +1,243 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+// Test the export and import of the snapshots of a storage -
+// 1. the `export` admin operation returns the values and deletes of the storage
+// 2. the `import` admin operation, on another router, restores them with their original timestamps
+// 3. the snapshot files are confined to the snapshot directory of the storage, and importing
+//    requires to be enabled in its configuration
+
+use std::{
+    path::{Path, PathBuf},
+    thread::sleep,
+};
+
+use tokio::runtime::Runtime;
+use zenoh::{
+    bytes::ZBytes,
+    internal::{runtime::Runtime as ZRuntime, zasync_executor_init},
+    query::{ConsolidationMode, Reply},
+    sample::Sample,
+    Config, Session,
+};
+use zenoh_plugin_trait::Plugin;
+
+async fn delete_data(session: &Session, key_expr: &str) {
+    println!("Deleting Data '{key_expr}'...");
+    session.delete(key_expr).await.unwrap();
+}
+
+async fn admin_operation(
+    session: &Session,
+    operation: &str,
+    payload: Option<ZBytes>,
+) -> Result<ZBytes, String> {
+    let selector = format!(
+        "@/{}/peer/status/plugins/storage-manager/storages/snapshot_test/{operation}",
+        session.zid()
+    );
+    let mut get = session.get(selector);
+    if let Some(payload) = payload {
+        get = get.payload(payload);
+    }
+    let replies: Vec<Reply> = get.await.unwrap().into_iter().collect();
+    assert_eq!(replies.len(), 1);
+    match replies.into_iter().next().unwrap().into_result() {
+        Ok(sample) => Ok(sample.payload().clone()),
+        Err(e) => Err(e.payload().try_to_string().unwrap().into_owned()),
+    }
+}
+
+async fn put_data(session: &Session, key_expr: &str, value: &str) {
+    println!("Putting Data ('{key_expr}': '{value}')...");
+    session.put(key_expr, value).await.unwrap();
+}
+
+async fn get_data(session: &Session, selector: &str) -> Vec<Sample> {
+    let replies: Vec<Reply> = session
+        .get(selector)
+        .consolidation(ConsolidationMode::None)
+        .await
+        .unwrap()
+        .into_iter()
+        .collect();
+    println!("Getting replies on '{selector}': '{replies:?}'...");
+    let mut samples = Vec::new();
+    for reply in replies {
+        if let Ok(sample) = reply.into_result() {
+            samples.push(sample);
+        }
+    }
+    println!("Getting Data on '{selector}': '{samples:?}'...");
+    samples
+}
+
+async fn start_storage(directory: &Path, import: bool) -> (ZRuntime, Session) {
+    let mut config = Config::default();
+    config
+        .insert_json5(
+            "plugins/storage-manager",
+            &format!(
+                r#"{{
+                    storages: {{
+                        snapshot_test: {{
+                            key_expr: "snapshot/test/**",
+                            strip_prefix: "snapshot/test",
+                            volume: {{
+                                id: "memory"
+                            }},
+                            snapshot: {{
+                                directory: {:?},
+                                import: {import},
+                            }}
+                        }}
+                    }}
+                }}"#,
+                directory.display().to_string()
+            ),
+        )
+        .unwrap();
+    config
+        .insert_json5(
+            "timestamping",
+            r#"{
+                    enabled: {
+                        router: true,
+                        peer: true,
+                        client: true
+                    }
+                }"#,
+        )
+        .unwrap();
+
+    let runtime: ZRuntime = zenoh::internal::runtime::RuntimeBuilder::new(config)
+        .build()
+        .await
+        .unwrap();
+    let session = zenoh::session::init(runtime.clone().into()).await.unwrap();
+    (runtime, session)
+}
+
+async fn test_snapshot() {
+    async {
+        zasync_executor_init!();
+    }
+    .await;
+
+    let directory: PathBuf =
+        std::env::temp_dir().join(format!("zenoh-snapshot-test-{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+
+    let (runtime, session) = start_storage(&directory, false).await;
+    let storage =
+        zenoh_plugin_storage_manager::StoragesPlugin::start("storage-manager", &runtime.into())
+            .unwrap();
+
+    sleep(std::time::Duration::from_secs(1));
+
+    put_data(&session, "snapshot/test/a", "a").await;
+    put_data(&session, "snapshot/test/b", "b").await;
+    delete_data(&session, "snapshot/test/b").await;
+
+    sleep(std::time::Duration::from_millis(10));
+
+    let data = get_data(&session, "snapshot/test/a").await;
+    assert_eq!(data.len(), 1);
+    let timestamp = *data[0].timestamp().unwrap();
+
+    let snapshot = admin_operation(&session, "export", None).await.unwrap();
+    println!("Snapshot: {}", snapshot.try_to_string().unwrap());
+    let snapshot_lines = snapshot
+        .try_to_string()
+        .unwrap()
+        .lines()
+        .map(String::from)
+        .collect::<Vec<_>>();
+    assert_eq!(snapshot_lines.len(), 3);
+
+    // The snapshot is written in the snapshot directory, and only there.
+    let status = admin_operation(&session, "export?file=snapshot_test.snapshot", None)
+        .await
+        .unwrap();
+    let status: serde_json::Value = serde_json::from_slice(&status.to_bytes()).unwrap();
+    assert_eq!(status["records"], 2);
+    let file = std::fs::read_to_string(directory.join("snapshot_test.snapshot")).unwrap();
+    assert_eq!(file.lines().collect::<Vec<_>>(), snapshot_lines);
+    for file in ["../snapshot_test.snapshot", "/tmp/snapshot_test.snapshot"] {
+        assert!(
+            admin_operation(&session, &format!("export?file={file}"), None)
+                .await
+                .is_err()
+        );
+    }
+
+    // Importing is not enabled on this storage.
+    assert!(admin_operation(&session, "import", Some(snapshot))
+        .await
+        .is_err());
+
+    drop(storage);
+    session.close().await.unwrap();
+
+    let (runtime, session) = start_storage(&directory, true).await;
+    let storage =
+        zenoh_plugin_storage_manager::StoragesPlugin::start("storage-manager", &runtime.into())
+            .unwrap();
+
+    sleep(std::time::Duration::from_secs(1));
+
+    assert!(get_data(&session, "snapshot/test/**").await.is_empty());
+
+    assert!(
+        admin_operation(&session, "import?file=../snapshot_test.snapshot", None)
+            .await
+            .is_err()
+    );
+    assert!(get_data(&session, "snapshot/test/**").await.is_empty());
+
+    let status = admin_operation(&session, "import?file=snapshot_test.snapshot", None)
+        .await
+        .unwrap();
+    let status: serde_json::Value = serde_json::from_slice(&status.to_bytes()).unwrap();
+    assert_eq!(status["imported"], 2);
+    assert_eq!(status["skipped"], 0);
+
+    let data = get_data(&session, "snapshot/test/**").await;
+    assert_eq!(data.len(), 1);
+    assert_eq!(data[0].key_expr().as_str(), "snapshot/test/a");
+    assert_eq!(data[0].payload().try_to_string().unwrap(), "a");
+    assert_eq!(data[0].timestamp(), Some(&timestamp));
+
+    // The delete was imported as well: exporting the storage again gives the same snapshot.
+    let reexported = admin_operation(&session, "export", None).await.unwrap();
+    let mut lines = reexported
+        .try_to_string()
+        .unwrap()
+        .lines()
+        .map(String::from)
+        .collect::<Vec<_>>();
+    let mut expected_lines = snapshot_lines;
+    lines.sort();
+    expected_lines.sort();
+    assert_eq!(lines, expected_lines);
+
+    drop(storage);
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn snapshot_test() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async { test_snapshot().await });
+}
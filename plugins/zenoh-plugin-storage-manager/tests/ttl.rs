@@ -0,0 +1,213 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+// Test the expiry of the values stored for longer than the `ttl` of a storage -
+// 1. a value is returned until it expires
+// 2. an expired value is deleted while a value updated in the meantime is kept
+// 3. the expired value is removed from the backend and its delete recorded in the Replication Log,
+//    as shown by the snapshot of the storage
+
+use std::{str::FromStr, thread::sleep, time::Duration};
+
+use tokio::runtime::Runtime;
+use zenoh::{
+    internal::zasync_executor_init,
+    query::{ConsolidationMode, Reply},
+    sample::Sample,
+    time::Timestamp,
+    Config, Session,
+};
+use zenoh_plugin_trait::Plugin;
+
+async fn put_data(session: &Session, key_expr: &str, value: &str) {
+    println!("Putting Data ('{key_expr}': '{value}')...");
+    session.put(key_expr, value).await.unwrap();
+}
+
+async fn get_data(session: &Session, selector: &str) -> Vec<Sample> {
+    let replies: Vec<Reply> = session
+        .get(selector)
+        .consolidation(ConsolidationMode::None)
+        .await
+        .unwrap()
+        .into_iter()
+        .collect();
+    println!("Getting replies on '{selector}': '{replies:?}'...");
+    let mut samples = Vec::new();
+    for reply in replies {
+        if let Ok(sample) = reply.into_result() {
+            samples.push(sample);
+        }
+    }
+    println!("Getting Data on '{selector}': '{samples:?}'...");
+    samples
+}
+
+/// Returns the (kind, key, timestamp) of the records of the snapshot of the storage.
+async fn export_snapshot(session: &Session) -> Vec<(String, String, Timestamp)> {
+    let selector = format!(
+        "@/{}/peer/status/plugins/storage-manager/storages/ttl_test/export",
+        session.zid()
+    );
+    let replies: Vec<Reply> = session.get(selector).await.unwrap().into_iter().collect();
+    assert_eq!(replies.len(), 1);
+    let snapshot = replies[0]
+        .result()
+        .unwrap()
+        .payload()
+        .try_to_string()
+        .unwrap()
+        .into_owned();
+    println!("Snapshot: {snapshot}");
+    snapshot
+        .lines()
+        .skip(1)
+        .map(|line| {
+            let record: serde_json::Value = serde_json::from_str(line).unwrap();
+            (
+                record["kind"].as_str().unwrap().to_string(),
+                record["key"].as_str().unwrap().to_string(),
+                Timestamp::from_str(record["timestamp"].as_str().unwrap()).unwrap(),
+            )
+        })
+        .collect()
+}
+
+async fn test_ttl() {
+    async {
+        zasync_executor_init!();
+    }
+    .await;
+    let mut config = Config::default();
+    config
+        .insert_json5(
+            "plugins/storage-manager",
+            r#"{
+                    storages: {
+                        ttl_test: {
+                            key_expr: "ttl/test/**",
+                            volume: {
+                                id: "memory"
+                            },
+                            ttl: 3,
+                            garbage_collection: {
+                                period: 1,
+                            },
+                            replication: {
+                                interval: 1,
+                                propagation_delay: 100,
+                            }
+                        }
+                    }
+                }"#,
+        )
+        .unwrap();
+    config
+        .insert_json5(
+            "timestamping",
+            r#"{
+                    enabled: {
+                        router: true,
+                        peer: true,
+                        client: true
+                    }
+                }"#,
+        )
+        .unwrap();
+
+    let runtime = zenoh::internal::runtime::RuntimeBuilder::new(config)
+        .build()
+        .await
+        .unwrap()
+        .into();
+    let storage =
+        zenoh_plugin_storage_manager::StoragesPlugin::start("storage-manager", &runtime).unwrap();
+
+    let session = zenoh::session::init(runtime).await.unwrap();
+
+    sleep(std::time::Duration::from_secs(1));
+
+    put_data(&session, "ttl/test/a", "1").await;
+    put_data(&session, "ttl/test/b", "1").await;
+
+    sleep(std::time::Duration::from_millis(1500));
+
+    let data = get_data(&session, "ttl/test/**").await;
+    assert_eq!(data.len(), 2);
+    let timestamp_a = *data
+        .iter()
+        .find(|s| s.key_expr().as_str() == "ttl/test/a")
+        .unwrap()
+        .timestamp()
+        .unwrap();
+
+    put_data(&session, "ttl/test/b", "2").await;
+
+    sleep(std::time::Duration::from_millis(2000));
+
+    let data = get_data(&session, "ttl/test/**").await;
+    assert_eq!(data.len(), 1);
+    assert_eq!(data[0].key_expr().as_str(), "ttl/test/b");
+    assert_eq!(data[0].payload().try_to_string().unwrap(), "2");
+
+    let data = get_data(&session, "ttl/test/a").await;
+    assert_eq!(data.len(), 0);
+
+    // Once the expiry check ran, the expired value is no longer in the backend: only its delete
+    // remains in the Replication Log, timestamped with the expiry time.
+    sleep(std::time::Duration::from_millis(1000));
+
+    let records = export_snapshot(&session).await;
+    assert!(!records
+        .iter()
+        .any(|(kind, key, _)| kind == "put" && key == "ttl/test/a"));
+    let (_, _, delete_timestamp) = records
+        .iter()
+        .find(|(kind, key, _)| kind == "delete" && key == "ttl/test/a")
+        .unwrap();
+    let expiry = delete_timestamp
+        .get_time()
+        .to_system_time()
+        .duration_since(timestamp_a.get_time().to_system_time())
+        .unwrap();
+    assert!(expiry > Duration::from_millis(2999) && expiry < Duration::from_millis(3001));
+    assert!(records
+        .iter()
+        .any(|(kind, key, _)| kind == "put" && key == "ttl/test/b"));
+
+    sleep(std::time::Duration::from_millis(1500));
+
+    let data = get_data(&session, "ttl/test/**").await;
+    assert_eq!(data.len(), 0);
+
+    sleep(std::time::Duration::from_millis(1000));
+
+    let records = export_snapshot(&session).await;
+    assert!(!records.iter().any(|(kind, _, _)| kind == "put"));
+    assert_eq!(
+        records
+            .iter()
+            .filter(|(kind, _, _)| kind == "delete")
+            .count(),
+        2
+    );
+
+    drop(storage);
+}
+
+#[test]
+fn ttl_test() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async { test_ttl().await });
+}
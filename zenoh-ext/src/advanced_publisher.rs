@@ -37,7 +37,7 @@ use zenoh::{
         PublisherBuilder, PublisherUndeclaration,
     },
     qos::{CongestionControl, Priority, Reliability},
-    sample::{Locality, SourceInfo},
+    sample::{Locality, SourceInfo, TraceContext},
     session::EntityGlobalId,
     Resolvable, Resolve, Result as ZResult, Session, Wait, KE_ADV_PREFIX, KE_EMPTY,
 };
@@ -709,6 +709,14 @@ impl<P> SampleBuilderTrait for AdvancedPublicationBuilder<'_, P> {
         }
     }
     #[zenoh_macros::unstable]
+    /// Sets an optional W3C [`TraceContext`](zenoh::sample::TraceContext) to be sent along with the publication.
+    fn trace_context<T: Into<Option<TraceContext>>>(self, trace_context: T) -> Self {
+        Self {
+            builder: self.builder.trace_context(trace_context),
+            ..self
+        }
+    }
+    #[zenoh_macros::unstable]
     /// Sets an optional attachment to be sent along with the publication.
     ///
     /// The argument is converted via [`OptionZBytes`], which supports both `T: Into<ZBytes>`
@@ -224,6 +224,8 @@ pub(crate) fn init(session: WeakSession) {
                 None,
                 #[cfg(feature = "unstable")]
                 None,
+                #[cfg(feature = "unstable")]
+                None,
                 None,
             ) {
                 tracing::error!("Unable to publish transport event: {}", e);
@@ -278,6 +280,8 @@ pub(crate) fn init(session: WeakSession) {
                     None,
                     #[cfg(feature = "unstable")]
                     None,
+                    #[cfg(feature = "unstable")]
+                    None,
                     None,
                 ) {
                     tracing::error!("Unable to publish link event: {}", e);
@@ -0,0 +1,253 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+#[zenoh_macros::unstable]
+use std::{
+    collections::HashSet,
+    future::{IntoFuture, Ready},
+    sync::{Arc, Mutex},
+};
+
+#[zenoh_macros::unstable]
+use zenoh_core::{Resolvable, Wait};
+#[zenoh_macros::unstable]
+use zenoh_result::ZResult;
+
+#[zenoh_macros::unstable]
+use crate::{
+    api::{
+        cancellation::{SyncGroup, SyncGroupNotifier},
+        drop::{DropEvent, DropListener, DropListenerInner},
+        handlers::{Callback, DefaultHandler, IntoHandler},
+        session::WeakSession,
+        Id,
+    },
+    key_expr::KeyExpr,
+};
+
+/// A builder for initializing a [`DropListener`].
+///
+/// It is returned by the [`Publisher::drop_listener`](crate::pubsub::Publisher::drop_listener)
+/// or [`Session::drop_listener`](crate::Session::drop_listener) methods.
+#[must_use = "Resolvables do nothing unless you resolve them using `.await` or `zenoh::Wait::wait`"]
+#[zenoh_macros::unstable]
+#[derive(Debug)]
+pub struct DropListenerBuilder<'a, Handler, const BACKGROUND: bool = false> {
+    pub(crate) session: WeakSession,
+    pub(crate) key_expr: Option<&'a KeyExpr<'a>>,
+    pub(crate) drop_listeners: Option<&'a Arc<Mutex<HashSet<Id>>>>,
+    pub handler: Handler,
+    pub(crate) parent_callback_sync_group_notifier: Option<SyncGroupNotifier>,
+}
+
+#[zenoh_macros::unstable]
+impl<'a> DropListenerBuilder<'a, DefaultHandler> {
+    /// Receive the DropEvents for this listener with a callback.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let publisher = session.declare_publisher("key/expression").await.unwrap();
+    /// let drop_listener = publisher
+    ///     .drop_listener()
+    ///     .callback(|event| println!("{} publications dropped", event.count()))
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn callback<F>(self, callback: F) -> DropListenerBuilder<'a, Callback<DropEvent>>
+    where
+        F: Fn(DropEvent) + Send + Sync + 'static,
+    {
+        self.with(Callback::from(callback))
+    }
+
+    /// Receive the DropEvents for this listener with a mutable callback.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let mut dropped = 0;
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let publisher = session.declare_publisher("key/expression").await.unwrap();
+    /// let drop_listener = publisher
+    ///     .drop_listener()
+    ///     .callback_mut(move |event| { dropped += event.count(); })
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn callback_mut<F>(self, callback: F) -> DropListenerBuilder<'a, Callback<DropEvent>>
+    where
+        F: FnMut(DropEvent) + Send + Sync + 'static,
+    {
+        self.callback(crate::api::handlers::locked(callback))
+    }
+
+    /// Receive the DropEvents for this listener with a [`Handler`](IntoHandler).
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let drop_listener = session
+    ///     .drop_listener()
+    ///     .with(flume::bounded(32))
+    ///     .await
+    ///     .unwrap();
+    /// while let Ok(event) = drop_listener.recv_async().await {
+    ///     println!("{} publications on {} dropped", event.count(), event.key_expr());
+    /// }
+    /// # }
+    /// ```
+    #[inline]
+    pub fn with<Handler>(self, handler: Handler) -> DropListenerBuilder<'a, Handler>
+    where
+        Handler: IntoHandler<DropEvent>,
+    {
+        DropListenerBuilder {
+            session: self.session,
+            key_expr: self.key_expr,
+            drop_listeners: self.drop_listeners,
+            handler,
+            parent_callback_sync_group_notifier: self.parent_callback_sync_group_notifier,
+        }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<'a> DropListenerBuilder<'a, Callback<DropEvent>> {
+    /// Make listener run in the background until the publisher is undeclared, or until the
+    /// session is closed for a session-wide listener.
+    ///
+    /// The background builder doesn't return a `DropListener` object anymore.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let publisher = session.declare_publisher("key/expression").await.unwrap();
+    /// // no need to assign and keep a variable for a background listener
+    /// publisher
+    ///     .drop_listener()
+    ///     .callback(|event| println!("{} publications dropped", event.count()))
+    ///     .background()
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    pub fn background(self) -> DropListenerBuilder<'a, Callback<DropEvent>, true> {
+        DropListenerBuilder {
+            session: self.session,
+            key_expr: self.key_expr,
+            drop_listeners: self.drop_listeners,
+            handler: self.handler,
+            parent_callback_sync_group_notifier: self.parent_callback_sync_group_notifier,
+        }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> Resolvable for DropListenerBuilder<'_, Handler>
+where
+    Handler: IntoHandler<DropEvent> + Send,
+    Handler::Handler: Send,
+{
+    type To = ZResult<DropListener<Handler::Handler>>;
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> Wait for DropListenerBuilder<'_, Handler>
+where
+    Handler: IntoHandler<DropEvent> + Send,
+    Handler::Handler: Send,
+{
+    fn wait(self) -> <Self as Resolvable>::To {
+        let callback_sync_group = SyncGroup::default();
+        let (callback, handler) = self.handler.into_handler();
+        let state = self.session.declare_drop_listener_inner(
+            self.key_expr,
+            callback,
+            callback_sync_group.notifier(),
+        )?;
+        if let Some(drop_listeners) = self.drop_listeners {
+            zlock!(drop_listeners).insert(state.id);
+        }
+        Ok(DropListener {
+            inner: DropListenerInner {
+                session: self.session,
+                drop_listeners: self.drop_listeners.cloned(),
+                id: state.id,
+                undeclare_on_drop: true,
+            },
+            handler,
+            callback_sync_group,
+        })
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> IntoFuture for DropListenerBuilder<'_, Handler>
+where
+    Handler: IntoHandler<DropEvent> + Send,
+    Handler::Handler: Send,
+{
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Ready<<Self as Resolvable>::To>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        std::future::ready(self.wait())
+    }
+}
+
+#[zenoh_macros::unstable]
+impl Resolvable for DropListenerBuilder<'_, Callback<DropEvent>, true> {
+    type To = ZResult<()>;
+}
+
+#[zenoh_macros::unstable]
+impl Wait for DropListenerBuilder<'_, Callback<DropEvent>, true> {
+    fn wait(self) -> <Self as Resolvable>::To {
+        let state = self.session.declare_drop_listener_inner(
+            self.key_expr,
+            self.handler,
+            self.parent_callback_sync_group_notifier,
+        )?;
+        if let Some(drop_listeners) = self.drop_listeners {
+            zlock!(drop_listeners).insert(state.id);
+        }
+        Ok(())
+    }
+}
+
+#[zenoh_macros::unstable]
+impl IntoFuture for DropListenerBuilder<'_, Callback<DropEvent>, true> {
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Ready<<Self as Resolvable>::To>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        std::future::ready(self.wait())
+    }
+}
@@ -13,6 +13,7 @@
 //
 
 pub(crate) mod close;
+pub(crate) mod drop_listener;
 pub(crate) mod info;
 pub(crate) mod info_links;
 pub(crate) mod info_transport;
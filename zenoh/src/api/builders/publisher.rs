@@ -19,7 +19,7 @@ use zenoh_protocol::core::CongestionControl;
 use zenoh_protocol::core::Reliability;
 
 #[cfg(feature = "unstable")]
-use crate::api::sample::SourceInfo;
+use crate::api::{sample::SourceInfo, trace::TraceContext};
 use crate::{
     api::{
         builders::sample::{
@@ -102,6 +102,8 @@ pub struct PublicationBuilder<P, T> {
     pub(crate) timestamp: Option<uhlc::Timestamp>,
     #[cfg(feature = "unstable")]
     pub(crate) source_info: Option<SourceInfo>,
+    #[cfg(feature = "unstable")]
+    pub(crate) trace_context: Option<TraceContext>,
     pub(crate) attachment: Option<ZBytes>,
 }
 
@@ -199,6 +201,14 @@ impl<P, T> SampleBuilderTrait for PublicationBuilder<P, T> {
             ..self
         }
     }
+    /// Sets an optional W3C [`TraceContext`](crate::sample::TraceContext) to be sent along with the publication.
+    #[zenoh_macros::unstable]
+    fn trace_context<TT: Into<Option<TraceContext>>>(self, trace_context: TT) -> Self {
+        Self {
+            trace_context: trace_context.into(),
+            ..self
+        }
+    }
     /// Sets an optional attachment to be sent along with the publication.
     /// The method accepts both `Into<ZBytes>` and `Option<Into<ZBytes>>`.
     fn attachment<TA: Into<OptionZBytes>>(self, attachment: TA) -> Self {
@@ -243,6 +253,8 @@ impl Wait for PublicationBuilder<PublisherBuilder<'_, '_>, PublicationBuilderPut
             self.timestamp,
             #[cfg(feature = "unstable")]
             self.source_info,
+            #[cfg(feature = "unstable")]
+            self.trace_context,
             self.attachment,
         )
     }
@@ -266,6 +278,8 @@ impl Wait for PublicationBuilder<PublisherBuilder<'_, '_>, PublicationBuilderDel
             self.timestamp,
             #[cfg(feature = "unstable")]
             self.source_info,
+            #[cfg(feature = "unstable")]
+            self.trace_context,
             self.attachment,
         )
     }
@@ -474,6 +488,8 @@ impl Wait for PublisherBuilder<'_, '_> {
             #[cfg(feature = "unstable")]
             reliability: self.reliability,
             matching_listeners: Default::default(),
+            #[cfg(feature = "unstable")]
+            drop_listeners: Default::default(),
             undeclare_on_drop: true,
             sync_group: SyncGroup::default(),
         })
@@ -505,6 +521,8 @@ impl Wait for PublicationBuilder<&Publisher<'_>, PublicationBuilderPut> {
             self.timestamp,
             #[cfg(feature = "unstable")]
             self.source_info,
+            #[cfg(feature = "unstable")]
+            self.trace_context,
             self.attachment,
         )
     }
@@ -526,6 +544,8 @@ impl Wait for PublicationBuilder<&Publisher<'_>, PublicationBuilderDelete> {
             self.timestamp,
             #[cfg(feature = "unstable")]
             self.source_info,
+            #[cfg(feature = "unstable")]
+            self.trace_context,
             self.attachment,
         )
     }
@@ -27,7 +27,7 @@ use super::sample::QoSBuilderTrait;
 #[cfg(feature = "unstable")]
 use crate::api::cancellation::CancellationTokenBuilderTrait;
 #[cfg(feature = "unstable")]
-use crate::api::sample::SourceInfo;
+use crate::api::{sample::SourceInfo, trace::TraceContext};
 use crate::{
     api::{
         builders::sample::{EncodingBuilderTrait, SampleBuilderTrait},
@@ -244,6 +244,8 @@ pub struct QuerierGetBuilder<'a, 'b, Handler> {
     #[cfg(feature = "unstable")]
     pub(crate) source_info: Option<SourceInfo>,
     #[cfg(feature = "unstable")]
+    pub(crate) trace_context: Option<TraceContext>,
+    #[cfg(feature = "unstable")]
     pub(crate) cancellation_token: Option<crate::api::cancellation::CancellationToken>,
 }
 
@@ -300,6 +302,14 @@ impl<Handler> SampleBuilderTrait for QuerierGetBuilder<'_, '_, Handler> {
             ..self
         }
     }
+    /// Sets an optional W3C [`TraceContext`](crate::sample::TraceContext) to be sent along with the query request.
+    #[zenoh_macros::unstable]
+    fn trace_context<T: Into<Option<TraceContext>>>(self, trace_context: T) -> Self {
+        Self {
+            trace_context: trace_context.into(),
+            ..self
+        }
+    }
     /// Sets an optional attachment to be sent along with the query request.
     /// The method accepts both values convertible to [`ZBytes`](crate::bytes::ZBytes)
     /// and optional values of such types (`Option<T>` where `T: Into<ZBytes>`).
@@ -424,6 +434,8 @@ impl<'a, 'b> QuerierGetBuilder<'a, 'b, DefaultHandler> {
             attachment,
             #[cfg(feature = "unstable")]
             source_info,
+            #[cfg(feature = "unstable")]
+            trace_context,
             handler: _,
             #[cfg(feature = "unstable")]
             cancellation_token,
@@ -435,6 +447,8 @@ impl<'a, 'b> QuerierGetBuilder<'a, 'b, DefaultHandler> {
             attachment,
             #[cfg(feature = "unstable")]
             source_info,
+            #[cfg(feature = "unstable")]
+            trace_context,
             handler,
             #[cfg(feature = "unstable")]
             cancellation_token,
@@ -498,6 +512,8 @@ where
             self.attachment,
             #[cfg(feature = "unstable")]
             self.source_info,
+            #[cfg(feature = "unstable")]
+            self.trace_context,
             callback,
             #[cfg(feature = "unstable")]
             self.cancellation_token,
@@ -23,7 +23,7 @@ use zenoh_result::ZResult;
 #[cfg(feature = "unstable")]
 use crate::api::cancellation::CancellationTokenBuilderTrait;
 #[cfg(feature = "unstable")]
-use crate::api::sample::SourceInfo;
+use crate::api::{sample::SourceInfo, trace::TraceContext};
 use crate::{
     api::{
         builders::sample::{EncodingBuilderTrait, QoSBuilderTrait, SampleBuilderTrait},
@@ -79,6 +79,8 @@ pub struct SessionGetBuilder<'a, 'b, Handler> {
     #[cfg(feature = "unstable")]
     pub(crate) source_info: Option<SourceInfo>,
     #[cfg(feature = "unstable")]
+    pub(crate) trace_context: Option<TraceContext>,
+    #[cfg(feature = "unstable")]
     pub(crate) cancellation_token: Option<crate::api::cancellation::CancellationToken>,
 }
 
@@ -93,6 +95,15 @@ impl<Handler> SampleBuilderTrait for SessionGetBuilder<'_, '_, Handler> {
         }
     }
 
+    #[zenoh_macros::unstable]
+    /// Sets an optional W3C [`TraceContext`](crate::sample::TraceContext) to be sent along with the request/query.
+    fn trace_context<T: Into<Option<TraceContext>>>(self, trace_context: T) -> Self {
+        Self {
+            trace_context: trace_context.into(),
+            ..self
+        }
+    }
+
     /// Sets an optional attachment to be sent along with the request/query.
     /// The method accepts both `T` where `T: Into<ZBytes>` and `Option<T>` where `T: Into<ZBytes>` (see [`OptionZBytes`](crate::bytes::OptionZBytes)).
     fn attachment<T: Into<OptionZBytes>>(self, attachment: T) -> Self {
@@ -264,6 +275,8 @@ impl<'a, 'b> SessionGetBuilder<'a, 'b, DefaultHandler> {
             attachment,
             #[cfg(feature = "unstable")]
             source_info,
+            #[cfg(feature = "unstable")]
+            trace_context,
             handler: _,
             #[cfg(feature = "unstable")]
             cancellation_token,
@@ -280,6 +293,8 @@ impl<'a, 'b> SessionGetBuilder<'a, 'b, DefaultHandler> {
             attachment,
             #[cfg(feature = "unstable")]
             source_info,
+            #[cfg(feature = "unstable")]
+            trace_context,
             handler,
             #[cfg(feature = "unstable")]
             cancellation_token,
@@ -400,6 +415,8 @@ where
             self.attachment,
             #[cfg(feature = "unstable")]
             self.source_info,
+            #[cfg(feature = "unstable")]
+            self.trace_context,
             callback,
             #[cfg(feature = "unstable")]
             self.cancellation_token,
@@ -22,8 +22,6 @@ use zenoh_protocol::{
 };
 use zenoh_result::ZResult;
 
-#[zenoh_macros::unstable]
-use crate::api::sample::SourceInfo;
 use crate::api::{
     builders::sample::{
         EncodingBuilderTrait, QoSBuilderTrait, SampleBuilder, SampleBuilderTrait,
@@ -36,6 +34,8 @@ use crate::api::{
     queryable::Query,
     sample::QoSBuilder,
 };
+#[zenoh_macros::unstable]
+use crate::api::{sample::SourceInfo, trace::TraceContext};
 
 /// The type modifier for a [`ReplyBuilder`] to create a reply with a [`Put`](crate::sample::SampleKind::Put) sample.
 #[derive(Debug)]
@@ -60,6 +60,8 @@ pub struct ReplyBuilder<'a, 'b, T> {
     qos: QoSBuilder,
     #[cfg(feature = "unstable")]
     source_info: Option<SourceInfo>,
+    #[cfg(feature = "unstable")]
+    trace_context: Option<TraceContext>,
     attachment: Option<ZBytes>,
 }
 
@@ -85,6 +87,8 @@ impl<'a, 'b> ReplyBuilder<'a, 'b, ReplyBuilderPut> {
             timestamp: None,
             #[cfg(feature = "unstable")]
             source_info: None,
+            #[cfg(feature = "unstable")]
+            trace_context: query.inner.trace_context.as_ref().map(TraceContext::child),
             attachment: None,
         }
     }
@@ -104,6 +108,8 @@ impl<'a, 'b> ReplyBuilder<'a, 'b, ReplyBuilderDelete> {
             timestamp: None,
             #[cfg(feature = "unstable")]
             source_info: None,
+            #[cfg(feature = "unstable")]
+            trace_context: query.inner.trace_context.as_ref().map(TraceContext::child),
             attachment: None,
         }
     }
@@ -140,6 +146,18 @@ impl<T> SampleBuilderTrait for ReplyBuilder<'_, '_, T> {
             ..self
         }
     }
+
+    #[cfg(feature = "unstable")]
+    /// Sets an optional W3C [`TraceContext`](crate::sample::TraceContext) to be sent along with the reply/response.
+    ///
+    /// By default, the reply to a query carrying a trace context carries a
+    /// [`child`](crate::sample::TraceContext::child) of it.
+    fn trace_context<TT: Into<Option<TraceContext>>>(self, trace_context: TT) -> Self {
+        Self {
+            trace_context: trace_context.into(),
+            ..self
+        }
+    }
 }
 
 #[zenoh_macros::internal_trait]
@@ -190,7 +208,9 @@ impl Wait for ReplyBuilder<'_, '_, ReplyBuilderPut> {
             .timestamp(self.timestamp)
             .qos(self.qos.into());
         #[cfg(feature = "unstable")]
-        let sample = sample.source_info(self.source_info);
+        let sample = sample
+            .source_info(self.source_info)
+            .trace_context(self.trace_context);
         let sample = sample.attachment(self.attachment);
         self.query._reply_sample(sample.into())
     }
@@ -203,7 +223,9 @@ impl Wait for ReplyBuilder<'_, '_, ReplyBuilderDelete> {
             .timestamp(self.timestamp)
             .qos(self.qos.into());
         #[cfg(feature = "unstable")]
-        let sample = sample.source_info(self.source_info);
+        let sample = sample
+            .source_info(self.source_info)
+            .trace_context(self.trace_context);
         let sample = sample.attachment(self.attachment);
         self.query._reply_sample(sample.into())
     }
@@ -31,7 +31,7 @@ use crate::pubsub::{
     PublicationBuilder, PublicationBuilderDelete, PublicationBuilderPut, Publisher,
 };
 #[cfg(feature = "unstable")]
-use crate::sample::SourceInfo;
+use crate::sample::{SourceInfo, TraceContext};
 pub trait QoSBuilderTrait {
     /// Changes the [`CongestionControl`](crate::qos::CongestionControl) to apply when routing the data.
     fn congestion_control(self, congestion_control: CongestionControl) -> Self;
@@ -52,6 +52,9 @@ pub trait SampleBuilderTrait {
     /// Sets an optional [`SourceInfo`](crate::sample::SourceInfo) to be sent along with the publication.
     #[zenoh_macros::unstable]
     fn source_info<T: Into<Option<SourceInfo>>>(self, source_info: T) -> Self;
+    /// Sets an optional W3C [`TraceContext`](crate::sample::TraceContext) to be sent along with the publication.
+    #[zenoh_macros::unstable]
+    fn trace_context<T: Into<Option<TraceContext>>>(self, trace_context: T) -> Self;
     /// Sets an optional attachment to be sent along with the publication.
     /// The method accepts any `T` where `T: Into<ZBytes>` or `Option<T>` where `T: Into<ZBytes>`.
     /// See [`OptionZBytes`](crate::api::bytes::OptionZBytes) for the exact accepted forms.
@@ -110,6 +113,8 @@ impl SampleBuilder<SampleBuilderPut> {
                 reliability: Reliability::DEFAULT,
                 #[cfg(feature = "unstable")]
                 source_info: None,
+                #[cfg(feature = "unstable")]
+                trace_context: None,
                 attachment: None,
             },
             _t: PhantomData::<SampleBuilderPut>,
@@ -142,6 +147,8 @@ impl SampleBuilder<SampleBuilderDelete> {
                 reliability: Reliability::DEFAULT,
                 #[cfg(feature = "unstable")]
                 source_info: None,
+                #[cfg(feature = "unstable")]
+                trace_context: None,
                 attachment: None,
             },
             _t: PhantomData::<SampleBuilderDelete>,
@@ -212,6 +219,18 @@ impl<T> SampleBuilderTrait for SampleBuilder<T> {
         }
     }
 
+    #[zenoh_macros::unstable]
+    /// Sets an optional W3C [`TraceContext`](crate::sample::TraceContext) to be sent along with the publication.
+    fn trace_context<S: Into<Option<TraceContext>>>(self, trace_context: S) -> Self {
+        Self {
+            sample: Sample {
+                trace_context: trace_context.into(),
+                ..self.sample
+            },
+            _t: PhantomData::<T>,
+        }
+    }
+
     /// Sets an optional attachment to be sent along with the publication.
     /// The method accepts both `Into<ZBytes>` and `Option<Into<ZBytes>>`.
     fn attachment<U: Into<OptionZBytes>>(self, attachment: U) -> Self {
@@ -333,6 +352,8 @@ impl From<&PublicationBuilder<&Publisher<'_>, PublicationBuilderPut>> for Sample
             reliability: builder.publisher.reliability,
             #[cfg(feature = "unstable")]
             source_info: builder.source_info.clone(),
+            #[cfg(feature = "unstable")]
+            trace_context: builder.trace_context.clone(),
             attachment: builder.attachment.clone(),
         }
     }
@@ -356,6 +377,8 @@ impl From<&PublicationBuilder<&Publisher<'_>, PublicationBuilderDelete>> for Sam
             reliability: builder.publisher.reliability,
             #[cfg(feature = "unstable")]
             source_info: builder.source_info.clone(),
+            #[cfg(feature = "unstable")]
+            trace_context: builder.trace_context.clone(),
             attachment: builder.attachment.clone(),
         }
     }
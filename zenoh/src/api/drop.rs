@@ -0,0 +1,317 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+#[zenoh_macros::unstable]
+use std::{
+    collections::HashSet,
+    fmt,
+    future::{IntoFuture, Ready},
+    sync::{Arc, Mutex},
+};
+
+#[zenoh_macros::unstable]
+use tracing::error;
+#[zenoh_macros::unstable]
+use zenoh_core::{Resolvable, Wait};
+#[zenoh_macros::unstable]
+use zenoh_result::ZResult;
+
+#[zenoh_macros::unstable]
+use crate::api::{
+    cancellation::SyncGroup,
+    handlers::{Callback, CallbackParameter},
+    key_expr::KeyExpr,
+    session::{UndeclarableSealed, WeakSession},
+    Id,
+};
+
+/// The reason why a publication has been dropped before being sent.
+#[zenoh_macros::unstable_doc]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DropReason {
+    /// The publication could not be queued for transmission, either because the transmission
+    /// queue was full (with [`CongestionControl::Drop`](crate::qos::CongestionControl::Drop), or
+    /// when blocking for too long) or because the transport had no usable link.
+    Congestion,
+    /// The publication has been filtered out by an egress interceptor, e.g. access control,
+    /// downsampling or low-pass filtering.
+    Filtered,
+    /// The publication could not be sent because the transport to its destination was closed.
+    Closed,
+}
+
+impl DropReason {
+    pub(crate) const ALL: [DropReason; 3] = [
+        DropReason::Congestion,
+        DropReason::Filtered,
+        DropReason::Closed,
+    ];
+}
+
+/// An event reporting that publications on a key expression have been dropped before being sent.
+///
+/// # Examples
+/// ```
+/// # #[tokio::main]
+/// # async fn main() {
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let publisher = session.declare_publisher("key/expression").await.unwrap();
+/// let drop_listener = publisher
+///     .drop_listener()
+///     .callback(|event| {
+///         println!("{} publications on {} dropped ({:?})", event.count(), event.key_expr(), event.reason());
+///     })
+///     .await
+///     .unwrap();
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+#[derive(Clone, Debug)]
+pub struct DropEvent {
+    pub(crate) key_expr: KeyExpr<'static>,
+    pub(crate) reason: DropReason,
+    pub(crate) count: usize,
+}
+
+#[zenoh_macros::unstable]
+impl DropEvent {
+    /// Returns the key expression of the dropped publications.
+    pub fn key_expr(&self) -> &KeyExpr<'static> {
+        &self.key_expr
+    }
+
+    /// Returns the reason why the publications have been dropped.
+    pub fn reason(&self) -> DropReason {
+        self.reason
+    }
+
+    /// Returns the number of dropped messages, i.e. the number of destinations a publication
+    /// could not be sent to.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+#[zenoh_macros::unstable]
+impl CallbackParameter for DropEvent {
+    type Message<'a> = Self;
+
+    fn from_message(msg: Self::Message<'_>) -> Self {
+        msg
+    }
+}
+
+#[zenoh_macros::unstable]
+pub(crate) struct DropListenerState {
+    pub(crate) id: Id,
+    /// The key expression of the listened publications, all the publications of the session
+    /// being listened if `None`.
+    pub(crate) key_expr: Option<KeyExpr<'static>>,
+    pub(crate) callback: Callback<DropEvent>,
+}
+
+#[zenoh_macros::unstable]
+impl DropListenerState {
+    pub(crate) fn is_matching(&self, key_expr: &KeyExpr) -> bool {
+        self.key_expr
+            .as_ref()
+            .map_or(true, |listened| listened.intersects(key_expr))
+    }
+}
+
+#[zenoh_macros::unstable]
+impl fmt::Debug for DropListenerState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DropListener")
+            .field("id", &self.id)
+            .field("key_expr", &self.key_expr)
+            .finish()
+    }
+}
+
+#[zenoh_macros::unstable]
+#[derive(Debug)]
+pub(crate) struct DropListenerInner {
+    pub(crate) session: WeakSession,
+    /// The listeners of the publisher the listener has been declared on, if any.
+    pub(crate) drop_listeners: Option<Arc<Mutex<HashSet<Id>>>>,
+    pub(crate) id: Id,
+    pub(crate) undeclare_on_drop: bool,
+}
+
+/// A listener that sends notifications when publications are dropped before being sent.
+///
+/// Producers can use the notifications to adapt their publication rate.
+/// The notifications are delivered on the publication path: a handler that blocks when full, like
+/// the default one, blocks the publications until it is read.
+/// It is declared for the publications of a [`Publisher`](crate::pubsub::Publisher) with
+/// [`Publisher::drop_listener`](crate::pubsub::Publisher::drop_listener), or for all the
+/// publications of a [`Session`](crate::Session) with
+/// [`Session::drop_listener`](crate::Session::drop_listener).
+///
+/// # Examples
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() {
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let publisher = session.declare_publisher("key/expression").await.unwrap();
+/// let drop_listener = publisher.drop_listener().await.unwrap();
+/// while let Ok(event) = drop_listener.recv_async().await {
+///     println!("{} publications dropped ({:?})", event.count(), event.reason());
+/// }
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+#[derive(Debug)]
+pub struct DropListener<Handler> {
+    pub(crate) inner: DropListenerInner,
+    pub(crate) handler: Handler,
+    pub(crate) callback_sync_group: SyncGroup,
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> DropListener<Handler> {
+    /// Undeclare the [`DropListener`].
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let publisher = session.declare_publisher("key/expression").await.unwrap();
+    /// let drop_listener = publisher.drop_listener().await.unwrap();
+    /// drop_listener.undeclare().await.unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn undeclare(self) -> DropListenerUndeclaration<Handler>
+    where
+        Handler: Send,
+    {
+        self.undeclare_inner(())
+    }
+
+    fn undeclare_impl(&mut self) -> ZResult<()> {
+        // set the flag first to avoid a double panic if this function panics
+        self.inner.undeclare_on_drop = false;
+        if let Some(drop_listeners) = &self.inner.drop_listeners {
+            // the listener has already been undeclared with its publisher
+            if !zlock!(drop_listeners).remove(&self.inner.id) {
+                return Ok(());
+            }
+        }
+        self.inner
+            .session
+            .undeclare_drop_listener_inner(self.inner.id)
+    }
+
+    /// Returns a reference to this drop listener's handler.
+    /// A handler is anything that implements [`IntoHandler`](crate::handlers::IntoHandler).
+    /// The default handler is [`DefaultHandler`](crate::handlers::DefaultHandler).
+    pub fn handler(&self) -> &Handler {
+        &self.handler
+    }
+
+    /// Returns a mutable reference to this drop listener's handler.
+    /// A handler is anything that implements [`IntoHandler`](crate::handlers::IntoHandler).
+    /// The default handler is [`DefaultHandler`](crate::handlers::DefaultHandler).
+    pub fn handler_mut(&mut self) -> &mut Handler {
+        &mut self.handler
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> Drop for DropListener<Handler> {
+    fn drop(&mut self) {
+        if self.inner.undeclare_on_drop {
+            if let Err(error) = self.undeclare_impl() {
+                error!(error);
+            }
+        }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler: Send> UndeclarableSealed<()> for DropListener<Handler> {
+    type Undeclaration = DropListenerUndeclaration<Handler>;
+
+    fn undeclare_inner(self, _: ()) -> Self::Undeclaration {
+        DropListenerUndeclaration {
+            listener: self,
+            wait_callbacks: false,
+        }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> std::ops::Deref for DropListener<Handler> {
+    type Target = Handler;
+
+    fn deref(&self) -> &Self::Target {
+        &self.handler
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> std::ops::DerefMut for DropListener<Handler> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.handler
+    }
+}
+
+/// A [`Resolvable`] returned by [`DropListener::undeclare`]
+#[zenoh_macros::unstable]
+pub struct DropListenerUndeclaration<Handler> {
+    listener: DropListener<Handler>,
+    wait_callbacks: bool,
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> DropListenerUndeclaration<Handler> {
+    /// Block in undeclare operation until all currently running instances of drop listener callbacks (if any) return.
+    pub fn wait_callbacks(mut self) -> Self {
+        self.wait_callbacks = true;
+        self
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> Resolvable for DropListenerUndeclaration<Handler> {
+    type To = ZResult<()>;
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> Wait for DropListenerUndeclaration<Handler> {
+    fn wait(mut self) -> <Self as Resolvable>::To {
+        self.listener.undeclare_impl()?;
+        if self.wait_callbacks {
+            self.listener.callback_sync_group.wait();
+        }
+        Ok(())
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> IntoFuture for DropListenerUndeclaration<Handler> {
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Ready<<Self as Resolvable>::To>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        std::future::ready(self.wait())
+    }
+}
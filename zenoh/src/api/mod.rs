@@ -20,6 +20,7 @@ pub(crate) mod bytes;
 pub(crate) mod cancellation;
 pub(crate) mod config;
 pub(crate) mod connectivity;
+pub(crate) mod drop;
 pub(crate) mod encoding;
 pub(crate) mod handlers;
 pub(crate) mod info;
@@ -39,3 +40,4 @@ pub(crate) mod scouting;
 pub(crate) mod selector;
 pub(crate) mod session;
 pub(crate) mod subscriber;
+pub(crate) mod trace;
@@ -111,6 +111,14 @@ pub trait RunningPluginTrait: Send + Sync + PluginControl {
     ) -> ZResult<Vec<Response>> {
         Ok(Vec::new())
     }
+    /// Used to request the plugin's contribution to the metrics of the admin space.
+    /// Function called on any query on the `metrics` key of the admin space of this zenohd.
+    ///
+    /// Returns OpenMetrics metric families (without the final `# EOF` marker), which are inserted
+    /// in the reply after the metrics of the runtime. Returns an empty string by default.
+    fn metrics(&self) -> ZResult<String> {
+        Ok(String::new())
+    }
 }
 
 /// The zenoh plugins manager. It handles the full lifetime of plugins, from loading to destruction.
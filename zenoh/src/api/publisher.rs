@@ -35,6 +35,9 @@ use {
     zenoh_protocol::core::Reliability,
 };
 
+#[cfg(feature = "unstable")]
+use crate::api::builders::drop_listener::DropListenerBuilder;
+
 use crate::api::{
     builders::{
         matching_listener::MatchingListenerBuilder,
@@ -120,6 +123,8 @@ pub struct Publisher<'a> {
     #[cfg(feature = "unstable")]
     pub(crate) reliability: Reliability,
     pub(crate) matching_listeners: Arc<Mutex<HashSet<Id>>>,
+    #[cfg(feature = "unstable")]
+    pub(crate) drop_listeners: Arc<Mutex<HashSet<Id>>>,
     pub(crate) undeclare_on_drop: bool,
     pub(crate) sync_group: SyncGroup,
 }
@@ -268,6 +273,8 @@ impl<'a> Publisher<'a> {
             timestamp: None,
             #[cfg(feature = "unstable")]
             source_info: None,
+            #[cfg(feature = "unstable")]
+            trace_context: None,
             attachment: None,
         }
     }
@@ -295,6 +302,8 @@ impl<'a> Publisher<'a> {
             timestamp: None,
             #[cfg(feature = "unstable")]
             source_info: None,
+            #[cfg(feature = "unstable")]
+            trace_context: None,
             attachment: None,
         }
     }
@@ -362,6 +371,41 @@ impl<'a> Publisher<'a> {
         }
     }
 
+    /// Return a [`DropListener`](crate::api::drop::DropListener) for this Publisher.
+    ///
+    /// The [`DropListener`](crate::api::drop::DropListener) will send a notification each time
+    /// publications on the Publisher's key expression are dropped before being sent, e.g. because
+    /// of congestion, so that the publication rate can be adapted.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// use zenoh::qos::CongestionControl;
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let publisher = session
+    ///     .declare_publisher("key/expression")
+    ///     .congestion_control(CongestionControl::Drop)
+    ///     .await
+    ///     .unwrap();
+    /// let drop_listener = publisher.drop_listener().await.unwrap();
+    /// while let Ok(event) = drop_listener.recv_async().await {
+    ///     println!("{} publications dropped ({:?})", event.count(), event.reason());
+    /// }
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn drop_listener(&self) -> DropListenerBuilder<'_, DefaultHandler> {
+        DropListenerBuilder {
+            session: self.session.clone(),
+            key_expr: Some(&self.key_expr),
+            drop_listeners: Some(&self.drop_listeners),
+            handler: DefaultHandler::default(),
+            parent_callback_sync_group_notifier: self.sync_group.notifier(),
+        }
+    }
+
     /// Undeclare the [`Publisher`], informing the network that it needn't optimize publications for its key expression anymore.
     ///
     /// # Examples
@@ -385,6 +429,13 @@ impl<'a> Publisher<'a> {
         for id in ids {
             self.session.undeclare_matches_listener_inner(id)?
         }
+        #[cfg(feature = "unstable")]
+        {
+            let ids: Vec<Id> = zlock!(self.drop_listeners).drain().collect();
+            for id in ids {
+                self.session.undeclare_drop_listener_inner(id)?
+            }
+        }
         self.session.undeclare_publisher_inner(self.id)
     }
 
@@ -496,6 +547,8 @@ impl Sink<Sample> for Publisher<'_> {
             None,
             #[cfg(feature = "unstable")]
             None,
+            #[cfg(feature = "unstable")]
+            None,
             attachment,
         )
     }
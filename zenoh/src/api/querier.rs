@@ -169,6 +169,8 @@ impl<'a> Querier<'a> {
             querier: self,
             #[cfg(feature = "unstable")]
             source_info: None,
+            #[cfg(feature = "unstable")]
+            trace_context: None,
             value: None,
             attachment: None,
             parameters: Parameters::empty(),
@@ -18,7 +18,7 @@ use std::{
     sync::Arc,
 };
 
-use tracing::error;
+use tracing::{error, Span};
 use zenoh_core::{Resolvable, Wait};
 use zenoh_protocol::{
     core::{EntityId, Parameters, WireExpr, ZenohIdProto},
@@ -30,7 +30,7 @@ use zenoh_result::ZResult;
 use {zenoh_config::wrappers::EntityGlobalId, zenoh_protocol::core::EntityGlobalIdProto};
 
 #[zenoh_macros::unstable]
-use crate::api::sample::SourceInfo;
+use crate::api::{sample::SourceInfo, trace::TraceContext};
 #[zenoh_macros::internal]
 use crate::net::primitives::DummyPrimitives;
 use crate::{
@@ -45,6 +45,7 @@ use crate::{
         sample::{Locality, QoS, Sample, SampleKind},
         selector::{Selector, REPLY_KEY_EXPR_ANY_SEL_PARAM},
         session::{UndeclarableSealed, WeakSession},
+        trace::{self, SpanKind},
         Id,
     },
     handlers::Callback,
@@ -117,6 +118,8 @@ pub(crate) struct QueryInner {
     pub(crate) qos: QoS,
     #[cfg(feature = "unstable")]
     pub(crate) source_info: Option<SourceInfo>,
+    #[cfg(feature = "unstable")]
+    pub(crate) trace_context: Option<TraceContext>,
     pub(crate) primitives: ReplyPrimitives,
 }
 
@@ -131,6 +134,8 @@ impl QueryInner {
             qos: QoS::default(),
             #[cfg(feature = "unstable")]
             source_info: None,
+            #[cfg(feature = "unstable")]
+            trace_context: None,
             primitives: ReplyPrimitives::new_remote(None, Arc::new(DummyPrimitives)),
         }
     }
@@ -344,6 +349,13 @@ impl Query {
         self.inner.source_info.as_ref()
     }
 
+    /// Gets the W3C trace context sent along with this Query.
+    #[zenoh_macros::unstable]
+    #[inline]
+    pub fn trace_context(&self) -> Option<&TraceContext> {
+        self.inner.trace_context.as_ref()
+    }
+
     /// Sends a reply in the form of [`Sample`] to this Query.
     ///
     /// This api is for internal use only.
@@ -557,7 +569,7 @@ impl Query {
         let ext_sinfo = None;
         #[cfg(feature = "unstable")]
         let ext_sinfo = sample.source_info.map(Into::into);
-        self.inner.primitives.send_response(&mut Response {
+        let mut response = Response {
             rid: self.inner.qid,
             wire_expr: self.inner.primitives.keyexpr_to_wire(&sample.key_expr),
             payload: ResponseBody::Reply(zenoh::Reply {
@@ -571,6 +583,10 @@ impl Query {
                         #[cfg(feature = "shared-memory")]
                         ext_shm: None,
                         ext_attachment: sample.attachment.map(|a| a.into()),
+                        #[cfg(feature = "unstable")]
+                        ext_trace: sample.trace_context.map(Into::into),
+                        #[cfg(not(feature = "unstable"))]
+                        ext_trace: None,
                         ext_unknown: vec![],
                         payload: sample.payload.into(),
                     }),
@@ -578,6 +594,10 @@ impl Query {
                         timestamp: sample.timestamp,
                         ext_sinfo,
                         ext_attachment: sample.attachment.map(|a| a.into()),
+                        #[cfg(feature = "unstable")]
+                        ext_trace: sample.trace_context.map(Into::into),
+                        #[cfg(not(feature = "unstable"))]
+                        ext_trace: None,
                         ext_unknown: vec![],
                     }),
                 },
@@ -588,7 +608,15 @@ impl Query {
                 zid: self.inner.zid,
                 eid: self.eid,
             }),
-        });
+        };
+        let _span = match &response.payload {
+            ResponseBody::Reply(reply) => {
+                trace::reply_span(SpanKind::Send, sample.key_expr.as_str(), &reply.payload)
+            }
+            ResponseBody::Err(_) => None,
+        }
+        .map(Span::entered);
+        self.inner.primitives.send_response(&mut response);
         Ok(())
     }
 }
@@ -27,6 +27,8 @@ use zenoh_protocol::{
     zenoh::PushBody,
 };
 
+#[cfg(feature = "unstable")]
+use crate::api::trace::TraceContext;
 use crate::api::{
     builders::sample::QoSBuilderTrait, bytes::ZBytes, encoding::Encoding,
     handlers::CallbackParameter, key_expr::KeyExpr, publisher::Priority,
@@ -194,6 +196,8 @@ pub struct SampleFields {
     pub reliability: Reliability,
     #[cfg(feature = "unstable")]
     pub source_info: Option<SourceInfo>,
+    #[cfg(feature = "unstable")]
+    pub trace_context: Option<TraceContext>,
     pub attachment: Option<ZBytes>,
 }
 
@@ -212,6 +216,8 @@ impl From<Sample> for SampleFields {
             reliability: sample.reliability,
             #[cfg(feature = "unstable")]
             source_info: sample.source_info,
+            #[cfg(feature = "unstable")]
+            trace_context: sample.trace_context,
             attachment: sample.attachment,
         }
     }
@@ -234,6 +240,8 @@ pub struct Sample {
     pub(crate) reliability: Reliability,
     #[cfg(feature = "unstable")]
     pub(crate) source_info: Option<SourceInfo>,
+    #[cfg(feature = "unstable")]
+    pub(crate) trace_context: Option<TraceContext>,
     pub(crate) attachment: Option<ZBytes>,
 }
 
@@ -302,6 +310,13 @@ impl Sample {
         self.source_info.as_ref()
     }
 
+    /// Gets the W3C trace context sent along with this Sample.
+    #[zenoh_macros::unstable]
+    #[inline]
+    pub fn trace_context(&self) -> Option<&TraceContext> {
+        self.trace_context.as_ref()
+    }
+
     /// Gets the optional sample attachment as bytes.
     #[inline]
     pub fn attachment(&self) -> Option<&ZBytes> {
@@ -328,6 +343,8 @@ impl Sample {
             reliability: Reliability::default(),
             #[cfg(feature = "unstable")]
             source_info: None,
+            #[cfg(feature = "unstable")]
+            trace_context: None,
             attachment: None,
         }
     }
@@ -350,6 +367,8 @@ impl Sample {
                 reliability,
                 #[cfg(feature = "unstable")]
                 source_info: put.ext_sinfo.map(Into::into),
+                #[cfg(feature = "unstable")]
+                trace_context: mem::take(&mut put.ext_trace).map(Into::into),
                 attachment: mem::take(&mut put.ext_attachment).map(Into::into),
             },
             PushBody::Del(del) => Self {
@@ -363,6 +382,8 @@ impl Sample {
                 reliability,
                 #[cfg(feature = "unstable")]
                 source_info: del.ext_sinfo.map(Into::into),
+                #[cfg(feature = "unstable")]
+                trace_context: mem::take(&mut del.ext_trace).map(Into::into),
                 attachment: mem::take(&mut del.ext_attachment).map(Into::into),
             },
         }
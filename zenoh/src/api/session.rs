@@ -27,7 +27,7 @@ use std::{
 use async_trait::async_trait;
 use itertools::Itertools;
 use once_cell::sync::OnceCell;
-use tracing::{error, info, span::EnteredSpan, trace, warn};
+use tracing::{error, info, span::EnteredSpan, trace, warn, Span};
 use uhlc::Timestamp;
 #[cfg(feature = "internal")]
 use uhlc::HLC;
@@ -73,7 +73,14 @@ use super::{
     connectivity,
 };
 #[cfg(feature = "unstable")]
-use crate::api::{cancellation::CancellationToken, sample::SourceInfo, selector::ZenohParameters};
+use crate::api::{
+    builders::drop_listener::DropListenerBuilder,
+    cancellation::CancellationToken,
+    drop::{DropEvent, DropListenerState, DropReason},
+    sample::SourceInfo,
+    selector::ZenohParameters,
+    trace::TraceContext,
+};
 #[cfg(feature = "internal")]
 use crate::net::runtime::Runtime;
 #[cfg(all(feature = "shared-memory", feature = "unstable"))]
@@ -110,6 +117,7 @@ use crate::{
         sample::{Locality, QoS, Sample, SampleKind},
         selector::{Selector, REPLY_KEY_EXPR_ANY_SEL_PARAM},
         subscriber::{SubscriberKind, SubscriberState},
+        trace::{self, SpanKind},
         Id,
     },
     net::{
@@ -171,6 +179,8 @@ pub(crate) struct SessionState {
     pub(crate) queryables: HashMap<Id, Arc<QueryableState>>,
     pub(crate) remote_queryables: HashMap<Id, (KeyExpr<'static>, bool)>,
     pub(crate) matching_listeners: HashMap<Id, Arc<MatchingListenerState>>,
+    #[cfg(feature = "unstable")]
+    pub(crate) drop_listeners: HashMap<Id, Arc<DropListenerState>>,
     pub(crate) transport_events_listeners: HashMap<Id, Arc<TransportEventsListenerState>>,
     pub(crate) link_events_listeners: HashMap<Id, Arc<LinkEventsListenerState>>,
     pub(crate) queries: HashMap<RequestId, QueryState>,
@@ -204,6 +214,8 @@ impl SessionState {
             queryables: HashMap::new(),
             remote_queryables: HashMap::new(),
             matching_listeners: HashMap::new(),
+            #[cfg(feature = "unstable")]
+            drop_listeners: HashMap::new(),
             transport_events_listeners: HashMap::new(),
             link_events_listeners: HashMap::new(),
             queries: HashMap::new(),
@@ -555,12 +567,14 @@ impl SubscriberCallbacks {
     ) {
         let zenoh_collections::single_or_vec::IntoIter { drain, last } = self.0.into_iter();
         for (cb, key_expr) in drain {
+            let _span = trace::push_span(SpanKind::Receive, &key_expr, msg).map(Span::entered);
             #[cfg(feature = "unstable")]
             cb.call_with_message((key_expr, qos, &mut msg.clone(), reliability));
             #[cfg(not(feature = "unstable"))]
             cb.call_with_message((key_expr, qos, &mut msg.clone()));
         }
         if let Some((cb, key_expr)) = last {
+            let _span = trace::push_span(SpanKind::Receive, &key_expr, msg).map(Span::entered);
             let mut msg = &mut *msg;
             let mut msg_clone;
             if !consume {
@@ -1260,6 +1274,34 @@ impl Session {
     pub fn liveliness(&self) -> Liveliness<'_> {
         Liveliness { session: self }
     }
+
+    /// Return a [`DropListener`](crate::api::drop::DropListener) for the publications of this Session.
+    ///
+    /// The [`DropListener`](crate::api::drop::DropListener) will send a notification each time
+    /// publications of the session are dropped before being sent, e.g. because of congestion.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let drop_listener = session.drop_listener().await.unwrap();
+    /// while let Ok(event) = drop_listener.recv_async().await {
+    ///     println!("{} publications on {} dropped", event.count(), event.key_expr());
+    /// }
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn drop_listener(&self) -> DropListenerBuilder<'_, DefaultHandler> {
+        DropListenerBuilder {
+            session: self.downgrade(),
+            key_expr: None,
+            drop_listeners: None,
+            handler: DefaultHandler::default(),
+            parent_callback_sync_group_notifier: None,
+        }
+    }
 }
 
 impl Session {
@@ -1337,6 +1379,8 @@ impl Session {
             attachment: None,
             #[cfg(feature = "unstable")]
             source_info: None,
+            #[cfg(feature = "unstable")]
+            trace_context: None,
         }
     }
 
@@ -1372,6 +1416,8 @@ impl Session {
             attachment: None,
             #[cfg(feature = "unstable")]
             source_info: None,
+            #[cfg(feature = "unstable")]
+            trace_context: None,
         }
     }
     /// Query data from the matching queryables in the system. This is a shortcut for declaring
@@ -1421,6 +1467,8 @@ impl Session {
             #[cfg(feature = "unstable")]
             source_info: None,
             #[cfg(feature = "unstable")]
+            trace_context: None,
+            #[cfg(feature = "unstable")]
             cancellation_token: None,
         }
     }
@@ -2053,6 +2101,8 @@ impl Session {
                             reliability: Reliability::Reliable,
                             #[cfg(feature = "unstable")]
                             source_info: None,
+                            #[cfg(feature = "unstable")]
+                            trace_context: None,
                             attachment: None,
                         });
                     }
@@ -2263,6 +2313,81 @@ impl Session {
         }
     }
 
+    #[cfg(feature = "unstable")]
+    #[allow(unused_mut)] // for callback drop on undeclare
+    pub(crate) fn declare_drop_listener_inner(
+        &self,
+        key_expr: Option<&KeyExpr>,
+        mut callback: Callback<DropEvent>,
+        callback_sync_group_notifier: Option<SyncGroupNotifier>,
+    ) -> ZResult<Arc<DropListenerState>> {
+        let id = self.0.runtime.next_id();
+        trace!("declare_drop_listener({:?}) => {id}", key_expr);
+        let mut state = zwrite!(self.0.state);
+        if state.primitives.is_none() {
+            return Err(SessionClosedError.into());
+        }
+        self.register_callback_drop_notifier(callback_sync_group_notifier, &mut callback);
+        let listener_state = Arc::new(DropListenerState {
+            id,
+            key_expr: key_expr.map(|k| k.clone().into_owned()),
+            callback,
+        });
+        state.drop_listeners.insert(id, listener_state.clone());
+        Ok(listener_state)
+    }
+
+    #[cfg(feature = "unstable")]
+    pub(crate) fn undeclare_drop_listener_inner(&self, sid: Id) -> ZResult<()> {
+        let state = {
+            let mut state = zwrite!(self.0.state);
+            if state.primitives.is_none() {
+                return Ok(());
+            }
+
+            state.drop_listeners.remove(&sid)
+        };
+
+        if let Some(state) = state {
+            trace!("undeclare_drop_listener_inner({:?})", state);
+            Ok(())
+        } else {
+            Err(zerror!("Unable to find DropListener").into())
+        }
+    }
+
+    #[cfg(feature = "unstable")]
+    pub(crate) fn broadcast_drop_event(
+        &self,
+        key_expr: &keyexpr,
+        reason: DropReason,
+        count: usize,
+    ) {
+        let state = zread!(self.0.state);
+        if state.drop_listeners.is_empty() {
+            return;
+        }
+        let key_expr = KeyExpr::from(key_expr);
+        let listeners = state
+            .drop_listeners
+            .values()
+            .filter(|listener| listener.is_matching(&key_expr))
+            .cloned()
+            .collect::<Vec<_>>();
+        drop(state);
+        if listeners.is_empty() {
+            return;
+        }
+        let event = DropEvent {
+            key_expr: key_expr.into_owned(),
+            reason,
+            count,
+        };
+        for listener in listeners {
+            listener.callback.call(event.clone());
+        }
+    }
+
     #[allow(unused_mut)] // for callback drop on undeclare
     pub(crate) fn declare_transport_events_listener_inner(
         &self,
@@ -2469,6 +2594,7 @@ impl Session {
         #[cfg(feature = "unstable")] reliability: Reliability,
         timestamp: Option<uhlc::Timestamp>,
         #[cfg(feature = "unstable")] source_info: Option<SourceInfo>,
+        #[cfg(feature = "unstable")] trace_context: Option<TraceContext>,
         attachment: Option<ZBytes>,
     ) -> ZResult<()> {
         trace!("write({:?}, [...])", key_expr);
@@ -2497,6 +2623,10 @@ impl Session {
                     #[cfg(feature = "shared-memory")]
                     ext_shm: None,
                     ext_attachment: attachment.map(Into::into),
+                    #[cfg(feature = "unstable")]
+                    ext_trace: trace_context.map(Into::into),
+                    #[cfg(not(feature = "unstable"))]
+                    ext_trace: None,
                     ext_unknown: vec![],
                     payload: payload.into(),
                 }),
@@ -2507,10 +2637,16 @@ impl Session {
                     #[cfg(not(feature = "unstable"))]
                     ext_sinfo: None,
                     ext_attachment: attachment.map(Into::into),
+                    #[cfg(feature = "unstable")]
+                    ext_trace: trace_context.map(Into::into),
+                    #[cfg(not(feature = "unstable"))]
+                    ext_trace: None,
                     ext_unknown: vec![],
                 }),
             })
         };
+        let _span =
+            trace::push_span(SpanKind::Send, key_expr.as_str(), &push.payload).map(Span::entered);
         let has_local_callbacks = !callbacks.is_empty();
         if destination != Locality::SessionLocal {
             primitives.send_push_consume(
@@ -2618,6 +2754,7 @@ impl Session {
         value: Option<(ZBytes, Encoding)>,
         attachment: Option<ZBytes>,
         #[cfg(feature = "unstable")] source: Option<SourceInfo>,
+        #[cfg(feature = "unstable")] trace_context: Option<TraceContext>,
         mut callback: Callback<Reply>,
         #[cfg(feature = "unstable")] cancellation_token: Option<CancellationToken>,
         querier_id: Option<EntityId>,
@@ -2701,6 +2838,13 @@ impl Session {
         );
         drop(state);
 
+        #[cfg(feature = "unstable")]
+        let ext_trace: Option<query::ext::TraceContextType> = trace_context.map(Into::into);
+        #[cfg(not(feature = "unstable"))]
+        let ext_trace: Option<query::ext::TraceContextType> = None;
+        let _span = ext_trace
+            .as_ref()
+            .map(|ctx| trace::span(SpanKind::Send, "query", key_expr.as_str(), ctx).entered());
         if destination != Locality::SessionLocal {
             let wexpr = key_expr.to_wire(self).to_owned();
             let ext_attachment = attachment.clone().map(Into::into);
@@ -2727,6 +2871,7 @@ impl Session {
                         payload: v.0.clone().into(),
                     }),
                     ext_attachment,
+                    ext_trace: ext_trace.clone(),
                     ext_unknown: vec![],
                 }),
             });
@@ -2750,6 +2895,7 @@ impl Session {
                     payload: v.0.clone().into(),
                 }),
                 attachment,
+                ext_trace,
             );
         }
         Ok(())
@@ -2866,6 +3012,7 @@ impl Session {
         #[cfg(feature = "unstable")] source_info: Option<SourceInfo>,
         body: Option<QueryBodyType>,
         attachment: Option<ZBytes>,
+        ext_trace: Option<query::ext::TraceContextType>,
     ) {
         let Ok(primitives) = state.primitives() else {
             return;
@@ -2894,6 +3041,8 @@ impl Session {
             qos,
             #[cfg(feature = "unstable")]
             source_info,
+            #[cfg(feature = "unstable")]
+            trace_context: ext_trace.clone().map(Into::into),
             primitives: if local {
                 ReplyPrimitives::new_local(self.downgrade())
             } else {
@@ -2908,6 +3057,9 @@ impl Session {
                 attachment,
             };
             for (eid, cb) in queryables {
+                let _span = ext_trace.as_ref().map(|ctx| {
+                    trace::span(SpanKind::Receive, "query", key_expr.as_str(), ctx).entered()
+                });
                 query.eid = eid;
                 cb.call(query.clone());
             }
@@ -3107,6 +3259,8 @@ impl Primitives for WeakSession {
                                         reliability: Reliability::Reliable,
                                         #[cfg(feature = "unstable")]
                                         source_info: None,
+                                        #[cfg(feature = "unstable")]
+                                        trace_context: None,
                                         attachment: None,
                                     }),
                                     #[cfg(feature = "unstable")]
@@ -3244,6 +3398,7 @@ impl Primitives for WeakSession {
                             m.ext_sinfo.map(Into::into),
                             mem::take(&mut m.ext_body),
                             mem::take(&mut m.ext_attachment).map(Into::into),
+                            mem::take(&mut m.ext_trace),
                         );
                     }
                     Err(err) => {
@@ -3312,6 +3467,8 @@ impl Primitives for WeakSession {
                             );
                             return;
                         }
+                        let span =
+                            trace::reply_span(SpanKind::Receive, key_expr.as_str(), &m.payload);
                         let new_reply = Reply {
                             result: Ok(Sample::from_push(
                                 key_expr.into_owned(),
@@ -3410,6 +3567,7 @@ impl Primitives for WeakSession {
                             };
                         std::mem::drop(state);
                         if let Some((callback, new_reply)) = callback {
+                            let _span = span.map(Span::entered);
                             callback.call(new_reply);
                         }
                     }
@@ -3493,6 +3651,11 @@ impl crate::net::primitives::EPrimitives for WeakSession {
         false
     }
 
+    #[cfg(feature = "unstable")]
+    fn notify_push_dropped(&self, key_expr: &keyexpr, reason: DropReason, count: usize) {
+        self.broadcast_drop_event(key_expr, reason, count)
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -3561,6 +3724,8 @@ impl Closee for WeakSession {
             let _remote_resources = std::mem::take(&mut state.remote_resources);
             let _queries = std::mem::take(&mut state.queries);
             let _matching_listeners = std::mem::take(&mut state.matching_listeners);
+            #[cfg(feature = "unstable")]
+            let _drop_listeners = std::mem::take(&mut state.drop_listeners);
             let _transport_event_listeners = std::mem::take(&mut state.transport_events_listeners);
             let _link_event_listeners = std::mem::take(&mut state.link_events_listeners);
             drop(state);
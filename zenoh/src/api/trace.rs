@@ -0,0 +1,434 @@
+//
+// Copyright (c) 2023 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Trace context propagation
+//!
+//! A [W3C trace context](https://www.w3.org/TR/trace-context/) can be set on publications, queries
+//! and replies. It is carried in a dedicated protocol extension, forwarded as is by the routers, and
+//! the `tracing` spans created along the way by the publishers, routers, subscribers, queryables
+//! and queriers record its trace id and the span id of their remote parent:
+//!
+//! - `zenoh.send` when a message carrying a trace context is sent, with the span id of the context;
+//! - `zenoh.route` when a router forwards it;
+//! - `zenoh.receive` when it is delivered to a subscriber, a queryable or a reply callback.
+//!
+//! All the spans have `operation`, `key_expr`, `trace_id` and `span_id` fields, and a `parent_span_id`
+//! field for the routing and reception spans.
+use std::fmt;
+#[cfg(feature = "unstable")]
+use std::str::FromStr;
+
+use tracing::Span;
+use zenoh_protocol::zenoh::{ext::TraceContextType, PushBody};
+#[cfg(feature = "unstable")]
+use zenoh_result::{bail, ZResult};
+
+#[cfg(feature = "unstable")]
+const VERSION: &str = "00";
+#[cfg(feature = "unstable")]
+const SAMPLED: u8 = 0x01;
+/// The limits of a `tracestate` specified by W3C: its number of list members, its length, and the
+/// length above which its members are removed first when it is truncated.
+#[cfg(feature = "unstable")]
+const TRACESTATE_MAX_MEMBERS: usize = 32;
+#[cfg(feature = "unstable")]
+const TRACESTATE_MAX_LEN: usize = 512;
+#[cfg(feature = "unstable")]
+const TRACESTATE_MAX_MEMBER_LEN: usize = 128;
+
+/// The [W3C trace context](https://www.w3.org/TR/trace-context/) of a publication, a query or a reply.
+///
+/// It holds the `traceparent` and `tracestate` of the message, and identifies the span of the
+/// operation sending it: a trace context received with a [`Sample`](crate::sample::Sample) or a
+/// [`Query`](crate::query::Query) can be continued with [`TraceContext::child`].
+///
+/// # Examples
+/// ```
+/// # #[tokio::main]
+/// # async fn main() {
+/// use zenoh::sample::TraceContext;
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let context: TraceContext = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+///     .parse()
+///     .unwrap();
+/// session
+///     .put("key/expression", "value")
+///     .trace_context(context.child())
+///     .await
+///     .unwrap();
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TraceContext {
+    trace_id: [u8; 16],
+    span_id: [u8; 8],
+    flags: u8,
+    state: String,
+}
+
+#[zenoh_macros::unstable]
+impl TraceContext {
+    /// Starts a new sampled trace, with random trace and span ids.
+    pub fn root() -> Self {
+        Self {
+            trace_id: random_id(),
+            span_id: random_id(),
+            flags: SAMPLED,
+            state: String::new(),
+        }
+    }
+
+    /// Returns the context of a new span of the same trace, child of this one.
+    pub fn child(&self) -> Self {
+        Self {
+            span_id: random_id(),
+            ..self.clone()
+        }
+    }
+
+    /// Parses a context from the values of the `traceparent` and `tracestate` HTTP headers. The
+    /// `tracestate` is truncated to the W3C limits, see [`TraceContext::with_tracestate`].
+    pub fn from_w3c(traceparent: &str, tracestate: Option<&str>) -> ZResult<Self> {
+        let mut fields = traceparent.trim().split('-');
+        let (Some(version), Some(trace_id), Some(span_id), Some(flags)) =
+            (fields.next(), fields.next(), fields.next(), fields.next())
+        else {
+            bail!("Invalid traceparent {traceparent}: expected 4 fields");
+        };
+        // Future versions may add fields after the flags
+        if version.len() != 2 || version == "ff" || (version == VERSION && fields.next().is_some())
+        {
+            bail!("Invalid traceparent {traceparent}: unsupported version");
+        }
+        let (Some(trace_id), Some(span_id), Some([flags])) = (
+            parse_hex::<16>(trace_id),
+            parse_hex::<8>(span_id),
+            parse_hex::<1>(flags),
+        ) else {
+            bail!("Invalid traceparent {traceparent}: malformed field");
+        };
+        if trace_id == [0; 16] || span_id == [0; 8] {
+            bail!("Invalid traceparent {traceparent}: all-zero id");
+        }
+        Ok(Self {
+            trace_id,
+            span_id,
+            flags,
+            state: truncate_tracestate(tracestate.unwrap_or_default()),
+        })
+    }
+
+    /// Sets the `tracestate` of the context, i.e. vendor-specific trace information.
+    ///
+    /// As allowed by W3C, it is truncated to 32 list members and 512 characters, by removing whole
+    /// members: the ones longer than 128 characters first, then the last ones.
+    pub fn with_tracestate<S: AsRef<str>>(self, tracestate: S) -> Self {
+        Self {
+            state: truncate_tracestate(tracestate.as_ref()),
+            ..self
+        }
+    }
+
+    /// Gets the id of the trace.
+    pub fn trace_id(&self) -> [u8; 16] {
+        self.trace_id
+    }
+
+    /// Gets the id of the span which sent the message.
+    pub fn span_id(&self) -> [u8; 8] {
+        self.span_id
+    }
+
+    /// Gets the trace flags.
+    pub fn flags(&self) -> u8 {
+        self.flags
+    }
+
+    /// Whether the trace is sampled, i.e. whether the caller may have recorded it.
+    pub fn is_sampled(&self) -> bool {
+        self.flags & SAMPLED != 0
+    }
+
+    /// Gets the value of the `traceparent` HTTP header for this context.
+    pub fn traceparent(&self) -> String {
+        self.to_string()
+    }
+
+    /// Gets the value of the `tracestate` HTTP header for this context, possibly empty.
+    pub fn tracestate(&self) -> &str {
+        &self.state
+    }
+}
+
+#[zenoh_macros::unstable]
+impl fmt::Display for TraceContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{VERSION}-{}-{}-{}",
+            Hex(&self.trace_id),
+            Hex(&self.span_id),
+            Hex(&[self.flags])
+        )
+    }
+}
+
+#[zenoh_macros::unstable]
+impl FromStr for TraceContext {
+    type Err = zenoh_result::Error;
+
+    /// Parses a context from a `traceparent`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_w3c(s, None)
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<const ID: u8> From<TraceContextType<ID>> for TraceContext {
+    fn from(value: TraceContextType<ID>) -> Self {
+        Self {
+            trace_id: value.trace_id,
+            span_id: value.span_id,
+            flags: value.flags,
+            state: value.state,
+        }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<const ID: u8> From<TraceContext> for TraceContextType<ID> {
+    fn from(value: TraceContext) -> Self {
+        Self {
+            trace_id: value.trace_id,
+            span_id: value.span_id,
+            flags: value.flags,
+            state: value.state,
+        }
+    }
+}
+
+#[cfg(feature = "unstable")]
+fn parse_hex<const N: usize>(s: &str) -> Option<[u8; N]> {
+    if s.len() != 2 * N || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let mut bytes = [0; N];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&s[2 * i..2 * i + 2], 16).ok()?;
+    }
+    Some(bytes)
+}
+
+/// Returns `tracestate` without its empty list members, truncated to the W3C limits.
+#[cfg(feature = "unstable")]
+fn truncate_tracestate(tracestate: &str) -> String {
+    let mut members: Vec<&str> = tracestate
+        .split(',')
+        .map(str::trim)
+        .filter(|member| !member.is_empty())
+        .take(TRACESTATE_MAX_MEMBERS)
+        .collect();
+    // The members are joined with commas
+    let len = |members: &[&str]| members.iter().map(|member| member.len() + 1).sum::<usize>();
+    while len(&members) > TRACESTATE_MAX_LEN + 1 {
+        match members
+            .iter()
+            .position(|member| member.len() > TRACESTATE_MAX_MEMBER_LEN)
+        {
+            Some(long) => members.remove(long),
+            None => members.pop().unwrap_or_default(),
+        };
+    }
+    members.join(",")
+}
+
+/// Returns a random non-zero id, as required for trace and span ids.
+fn random_id<const N: usize>() -> [u8; N] {
+    loop {
+        let id: [u8; N] = std::array::from_fn(|_| rand::random());
+        if id != [0; N] {
+            return id;
+        }
+    }
+}
+
+struct Hex<'a>(&'a [u8]);
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|b| write!(f, "{b:02x}"))
+    }
+}
+
+/// The spans created for the messages carrying a trace context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SpanKind {
+    /// The message is sent, in the span of the context
+    Send,
+    /// The message is forwarded by a router
+    Route,
+    /// The message is delivered to a callback
+    Receive,
+}
+
+/// Returns the span of kind `kind` of a message with the trace context `ctx`.
+pub(crate) fn span<const ID: u8>(
+    kind: SpanKind,
+    operation: &'static str,
+    key_expr: &str,
+    ctx: &TraceContextType<ID>,
+) -> Span {
+    let trace_id = Hex(&ctx.trace_id);
+    match kind {
+        SpanKind::Send => tracing::info_span!(
+            "zenoh.send",
+            operation,
+            key_expr,
+            %trace_id,
+            span_id = %Hex(&ctx.span_id),
+        ),
+        SpanKind::Route => tracing::info_span!(
+            "zenoh.route",
+            operation,
+            key_expr,
+            %trace_id,
+            span_id = %Hex(&random_id::<8>()),
+            parent_span_id = %Hex(&ctx.span_id),
+        ),
+        SpanKind::Receive => tracing::info_span!(
+            "zenoh.receive",
+            operation,
+            key_expr,
+            %trace_id,
+            span_id = %Hex(&random_id::<8>()),
+            parent_span_id = %Hex(&ctx.span_id),
+        ),
+    }
+}
+
+/// Returns `true` if the publication or the reply `body` carries a trace context.
+pub(crate) fn has_context(body: &PushBody) -> bool {
+    match body {
+        PushBody::Put(p) => p.ext_trace.is_some(),
+        PushBody::Del(d) => d.ext_trace.is_some(),
+    }
+}
+
+/// Returns the span of kind `kind` of a publication, if it carries a trace context.
+pub(crate) fn push_span(kind: SpanKind, key_expr: &str, body: &PushBody) -> Option<Span> {
+    body_span(kind, ["put", "delete"], key_expr, body)
+}
+
+/// Returns the span of kind `kind` of a reply, if it carries a trace context.
+pub(crate) fn reply_span(kind: SpanKind, key_expr: &str, body: &PushBody) -> Option<Span> {
+    body_span(kind, ["reply", "reply"], key_expr, body)
+}
+
+fn body_span(
+    kind: SpanKind,
+    [put, delete]: [&'static str; 2],
+    key_expr: &str,
+    body: &PushBody,
+) -> Option<Span> {
+    match body {
+        PushBody::Put(p) => p
+            .ext_trace
+            .as_ref()
+            .map(|ctx| span(kind, put, key_expr, ctx)),
+        PushBody::Del(d) => d
+            .ext_trace
+            .as_ref()
+            .map(|ctx| span(kind, delete, key_expr, ctx)),
+    }
+}
+
+#[cfg(all(test, feature = "unstable"))]
+mod tests {
+    use super::TraceContext;
+
+    #[test]
+    fn test_trace_context() {
+        let traceparent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let ctx = TraceContext::from_w3c(traceparent, Some("congo=t61rcWkgMzE")).unwrap();
+        assert_eq!(ctx.traceparent(), traceparent);
+        assert_eq!(ctx.tracestate(), "congo=t61rcWkgMzE");
+        assert_eq!(
+            ctx.span_id(),
+            [0x00, 0xf0, 0x67, 0xaa, 0x0b, 0xa9, 0x02, 0xb7]
+        );
+        assert!(ctx.is_sampled());
+
+        let child = ctx.child();
+        assert_eq!(child.trace_id(), ctx.trace_id());
+        assert_ne!(child.span_id(), ctx.span_id());
+        assert_eq!(child.tracestate(), ctx.tracestate());
+
+        // Future versions may have more fields
+        assert!(
+            "01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-00-extra"
+                .parse::<TraceContext>()
+                .is_ok_and(|ctx| !ctx.is_sampled())
+        );
+
+        for invalid in [
+            "",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e473-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e473g-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-+0f067aa0ba902b7-01",
+        ] {
+            assert!(invalid.parse::<TraceContext>().is_err(), "{invalid}");
+        }
+
+        let root = TraceContext::root().with_tracestate(" a=1, ,b=2 ");
+        assert_eq!(root.tracestate(), "a=1,b=2");
+        assert!(root.is_sampled());
+        assert_eq!(
+            TraceContext::from_w3c(&root.traceparent(), Some(root.tracestate())).unwrap(),
+            root
+        );
+    }
+
+    #[test]
+    fn test_tracestate_limits() {
+        let members: Vec<String> = (0..40).map(|i| format!("k{i}=v")).collect();
+        let ctx = TraceContext::root().with_tracestate(members.join(","));
+        assert_eq!(ctx.tracestate(), members[..32].join(","));
+
+        // The members longer than 128 characters are removed first, then the last ones
+        let long = format!("long={}", "x".repeat(200));
+        let medium: Vec<String> = (0..5)
+            .map(|i| format!("m{i}={}", "y".repeat(100)))
+            .collect();
+        let tracestate = [&medium[..2], &[long.clone()], &medium[2..]]
+            .concat()
+            .join(",");
+        let ctx = TraceContext::root().with_tracestate(&tracestate);
+        assert_eq!(ctx.tracestate(), medium[..4].join(","));
+        assert!(ctx.tracestate().len() <= 512);
+
+        let ctx = TraceContext::from_w3c(
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            Some("x".repeat(u16::MAX as usize + 1).as_str()),
+        )
+        .unwrap();
+        assert_eq!(ctx.tracestate(), "");
+    }
+}
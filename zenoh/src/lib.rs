@@ -436,6 +436,8 @@ pub mod session {
 pub mod sample {
     #[zenoh_macros::unstable]
     pub use crate::api::sample::{SourceInfo, SourceSn};
+    #[zenoh_macros::unstable]
+    pub use crate::api::trace::TraceContext;
     pub use crate::api::{
         builders::sample::{
             SampleBuilder, SampleBuilderAny, SampleBuilderDelete, SampleBuilderPut,
@@ -548,6 +550,11 @@ pub mod bytes {
 /// # }
 /// ```
 pub mod pubsub {
+    #[zenoh_macros::unstable]
+    pub use crate::api::{
+        builders::drop_listener::DropListenerBuilder,
+        drop::{DropEvent, DropListener, DropListenerUndeclaration, DropReason},
+    };
     pub use crate::api::{
         builders::{
             publisher::{
@@ -1075,6 +1082,13 @@ pub mod internal {
             PluginsManager, Response, RunningPlugin, RunningPluginTrait, ZenohPlugin, PLUGIN_PREFIX,
         };
     }
+    /// Access control of the clients authenticated by the plugins
+    #[cfg(feature = "plugins")]
+    pub mod access_control {
+        pub use zenoh_config::{AclConfig, AclMessage, InterceptorFlow, Permission};
+
+        pub use crate::net::routing::interceptor::{AclAuthorizer, AclIdentity};
+    }
 
     pub use zenoh_result::ErrNo;
 }
@@ -18,12 +18,14 @@ use std::any::Any;
 
 pub use demux::*;
 pub use mux::*;
+use zenoh_keyexpr::keyexpr;
 use zenoh_protocol::{
     core::Reliability,
     network::{interest::Interest, Declare, Push, Request, Response, ResponseFinal},
 };
 
 use super::routing::RoutingContext;
+use crate::api::drop::DropReason;
 
 pub trait Primitives: Send + Sync {
     fn send_interest(&self, msg: &mut Interest);
@@ -58,11 +60,25 @@ pub(crate) trait EPrimitives: Send + Sync {
 
     fn send_push(&self, msg: &mut Push, reliability: Reliability) -> bool;
 
+    /// Sends a push message like [`send_push`](EPrimitives::send_push), returning the reason why
+    /// it has been dropped if it has not been sent.
+    fn try_send_push(&self, msg: &mut Push, reliability: Reliability) -> Result<(), DropReason> {
+        if self.send_push(msg, reliability) {
+            Ok(())
+        } else {
+            Err(DropReason::Congestion)
+        }
+    }
+
     fn send_request(&self, msg: &mut Request) -> bool;
 
     fn send_response(&self, msg: &mut Response) -> bool;
 
     fn send_response_final(&self, msg: &mut ResponseFinal) -> bool;
+
+    /// Notifies that `count` push messages on `key_expr` sent by this face have been dropped
+    /// before being sent to their destinations.
+    fn notify_push_dropped(&self, _key_expr: &keyexpr, _reason: DropReason, _count: usize) {}
 }
 
 #[derive(Default)]
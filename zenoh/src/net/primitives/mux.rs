@@ -28,11 +28,14 @@ use zenoh_protocol::{
 use zenoh_transport::{multicast::TransportMulticast, unicast::TransportUnicast};
 
 use super::{EPrimitives, Primitives};
-use crate::net::routing::{
-    dispatcher::face::{Face, WeakFace},
-    gateway::{InterceptorCacheValueType, Resource},
-    interceptor::{has_interceptor, InterceptorContext, InterceptorTrait, InterceptorsChain},
-    RoutingContext,
+use crate::{
+    api::drop::DropReason,
+    net::routing::{
+        dispatcher::face::{Face, WeakFace},
+        gateway::{InterceptorCacheValueType, Resource},
+        interceptor::{has_interceptor, InterceptorContext, InterceptorTrait, InterceptorsChain},
+        RoutingContext,
+    },
 };
 
 pub struct Mux {
@@ -72,6 +75,18 @@ impl Mux {
     fn schedule(&self, mut msg: NetworkMessageMut) -> bool {
         self.can_schedule(&mut msg) && self.handler.schedule(msg).unwrap_or(false)
     }
+
+    #[inline(always)]
+    fn try_schedule(&self, mut msg: NetworkMessageMut) -> Result<(), DropReason> {
+        if !self.can_schedule(&mut msg) {
+            return Err(DropReason::Filtered);
+        }
+        match self.handler.schedule(msg) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(DropReason::Congestion),
+            Err(_) => Err(DropReason::Closed),
+        }
+    }
 }
 
 struct MuxContext<'a> {
@@ -176,11 +191,15 @@ impl EPrimitives for Mux {
     }
 
     fn send_push(&self, msg: &mut Push, reliability: Reliability) -> bool {
+        self.try_send_push(msg, reliability).is_ok()
+    }
+
+    fn try_send_push(&self, msg: &mut Push, reliability: Reliability) -> Result<(), DropReason> {
         let msg = NetworkMessageMut {
             body: NetworkBodyMut::Push(msg),
             reliability,
         };
-        self.schedule(msg)
+        self.try_schedule(msg)
     }
 
     fn send_request(&self, msg: &mut Request) -> bool {
@@ -260,6 +279,18 @@ impl McastMux {
     fn schedule(&self, mut msg: NetworkMessageMut) -> bool {
         self.can_schedule(&mut msg) && self.handler.schedule(msg).unwrap_or(false)
     }
+
+    #[inline(always)]
+    fn try_schedule(&self, mut msg: NetworkMessageMut) -> Result<(), DropReason> {
+        if !self.can_schedule(&mut msg) {
+            return Err(DropReason::Filtered);
+        }
+        match self.handler.schedule(msg) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(DropReason::Congestion),
+            Err(_) => Err(DropReason::Closed),
+        }
+    }
 }
 
 struct McastMuxContext<'a> {
@@ -368,11 +399,15 @@ impl EPrimitives for McastMux {
     }
 
     fn send_push(&self, msg: &mut Push, reliability: Reliability) -> bool {
+        self.try_send_push(msg, reliability).is_ok()
+    }
+
+    fn try_send_push(&self, msg: &mut Push, reliability: Reliability) -> Result<(), DropReason> {
         let msg = NetworkMessageMut {
             body: NetworkBodyMut::Push(msg),
             reliability,
         };
-        self.schedule(msg)
+        self.try_schedule(msg)
     }
 
     fn send_request(&self, msg: &mut Request) -> bool {
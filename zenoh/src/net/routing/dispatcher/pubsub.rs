@@ -12,9 +12,10 @@
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
 
-use std::sync::Arc;
+use std::{cell::Cell, sync::Arc};
 
 use itertools::Itertools;
+use tracing::Span;
 use zenoh_core::zread;
 use zenoh_protocol::{
     core::{Region, Reliability, WireExpr},
@@ -26,14 +27,20 @@ use super::{
     resource::Resource,
     tables::{NodeId, Route, RoutingExpr, Tables, TablesLock},
 };
-use crate::net::routing::{
-    dispatcher::{
-        face::Face,
-        local_resources::{LocalResourceInfoTrait, LocalResources},
-        tables::InterRegionFilter,
+use crate::{
+    api::{
+        drop::DropReason,
+        trace::{self, SpanKind},
+    },
+    net::routing::{
+        dispatcher::{
+            face::Face,
+            local_resources::{LocalResourceInfoTrait, LocalResources},
+            tables::InterRegionFilter,
+        },
+        gateway::{get_or_set_route, node_id_as_source, Direction, RouteBuilder},
+        hat::{DispatcherContext, SendDeclare},
     },
-    gateway::{get_or_set_route, node_id_as_source, Direction, RouteBuilder},
-    hat::{DispatcherContext, SendDeclare},
 };
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -269,10 +276,33 @@ pub fn route_data(
         return;
     }
 
+    // The key expression is only computed for the messages carrying a trace context
+    let _span = (!src_face.is_local && trace::has_context(&msg.payload))
+        .then(|| {
+            let key_expr = expr.key_expr().map(|k| k.as_str()).unwrap_or_default();
+            trace::push_span(SpanKind::Route, key_expr, &msg.payload)
+        })
+        .flatten()
+        .map(Span::entered);
+
+    // the messages of local sessions dropped on remote faces are reported back to the sessions
+    let dropped: [Cell<usize>; DropReason::ALL.len()] = Default::default();
+
     let send_push = |dst_face: &FaceState, msg: &mut Push, reliability: Reliability| {
-        if dst_face.primitives.send_push(msg, reliability) {
-            #[cfg(feature = "stats")]
-            payload_observer.observe_payload(zenoh_stats::Tx, dst_face, msg);
+        #[cfg(feature = "stats")]
+        if dst_face.is_local {
+            payload_observer.observe_publication_latency(src_face);
+        }
+        match dst_face.primitives.try_send_push(msg, reliability) {
+            Ok(()) => {
+                #[cfg(feature = "stats")]
+                payload_observer.observe_payload(zenoh_stats::Tx, dst_face, msg);
+            }
+            Err(reason) if src_face.is_local && !dst_face.is_local => {
+                let dropped = &dropped[reason as usize];
+                dropped.set(dropped.get() + 1);
+            }
+            Err(_) => {}
         }
     };
 
@@ -314,11 +344,13 @@ pub fn route_data(
                     msg = &mut msg_clone;
                 }
 
-                msg.wire_expr = dir.wire_expr.clone();
+                let src_wire_expr = std::mem::replace(&mut msg.wire_expr, dir.wire_expr.clone());
                 msg.ext_nodeid = ext::NodeIdType {
                     node_id: dir.node_id,
                 };
                 send_push(&dir.dst_face, msg, reliability);
+                // restore the wire expression of the source face, to report dropped messages
+                msg.wire_expr = src_wire_expr;
             }
         } else {
             let dirs = route
@@ -346,6 +378,39 @@ pub fn route_data(
             }
         }
     }
+
+    if dropped.iter().any(|count| count.get() != 0) {
+        notify_push_dropped(tables_ref, src_face, msg, &dropped);
+    }
+}
+
+#[cold]
+fn notify_push_dropped(
+    tables_ref: &Arc<TablesLock>,
+    src_face: &FaceState,
+    msg: &Push,
+    dropped: &[Cell<usize>],
+) {
+    let rtables = zread!(tables_ref.tables);
+    let Some(prefix) =
+        rtables
+            .data
+            .get_mapping(src_face, &msg.wire_expr.scope, msg.wire_expr.mapping)
+    else {
+        return;
+    };
+    let expr = RoutingExpr::new(prefix, msg.wire_expr.suffix.as_ref());
+    let Some(key_expr) = expr.key_expr().map(ToOwned::to_owned) else {
+        return;
+    };
+    drop(rtables);
+    for (reason, count) in DropReason::ALL.into_iter().zip(dropped) {
+        if count.get() != 0 {
+            src_face
+                .primitives
+                .notify_push_dropped(&key_expr, reason, count.get());
+        }
+    }
 }
 
 impl LocalResourceInfoTrait<Arc<Resource>> for SubscriberInfo {
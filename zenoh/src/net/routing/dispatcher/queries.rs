@@ -20,6 +20,7 @@ use std::{
 use async_trait::async_trait;
 use itertools::Itertools;
 use tokio_util::sync::CancellationToken;
+use tracing::Span;
 use zenoh_buffers::ZBuf;
 #[allow(unused_imports)]
 use zenoh_core::polyfill::*;
@@ -30,7 +31,7 @@ use zenoh_protocol::{
         request::{self, ext::QueryTarget, Request, RequestId},
         response::{self, Response, ResponseFinal},
     },
-    zenoh::{self, ResponseBody},
+    zenoh::{self, RequestBody, ResponseBody},
 };
 use zenoh_sync::get_mut_unchecked;
 use zenoh_util::Timed;
@@ -40,14 +41,19 @@ use super::{
     resource::{QueryTargetQablSet, Resource},
     tables::{NodeId, RoutingExpr, TablesLock},
 };
-use crate::net::routing::{
-    dispatcher::{
-        face::Face,
-        local_resources::{LocalResourceInfoTrait, LocalResources},
-        tables::{InterRegionFilter, Tables},
+use crate::{
+    api::trace::{self, SpanKind},
+    net::routing::{
+        dispatcher::{
+            face::Face,
+            local_resources::{LocalResourceInfoTrait, LocalResources},
+            tables::{InterRegionFilter, Tables},
+        },
+        gateway::{
+            get_or_set_route, node_id_as_source, QueryDirection, QueryTargetQabl, RouteBuilder,
+        },
+        hat::{DispatcherContext, SendDeclare, UnregisterEntityResult},
     },
-    gateway::{get_or_set_route, node_id_as_source, QueryDirection, QueryTargetQabl, RouteBuilder},
-    hat::{DispatcherContext, SendDeclare, UnregisterEntityResult},
 };
 
 #[derive(Clone)]
@@ -55,6 +61,8 @@ pub(crate) struct Query {
     src_face: Arc<FaceState>,
     src_qid: RequestId,
     src_qos: response::ext::QoSType,
+    #[cfg(feature = "stats")]
+    round_trip_observer: Option<super::stats::RoundTripObserver>,
 }
 
 impl Face {
@@ -226,6 +234,12 @@ impl Face {
                     src_face: self.state.clone(),
                     src_qid: msg.id,
                     src_qos: msg.ext_qos,
+                    #[cfg(feature = "stats")]
+                    round_trip_observer: self
+                        .state
+                        .is_local
+                        .then(|| payload_observer.round_trip_observer())
+                        .flatten(),
                 });
 
                 let src_face = &self.state;
@@ -234,6 +248,16 @@ impl Face {
                     return;
                 }
 
+                let RequestBody::Query(q) = &msg.payload;
+                let _span = q
+                    .ext_trace
+                    .as_ref()
+                    .filter(|_| !src_face.is_local)
+                    .map(|ctx| {
+                        let key_expr = expr.key_expr().map(|k| k.as_str()).unwrap_or_default();
+                        trace::span(SpanKind::Route, "query", key_expr, ctx).entered()
+                    });
+
                 for dst in rtables.hats.regions() {
                     let qabls =
                         get_query_route(&rtables, src_face, &expr, msg.ext_nodeid.node_id, &dst);
@@ -583,8 +607,24 @@ pub(crate) fn route_send_response(
                     drop(tables);
                     drop(queries_lock);
 
+                    let _span = match &msg.payload {
+                        ResponseBody::Reply(reply) if !face.is_local => trace::reply_span(
+                            SpanKind::Route,
+                            msg.wire_expr.suffix.as_ref(),
+                            &reply.payload,
+                        ),
+                        _ => None,
+                    }
+                    .map(Span::entered);
+
                     msg.rid = query.src_qid;
                     msg.ext_qos = query.src_qos;
+                    #[cfg(feature = "stats")]
+                    if let (ResponseBody::Reply(_), Some(observer)) =
+                        (&msg.payload, &query.round_trip_observer)
+                    {
+                        observer.observe_round_trip_time(face);
+                    }
                     if query.src_face.primitives.send_response(msg) {
                         #[cfg(feature = "stats")]
                         payload_observer.observe_payload(zenoh_stats::Tx, &query.src_face, msg);
@@ -1,4 +1,4 @@
-use std::marker::PhantomData;
+use std::{marker::PhantomData, time::Instant};
 
 use zenoh_protocol::{
     core::Priority,
@@ -25,6 +25,9 @@ pub(super) trait ObservableMessage {
     fn message(&self) -> MessageLabel;
     fn priority(&self) -> Priority;
     fn payload_size(&self) -> usize;
+    fn timestamp(&self) -> Option<&uhlc::Timestamp> {
+        None
+    }
     fn tx_shm(&self) -> bool {
         false
     }
@@ -46,6 +49,12 @@ impl ObservableMessage for Push {
     fn payload_size(&self) -> usize {
         self.payload_size()
     }
+    fn timestamp(&self) -> Option<&uhlc::Timestamp> {
+        match &self.payload {
+            PushBody::Put(put) => put.timestamp.as_ref(),
+            PushBody::Del(del) => del.timestamp.as_ref(),
+        }
+    }
     #[cfg(feature = "shared-memory")]
     fn tx_shm(&self) -> bool {
         match &self.payload {
@@ -134,6 +143,7 @@ pub(super) struct PayloadObserver<Msg> {
     payload_size: usize,
     space: SpaceLabel,
     keys: StatsKeys,
+    timestamp: Option<uhlc::NTP64>,
     _phantom: PhantomData<Msg>,
 }
 
@@ -158,6 +168,8 @@ impl<Msg: ObservableMessage> PayloadObserver<Msg> {
             space,
             // SAFETY: the tree is always the table's one
             keys,
+            // the timestamp is read before it is possibly added by the routing
+            timestamp: msg.timestamp().map(|ts| *ts.get_time()),
             _phantom: PhantomData,
         }
     }
@@ -180,3 +192,58 @@ impl<Msg: ObservableMessage> PayloadObserver<Msg> {
         }
     }
 }
+
+impl PayloadObserver<Push> {
+    /// Observes the latency between the publication of the sample, as given by its timestamp, and
+    /// its delivery to a local face, `face` being the one it has been received from.
+    pub(super) fn observe_publication_latency(&self, face: &FaceState) {
+        let (Some(stats), Some(timestamp)) = (face.stats.as_ref(), self.timestamp) else {
+            return;
+        };
+        let latency = uhlc::system_time_clock()
+            .to_duration()
+            .saturating_sub(timestamp.to_duration());
+        stats.observe_publication_latency(
+            self.message,
+            self.priority,
+            self.space,
+            &self.keys,
+            latency,
+        );
+    }
+}
+
+impl PayloadObserver<Request> {
+    /// Returns the observer of the round-trip time of the query, if it matches some stats keys.
+    pub(super) fn round_trip_observer(&self) -> Option<RoundTripObserver> {
+        (!self.keys.is_empty()).then(|| RoundTripObserver {
+            priority: self.priority,
+            space: self.space,
+            keys: self.keys.clone(),
+            start: Instant::now(),
+        })
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct RoundTripObserver {
+    priority: Priority,
+    space: SpaceLabel,
+    keys: StatsKeys,
+    start: Instant,
+}
+
+impl RoundTripObserver {
+    /// Observes the round-trip time of the query, `face` being the one its reply has been received
+    /// from.
+    pub(super) fn observe_round_trip_time(&self, face: &FaceState) {
+        if let Some(stats) = face.stats.as_ref() {
+            stats.observe_query_round_trip_time(
+                self.priority,
+                self.space,
+                &self.keys,
+                self.start.elapsed(),
+            );
+        }
+    }
+}
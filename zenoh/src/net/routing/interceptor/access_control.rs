@@ -717,6 +717,87 @@ pub(crate) fn acl_interceptor_factories(
     Ok(res)
 }
 
+#[cfg(feature = "plugins")]
+/// Identity of a client authenticated outside of the Zenoh transports, e.g. by a plugin.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AclIdentity {
+    pub username: Option<String>,
+    pub cert_common_name: Option<String>,
+}
+
+#[cfg(feature = "plugins")]
+impl std::fmt::Display for AclIdentity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.username, &self.cert_common_name) {
+            (Some(username), Some(cert_common_name)) => write!(f, "{username}+{cert_common_name}"),
+            (Some(name), None) | (None, Some(name)) => write!(f, "{name}"),
+            (None, None) => write!(f, "anonymous"),
+        }
+    }
+}
+
+#[cfg(feature = "plugins")]
+/// Evaluates the `access_control` policies for [`AclIdentity`]s, so that clients of the plugins
+/// are subject to the same rules as the sessions connected through the Zenoh transports.
+pub struct AclAuthorizer {
+    enforcer: PolicyEnforcer,
+}
+
+#[cfg(feature = "plugins")]
+impl AclAuthorizer {
+    /// Returns `None` if access control is disabled.
+    pub fn new(acl_config: &AclConfig) -> ZResult<Option<Self>> {
+        if !acl_config.enabled {
+            return Ok(None);
+        }
+        let mut enforcer = PolicyEnforcer::new();
+        enforcer.init(acl_config)?;
+        Ok(Some(AclAuthorizer { enforcer }))
+    }
+
+    /// Returns the permission of `identity` to perform `action` on `key_expr`, as if it was
+    /// received (`Ingress`) from or sent (`Egress`) to a session authenticated with it.
+    pub fn authorize(
+        &self,
+        identity: &AclIdentity,
+        flow: InterceptorFlow,
+        action: AclMessage,
+        key_expr: &keyexpr,
+    ) -> Permission {
+        let interface_enabled = match flow {
+            InterceptorFlow::Ingress => self.enforcer.interface_enabled.ingress,
+            InterceptorFlow::Egress => self.enforcer.interface_enabled.egress,
+        };
+        if !interface_enabled {
+            return Permission::Allow;
+        }
+        let query = SubjectQuery {
+            interface: None,
+            cert_common_name: identity.cert_common_name.clone().map(CertCommonName),
+            username: identity.username.clone().map(Username),
+            link_protocol: None,
+            zid: None,
+        };
+        let mut decision = self.enforcer.default_permission;
+        for entry in self.enforcer.subject_store.query(&query) {
+            match self
+                .enforcer
+                .policy_decision_point(entry.id, flow, action, key_expr)
+            {
+                Ok(Permission::Allow) => return Permission::Allow,
+                Ok(Permission::Deny) => decision = Permission::Deny,
+                Err(e) => {
+                    tracing::debug!(
+                        "{identity} has an authorization error to {action:?} on {key_expr}: {e}"
+                    );
+                    return Permission::Deny;
+                }
+            }
+        }
+        decision
+    }
+}
+
 impl InterceptorFactoryTrait for AclEnforcer {
     fn new_transport_unicast(
         &self,
@@ -20,6 +20,8 @@
 //!
 mod access_control;
 use access_control::acl_interceptor_factories;
+#[cfg(feature = "plugins")]
+pub use access_control::{AclAuthorizer, AclIdentity};
 use nonempty_collections::NEVec;
 use zenoh_link::LinkAuthId;
 
@@ -27,7 +27,10 @@ use zenoh_protocol::{
 };
 
 use super::dispatcher::face::Face;
-use crate::net::primitives::{EPrimitives, Primitives};
+use crate::{
+    api::drop::DropReason,
+    net::primitives::{EPrimitives, Primitives},
+};
 
 pub(crate) struct Namespace {
     namespace: OwnedNonWildKeyExpr,
@@ -283,4 +286,10 @@ impl EPrimitives for ENamespace {
     fn send_response_final(&self, msg: &mut ResponseFinal) -> bool {
         self.primitives.send_response_final(msg)
     }
+
+    fn notify_push_dropped(&self, key_expr: &keyexpr, reason: DropReason, count: usize) {
+        if let Some(tail) = key_expr.strip_nonwild_prefix(&self.namespace) {
+            self.primitives.notify_push_dropped(tail, reason, count)
+        }
+    }
 }
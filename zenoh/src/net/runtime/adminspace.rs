@@ -472,6 +472,8 @@ impl Primitives for AdminSpace {
                         qos: msg.ext_qos.into(),
                         #[cfg(feature = "unstable")]
                         source_info: query.ext_sinfo.map(Into::into),
+                        #[cfg(feature = "unstable")]
+                        trace_context: query.ext_trace.take().map(Into::into),
                         primitives: ReplyPrimitives::new_remote(None, primitives),
                     }),
                     eid: self.queryable_id,
@@ -712,6 +714,8 @@ fn metrics(prefix: &keyexpr, context: &AdminContext, query: Query) {
             query.parameters().get("per_key") != Some("false"),
         )
         .expect("metrics should be encodable");
+    #[cfg(feature = "plugins")]
+    plugins_metrics(context, &mut metrics);
     if query.parameters().get("descriptors") == Some("false") {
         metrics = metrics
             .split_inclusive("\n")
@@ -732,6 +736,25 @@ fn metrics(prefix: &keyexpr, context: &AdminContext, query: Query) {
     }
 }
 
+/// Inserts the metrics of the started plugins before the `# EOF` marker of `metrics`.
+#[cfg(feature = "plugins")]
+fn plugins_metrics(context: &AdminContext, metrics: &mut String) {
+    let mut plugins_metrics = String::new();
+    for plugin in context.runtime.plugins_manager().started_plugins_iter() {
+        match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| plugin.instance().metrics()))
+        {
+            Ok(Ok(families)) => plugins_metrics.push_str(&families),
+            Ok(Err(e)) => {
+                tracing::error!("Plugin {} failed to encode its metrics: {}", plugin.id(), e)
+            }
+            Err(_) => tracing::error!("Plugin {} panicked while encoding its metrics", plugin.id()),
+        }
+    }
+    if let Some(eof) = metrics.rfind("# EOF") {
+        metrics.insert_str(eof, &plugins_metrics);
+    }
+}
+
 fn resources_data<F>(prefix: &keyexpr, context: &AdminContext, query: Query, f: F)
 where
     F: Fn(&Tables) -> HashMap<Arc<Resource>, Sources>,
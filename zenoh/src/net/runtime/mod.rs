@@ -20,6 +20,8 @@
 mod adminspace;
 pub mod orchestrator;
 mod region;
+#[cfg(feature = "stats")]
+mod stats_export;
 
 #[cfg(feature = "unstable")]
 #[cfg(feature = "plugins")]
@@ -680,6 +682,14 @@ impl RuntimeBuilder {
         #[cfg(feature = "shared-memory")]
         let shm_init_mode = *config.transport.shared_memory.mode();
 
+        #[cfg(feature = "stats")]
+        let stats_exporter = config
+            .stats
+            .export()
+            .as_ref()
+            .map(stats_export::StatsExporter::new)
+            .transpose()?;
+
         let namespace = config.namespace().clone();
         let config = Notifier::new(config);
         let span = tracing::debug_span!("rt", zid = %zid.short());
@@ -717,6 +727,11 @@ impl RuntimeBuilder {
         #[cfg(feature = "plugins")]
         start_plugins(&runtime);
 
+        #[cfg(feature = "stats")]
+        if let Some(stats_exporter) = stats_exporter {
+            stats_exporter.start(&runtime);
+        }
+
         #[cfg(feature = "shared-memory")]
         match shm_init_mode {
             zenoh_config::ShmInitMode::Init => zenoh_shm::init::init(),
@@ -0,0 +1,146 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Periodic push of the stats to an OpenTelemetry collector, configured in `stats/export`.
+//!
+//! The metrics are posted with OTLP over HTTP, in the JSON encoding of an
+//! `ExportMetricsServiceRequest`, the `metadata` of the configuration giving the resource attributes.
+use std::time::Duration;
+
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::TcpStream,
+};
+use url::Url;
+use zenoh_config::StatsExportConfig;
+use zenoh_result::{bail, zerror, ZResult};
+
+use super::Runtime;
+
+const DEFAULT_PATH: &str = "/v1/metrics";
+
+pub(crate) struct StatsExporter {
+    host: String,
+    port: u16,
+    path: String,
+    headers: Vec<(String, String)>,
+    interval: Duration,
+    per_transport: bool,
+    per_link: bool,
+    per_key: bool,
+}
+
+impl StatsExporter {
+    pub(crate) fn new(config: &StatsExportConfig) -> ZResult<Self> {
+        let url = Url::parse(&config.endpoint)
+            .map_err(|e| zerror!("Invalid stats export endpoint {}: {e}", config.endpoint))?;
+        if url.scheme() != "http" {
+            bail!(
+                "Invalid stats export endpoint {}: only http endpoints are supported",
+                config.endpoint
+            );
+        }
+        let Some(host) = url.host_str() else {
+            bail!("Invalid stats export endpoint {}: no host", config.endpoint);
+        };
+        if config.interval == 0 {
+            bail!("Invalid stats export interval: it must be greater than 0");
+        }
+        let path = match url.path() {
+            "/" => DEFAULT_PATH.to_string(),
+            path => match url.query() {
+                Some(query) => format!("{path}?{query}"),
+                None => path.to_string(),
+            },
+        };
+        Ok(Self {
+            host: host
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+                .to_string(),
+            port: url.port_or_known_default().unwrap_or(80),
+            path,
+            headers: config
+                .headers
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            interval: Duration::from_millis(config.interval),
+            per_transport: config.per_transport,
+            per_link: config.per_link,
+            per_key: config.per_key,
+        })
+    }
+
+    /// Starts the periodic export of the stats of `runtime`, until it is closed.
+    pub(crate) fn start(self, runtime: &Runtime) {
+        let stats = runtime.stats().clone();
+        let metadata = runtime.config().lock().metadata().clone();
+        runtime.spawn_abortable(async move {
+            let mut interval = tokio::time::interval(self.interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            // the first tick completes immediately
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                let body = stats
+                    .encode_otlp(&metadata, self.per_transport, self.per_link, self.per_key)
+                    .to_string();
+                match tokio::time::timeout(self.interval, self.post(body.as_bytes())).await {
+                    Ok(Ok(())) => tracing::trace!("Stats exported to {}", self.authority()),
+                    Ok(Err(e)) => tracing::warn!("Unable to export stats: {e}"),
+                    Err(_) => tracing::warn!(
+                        "Unable to export stats: {} did not respond in time",
+                        self.authority()
+                    ),
+                }
+            }
+        });
+    }
+
+    fn authority(&self) -> String {
+        match self.host.contains(':') {
+            true => format!("[{}]:{}", self.host, self.port),
+            false => format!("{}:{}", self.host, self.port),
+        }
+    }
+
+    async fn post(&self, body: &[u8]) -> ZResult<()> {
+        let authority = self.authority();
+        let mut stream = TcpStream::connect((self.host.as_str(), self.port))
+            .await
+            .map_err(|e| zerror!("Unable to connect to {authority}: {e}"))?;
+        let mut request = format!(
+            "POST {} HTTP/1.1\r\nHost: {authority}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+            self.path,
+            body.len()
+        );
+        for (name, value) in &self.headers {
+            request.push_str(&format!("{name}: {value}\r\n"));
+        }
+        request.push_str("\r\n");
+        stream.write_all(request.as_bytes()).await?;
+        stream.write_all(body).await?;
+        stream.flush().await?;
+
+        let mut status_line = String::new();
+        BufReader::new(stream).read_line(&mut status_line).await?;
+        let status_line = status_line.trim_end();
+        match status_line.split(' ').nth(1).map(str::parse::<u16>) {
+            Some(Ok(status)) if (200..300).contains(&status) => Ok(()),
+            Some(Ok(_)) => bail!("{authority} responded with {status_line}"),
+            _ => bail!("{authority} sent an invalid response: {status_line}"),
+        }
+    }
+}
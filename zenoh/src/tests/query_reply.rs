@@ -76,6 +76,8 @@ async fn test_reply_preserves_optimized_ke() {
         qos: QoS::default(),
         #[cfg(feature = "unstable")]
         source_info: None,
+        #[cfg(feature = "unstable")]
+        trace_context: None,
         primitives: ReplyPrimitives::new_remote(Some(session.downgrade()), primitives.clone()),
     };
     let query = Query {
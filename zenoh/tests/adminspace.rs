@@ -876,3 +876,107 @@ async fn test_adminspace_regression_1() {
     assert_eq!(rx_t_bytes, rx_l_bytes);
     assert_eq!(tx_t_bytes, tx_l_bytes);
 }
+
+#[cfg(feature = "stats")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_adminspace_latency_per_key() {
+    use zenoh::Wait;
+
+    const TIMEOUT: Duration = Duration::from_secs(60);
+    const ROUTER_ENDPOINT: &str = "tcp/localhost:31003";
+    const PUBLICATIONS: usize = 5;
+    const QUERIES: usize = 3;
+
+    zenoh_util::init_log_from_env_or("error");
+
+    // Only the key expressions matching the stats filters have latency histograms
+    let router = {
+        let mut c = zenoh_config::Config::default();
+        c.set_mode(Some(WhatAmI::Router)).unwrap();
+        c.listen
+            .endpoints
+            .set(vec![ROUTER_ENDPOINT.parse::<EndPoint>().unwrap()])
+            .unwrap();
+        c.scouting.multicast.set_enabled(Some(false)).unwrap();
+        c.adminspace.set_enabled(true).unwrap();
+        c.adminspace.permissions.set_read(true).unwrap();
+        c.insert_json5("stats/filters", r#"[{key: "test/latency/on/**"}]"#)
+            .unwrap();
+        ztimeout!(zenoh::open(c)).unwrap()
+    };
+    let zid = router.zid();
+    let client = {
+        let mut c = zenoh_config::Config::default();
+        c.set_mode(Some(WhatAmI::Client)).unwrap();
+        c.connect
+            .endpoints
+            .set(vec![ROUTER_ENDPOINT.parse::<EndPoint>().unwrap()])
+            .unwrap();
+        c.scouting.multicast.set_enabled(Some(false)).unwrap();
+        ztimeout!(zenoh::open(c)).unwrap()
+    };
+
+    let subscriber = ztimeout!(router.declare_subscriber("test/latency/**")).unwrap();
+    let _queryable = ztimeout!(client
+        .declare_queryable("test/latency/**")
+        .callback(|query| {
+            let key_expr = query.key_expr().clone();
+            query.reply(key_expr, "reply").wait().unwrap();
+        }))
+    .unwrap();
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    // The latency of the publications is measured from their timestamp
+    for key_expr in ["test/latency/on/a", "test/latency/off/a"] {
+        for _ in 0..PUBLICATIONS {
+            ztimeout!(client
+                .put(key_expr, "data")
+                .timestamp(client.new_timestamp()))
+            .unwrap();
+        }
+        ztimeout!(client.put(key_expr, "untimestamped")).unwrap();
+    }
+    for _ in 0..2 * (PUBLICATIONS + 1) {
+        ztimeout!(subscriber.recv_async()).unwrap();
+    }
+    for key_expr in ["test/latency/on/q", "test/latency/off/q"] {
+        for _ in 0..QUERIES {
+            let replies = ztimeout!(router.get(key_expr)).unwrap();
+            assert!(ztimeout!(replies.recv_async()).unwrap().result().is_ok());
+        }
+    }
+
+    let reply = ztimeout!(router.get(format!(
+        "@/{zid}/router/metrics?compression=false;descriptors=false"
+    )))
+    .unwrap()
+    .into_iter()
+    .next()
+    .unwrap();
+    let metrics = reply.result().unwrap().payload().try_to_string().unwrap();
+    // The count of the histograms of the filter, for all the transports and per transport
+    let count = |family: &str, key: &str| -> Vec<u64> {
+        [
+            family.to_string(),
+            family.replace("_per_key", "_per_key_per_transport"),
+        ]
+        .iter()
+        .filter_map(|family| {
+            metrics.lines().find(|line| {
+                line.starts_with(&format!("{family}_microseconds_count{{"))
+                    && line.contains(&format!("key=\"{key}\""))
+            })
+        })
+        .map(|line| line.rsplit_once(' ').unwrap().1.parse().unwrap())
+        .collect()
+    };
+    assert_eq!(
+        count("zenoh_publication_latency_per_key", "test/latency/on/**"),
+        [PUBLICATIONS as u64; 2]
+    );
+    assert_eq!(
+        count("zenoh_query_round_trip_time_per_key", "test/latency/on/**"),
+        [QUERIES as u64; 2]
+    );
+    assert!(!metrics.contains("test/latency/off"));
+}
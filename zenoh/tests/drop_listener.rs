@@ -0,0 +1,161 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+#![cfg(feature = "unstable")]
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use zenoh::{
+    pubsub::{DropEvent, DropReason},
+    qos::CongestionControl,
+    Session,
+};
+use zenoh_config::{ModeDependentValue, WhatAmI};
+use zenoh_core::ztimeout;
+
+const TIMEOUT: Duration = Duration::from_secs(60);
+const SLEEP: Duration = Duration::from_secs(1);
+const PUBLICATIONS: usize = 2_000;
+const PAYLOAD_SIZE: usize = 32 * 1024;
+
+async fn open_peers(locator: &str) -> (Session, Session) {
+    let mut config1 = zenoh::Config::default();
+    config1.set_mode(Some(WhatAmI::Peer)).unwrap();
+    config1.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config1
+        .listen
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    let mut config2 = zenoh::Config::default();
+    config2.set_mode(Some(WhatAmI::Peer)).unwrap();
+    config2.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config2
+        .connect
+        .set_endpoints(ModeDependentValue::Unique(vec![locator.parse().unwrap()]))
+        .unwrap();
+
+    let session1 = ztimeout!(zenoh::open(config1)).unwrap();
+    let session2 = ztimeout!(zenoh::open(config2)).unwrap();
+    (session1, session2)
+}
+
+/// Declares on `session` a subscriber on `key_expr` that doesn't process its samples until the
+/// returned sender is dropped, congesting the transport of the publishers.
+async fn block_subscriber(session: &Session, key_expr: &str) -> flume::Sender<()> {
+    let (release, blocked) = flume::bounded::<()>(0);
+    ztimeout!(session
+        .declare_subscriber(key_expr)
+        .callback(move |_| {
+            let _ = blocked.recv();
+        })
+        .background())
+    .unwrap();
+    tokio::time::sleep(SLEEP).await;
+    release
+}
+
+/// Returns the events received on `listener`, without waiting for new ones.
+fn events(listener: &flume::Receiver<DropEvent>) -> Vec<DropEvent> {
+    listener.drain().collect()
+}
+
+fn assert_congestion(events: &[DropEvent], key_expr: &str) {
+    assert!(!events.is_empty());
+    for event in events {
+        assert_eq!(event.key_expr().as_str(), key_expr);
+        assert_eq!(event.reason(), DropReason::Congestion);
+        assert!(event.count() >= 1);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_drop_listener_congestion() {
+    zenoh::init_log_from_env_or("error");
+    let (session1, session2) = open_peers("tcp/127.0.0.1:31450").await;
+    let release = block_subscriber(&session2, "test/drop_listener/congestion/**").await;
+
+    let ke_pub = "test/drop_listener/congestion/publisher";
+    let ke_put = "test/drop_listener/congestion/put";
+    let publisher = ztimeout!(session1
+        .declare_publisher(ke_pub)
+        .congestion_control(CongestionControl::Drop))
+    .unwrap();
+    // The listeners are unbounded not to block the publications while they are not read
+    let publisher_listener = ztimeout!(publisher.drop_listener().with(flume::unbounded())).unwrap();
+    let session_listener = ztimeout!(session1.drop_listener().with(flume::unbounded())).unwrap();
+
+    let payload = vec![0u8; PAYLOAD_SIZE];
+    for _ in 0..PUBLICATIONS {
+        ztimeout!(publisher.put(payload.clone())).unwrap();
+    }
+    assert_congestion(&events(&publisher_listener), ke_pub);
+    assert_congestion(&events(&session_listener), ke_pub);
+
+    // The session listener reports the publications of the session, without a publisher
+    for _ in 0..PUBLICATIONS {
+        ztimeout!(session1
+            .put(ke_put, payload.clone())
+            .congestion_control(CongestionControl::Drop))
+        .unwrap();
+    }
+    assert_congestion(&events(&session_listener), ke_put);
+    assert!(events(&publisher_listener).is_empty());
+
+    drop(release);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_drop_listener_undeclare() {
+    zenoh::init_log_from_env_or("error");
+    let (session1, session2) = open_peers("tcp/127.0.0.1:31451").await;
+    let release = block_subscriber(&session2, "test/drop_listener/undeclare").await;
+
+    let ke = "test/drop_listener/undeclare";
+    let publisher = ztimeout!(session1
+        .declare_publisher(ke)
+        .congestion_control(CongestionControl::Drop))
+    .unwrap();
+    let undeclared_count = Arc::new(AtomicUsize::new(0));
+    let undeclared = ztimeout!(publisher.drop_listener().callback({
+        let undeclared_count = undeclared_count.clone();
+        move |_| {
+            undeclared_count.fetch_add(1, Ordering::Relaxed);
+        }
+    }))
+    .unwrap();
+    ztimeout!(undeclared.undeclare()).unwrap();
+    let session_undeclared = ztimeout!(session1.drop_listener()).unwrap();
+    ztimeout!(session_undeclared.undeclare()).unwrap();
+    let listener = ztimeout!(publisher.drop_listener().with(flume::unbounded())).unwrap();
+
+    let payload = vec![0u8; PAYLOAD_SIZE];
+    for _ in 0..PUBLICATIONS {
+        ztimeout!(publisher.put(payload.clone())).unwrap();
+    }
+    assert_congestion(&events(&listener), ke);
+    assert_eq!(undeclared_count.load(Ordering::Relaxed), 0);
+
+    // Undeclaring the publisher undeclares its listeners
+    ztimeout!(publisher.undeclare()).unwrap();
+    events(&listener);
+    assert!(ztimeout!(listener.recv_async()).is_err());
+
+    drop(release);
+}
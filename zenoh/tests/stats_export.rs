@@ -0,0 +1,285 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "stats")]
+use std::time::Duration;
+
+use serde_json::Value;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::TcpListener,
+};
+use zenoh::{Session, Wait};
+use zenoh_config::WhatAmI;
+use zenoh_core::ztimeout;
+use zenoh_link::EndPoint;
+
+const TIMEOUT: Duration = Duration::from_secs(60);
+const PUBLICATIONS: u64 = 5;
+
+/// An export request received by the collector.
+struct Export {
+    request_line: String,
+    headers: Vec<(String, String)>,
+    body: Value,
+}
+
+impl Export {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn resource_attribute(&self, key: &str) -> Option<&Value> {
+        self.body["resourceMetrics"][0]["resource"]["attributes"]
+            .as_array()?
+            .iter()
+            .find(|attr| attr["key"] == key)
+            .map(|attr| &attr["value"])
+    }
+
+    fn metrics(&self) -> &[Value] {
+        self.body["resourceMetrics"][0]["scopeMetrics"][0]["metrics"]
+            .as_array()
+            .unwrap()
+    }
+
+    fn has_metric(&self, name: &str) -> bool {
+        self.metrics().iter().any(|metric| metric["name"] == name)
+    }
+
+    /// The data points of the counter `name` whose attributes contain `attributes`.
+    fn counter(&self, name: &str, attributes: &[(&str, &str)]) -> Option<u64> {
+        let metric = self.metrics().iter().find(|m| m["name"] == name)?;
+        metric["sum"]["dataPoints"]
+            .as_array()?
+            .iter()
+            .find(|point| {
+                attributes.iter().all(|(key, value)| {
+                    point["attributes"]
+                        .as_array()
+                        .unwrap()
+                        .iter()
+                        .any(|attr| attr["key"] == *key && attr["value"]["stringValue"] == *value)
+                })
+            })
+            .map(|point| point["asInt"].as_str().unwrap().parse().unwrap())
+    }
+}
+
+/// Receives one export request and responds to it with `200 OK`.
+async fn collect(listener: &TcpListener) -> Export {
+    let (stream, _) = listener.accept().await.unwrap();
+    let mut stream = BufReader::new(stream);
+    let mut request_line = String::new();
+    stream.read_line(&mut request_line).await.unwrap();
+    let mut headers = Vec::new();
+    loop {
+        let mut line = String::new();
+        stream.read_line(&mut line).await.unwrap();
+        match line.trim_end().split_once(": ") {
+            Some((name, value)) => headers.push((name.to_string(), value.to_string())),
+            None => break,
+        }
+    }
+    let length: usize = headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+        .unwrap()
+        .1
+        .parse()
+        .unwrap();
+    let mut body = vec![0; length];
+    stream.read_exact(&mut body).await.unwrap();
+    stream
+        .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n")
+        .await
+        .unwrap();
+    Export {
+        request_line: request_line.trim_end().to_string(),
+        headers,
+        body: serde_json::from_slice(&body).unwrap(),
+    }
+}
+
+/// Opens a router exporting its stats with `export`, and a client publishing on it.
+async fn open(router_endpoint: &str, export: &str) -> (Session, Session) {
+    let router = {
+        let mut c = zenoh_config::Config::default();
+        c.set_mode(Some(WhatAmI::Router)).unwrap();
+        c.listen
+            .endpoints
+            .set(vec![router_endpoint.parse::<EndPoint>().unwrap()])
+            .unwrap();
+        c.scouting.multicast.set_enabled(Some(false)).unwrap();
+        c.insert_json5(
+            "metadata",
+            r#"{name: "strawberry", location: {city: "Liverpool"}}"#,
+        )
+        .unwrap();
+        c.insert_json5("stats/filters", r#"[{key: "test/export/**"}]"#)
+            .unwrap();
+        c.insert_json5("stats/export", export).unwrap();
+        ztimeout!(zenoh::open(c)).unwrap()
+    };
+    let client = {
+        let mut c = zenoh_config::Config::default();
+        c.set_mode(Some(WhatAmI::Client)).unwrap();
+        c.connect
+            .endpoints
+            .set(vec![router_endpoint.parse::<EndPoint>().unwrap()])
+            .unwrap();
+        c.scouting.multicast.set_enabled(Some(false)).unwrap();
+        ztimeout!(zenoh::open(c)).unwrap()
+    };
+    router
+        .declare_subscriber("test/export/**")
+        .callback(|_| {})
+        .background()
+        .wait()
+        .unwrap();
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    for _ in 0..PUBLICATIONS {
+        ztimeout!(client.put("test/export/a", "data")).unwrap();
+    }
+    (router, client)
+}
+
+/// Collects the exports until one contains the publications of the client.
+async fn collect_publications(listener: &TcpListener) -> Export {
+    loop {
+        let export = ztimeout!(collect(listener));
+        if export.counter("zenoh_rx_network_message", &[("message", "put")]) >= Some(PUBLICATIONS) {
+            return export;
+        }
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_stats_export() {
+    const ROUTER_ENDPOINT: &str = "tcp/localhost:31460";
+    const COLLECTOR: &str = "127.0.0.1:31461";
+
+    zenoh_util::init_log_from_env_or("error");
+
+    let listener = TcpListener::bind(COLLECTOR).await.unwrap();
+    let (router, client) = open(
+        ROUTER_ENDPOINT,
+        &format!(
+            r#"{{
+                endpoint: "http://{COLLECTOR}",
+                interval: 100,
+                headers: {{authorization: "Bearer token", "x-tenant": "zenoh"}},
+                per_transport: true,
+                per_link: true,
+                per_key: true,
+            }}"#
+        ),
+    )
+    .await;
+    let export = collect_publications(&listener).await;
+
+    // The default path of OTLP/HTTP is used, with the configured headers
+    assert_eq!(export.request_line, "POST /v1/metrics HTTP/1.1");
+    assert_eq!(export.header("content-type"), Some("application/json"));
+    assert_eq!(export.header("authorization"), Some("Bearer token"));
+    assert_eq!(export.header("x-tenant"), Some("zenoh"));
+
+    // The resource attributes identify the node, and include its metadata
+    let string = |s: &str| serde_json::json!({"stringValue": s});
+    let zid = router.zid().to_string();
+    assert_eq!(
+        export.resource_attribute("service.name"),
+        Some(&string("zenoh"))
+    );
+    assert_eq!(
+        export.resource_attribute("service.instance.id"),
+        Some(&string(&zid))
+    );
+    assert_eq!(
+        export.resource_attribute("zenoh.whatami"),
+        Some(&string("router"))
+    );
+    assert_eq!(
+        export.resource_attribute("name"),
+        Some(&string("strawberry"))
+    );
+    assert_eq!(
+        export.resource_attribute("location.city"),
+        Some(&string("Liverpool"))
+    );
+    assert_eq!(
+        export.body["resourceMetrics"][0]["scopeMetrics"][0]["scope"]["name"],
+        "zenoh-stats"
+    );
+
+    // The counters are labelled with the transports and the links
+    let remote_zid = client.zid().to_string();
+    let put = ("message", "put");
+    assert_eq!(
+        export.counter(
+            "zenoh_rx_network_message_per_transport",
+            &[put, ("remote_zid", &remote_zid)]
+        ),
+        Some(PUBLICATIONS)
+    );
+    assert!(export.has_metric("zenoh_rx_network_message_per_link"));
+    assert!(export.has_metric("zenoh_rx_network_message_payload_per_key_bytes"));
+
+    client.close().await.unwrap();
+    router.close().await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_stats_export_toggles() {
+    const ROUTER_ENDPOINT: &str = "tcp/localhost:31462";
+    const COLLECTOR: &str = "127.0.0.1:31463";
+
+    zenoh_util::init_log_from_env_or("error");
+
+    let listener = TcpListener::bind(COLLECTOR).await.unwrap();
+    let (router, client) = open(
+        ROUTER_ENDPOINT,
+        &format!(
+            r#"{{
+                endpoint: "http://{COLLECTOR}/custom/metrics?tenant=zenoh",
+                interval: 100,
+                per_transport: false,
+                per_link: false,
+                per_key: false,
+            }}"#
+        ),
+    )
+    .await;
+    let export = collect_publications(&listener).await;
+
+    // The path and query of the endpoint are kept, with no additional headers
+    assert_eq!(
+        export.request_line,
+        "POST /custom/metrics?tenant=zenoh HTTP/1.1"
+    );
+    assert_eq!(export.header("authorization"), None);
+
+    // Only the aggregated metrics are exported
+    for metric in export.metrics() {
+        let name = metric["name"].as_str().unwrap();
+        assert!(!name.contains("_per_transport"), "{name}");
+        assert!(!name.contains("_per_link"), "{name}");
+        assert!(!name.contains("_per_key"), "{name}");
+    }
+
+    client.close().await.unwrap();
+    router.close().await.unwrap();
+}
@@ -0,0 +1,121 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+#![cfg(feature = "unstable")]
+use core::time::Duration;
+
+use zenoh::{
+    sample::{SampleKind, TraceContext},
+    Session,
+};
+use zenoh_config::{ModeDependentValue, WhatAmI};
+use zenoh_core::ztimeout;
+
+const TIMEOUT: Duration = Duration::from_secs(60);
+const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+const TRACESTATE: &str = "congo=t61rcWkgMzE";
+
+async fn create_peer_client_pair(locator: &str) -> (Session, Session) {
+    let config1 = {
+        let mut config = zenoh::Config::default();
+        config.scouting.multicast.set_enabled(Some(false)).unwrap();
+        config
+            .listen
+            .endpoints
+            .set(vec![locator.parse().unwrap()])
+            .unwrap();
+        config
+    };
+    let mut config2 = zenoh::Config::default();
+    config2.set_mode(Some(WhatAmI::Client)).unwrap();
+    config2.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config2
+        .connect
+        .set_endpoints(ModeDependentValue::Unique(vec![locator.parse().unwrap()]))
+        .unwrap();
+
+    let session1 = zenoh::open(config1).await.unwrap();
+    let session2 = zenoh::open(config2).await.unwrap();
+    (session1, session2)
+}
+
+fn trace_context() -> TraceContext {
+    TraceContext::from_w3c(TRACEPARENT, Some(TRACESTATE)).unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_trace_context_pub_sub() {
+    zenoh::init_log_from_env_or("error");
+    let ke = "test/trace_context/pub_sub";
+    let (session1, session2) = ztimeout!(create_peer_client_pair("tcp/127.0.0.1:51011"));
+    let publisher = ztimeout!(session2.declare_publisher(ke)).unwrap();
+    let subscriber = ztimeout!(session1.declare_subscriber(ke)).unwrap();
+
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    ztimeout!(publisher.put("data").trace_context(trace_context())).unwrap();
+    ztimeout!(session2.put(ke, "data").trace_context(trace_context())).unwrap();
+    ztimeout!(session2.delete(ke).trace_context(trace_context())).unwrap();
+    ztimeout!(publisher.put("data")).unwrap();
+
+    for kind in [SampleKind::Put, SampleKind::Put, SampleKind::Delete] {
+        let sample = ztimeout!(subscriber.recv_async()).unwrap();
+        assert_eq!(sample.kind(), kind);
+        assert_eq!(sample.trace_context(), Some(&trace_context()));
+    }
+    let sample = ztimeout!(subscriber.recv_async()).unwrap();
+    assert!(sample.trace_context().is_none());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_trace_context_query_reply() {
+    zenoh::init_log_from_env_or("error");
+    let ke = "test/trace_context/query_reply";
+    let (session1, session2) = ztimeout!(create_peer_client_pair("tcp/127.0.0.1:51012"));
+    let queryable = ztimeout!(session1.declare_queryable(ke)).unwrap();
+
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    // The reply carries the trace context it is given
+    let replies = ztimeout!(session2.get(ke).trace_context(trace_context())).unwrap();
+    let query = ztimeout!(queryable.recv_async()).unwrap();
+    assert_eq!(query.trace_context(), Some(&trace_context()));
+    let reply_context = trace_context().child();
+    ztimeout!(query.reply(ke, "data").trace_context(reply_context.clone())).unwrap();
+    std::mem::drop(query);
+    let reply = ztimeout!(replies.recv_async()).unwrap();
+    assert_eq!(
+        reply.result().unwrap().trace_context(),
+        Some(&reply_context)
+    );
+
+    // By default, the reply continues the trace of the query
+    let replies = ztimeout!(session2.get(ke).trace_context(trace_context())).unwrap();
+    let query = ztimeout!(queryable.recv_async()).unwrap();
+    ztimeout!(query.reply(ke, "data")).unwrap();
+    std::mem::drop(query);
+    let reply = ztimeout!(replies.recv_async()).unwrap();
+    let context = reply.result().unwrap().trace_context().unwrap();
+    assert_eq!(context.trace_id(), trace_context().trace_id());
+    assert_ne!(context.span_id(), trace_context().span_id());
+    assert_eq!(context.tracestate(), TRACESTATE);
+
+    // Without trace context
+    let replies = ztimeout!(session2.get(ke)).unwrap();
+    let query = ztimeout!(queryable.recv_async()).unwrap();
+    assert!(query.trace_context().is_none());
+    ztimeout!(query.reply(ke, "data")).unwrap();
+    std::mem::drop(query);
+    let reply = ztimeout!(replies.recv_async()).unwrap();
+    assert!(reply.result().unwrap().trace_context().is_none());
+}